@@ -184,6 +184,9 @@ pub mod rpc {
 /// Ress subprotocol installation.
 pub mod ress;
 
+/// Snap subprotocol installation.
+pub mod snap;
+
 // re-export for convenience
 #[doc(inline)]
 pub use reth_cli_runner::{tokio_runtime, CliContext, CliRunner};
@@ -194,3 +197,4 @@ use aquamarine as _;
 // used in main
 use clap as _;
 use reth_cli_util as _;
+use reth_exex_bridge as _;
@@ -3,13 +3,33 @@
 #[global_allocator]
 static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::new_allocator();
 
-use clap::Parser;
-use reth::{args::RessArgs, cli::Cli, ress::install_ress_subprotocol};
+use clap::{Args, Parser};
+use reth::{
+    args::{RessArgs, SnapArgs},
+    cli::Cli,
+    ress::install_ress_subprotocol,
+    snap::install_snap_subprotocol,
+};
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+use reth_exex_bridge::{
+    BridgeArgs, BridgeIndexer, BridgeRequestsApiServer, BridgeRequestsDb, BridgeRequestsRpc,
+};
 use reth_node_builder::NodeHandle;
 use reth_node_ethereum::EthereumNode;
+use reth_rpc_server_types::RethRpcModule;
 use tracing::info;
 
+/// Additional arguments of the `node` command.
+#[derive(Debug, Clone, Args)]
+struct NodeExtArgs {
+    #[command(flatten)]
+    ress: RessArgs,
+    #[command(flatten)]
+    bridge: BridgeArgs,
+    #[command(flatten)]
+    snap: SnapArgs,
+}
+
 fn main() {
     reth_cli_util::sigsegv_handler::install();
 
@@ -19,10 +39,41 @@ fn main() {
     }
 
     if let Err(err) =
-        Cli::<EthereumChainSpecParser, RessArgs>::parse().run(async move |builder, ress_args| {
+        Cli::<EthereumChainSpecParser, NodeExtArgs>::parse().run(async move |builder, args| {
+            let NodeExtArgs { ress: ress_args, bridge: bridge_args, snap: snap_args } = args;
+
+            // Open the bridge request database shared by the indexer and its RPC.
+            let bridge_db = bridge_args
+                .index
+                .then(|| BridgeRequestsDb::open(builder.config().datadir().bridge_requests()))
+                .transpose()?;
+
             info!(target: "reth::cli", "Launching node");
-            let NodeHandle { node, node_exit_future } =
-                builder.node(EthereumNode::default()).launch_with_debug_capabilities().await?;
+            let NodeHandle { node, node_exit_future } = builder
+                .node(EthereumNode::default())
+                .install_exex_if(bridge_db.is_some(), "bridge-indexer", {
+                    let bridge_db = bridge_db.clone();
+                    async move |ctx| {
+                        let db = bridge_db.expect("bridge indexing is enabled");
+                        Ok(BridgeIndexer::new(ctx, db, bridge_args.contract)?.run())
+                    }
+                })
+                .extend_rpc_modules(move |ctx| {
+                    if let Some(db) = bridge_db {
+                        ctx.modules.merge_if_module_configured(
+                            RethRpcModule::Bridge,
+                            BridgeRequestsRpc::new(db).into_rpc(),
+                        )?;
+                    }
+                    Ok(())
+                })
+                .launch_with_debug_capabilities()
+                .await?;
+
+            // Install snap subprotocol.
+            if snap_args.serve {
+                install_snap_subprotocol(snap_args, node.provider.clone(), node.network.clone());
+            }
 
             // Install ress subprotocol.
             if ress_args.enabled {
@@ -0,0 +1,26 @@
+use reth_network::{protocol::IntoRlpxSubProtocol, NetworkProtocols};
+use reth_network_api::FullNetwork;
+use reth_node_core::args::SnapArgs;
+use reth_provider::providers::{BlockchainProvider, ProviderNodeTypes};
+use reth_snap_protocol::SnapProtocolHandler;
+use reth_snap_provider::RethSnapProtocolProvider;
+use tracing::*;
+
+/// Install `snap` subprotocol to serve the state of recent blocks to peers.
+pub fn install_snap_subprotocol<P, N>(args: SnapArgs, provider: BlockchainProvider<P>, network: N)
+where
+    P: ProviderNodeTypes,
+    N: FullNetwork + NetworkProtocols,
+{
+    info!(target: "reth::cli", "Installing snap subprotocol");
+    let provider = RethSnapProtocolProvider::new(provider, args.max_state_depth);
+    network.add_rlpx_sub_protocol(
+        SnapProtocolHandler::new(
+            provider,
+            network.peers_handle().clone(),
+            args.max_active_connections,
+        )
+        .into_rlpx_sub_protocol(),
+    );
+    info!(target: "reth::cli", "Snap subprotocol support enabled");
+}
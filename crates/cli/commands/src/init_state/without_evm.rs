@@ -48,7 +48,8 @@ where
 
     append_first_block(provider_rw, &header, total_difficulty)?;
 
-    for stage in StageId::ALL {
+    // optional stages create their checkpoint once they run
+    for stage in StageId::ALL.into_iter().filter(|stage| !stage.is_optional()) {
         provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(header.number()))?;
     }
 
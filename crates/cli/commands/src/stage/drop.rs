@@ -149,6 +149,32 @@ impl<C: ChainSpecParser> Command<C> {
 
                 insert_genesis_history(&provider_rw, self.env.chain.genesis().alloc.iter())?;
             }
+            StageEnum::AddressAppearances => {
+                tx.clear::<tables::AddressAppearances>()?;
+                reset_prune_checkpoint(tx, PruneSegment::AddressAppearances)?;
+
+                // The index is optional, so the checkpoint is removed rather than reset to signal
+                // that the index is not available until the stage runs again.
+                tx.delete::<tables::StageCheckpoints>(
+                    StageId::IndexAddressAppearances.to_string(),
+                    None,
+                )?;
+            }
+            StageEnum::LogIndex => {
+                tx.clear::<tables::LogAddressIndex>()?;
+                tx.clear::<tables::LogTopicIndex>()?;
+                reset_prune_checkpoint(tx, PruneSegment::LogIndex)?;
+
+                // Same as for the address appearances, the index is optional.
+                tx.delete::<tables::StageCheckpoints>(StageId::IndexLogs.to_string(), None)?;
+            }
+            StageEnum::TraceIndex => {
+                tx.clear::<tables::TraceAddressIndex>()?;
+                tx.clear::<tables::BlockTraceAddresses>()?;
+                reset_prune_checkpoint(tx, PruneSegment::TraceIndex)?;
+
+                tx.delete::<tables::StageCheckpoints>(StageId::IndexTraces.to_string(), None)?;
+            }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
                 reset_prune_checkpoint(tx, PruneSegment::TransactionLookup)?;
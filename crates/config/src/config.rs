@@ -122,6 +122,12 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Appearances stage configuration.
+    pub index_address_appearances: IndexAddressAppearancesConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
+    /// Index Traces stage configuration.
+    pub index_traces: IndexTracesConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -388,6 +394,66 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Address appearance index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexAddressAppearancesConfig {
+    /// Whether the stage is part of the pipeline.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexAddressAppearancesConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexLogsConfig {
+    /// Whether the stage is part of the pipeline.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Trace index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexTracesConfig {
+    /// Whether the stage is part of the pipeline.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    ///
+    /// The blocks are re-executed to collect their traces, so this is lower than for the other
+    /// index stages.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexTracesConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 10_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -425,6 +491,9 @@ impl PruneConfig {
                     receipts,
                     account_history,
                     storage_history,
+                    address_appearances,
+                    log_index,
+                    trace_index,
                     receipts_log_filter,
                 },
         } = other;
@@ -440,6 +509,10 @@ impl PruneConfig {
         self.segments.receipts = self.segments.receipts.or(receipts);
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
+        self.segments.address_appearances =
+            self.segments.address_appearances.or(address_appearances);
+        self.segments.log_index = self.segments.log_index.or(log_index);
+        self.segments.trace_index = self.segments.trace_index.or(trace_index);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -960,6 +1033,9 @@ receipts = 'full'
                 receipts: Some(PruneMode::Distance(1000)),
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
+                address_appearances: None,
+                log_index: Some(PruneMode::Distance(6000)),
+                trace_index: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -975,6 +1051,9 @@ receipts = 'full'
                 receipts: Some(PruneMode::Full),
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
+                address_appearances: Some(PruneMode::Distance(4000)),
+                log_index: Some(PruneMode::Distance(5000)),
+                trace_index: Some(PruneMode::Distance(7000)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
@@ -993,6 +1072,9 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.address_appearances, Some(PruneMode::Distance(4000)));
+        assert_eq!(config1.segments.log_index, Some(PruneMode::Distance(6000)));
+        assert_eq!(config1.segments.trace_index, Some(PruneMode::Distance(7000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
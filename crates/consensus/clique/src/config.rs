@@ -0,0 +1,180 @@
+//! Clique configuration.
+
+use crate::{
+    seal::{EXTRA_SEAL, EXTRA_VANITY},
+    CliqueError,
+};
+use alloy_genesis::Genesis;
+use alloy_primitives::Address;
+use std::collections::{btree_map, BTreeMap};
+
+/// Genesis config field with the authority sets, keyed by the block number they become active at.
+///
+/// ```json
+/// "cliqueAuthorities": {
+///     "0": ["0x..", "0x.."],
+///     "100000": ["0x..", "0x..", "0x.."]
+/// }
+/// ```
+pub const AUTHORITIES_FIELD: &str = "cliqueAuthorities";
+
+/// Configuration of the clique consensus.
+///
+/// Blocks are sealed by a rotating set of authorities. The in-turn signer of block `n` is the
+/// authority at index `n % len` of the active set and may seal the block `period` seconds after
+/// its parent. Every other authority backs it up with an additional `period` seconds of delay per
+/// position it is away from the in-turn signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliqueConfig {
+    /// Minimum number of seconds between a block and its parent.
+    pub period: u64,
+    /// Authority sets, keyed by the block number they become active at.
+    pub authorities: BTreeMap<u64, Vec<Address>>,
+}
+
+impl CliqueConfig {
+    /// Reads the configuration from the genesis.
+    ///
+    /// The block period is taken from the `clique` section of the genesis config. The authority
+    /// sets are read from [`AUTHORITIES_FIELD`], the initial set defaults to the signers listed
+    /// in the genesis extra-data.
+    pub fn from_genesis(genesis: &Genesis) -> Result<Self, CliqueError> {
+        let period = genesis
+            .config
+            .clique
+            .as_ref()
+            .and_then(|clique| clique.period)
+            .ok_or(CliqueError::MissingPeriod)?;
+
+        let mut authorities: BTreeMap<u64, Vec<Address>> =
+            match genesis.config.extra_fields.get_deserialized(AUTHORITIES_FIELD) {
+                Some(authorities) => {
+                    authorities.map_err(|err| CliqueError::InvalidAuthorities(err.to_string()))?
+                }
+                None => BTreeMap::new(),
+            };
+        if let btree_map::Entry::Vacant(entry) = authorities.entry(0) {
+            entry.insert(genesis_signers(genesis)?);
+        }
+
+        Self::new(period, authorities)
+    }
+
+    /// Creates a new configuration, the authority set at block 0 is required.
+    pub fn new(period: u64, authorities: BTreeMap<u64, Vec<Address>>) -> Result<Self, CliqueError> {
+        if period == 0 {
+            return Err(CliqueError::ZeroPeriod)
+        }
+        if !authorities.contains_key(&0) {
+            return Err(CliqueError::InvalidAuthorities("missing initial authority set".into()))
+        }
+        if let Some((number, _)) = authorities.iter().find(|(_, set)| set.is_empty()) {
+            return Err(CliqueError::InvalidAuthorities(format!(
+                "empty authority set at block {number}"
+            )))
+        }
+
+        Ok(Self { period, authorities })
+    }
+
+    /// Returns the authority set that seals the block with the given number.
+    pub fn authorities_at(&self, number: u64) -> &[Address] {
+        self.authorities.range(..=number).next_back().map(|(_, set)| set.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns the in-turn signer of the block with the given number.
+    pub fn in_turn_signer(&self, number: u64) -> Option<Address> {
+        let authorities = self.authorities_at(number);
+        authorities.get((number % authorities.len() as u64) as usize).copied()
+    }
+
+    /// Returns how many positions the signer is away from the in-turn signer of the block, `0` if
+    /// it is in turn, or `None` if it is not an authority.
+    pub fn signer_rank(&self, number: u64, signer: Address) -> Option<u64> {
+        let authorities = self.authorities_at(number);
+        let len = authorities.len() as u64;
+        let index = authorities.iter().position(|authority| *authority == signer)? as u64;
+        Some((index + len - number % len) % len)
+    }
+
+    /// Returns the earliest timestamp a signer with the given rank may seal a block at.
+    pub const fn earliest_timestamp(&self, parent_timestamp: u64, rank: u64) -> u64 {
+        parent_timestamp + self.period * (rank + 1)
+    }
+}
+
+/// Returns the signers listed in the genesis extra-data between the vanity and the seal.
+fn genesis_signers(genesis: &Genesis) -> Result<Vec<Address>, CliqueError> {
+    let extra_data = &genesis.extra_data;
+    let signers = extra_data
+        .len()
+        .checked_sub(EXTRA_VANITY + EXTRA_SEAL)
+        .filter(|len| len % Address::len_bytes() == 0)
+        .ok_or_else(|| {
+            CliqueError::InvalidAuthorities("malformed signer list in genesis extra-data".into())
+        })?;
+
+    Ok(extra_data[EXTRA_VANITY..EXTRA_VANITY + signers]
+        .chunks_exact(Address::len_bytes())
+        .map(Address::from_slice)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const ALICE: Address = address!("0x000000000000000000000000000000000000a11c");
+    const BOB: Address = address!("0x0000000000000000000000000000000000000b0b");
+    const CAROL: Address = address!("0x00000000000000000000000000000000000ca201");
+
+    #[test]
+    fn rotates_authorities() {
+        let config = CliqueConfig::new(
+            5,
+            BTreeMap::from([(0, vec![ALICE, BOB]), (10, vec![ALICE, BOB, CAROL])]),
+        )
+        .unwrap();
+
+        assert_eq!(config.in_turn_signer(1), Some(BOB));
+        assert_eq!(config.in_turn_signer(2), Some(ALICE));
+        assert_eq!(config.signer_rank(1, BOB), Some(0));
+        assert_eq!(config.signer_rank(1, ALICE), Some(1));
+        assert_eq!(config.signer_rank(1, CAROL), None);
+
+        // the third authority joins at block 10
+        assert_eq!(config.in_turn_signer(11), Some(CAROL));
+        assert_eq!(config.signer_rank(11, ALICE), Some(1));
+        assert_eq!(config.signer_rank(11, BOB), Some(2));
+
+        assert_eq!(config.earliest_timestamp(100, 0), 105);
+        assert_eq!(config.earliest_timestamp(100, 2), 115);
+    }
+
+    #[test]
+    fn from_genesis() {
+        let mut extra_data = vec![0u8; EXTRA_VANITY];
+        extra_data.extend_from_slice(ALICE.as_slice());
+        extra_data.extend_from_slice(BOB.as_slice());
+        extra_data.extend_from_slice(&[0u8; EXTRA_SEAL]);
+
+        let genesis: Genesis = serde_json::from_value(serde_json::json!({
+            "config": {
+                "chainId": 1337,
+                "clique": { "period": 2, "epoch": 30000 },
+                "cliqueAuthorities": { "100": [CAROL] }
+            },
+            "extraData": alloy_primitives::hex::encode_prefixed(&extra_data),
+            "gasLimit": "0x1c9c380",
+            "difficulty": "0x0",
+            "alloc": {}
+        }))
+        .unwrap();
+
+        let config = CliqueConfig::from_genesis(&genesis).unwrap();
+        assert_eq!(config.period, 2);
+        assert_eq!(config.authorities_at(99), &[ALICE, BOB]);
+        assert_eq!(config.authorities_at(100), &[CAROL]);
+    }
+}
@@ -0,0 +1,206 @@
+//! Clique consensus implementation.
+
+use crate::{
+    seal::{recover_signer, EXTRA_SEAL, EXTRA_VANITY},
+    CliqueConfig, CliqueError,
+};
+use alloy_consensus::Header;
+use alloy_primitives::Bytes;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
+use reth_ethereum_consensus::EthBeaconConsensus;
+use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
+use std::{fmt::Debug, sync::Arc, time::SystemTime};
+
+/// Clique proof-of-authority consensus.
+///
+/// Headers carry the seal of an authority at the end of their extra-data, see
+/// [`CliqueConfig`] for the turn order. All other rules are the ones of [`EthBeaconConsensus`].
+#[derive(Debug, Clone)]
+pub struct CliqueConsensus<ChainSpec> {
+    /// The ethereum consensus rules.
+    inner: EthBeaconConsensus<ChainSpec>,
+    /// The clique configuration.
+    config: Arc<CliqueConfig>,
+}
+
+impl<ChainSpec: EthChainSpec + EthereumHardforks> CliqueConsensus<ChainSpec> {
+    /// Creates a new instance with the configuration of the chain spec's genesis.
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Result<Self, CliqueError> {
+        let config = CliqueConfig::from_genesis(chain_spec.genesis())?;
+        Ok(Self::with_config(chain_spec, Arc::new(config)))
+    }
+
+    /// Creates a new instance with the given configuration.
+    pub const fn with_config(chain_spec: Arc<ChainSpec>, config: Arc<CliqueConfig>) -> Self {
+        Self { inner: EthBeaconConsensus::new(chain_spec), config }
+    }
+
+    /// Returns the clique configuration.
+    pub const fn config(&self) -> &Arc<CliqueConfig> {
+        &self.config
+    }
+}
+
+impl<ChainSpec, N> FullConsensus<N> for CliqueConsensus<ChainSpec>
+where
+    ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug,
+    N: NodePrimitives<BlockHeader = Header>,
+{
+    fn validate_block_post_execution(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+    ) -> Result<(), ConsensusError> {
+        FullConsensus::<N>::validate_block_post_execution(&self.inner, block, result)
+    }
+}
+
+impl<B, ChainSpec> Consensus<B> for CliqueConsensus<ChainSpec>
+where
+    B: Block<Header = Header>,
+    ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug,
+{
+    type Error = ConsensusError;
+
+    fn validate_body_against_header(
+        &self,
+        body: &B::Body,
+        header: &SealedHeader<B::Header>,
+    ) -> Result<(), Self::Error> {
+        Consensus::<B>::validate_body_against_header(&self.inner, body, header)
+    }
+
+    fn validate_block_pre_execution(&self, block: &SealedBlock<B>) -> Result<(), Self::Error> {
+        Consensus::<B>::validate_block_pre_execution(&self.inner, block)
+    }
+}
+
+impl<ChainSpec> HeaderValidator<Header> for CliqueConsensus<ChainSpec>
+where
+    ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug,
+{
+    fn validate_header(&self, header: &SealedHeader<Header>) -> Result<(), ConsensusError> {
+        let len = header.extra_data.len();
+        if len != EXTRA_VANITY + EXTRA_SEAL {
+            return Err(CliqueError::InvalidExtraDataLength {
+                len,
+                expected: EXTRA_VANITY + EXTRA_SEAL,
+            }
+            .into())
+        }
+
+        // the seal exceeds the maximum extra-data size, the ethereum rules are checked without it
+        let mut unsealed = header.header().clone();
+        unsealed.extra_data = Bytes::copy_from_slice(&header.extra_data[..EXTRA_VANITY]);
+        self.inner.validate_header(&SealedHeader::new(unsealed, header.hash()))?;
+
+        // the turn order is only enforced against the parent, which bounds how early a block can
+        // be sealed, the future bound prevents signers from skipping ahead of their turn
+        let present_timestamp =
+            SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+        if header.timestamp >
+            present_timestamp + alloy_eips::merge::ALLOWED_FUTURE_BLOCK_TIME_SECONDS
+        {
+            return Err(ConsensusError::TimestampIsInFuture {
+                timestamp: header.timestamp,
+                present_timestamp,
+            })
+        }
+
+        let signer = recover_signer(header)?;
+        if self.config.signer_rank(header.number, signer).is_none() {
+            return Err(CliqueError::UnauthorizedSigner { number: header.number, signer }.into())
+        }
+
+        Ok(())
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader<Header>,
+        parent: &SealedHeader<Header>,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_header_against_parent(header, parent)?;
+
+        let signer = recover_signer(header)?;
+        let rank = self
+            .config
+            .signer_rank(header.number, signer)
+            .ok_or(CliqueError::UnauthorizedSigner { number: header.number, signer })?;
+        let earliest = self.config.earliest_timestamp(parent.timestamp, rank);
+        if header.timestamp < earliest {
+            return Err(CliqueError::SealedBeforeTurn {
+                number: header.number,
+                signer,
+                timestamp: header.timestamp,
+                earliest,
+            }
+            .into())
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::seal_header;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder};
+    use std::collections::BTreeMap;
+
+    fn sealed(mut header: Header, signer: &PrivateKeySigner) -> SealedHeader<Header> {
+        seal_header(&mut header, signer).unwrap();
+        SealedHeader::seal_slow(header)
+    }
+
+    #[test]
+    fn validates_turn_order() {
+        let alice = PrivateKeySigner::random();
+        let bob = PrivateKeySigner::random();
+        let mallory = PrivateKeySigner::random();
+        let config =
+            CliqueConfig::new(5, BTreeMap::from([(0, vec![alice.address(), bob.address()])]))
+                .unwrap();
+        let chain_spec: Arc<ChainSpec> = Arc::new(ChainSpecBuilder::mainnet().build());
+        let consensus = CliqueConsensus::with_config(chain_spec, Arc::new(config));
+
+        let parent = sealed(
+            Header { number: 1, timestamp: 100, gas_limit: 30_000_000, ..Default::default() },
+            &bob,
+        );
+        let child = |timestamp, signer| {
+            sealed(
+                Header {
+                    number: 2,
+                    timestamp,
+                    parent_hash: parent.hash(),
+                    gas_limit: parent.gas_limit,
+                    ..Default::default()
+                },
+                signer,
+            )
+        };
+
+        // alice is in turn for block 2
+        let header = child(105, &alice);
+        assert_eq!(consensus.validate_header_against_parent(&header, &parent), Ok(()));
+        let header = child(104, &alice);
+        assert!(consensus.validate_header_against_parent(&header, &parent).is_err());
+
+        // bob backs alice up one period later
+        let header = child(109, &bob);
+        assert!(consensus.validate_header_against_parent(&header, &parent).is_err());
+        let header = child(110, &bob);
+        assert_eq!(consensus.validate_header_against_parent(&header, &parent), Ok(()));
+
+        let header = child(200, &mallory);
+        assert_eq!(
+            consensus.validate_header_against_parent(&header, &parent),
+            Err(CliqueError::UnauthorizedSigner { number: 2, signer: mallory.address() }.into())
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use alloy_primitives::Address;
+use reth_consensus::ConsensusError;
+
+/// Errors of the clique consensus.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CliqueError {
+    /// The genesis config has no clique block period.
+    #[error("missing clique block period in the genesis config")]
+    MissingPeriod,
+    /// The clique block period is zero.
+    #[error("clique block period must be greater than zero")]
+    ZeroPeriod,
+    /// The authority sets of the genesis config are invalid.
+    #[error("invalid clique authorities: {0}")]
+    InvalidAuthorities(String),
+    /// The extra-data doesn't consist of the vanity and the seal.
+    #[error("invalid extra-data length {len}, expected {expected}")]
+    InvalidExtraDataLength {
+        /// The length of the extra-data.
+        len: usize,
+        /// The expected length.
+        expected: usize,
+    },
+    /// The seal is not a valid signature.
+    #[error("invalid seal signature")]
+    InvalidSeal,
+    /// The block was sealed by a signer that is not an authority.
+    #[error("block {number} sealed by {signer}, which is not an authority")]
+    UnauthorizedSigner {
+        /// The number of the block.
+        number: u64,
+        /// The signer of the seal.
+        signer: Address,
+    },
+    /// The block was sealed before the turn of its signer.
+    #[error("block {number} sealed by {signer} at {timestamp}, before its turn at {earliest}")]
+    SealedBeforeTurn {
+        /// The number of the block.
+        number: u64,
+        /// The signer of the seal.
+        signer: Address,
+        /// The timestamp of the block.
+        timestamp: u64,
+        /// The earliest timestamp the signer may seal the block at.
+        earliest: u64,
+    },
+    /// Signing the seal failed.
+    #[error("failed to sign seal: {0}")]
+    Signing(String),
+}
+
+impl From<CliqueError> for ConsensusError {
+    fn from(err: CliqueError) -> Self {
+        Self::Other(err.to_string())
+    }
+}
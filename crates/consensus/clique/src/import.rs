@@ -0,0 +1,97 @@
+//! Block propagation of clique blocks over devp2p.
+
+use reth_network::{
+    import::{
+        BlockImport, BlockImportError, BlockImportEvent, BlockImportOutcome, BlockValidation,
+        NewBlockEvent,
+    },
+    message::NewBlockMessage,
+    types::NewBlock,
+};
+use reth_network_peers::PeerId;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+/// A block announced by a peer.
+pub type PeerBlock<B> = (PeerId, NewBlockMessage<NewBlock<B>>);
+
+/// [`BlockImport`] of the network that hands the blocks announced by peers to the
+/// [`CliqueSealer`](crate::CliqueSealer), which imports them into the engine and reports the
+/// outcome back. Blocks sealed locally are announced through it as well.
+///
+/// The network has to run in proof-of-work mode for blocks to be propagated.
+#[derive(Debug)]
+pub struct CliqueBlockImport<B> {
+    /// Sends the announced blocks to the sealer.
+    to_sealer: mpsc::UnboundedSender<PeerBlock<B>>,
+    /// Receives the outcomes of imports and the blocks to announce.
+    events: mpsc::UnboundedReceiver<BlockImportEvent<NewBlock<B>>>,
+}
+
+impl<B> CliqueBlockImport<B> {
+    /// Creates a new block import and the handle for the sealer.
+    pub fn new() -> (Self, CliqueBlockImportHandle<B>) {
+        let (to_sealer, from_network) = mpsc::unbounded_channel();
+        let (events_tx, events) = mpsc::unbounded_channel();
+        (Self { to_sealer, events }, CliqueBlockImportHandle { from_network, events: events_tx })
+    }
+}
+
+impl<B> BlockImport<NewBlock<B>> for CliqueBlockImport<B>
+where
+    B: std::fmt::Debug + Send + Sync,
+{
+    fn on_new_block(&mut self, peer_id: PeerId, incoming_block: NewBlockEvent<NewBlock<B>>) {
+        match incoming_block {
+            NewBlockEvent::Block(block) => {
+                let _ = self.to_sealer.send((peer_id, block));
+            }
+            // blocks that are only announced by hash are not fetched, the engine downloads missing
+            // ancestors once a descendant is imported
+            NewBlockEvent::Hashes(_) => {}
+        }
+    }
+
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<BlockImportEvent<NewBlock<B>>> {
+        match self.events.poll_recv(cx) {
+            Poll::Ready(Some(event)) => Poll::Ready(event),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+/// Handle of the [`CliqueSealer`](crate::CliqueSealer) to the [`CliqueBlockImport`] of the
+/// network.
+#[derive(Debug)]
+pub struct CliqueBlockImportHandle<B> {
+    /// Receives the blocks announced by peers.
+    from_network: mpsc::UnboundedReceiver<PeerBlock<B>>,
+    /// Sends the outcomes of imports and the blocks to announce.
+    events: mpsc::UnboundedSender<BlockImportEvent<NewBlock<B>>>,
+}
+
+impl<B> CliqueBlockImportHandle<B> {
+    /// Returns the next block announced by a peer.
+    pub(crate) async fn next_block(&mut self) -> Option<PeerBlock<B>> {
+        self.from_network.recv().await
+    }
+
+    /// Announces a block that was sealed locally to the peers.
+    pub(crate) fn announce(&self, block: NewBlockMessage<NewBlock<B>>) {
+        let _ = self
+            .events
+            .send(BlockImportEvent::Announcement(BlockValidation::ValidHeader { block }));
+    }
+
+    /// Reports the outcome of importing a block announced by the peer.
+    ///
+    /// Valid blocks are relayed to the other peers, the peer of an invalid block is penalized.
+    pub(crate) fn on_outcome(
+        &self,
+        peer: PeerId,
+        result: Result<NewBlockMessage<NewBlock<B>>, BlockImportError>,
+    ) {
+        let result = result.map(|block| BlockValidation::ValidHeader { block });
+        let _ = self.events.send(BlockImportEvent::Outcome(BlockImportOutcome { peer, result }));
+    }
+}
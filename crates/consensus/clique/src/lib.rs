@@ -0,0 +1,30 @@
+//! Clique-style proof-of-authority consensus.
+//!
+//! Blocks are sealed by a rotating set of authorities, which sign the header and store the seal at
+//! the end of the extra-data. The [`CliqueConsensus`] verifies the seals and the turn order, the
+//! [`CliqueSealer`] drives the payload builder of the local authority through the engine and
+//! propagates the sealed blocks over devp2p with the [`CliqueBlockImport`] of the network.
+//!
+//! The chain is configured through the genesis, see [`CliqueConfig::from_genesis`]. Blocks are
+//! built and executed like post-merge ethereum blocks, so the merge has to be active at genesis.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+pub mod config;
+pub mod consensus;
+mod error;
+pub mod import;
+pub mod seal;
+pub mod sealer;
+
+pub use config::CliqueConfig;
+pub use consensus::CliqueConsensus;
+pub use error::CliqueError;
+pub use import::{CliqueBlockImport, CliqueBlockImportHandle};
+pub use sealer::CliqueSealer;
@@ -0,0 +1,99 @@
+//! Signer seals in the header extra-data.
+
+use crate::CliqueError;
+use alloy_consensus::Header;
+use alloy_primitives::{Address, Bytes, Signature, B256, U256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use reth_primitives_traits::crypto::secp256k1::recover_signer_unchecked;
+
+/// Number of extra-data bytes reserved for the signer vanity.
+pub const EXTRA_VANITY: usize = 32;
+
+/// Number of extra-data bytes reserved for the signer seal.
+pub const EXTRA_SEAL: usize = 65;
+
+/// Returns the hash that is signed by the seal: the hash of the header without the seal.
+pub fn seal_hash(header: &Header) -> Result<B256, CliqueError> {
+    let len = header.extra_data.len();
+    if len < EXTRA_SEAL {
+        return Err(CliqueError::InvalidExtraDataLength {
+            len,
+            expected: EXTRA_VANITY + EXTRA_SEAL,
+        })
+    }
+
+    let mut unsealed = header.clone();
+    unsealed.extra_data = Bytes::copy_from_slice(&header.extra_data[..len - EXTRA_SEAL]);
+    Ok(unsealed.hash_slow())
+}
+
+/// Recovers the signer of the seal at the end of the extra-data.
+pub fn recover_signer(header: &Header) -> Result<Address, CliqueError> {
+    let hash = seal_hash(header)?;
+    let seal = &header.extra_data[header.extra_data.len() - EXTRA_SEAL..];
+
+    // the recovery id is stored as 0 or 1
+    let y_parity = match seal[64] {
+        0 => false,
+        1 => true,
+        _ => return Err(CliqueError::InvalidSeal),
+    };
+    let signature = Signature::new(
+        U256::from_be_slice(&seal[..32]),
+        U256::from_be_slice(&seal[32..64]),
+        y_parity,
+    );
+    recover_signer_unchecked(&signature, hash).map_err(|_| CliqueError::InvalidSeal)
+}
+
+/// Seals the header with the signer.
+///
+/// The extra-data of the header is padded or truncated to the vanity, followed by the seal.
+pub fn seal_header(header: &mut Header, signer: &PrivateKeySigner) -> Result<(), CliqueError> {
+    let mut extra_data = [0u8; EXTRA_VANITY + EXTRA_SEAL];
+    let vanity = header.extra_data.len().min(EXTRA_VANITY);
+    extra_data[..vanity].copy_from_slice(&header.extra_data[..vanity]);
+    header.extra_data = Bytes::copy_from_slice(&extra_data);
+
+    let signature = signer
+        .sign_hash_sync(&seal_hash(header)?)
+        .map_err(|err| CliqueError::Signing(err.to_string()))?;
+    extra_data[EXTRA_VANITY..EXTRA_VANITY + 32].copy_from_slice(&signature.r().to_be_bytes::<32>());
+    extra_data[EXTRA_VANITY + 32..EXTRA_VANITY + 64]
+        .copy_from_slice(&signature.s().to_be_bytes::<32>());
+    extra_data[EXTRA_VANITY + 64] = signature.v() as u8;
+    header.extra_data = Bytes::copy_from_slice(&extra_data);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_roundtrip() {
+        let signer = PrivateKeySigner::random();
+        let mut header = Header {
+            number: 1,
+            extra_data: Bytes::from_static(b"reth/clique"),
+            ..Default::default()
+        };
+
+        seal_header(&mut header, &signer).unwrap();
+        assert_eq!(header.extra_data.len(), EXTRA_VANITY + EXTRA_SEAL);
+        assert!(header.extra_data.starts_with(b"reth/clique"));
+        assert_eq!(recover_signer(&header).unwrap(), signer.address());
+
+        // any change to the header invalidates the seal
+        header.number = 2;
+        assert_ne!(recover_signer(&header), Ok(signer.address()));
+
+        header.extra_data = Bytes::from_static(b"unsealed");
+        assert_eq!(
+            recover_signer(&header),
+            Err(CliqueError::InvalidExtraDataLength { len: 8, expected: 97 })
+        );
+    }
+}
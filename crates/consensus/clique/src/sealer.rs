@@ -0,0 +1,521 @@
+//! In-process sealer of clique blocks.
+
+use crate::{
+    import::{CliqueBlockImportHandle, PeerBlock},
+    seal::seal_header,
+    CliqueConfig,
+};
+use alloy_consensus::Header;
+use alloy_primitives::{B256, U128};
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatusEnum};
+use alloy_signer_local::PrivateKeySigner;
+use eyre::OptionExt;
+use futures_util::{future, StreamExt};
+use reth_engine_primitives::BeaconConsensusEngineHandle;
+use reth_network::{import::BlockImportError, message::NewBlockMessage, types::NewBlock};
+use reth_network_peers::PeerId;
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_payload_primitives::{
+    BuiltPayload, EngineApiMessageVersion, PayloadAttributesBuilder, PayloadKind, PayloadTypes,
+};
+use reth_primitives_traits::{NodePrimitives, SealedBlock, SealedHeader};
+use reth_provider::{BlockNumReader, CanonStateSubscriptions, HeaderProvider};
+use std::{
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+use tracing::{debug, error, info, warn};
+
+/// The block type of the payloads built for the sealer.
+type BlockOf<T> =
+    <<<T as PayloadTypes>::BuiltPayload as BuiltPayload>::Primitives as NodePrimitives>::Block;
+
+/// Seals blocks of the local authority in turn order and imports the blocks of the other
+/// authorities.
+///
+/// Whenever the canonical head changes, the sealer waits for the turn of its signer on top of
+/// it, see [`CliqueConfig`], builds a payload through the engine, seals it and makes it canonical.
+/// Blocks announced by peers through the [`CliqueBlockImport`](crate::CliqueBlockImport) are
+/// inserted into the engine and become canonical if they extend the longest chain.
+///
+/// Clique has no finality, the safe and finalized blocks of the forkchoice state are not set.
+#[derive(Debug)]
+pub struct CliqueSealer<T: PayloadTypes, B, P> {
+    /// Provider of the canonical chain.
+    provider: P,
+    /// The clique configuration.
+    config: Arc<CliqueConfig>,
+    /// The signer of the local authority.
+    signer: PrivateKeySigner,
+    /// The payload attribute builder for the engine.
+    payload_attributes_builder: B,
+    /// Sender for events to engine.
+    to_engine: BeaconConsensusEngineHandle<T>,
+    /// The payload builder for the engine.
+    payload_builder: PayloadBuilderHandle<T>,
+    /// Handle to the block import of the network, if blocks are propagated.
+    import: Option<CliqueBlockImportHandle<BlockOf<T>>>,
+}
+
+impl<T, B, P> CliqueSealer<T, B, P>
+where
+    T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives: NodePrimitives<BlockHeader = Header>>>,
+    B: PayloadAttributesBuilder<T::PayloadAttributes>,
+    P: HeaderProvider<Header = Header> + BlockNumReader + CanonStateSubscriptions,
+{
+    /// Creates a new [`CliqueSealer`] that seals blocks with the given signer.
+    pub const fn new(
+        provider: P,
+        config: Arc<CliqueConfig>,
+        signer: PrivateKeySigner,
+        payload_attributes_builder: B,
+        to_engine: BeaconConsensusEngineHandle<T>,
+        payload_builder: PayloadBuilderHandle<T>,
+    ) -> Self {
+        Self {
+            provider,
+            config,
+            signer,
+            payload_attributes_builder,
+            to_engine,
+            payload_builder,
+            import: None,
+        }
+    }
+
+    /// Configures the handle to the block import of the network, which propagates the sealed
+    /// blocks and imports the blocks of peers.
+    pub fn with_block_import(mut self, import: CliqueBlockImportHandle<BlockOf<T>>) -> Self {
+        self.import = Some(import);
+        self
+    }
+
+    /// Runs the [`CliqueSealer`] in a loop, sealing blocks in turn and importing the blocks of
+    /// peers.
+    pub async fn run(mut self) {
+        let mut canon_state = self.provider.canonical_state_stream();
+        loop {
+            let head = match self.head() {
+                Ok(head) => head,
+                Err(err) => {
+                    error!(target: "consensus::clique", %err, "Failed to read the canonical head");
+                    tokio::time::sleep(Duration::from_secs(self.config.period)).await;
+                    continue
+                }
+            };
+            let turn = self
+                .config
+                .signer_rank(head.number + 1, self.signer.address())
+                .map(|rank| self.config.earliest_timestamp(head.timestamp, rank));
+
+            tokio::select! {
+                // Wait for the turn of the signer on top of the head
+                _ = wait_for_turn(turn) => {
+                    let timestamp = turn.unwrap_or_default().max(system_time());
+                    if let Err(err) = self.seal(&head, timestamp).await {
+                        error!(target: "consensus::clique", number = head.number + 1, %err, "Failed to seal block");
+                        tokio::time::sleep(Duration::from_secs(self.config.period)).await;
+                    }
+                }
+                // Wait for the canonical head to change, which starts the next turn
+                Some(_) = canon_state.next() => {}
+                // Wait for a peer to announce a block
+                block = next_block(&mut self.import) => {
+                    let Some((peer_id, block)) = block else {
+                        self.import = None;
+                        continue
+                    };
+                    self.on_peer_block(peer_id, block, &head).await;
+                }
+            }
+        }
+    }
+
+    /// Returns the canonical head.
+    fn head(&self) -> eyre::Result<SealedHeader<Header>> {
+        self.provider
+            .sealed_header(self.provider.best_block_number()?)?
+            .ok_or_eyre("missing canonical head")
+    }
+
+    /// Builds a block with the given timestamp on top of the head, seals it and makes it
+    /// canonical.
+    async fn seal(&self, head: &SealedHeader<Header>, timestamp: u64) -> eyre::Result<()> {
+        let mut attributes = self.payload_attributes_builder.build(timestamp);
+        self.payload_attributes_builder
+            .set_suggested_fee_recipient(&mut attributes, self.signer.address());
+
+        let res = self
+            .to_engine
+            .fork_choice_updated(
+                forkchoice_state(head.hash()),
+                Some(attributes),
+                EngineApiMessageVersion::default(),
+            )
+            .await?;
+        if !res.is_valid() {
+            eyre::bail!("Invalid payload status")
+        }
+        let payload_id = res.payload_id.ok_or_eyre("No payload id")?;
+
+        let Some(Ok(payload)) =
+            self.payload_builder.resolve_kind(payload_id, PayloadKind::WaitForPending).await
+        else {
+            eyre::bail!("No payload")
+        };
+
+        let (mut header, body) = payload.block().clone().split_header_body();
+        seal_header(&mut header, &self.signer)?;
+        let block = SealedBlock::<BlockOf<T>>::seal_parts(header, body);
+        let block_hash = block.hash();
+        let number = block.header().number;
+
+        let res = self.to_engine.new_payload(T::block_to_payload(block.clone())).await?;
+        if !res.is_valid() {
+            eyre::bail!("Invalid payload")
+        }
+        self.update_forkchoice_state(block_hash).await?;
+        info!(target: "consensus::clique", number, hash = %block_hash, "Sealed block");
+
+        if let Some(import) = &self.import {
+            // clique blocks have no difficulty
+            let block = NewBlock { block: block.into_block(), td: U128::ZERO };
+            import.announce(NewBlockMessage { hash: block_hash, block: Arc::new(block) });
+        }
+
+        Ok(())
+    }
+
+    /// Inserts a block announced by a peer into the engine and makes it canonical if it extends
+    /// the longest chain.
+    async fn on_peer_block(
+        &self,
+        peer_id: PeerId,
+        message: NewBlockMessage<NewBlock<BlockOf<T>>>,
+        head: &SealedHeader<Header>,
+    ) {
+        let block = SealedBlock::new_unchecked(message.block.block.clone(), message.hash);
+        let number = block.header().number;
+
+        let status = match self.to_engine.new_payload(T::block_to_payload(block)).await {
+            Ok(status) => status,
+            Err(err) => {
+                warn!(target: "consensus::clique", %peer_id, number, hash = %message.hash, %err, "Failed to insert block");
+                return
+            }
+        };
+
+        match status.status {
+            PayloadStatusEnum::Invalid { validation_error } => {
+                debug!(target: "consensus::clique", %peer_id, number, hash = %message.hash, %validation_error, "Received invalid block");
+                if let Some(import) = &self.import {
+                    import
+                        .on_outcome(peer_id, Err(BlockImportError::Other(validation_error.into())));
+                }
+            }
+            status => {
+                // if the parent is unknown, the engine downloads it for the forkchoice update
+                if number > head.number {
+                    if let Err(err) = self.update_forkchoice_state(message.hash).await {
+                        warn!(target: "consensus::clique", number, hash = %message.hash, %err, "Failed to update fork choice");
+                    }
+                }
+                if status.is_valid() {
+                    if let Some(import) = &self.import {
+                        import.on_outcome(peer_id, Ok(message));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends a FCU with the given head to the engine.
+    async fn update_forkchoice_state(&self, head_block_hash: B256) -> eyre::Result<()> {
+        let res = self
+            .to_engine
+            .fork_choice_updated(
+                forkchoice_state(head_block_hash),
+                None,
+                EngineApiMessageVersion::default(),
+            )
+            .await?;
+
+        if res.is_invalid() {
+            eyre::bail!("Invalid fork choice update")
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the forkchoice state with the given head.
+const fn forkchoice_state(head_block_hash: B256) -> ForkchoiceState {
+    ForkchoiceState {
+        head_block_hash,
+        safe_block_hash: B256::ZERO,
+        finalized_block_hash: B256::ZERO,
+    }
+}
+
+/// Returns the current system time in seconds since the UNIX epoch.
+fn system_time() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("cannot be earlier than UNIX_EPOCH")
+        .as_secs()
+}
+
+/// Waits until the given turn timestamp, or never resolves if the signer has no turn.
+async fn wait_for_turn(turn: Option<u64>) {
+    match turn {
+        Some(timestamp) => {
+            tokio::time::sleep(Duration::from_secs(timestamp.saturating_sub(system_time()))).await
+        }
+        None => future::pending().await,
+    }
+}
+
+/// Returns the next block announced by a peer, or never resolves if there is no block import.
+async fn next_block<B>(import: &mut Option<CliqueBlockImportHandle<B>>) -> Option<PeerBlock<B>> {
+    match import {
+        Some(import) => import.next_block().await,
+        None => future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{seal::recover_signer, CliqueBlockImport};
+    use alloy_primitives::{Address, U256};
+    use alloy_rpc_types_engine::{ExecutionData, PayloadAttributes, PayloadStatus};
+    use reth_engine_primitives::{BeaconEngineMessage, OnForkChoiceUpdated};
+    use reth_ethereum_engine_primitives::{
+        EthBuiltPayload, EthEngineTypes, EthPayloadBuilderAttributes,
+    };
+    use reth_ethereum_primitives::{Block, TransactionSigned};
+    use reth_network::import::{BlockImport, BlockImportEvent, BlockValidation, NewBlockEvent};
+    use reth_payload_builder::PayloadServiceCommand;
+    use reth_provider::test_utils::MockEthProvider;
+    use std::collections::{BTreeMap, HashMap};
+    use tokio::sync::{mpsc, oneshot};
+
+    const PERIOD: u64 = 10;
+
+    struct TestAttributesBuilder;
+
+    impl PayloadAttributesBuilder<PayloadAttributes> for TestAttributesBuilder {
+        fn build(&self, timestamp: u64) -> PayloadAttributes {
+            PayloadAttributes {
+                timestamp,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            }
+        }
+
+        fn set_suggested_fee_recipient(
+            &self,
+            attributes: &mut PayloadAttributes,
+            recipient: Address,
+        ) -> bool {
+            attributes.suggested_fee_recipient = recipient;
+            true
+        }
+    }
+
+    /// A node of an authority: the sealer and a stand-in for its engine and payload builder.
+    struct TestNode {
+        provider: MockEthProvider,
+        import: CliqueBlockImport<Block>,
+    }
+
+    impl TestNode {
+        fn spawn(
+            signer: PrivateKeySigner,
+            config: Arc<CliqueConfig>,
+            genesis: &SealedHeader,
+        ) -> Self {
+            let provider = MockEthProvider::default();
+            provider.add_header(genesis.hash(), genesis.header().clone());
+
+            let (to_engine, from_sealer) = mpsc::unbounded_channel();
+            let (to_payload_builder, from_payload_builder) = mpsc::unbounded_channel();
+            let (import, import_handle) = CliqueBlockImport::new();
+            let sealer = CliqueSealer::new(
+                provider.clone(),
+                config,
+                signer,
+                TestAttributesBuilder,
+                BeaconConsensusEngineHandle::<EthEngineTypes>::new(to_engine),
+                PayloadBuilderHandle::new(to_payload_builder),
+            )
+            .with_block_import(import_handle);
+
+            tokio::spawn(sealer.run());
+            tokio::spawn(run_engine(provider.clone(), from_sealer, from_payload_builder));
+            Self { provider, import }
+        }
+
+        async fn next_event(&mut self) -> BlockImportEvent<NewBlock<Block>> {
+            future::poll_fn(|cx| self.import.poll(cx)).await
+        }
+
+        /// Returns the next block the node announces to its peers.
+        async fn next_announcement(&mut self) -> NewBlockMessage<NewBlock<Block>> {
+            match self.next_event().await {
+                BlockImportEvent::Announcement(BlockValidation::ValidHeader { block }) => block,
+                event => panic!("unexpected event {event:?}"),
+            }
+        }
+
+        /// Hands a block announced by the peer to the node and returns the relayed block.
+        async fn import(
+            &mut self,
+            peer: PeerId,
+            block: NewBlockMessage<NewBlock<Block>>,
+        ) -> NewBlockMessage<NewBlock<Block>> {
+            self.import.on_new_block(peer, NewBlockEvent::Block(block));
+            match self.next_event().await {
+                BlockImportEvent::Outcome(outcome) => {
+                    assert_eq!(outcome.peer, peer);
+                    match outcome.result {
+                        Ok(BlockValidation::ValidHeader { block }) => block,
+                        result => panic!("unexpected outcome {result:?}"),
+                    }
+                }
+                event => panic!("unexpected event {event:?}"),
+            }
+        }
+
+        fn head(&self) -> SealedHeader {
+            self.provider
+                .sealed_header(self.provider.best_block_number().unwrap())
+                .unwrap()
+                .unwrap()
+        }
+    }
+
+    /// Stands in for the engine and the payload builder: payloads are built as empty blocks on
+    /// top of their parent, and inserted blocks become canonical with the next forkchoice update.
+    async fn run_engine(
+        provider: MockEthProvider,
+        mut from_sealer: mpsc::UnboundedReceiver<BeaconEngineMessage<EthEngineTypes>>,
+        mut from_payload_builder: mpsc::UnboundedReceiver<PayloadServiceCommand<EthEngineTypes>>,
+    ) {
+        let mut inserted = HashMap::new();
+        let mut jobs = HashMap::new();
+        loop {
+            tokio::select! {
+                Some(message) = from_sealer.recv() => match message {
+                    BeaconEngineMessage::NewPayload { payload, tx } => {
+                        let block = into_block(payload);
+                        let status =
+                            PayloadStatus::new(PayloadStatusEnum::Valid, Some(block.hash()));
+                        inserted.insert(block.hash(), block.into_block());
+                        let _ = tx.send(Ok(status));
+                    }
+                    BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx, .. } => {
+                        if let Some(block) = inserted.remove(&state.head_block_hash) {
+                            provider.add_block(state.head_block_hash, block);
+                        }
+                        let status = PayloadStatus::new(
+                            PayloadStatusEnum::Valid,
+                            Some(state.head_block_hash),
+                        );
+                        let outcome = match payload_attrs {
+                            Some(attributes) => {
+                                let attributes = EthPayloadBuilderAttributes::new(
+                                    state.head_block_hash,
+                                    attributes,
+                                );
+                                let (id_tx, id_rx) = oneshot::channel();
+                                let _ = id_tx.send(Ok(attributes.id));
+                                jobs.insert(attributes.id, attributes);
+                                OnForkChoiceUpdated::updated_with_pending_payload_id(status, id_rx)
+                            }
+                            None => OnForkChoiceUpdated::valid(status),
+                        };
+                        let _ = tx.send(Ok(outcome));
+                    }
+                },
+                Some(command) = from_payload_builder.recv() => {
+                    if let PayloadServiceCommand::Resolve(id, _, tx) = command {
+                        let payload = jobs.remove(&id).map(|attributes| {
+                            let parent = provider.header(&attributes.parent).unwrap().unwrap();
+                            let header = Header {
+                                parent_hash: attributes.parent,
+                                number: parent.number + 1,
+                                timestamp: attributes.timestamp,
+                                beneficiary: attributes.suggested_fee_recipient,
+                                gas_limit: parent.gas_limit,
+                                base_fee_per_gas: parent.base_fee_per_gas,
+                                ..Default::default()
+                            };
+                            let block = SealedBlock::seal_slow(Block { header, body: Default::default() });
+                            let payload = EthBuiltPayload::new(id, Arc::new(block), U256::ZERO, None);
+                            Box::pin(future::ready(Ok(payload))) as _
+                        });
+                        let _ = tx.send(payload);
+                    }
+                }
+                else => return,
+            }
+        }
+    }
+
+    /// Converts the payload of a sealed block, whose seal exceeds the extra-data limit of the
+    /// payload conversion.
+    fn into_block(data: ExecutionData) -> SealedBlock<Block> {
+        let ExecutionData { mut payload, sidecar } = data;
+        let extra_data = std::mem::take(&mut payload.as_v1_mut().extra_data);
+        let mut block = payload.try_into_block_with_sidecar::<TransactionSigned>(&sidecar).unwrap();
+        block.header.extra_data = extra_data;
+        SealedBlock::seal_slow(block)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn seals_in_turn_and_imports_peer_blocks() {
+        let alice = PrivateKeySigner::random();
+        let bob = PrivateKeySigner::random();
+        let config = Arc::new(
+            CliqueConfig::new(PERIOD, BTreeMap::from([(0, vec![alice.address(), bob.address()])]))
+                .unwrap(),
+        );
+        let genesis = SealedHeader::seal_slow(Header {
+            timestamp: system_time(),
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1),
+            ..Default::default()
+        });
+        let (alice_peer, bob_peer) = (PeerId::random(), PeerId::random());
+
+        let mut alice_node = TestNode::spawn(alice.clone(), config.clone(), &genesis);
+        let mut bob_node = TestNode::spawn(bob.clone(), config, &genesis);
+
+        // bob is in turn for block 1 and seals it one period after the genesis, before alice
+        let first = bob_node.next_announcement().await;
+        let header = &first.block.block.header;
+        assert_eq!(header.number, 1);
+        assert_eq!(header.timestamp, genesis.timestamp + PERIOD);
+        assert_eq!(recover_signer(header).unwrap(), bob.address());
+        assert_eq!(bob_node.head().hash(), first.hash);
+
+        // alice imports the block of her peer, makes it canonical and relays it
+        let relayed = alice_node.import(bob_peer, first.clone()).await;
+        assert_eq!(relayed.hash, first.hash);
+        assert_eq!(alice_node.head().hash(), first.hash);
+
+        // alice is in turn for block 2 and seals it on top of the imported block
+        let second = alice_node.next_announcement().await;
+        let header = &second.block.block.header;
+        assert_eq!(header.number, 2);
+        assert_eq!(header.parent_hash, first.hash);
+        assert_eq!(header.timestamp, genesis.timestamp + 2 * PERIOD);
+        assert_eq!(recover_signer(header).unwrap(), alice.address());
+
+        bob_node.import(alice_peer, second.clone()).await;
+        assert_eq!(bob_node.head().hash(), second.hash);
+    }
+}
@@ -0,0 +1,375 @@
+//! Validation of bridge credits that are paid out as withdrawals.
+//!
+//! Chains that deliver bridge credits as withdrawals can configure a committee in the genesis,
+//! see [`BridgeConfig::from_chain_spec`]. The extra-data of every block of such a chain ends with
+//! a [`BridgeSection`]:
+//!
+//! ```text
+//! from (8 bytes) | to (8 bytes) | threshold * signature (65 bytes)
+//! ```
+//!
+//! The withdrawal indices of a block are strictly increasing within `from..to` and `from` is the
+//! `to` of the parent, so a withdrawal index can never be paid out twice. A block with withdrawals
+//! carries the signatures of `threshold` distinct committee members over the
+//! [`attestation_hash`] of its withdrawals, so the block producer cannot mint credits on its own.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_consensus::BlockHeader as _;
+use alloy_primitives::{keccak256, Address, Signature, B256, U256};
+use reth_chainspec::EthChainSpec;
+use reth_consensus::ConsensusError;
+use reth_primitives_traits::{
+    crypto::secp256k1::recover_signer_unchecked, Block, BlockBody, BlockHeader, GotExpected,
+    SealedBlock,
+};
+
+/// Genesis config field with the bridge committee.
+///
+/// ```json
+/// "bridgeCommittee": {
+///     "members": ["0x..", "0x..", "0x.."],
+///     "threshold": 2
+/// }
+/// ```
+pub const BRIDGE_COMMITTEE_FIELD: &str = "bridgeCommittee";
+
+/// Number of bytes of the withdrawal index range of a [`BridgeSection`].
+const RANGE_LEN: usize = 16;
+
+/// Number of bytes of a committee signature of a [`BridgeSection`].
+pub const SIGNATURE_LEN: usize = 65;
+
+/// Errors of an invalid [`BridgeConfig`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BridgeConfigError {
+    /// The genesis field could not be deserialized.
+    #[error("invalid bridge committee: {0}")]
+    Invalid(String),
+    /// A committee member is listed more than once.
+    #[error("duplicate bridge committee member {0}")]
+    DuplicateMember(Address),
+    /// The threshold is zero or exceeds the number of committee members.
+    #[error("bridge committee threshold {threshold} is not within 1..={members}")]
+    InvalidThreshold {
+        /// The configured threshold.
+        threshold: usize,
+        /// The number of committee members.
+        members: usize,
+    },
+}
+
+/// Configuration of the committee that attests the withdrawals of a chain.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct BridgeConfig {
+    /// The committee members.
+    pub members: Vec<Address>,
+    /// Number of distinct members that sign the withdrawals of a block.
+    pub threshold: usize,
+}
+
+impl BridgeConfig {
+    /// Creates a new configuration, the threshold has to be within `1..=members.len()`.
+    pub fn new(members: Vec<Address>, threshold: usize) -> Result<Self, BridgeConfigError> {
+        if let Some(member) =
+            members.iter().enumerate().find_map(|(i, m)| members[..i].contains(m).then_some(*m))
+        {
+            return Err(BridgeConfigError::DuplicateMember(member))
+        }
+        if threshold == 0 || threshold > members.len() {
+            return Err(BridgeConfigError::InvalidThreshold { threshold, members: members.len() })
+        }
+
+        Ok(Self { members, threshold })
+    }
+
+    /// Reads the configuration from the [`BRIDGE_COMMITTEE_FIELD`] of the genesis config.
+    ///
+    /// Returns `None` if the chain has no bridge committee.
+    pub fn from_chain_spec<ChainSpec: EthChainSpec>(
+        chain_spec: &ChainSpec,
+    ) -> Result<Option<Self>, BridgeConfigError> {
+        let Some(config) = chain_spec
+            .genesis()
+            .config
+            .extra_fields
+            .get_deserialized::<Self>(BRIDGE_COMMITTEE_FIELD)
+        else {
+            return Ok(None)
+        };
+        let config = config.map_err(|err| BridgeConfigError::Invalid(err.to_string()))?;
+
+        Self::new(config.members, config.threshold).map(Some)
+    }
+
+    /// Returns the number of bytes of the [`BridgeSection`] at the end of the extra-data.
+    pub const fn section_len(&self) -> usize {
+        RANGE_LEN + self.threshold * SIGNATURE_LEN
+    }
+}
+
+/// The bridge section at the end of the extra-data of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BridgeSection<'a> {
+    /// The lowest withdrawal index of the block.
+    pub from: u64,
+    /// The withdrawal index following the last withdrawal of the block.
+    pub to: u64,
+    /// The committee signatures over the [`attestation_hash`].
+    pub signatures: &'a [u8],
+}
+
+impl<'a> BridgeSection<'a> {
+    /// Reads the section at the end of the extra-data.
+    ///
+    /// The extra-data that precedes the section is validated by the consensus of the chain, see
+    /// [`validate_extra_data_sections`](crate::validation::validate_extra_data_sections).
+    pub fn from_extra_data(
+        extra_data: &'a [u8],
+        config: &BridgeConfig,
+    ) -> Result<Self, ConsensusError> {
+        let (len, section) = (extra_data.len(), config.section_len());
+        if len < section {
+            return Err(ConsensusError::BridgeExtraDataLength { len, section })
+        }
+
+        let extra_data = &extra_data[len - section..];
+        Ok(Self {
+            from: u64::from_be_bytes(extra_data[..8].try_into().unwrap()),
+            to: u64::from_be_bytes(extra_data[8..RANGE_LEN].try_into().unwrap()),
+            signatures: &extra_data[RANGE_LEN..],
+        })
+    }
+
+    /// Encodes a section, the recovery id of the signatures is stored as 0 or 1.
+    pub fn encode(from: u64, to: u64, signatures: &[Signature]) -> Vec<u8> {
+        let mut section = Vec::with_capacity(RANGE_LEN + signatures.len() * SIGNATURE_LEN);
+        section.extend_from_slice(&from.to_be_bytes());
+        section.extend_from_slice(&to.to_be_bytes());
+        for signature in signatures {
+            section.extend_from_slice(&signature.r().to_be_bytes::<32>());
+            section.extend_from_slice(&signature.s().to_be_bytes::<32>());
+            section.push(signature.v() as u8);
+        }
+        section
+    }
+}
+
+/// Returns the hash the committee signs for the withdrawals `from..to` of a block.
+pub fn attestation_hash(chain_id: u64, from: u64, to: u64, withdrawals_root: B256) -> B256 {
+    let mut buf = [0u8; 24 + 32];
+    buf[..8].copy_from_slice(&chain_id.to_be_bytes());
+    buf[8..16].copy_from_slice(&from.to_be_bytes());
+    buf[16..24].copy_from_slice(&to.to_be_bytes());
+    buf[24..].copy_from_slice(withdrawals_root.as_slice());
+    keccak256(buf)
+}
+
+/// Returns the first withdrawal index of the children of the given block, the `to` of its
+/// [`BridgeSection`].
+///
+/// The genesis has no bridge section, the withdrawal indices start at zero.
+#[inline]
+pub fn next_bridge_withdrawal_index<H: BlockHeader>(
+    parent: &H,
+    config: &BridgeConfig,
+) -> Result<u64, ConsensusError> {
+    if parent.number() == 0 {
+        return Ok(0)
+    }
+    Ok(BridgeSection::from_extra_data(parent.extra_data(), config)?.to)
+}
+
+/// Validates that the withdrawal index range of the header continues the range of its parent.
+#[inline]
+pub fn validate_against_parent_bridge_range<H: BlockHeader>(
+    header: &H,
+    parent: &H,
+    config: &BridgeConfig,
+) -> Result<(), ConsensusError> {
+    let from = BridgeSection::from_extra_data(header.extra_data(), config)?.from;
+    let parent_to = next_bridge_withdrawal_index(parent, config)?;
+    if from != parent_to {
+        return Err(ConsensusError::BridgeWithdrawalRangeDiff(GotExpected {
+            got: from,
+            expected: parent_to,
+        }))
+    }
+    Ok(())
+}
+
+/// Validates the withdrawals of the block against its [`BridgeSection`]:
+///
+/// - The withdrawal indices are strictly increasing within `from..to`, and `to` follows the last
+///   withdrawal.
+/// - If the block has withdrawals, every signature is from a distinct committee member.
+///
+/// The withdrawals root of the header is expected to be validated against the body.
+pub fn validate_bridge_withdrawals<B: Block>(
+    block: &SealedBlock<B>,
+    chain_id: u64,
+    config: &BridgeConfig,
+) -> Result<(), ConsensusError> {
+    let section = BridgeSection::from_extra_data(block.header().extra_data(), config)?;
+    let withdrawals =
+        block.body().withdrawals().map(|withdrawals| withdrawals.as_slice()).unwrap_or_default();
+
+    let mut next = section.from;
+    for withdrawal in withdrawals {
+        if withdrawal.index < next || withdrawal.index >= section.to {
+            return Err(ConsensusError::BridgeWithdrawalIndex {
+                index: withdrawal.index,
+                from: next,
+                to: section.to,
+            })
+        }
+        next = withdrawal.index + 1;
+    }
+    if next != section.to {
+        return Err(ConsensusError::BridgeWithdrawalRangeEnd(GotExpected {
+            got: section.to,
+            expected: next,
+        }))
+    }
+
+    if withdrawals.is_empty() {
+        return Ok(())
+    }
+
+    let withdrawals_root =
+        block.header().withdrawals_root().ok_or(ConsensusError::WithdrawalsRootMissing)?;
+    let hash = attestation_hash(chain_id, section.from, section.to, withdrawals_root);
+    let mut signers = Vec::with_capacity(config.threshold);
+    for (i, signature) in section.signatures.chunks_exact(SIGNATURE_LEN).enumerate() {
+        match recover_attestation_signer(signature, hash) {
+            Some(signer) if config.members.contains(&signer) && !signers.contains(&signer) => {
+                signers.push(signer)
+            }
+            _ => return Err(ConsensusError::BridgeAttestationInvalid(i)),
+        }
+    }
+
+    Ok(())
+}
+
+/// Recovers the signer of an encoded attestation signature.
+fn recover_attestation_signer(signature: &[u8], hash: B256) -> Option<Address> {
+    let y_parity = match signature[64] {
+        0 => false,
+        1 => true,
+        _ => return None,
+    };
+    let signature = Signature::new(
+        U256::from_be_slice(&signature[..32]),
+        U256::from_be_slice(&signature[32..64]),
+        y_parity,
+    );
+    recover_signer_unchecked(&signature, hash).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{proofs, Header};
+    use alloy_eips::eip4895::{Withdrawal, Withdrawals};
+    use reth_primitives_traits::crypto::secp256k1::sign_message;
+
+    fn member(key: B256) -> Address {
+        let signature = sign_message(key, B256::ZERO).unwrap();
+        recover_signer_unchecked(&signature, B256::ZERO).unwrap()
+    }
+
+    fn block(
+        indices: &[u64],
+        from: u64,
+        to: u64,
+        keys: &[B256],
+    ) -> SealedBlock<reth_ethereum_primitives::Block> {
+        let withdrawals: Vec<_> = indices
+            .iter()
+            .map(|&index| Withdrawal { index, amount: 1, ..Default::default() })
+            .collect();
+        let withdrawals_root = proofs::calculate_withdrawals_root(&withdrawals);
+        let hash = attestation_hash(1, from, to, withdrawals_root);
+        let signatures: Vec<_> = keys.iter().map(|key| sign_message(*key, hash).unwrap()).collect();
+
+        let header = Header {
+            number: 1,
+            withdrawals_root: Some(withdrawals_root),
+            extra_data: BridgeSection::encode(from, to, &signatures).into(),
+            ..Default::default()
+        };
+        let body = reth_ethereum_primitives::BlockBody {
+            withdrawals: Some(Withdrawals::new(withdrawals)),
+            ..Default::default()
+        };
+        SealedBlock::seal_slow(reth_ethereum_primitives::Block { header, body })
+    }
+
+    #[test]
+    fn validates_bridge_withdrawals() {
+        let (alice, bob, mallory) =
+            (B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3));
+        let config = BridgeConfig::new(vec![member(alice), member(bob)], 2).unwrap();
+
+        let valid = block(&[3, 4, 7], 3, 8, &[alice, bob]);
+        assert_eq!(validate_bridge_withdrawals(&valid, 1, &config), Ok(()));
+        // the attestation is bound to the chain
+        assert_eq!(
+            validate_bridge_withdrawals(&valid, 2, &config),
+            Err(ConsensusError::BridgeAttestationInvalid(0))
+        );
+
+        assert_eq!(
+            validate_bridge_withdrawals(&block(&[3, 3], 3, 4, &[alice, bob]), 1, &config),
+            Err(ConsensusError::BridgeWithdrawalIndex { index: 3, from: 4, to: 4 })
+        );
+        assert_eq!(
+            validate_bridge_withdrawals(&block(&[2, 3], 3, 4, &[alice, bob]), 1, &config),
+            Err(ConsensusError::BridgeWithdrawalIndex { index: 2, from: 3, to: 4 })
+        );
+        assert_eq!(
+            validate_bridge_withdrawals(&block(&[3], 3, 5, &[alice, bob]), 1, &config),
+            Err(ConsensusError::BridgeWithdrawalRangeEnd(GotExpected { got: 5, expected: 4 }))
+        );
+        assert_eq!(
+            validate_bridge_withdrawals(&block(&[3], 3, 4, &[alice, alice]), 1, &config),
+            Err(ConsensusError::BridgeAttestationInvalid(1))
+        );
+        assert_eq!(
+            validate_bridge_withdrawals(&block(&[3], 3, 4, &[mallory, bob]), 1, &config),
+            Err(ConsensusError::BridgeAttestationInvalid(0))
+        );
+
+        // blocks without withdrawals need no attestation
+        let empty = block(&[], 4, 4, &[mallory, mallory]);
+        assert_eq!(validate_bridge_withdrawals(&empty, 1, &config), Ok(()));
+    }
+
+    #[test]
+    fn validates_range_against_parent() {
+        let config = BridgeConfig::new(vec![Address::ZERO], 1).unwrap();
+        let signature = sign_message(B256::with_last_byte(1), B256::ZERO).unwrap();
+        let header = |number, from, to| Header {
+            number,
+            extra_data: BridgeSection::encode(from, to, &[signature]).into(),
+            ..Default::default()
+        };
+
+        let genesis = Header::default();
+        assert_eq!(
+            validate_against_parent_bridge_range(&header(1, 0, 2), &genesis, &config),
+            Ok(())
+        );
+        assert_eq!(
+            validate_against_parent_bridge_range(&header(2, 2, 2), &header(1, 0, 2), &config),
+            Ok(())
+        );
+        assert_eq!(
+            validate_against_parent_bridge_range(&header(2, 1, 3), &header(1, 0, 2), &config),
+            Err(ConsensusError::BridgeWithdrawalRangeDiff(GotExpected { got: 1, expected: 2 }))
+        );
+    }
+}
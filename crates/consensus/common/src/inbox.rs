@@ -0,0 +1,178 @@
+//! Inbound cross-chain messages that are carried in the extra-data of a block.
+//!
+//! Chains with an inbox contract, see [`INBOX_CONTRACT_FIELD`], deliver the inbound messages of a
+//! block to it before the transactions of the block are executed. The extra-data of every block of
+//! such a chain carries the messages in an [`InboxSection`], which precedes the
+//! [`BridgeSection`](crate::bridge::BridgeSection) on chains with a bridge committee:
+//!
+//! ```text
+//! (length (4 bytes) | message)* | length of the messages (4 bytes) | INBOX_SECTION_TAG (1 byte)
+//! ```
+//!
+//! Since the block carries its messages, every node that executes it delivers the same messages,
+//! independent of the messages it has seen before.
+
+use alloc::vec::Vec;
+use alloy_primitives::Bytes;
+use reth_consensus::ConsensusError;
+
+/// Genesis config field with the address of the inbox contract.
+///
+/// ```json
+/// "inboxContract": "0x.."
+/// ```
+pub const INBOX_CONTRACT_FIELD: &str = "inboxContract";
+
+/// The last byte of an [`InboxSection`].
+pub const INBOX_SECTION_TAG: u8 = 0x49;
+
+/// Maximum number of bytes of the encoded messages of a block.
+pub const MAX_INBOX_MESSAGES_SIZE: usize = 64 * 1024;
+
+/// Number of bytes of the length prefix of a message and of the length of the messages.
+const LENGTH_LEN: usize = 4;
+
+/// Number of bytes that follow the messages of an [`InboxSection`].
+const TRAILER_LEN: usize = LENGTH_LEN + 1;
+
+/// The inbox section of the extra-data of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InboxSection<'a> {
+    /// The encoded messages.
+    messages: &'a [u8],
+}
+
+impl<'a> InboxSection<'a> {
+    /// Reads the section at the end of the given extra-data, and returns it together with the
+    /// extra-data that precedes it.
+    pub fn split_extra_data(extra_data: &'a [u8]) -> Result<(&'a [u8], Self), ConsensusError> {
+        let Some(trailer_start) = extra_data.len().checked_sub(TRAILER_LEN) else {
+            return Err(ConsensusError::InboxSectionInvalid)
+        };
+        let (rest, trailer) = extra_data.split_at(trailer_start);
+        if trailer[LENGTH_LEN] != INBOX_SECTION_TAG {
+            return Err(ConsensusError::InboxSectionInvalid)
+        }
+
+        let len = u32::from_be_bytes(trailer[..LENGTH_LEN].try_into().unwrap()) as usize;
+        if len > MAX_INBOX_MESSAGES_SIZE {
+            return Err(ConsensusError::InboxMessagesExceedMax { len, max: MAX_INBOX_MESSAGES_SIZE })
+        }
+        let Some(start) = rest.len().checked_sub(len) else {
+            return Err(ConsensusError::InboxSectionInvalid)
+        };
+
+        let section = Self { messages: &rest[start..] };
+        // every message has to be complete
+        let mut remaining = section.messages;
+        while !remaining.is_empty() {
+            remaining = split_message(remaining).ok_or(ConsensusError::InboxSectionInvalid)?.1;
+        }
+
+        Ok((&rest[..start], section))
+    }
+
+    /// Returns the number of bytes of the section.
+    pub const fn len(&self) -> usize {
+        self.messages.len() + TRAILER_LEN
+    }
+
+    /// Returns `true` if the section carries no messages.
+    pub const fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Returns the messages of the section.
+    pub fn messages(&self) -> Vec<Bytes> {
+        let mut messages = Vec::new();
+        let mut remaining = self.messages;
+        while let Some((message, rest)) = split_message(remaining) {
+            messages.push(Bytes::copy_from_slice(message));
+            remaining = rest;
+        }
+        messages
+    }
+
+    /// Encodes a section with the given messages.
+    ///
+    /// Returns [`ConsensusError::InboxMessagesExceedMax`] if the encoded messages exceed
+    /// [`MAX_INBOX_MESSAGES_SIZE`].
+    pub fn encode(messages: &[Bytes]) -> Result<Vec<u8>, ConsensusError> {
+        let len = messages.iter().map(|message| LENGTH_LEN + message.len()).sum::<usize>();
+        if len > MAX_INBOX_MESSAGES_SIZE {
+            return Err(ConsensusError::InboxMessagesExceedMax { len, max: MAX_INBOX_MESSAGES_SIZE })
+        }
+
+        let mut section = Vec::with_capacity(len + TRAILER_LEN);
+        for message in messages {
+            section.extend_from_slice(&(message.len() as u32).to_be_bytes());
+            section.extend_from_slice(message);
+        }
+        section.extend_from_slice(&(len as u32).to_be_bytes());
+        section.push(INBOX_SECTION_TAG);
+        Ok(section)
+    }
+}
+
+/// Splits the first message off the encoded messages.
+fn split_message(messages: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len = u32::from_be_bytes(messages.get(..LENGTH_LEN)?.try_into().unwrap()) as usize;
+    let rest = &messages[LENGTH_LEN..];
+    (len <= rest.len()).then(|| rest.split_at(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_section_off_extra_data() {
+        let messages = vec![Bytes::from_static(b"hello"), Bytes::new(), Bytes::from(vec![7; 300])];
+        let mut extra_data = b"reth".to_vec();
+        extra_data.extend(InboxSection::encode(&messages).unwrap());
+
+        let (vanity, section) = InboxSection::split_extra_data(&extra_data).unwrap();
+        assert_eq!(vanity, b"reth");
+        assert_eq!(section.len(), extra_data.len() - 4);
+        assert_eq!(section.messages(), messages);
+
+        let empty = InboxSection::encode(&[]).unwrap();
+        let (vanity, section) = InboxSection::split_extra_data(&empty).unwrap();
+        assert!(vanity.is_empty() && section.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_section() {
+        let section = InboxSection::encode(&[Bytes::from_static(b"hello")]).unwrap();
+        assert_eq!(
+            InboxSection::split_extra_data(b"reth"),
+            Err(ConsensusError::InboxSectionInvalid)
+        );
+        // missing tag
+        assert_eq!(
+            InboxSection::split_extra_data(&section[..section.len() - 1]),
+            Err(ConsensusError::InboxSectionInvalid)
+        );
+        // truncated messages
+        assert_eq!(
+            InboxSection::split_extra_data(&section[1..]),
+            Err(ConsensusError::InboxSectionInvalid)
+        );
+        // incomplete message
+        let mut incomplete = section.clone();
+        incomplete[3] += 1;
+        assert_eq!(
+            InboxSection::split_extra_data(&incomplete),
+            Err(ConsensusError::InboxSectionInvalid)
+        );
+
+        let oversized = vec![Bytes::from(vec![0; MAX_INBOX_MESSAGES_SIZE])];
+        assert_eq!(
+            InboxSection::encode(&oversized),
+            Err(ConsensusError::InboxMessagesExceedMax {
+                len: MAX_INBOX_MESSAGES_SIZE + 4,
+                max: MAX_INBOX_MESSAGES_SIZE
+            })
+        );
+    }
+}
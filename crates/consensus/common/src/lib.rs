@@ -9,5 +9,10 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+pub mod bridge;
+pub mod inbox;
+
 /// Collection of consensus validation methods.
 pub mod validation;
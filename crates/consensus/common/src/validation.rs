@@ -1,5 +1,9 @@
 //! Collection of methods for block validation.
 
+use crate::{
+    bridge::{BridgeConfig, BridgeSection},
+    inbox::InboxSection,
+};
 use alloy_consensus::{
     constants::MAXIMUM_EXTRA_DATA_SIZE, BlockHeader as _, EMPTY_OMMER_ROOT_HASH,
 };
@@ -231,6 +235,31 @@ pub fn validate_header_extra_data<H: BlockHeader>(header: &H) -> Result<(), Cons
     }
 }
 
+/// Validates the extra-data of a header that ends with the sections of the chain: the inbox
+/// section if `inbox` is set, followed by the bridge section of the given committee.
+///
+/// The extra-data that precedes the sections must be 32 bytes or fewer, see
+/// [`validate_header_extra_data`].
+#[inline]
+pub fn validate_extra_data_sections<H: BlockHeader>(
+    header: &H,
+    bridge: Option<&BridgeConfig>,
+    inbox: bool,
+) -> Result<(), ConsensusError> {
+    let mut extra_data = header.extra_data().as_ref();
+    if let Some(bridge) = bridge {
+        BridgeSection::from_extra_data(extra_data, bridge)?;
+        extra_data = &extra_data[..extra_data.len() - bridge.section_len()];
+    }
+    if inbox {
+        extra_data = InboxSection::split_extra_data(extra_data)?.0;
+    }
+    if extra_data.len() > MAXIMUM_EXTRA_DATA_SIZE {
+        return Err(ConsensusError::ExtraDataExceedsMax { len: extra_data.len() })
+    }
+    Ok(())
+}
+
 /// Validates against the parent hash and number.
 ///
 /// This function ensures that the header block number is sequential and that the hash of the parent
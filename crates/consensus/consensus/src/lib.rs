@@ -406,6 +406,54 @@ pub enum ConsensusError {
         /// The block's timestamp.
         timestamp: u64,
     },
+    /// Error when the extra-data of a block does not end with a bridge section.
+    #[error("extra-data of {len} bytes does not end with a bridge section of {section} bytes")]
+    BridgeExtraDataLength {
+        /// The length of the extra-data.
+        len: usize,
+        /// The length of the bridge section.
+        section: usize,
+    },
+
+    /// Error when the bridge withdrawal index range of a block does not continue the range of its
+    /// parent.
+    #[error("bridge withdrawal index range does not continue the parent range: {0}")]
+    BridgeWithdrawalRangeDiff(GotExpected<u64>),
+
+    /// Error when the bridge withdrawal index range of a block does not end after its last
+    /// withdrawal.
+    #[error("bridge withdrawal index range end mismatch: {0}")]
+    BridgeWithdrawalRangeEnd(GotExpected<u64>),
+
+    /// Error when a withdrawal index is not strictly increasing within the bridge withdrawal index
+    /// range of its block.
+    #[error("withdrawal index {index} is not within {from}..{to}")]
+    BridgeWithdrawalIndex {
+        /// The index of the withdrawal.
+        index: u64,
+        /// The lowest valid index.
+        from: u64,
+        /// The end of the range.
+        to: u64,
+    },
+
+    /// Error when a bridge attestation signature is not from a distinct committee member.
+    #[error("bridge attestation signature {0} is not from a distinct committee member")]
+    BridgeAttestationInvalid(usize),
+
+    /// Error when the extra-data of a block does not carry a well-formed inbox section.
+    #[error("extra-data does not carry a well-formed inbox section")]
+    InboxSectionInvalid,
+
+    /// Error when the inbox messages of a block exceed the maximum size.
+    #[error("inbox messages of {len} bytes exceed the maximum of {max} bytes")]
+    InboxMessagesExceedMax {
+        /// The size of the messages of the block.
+        len: usize,
+        /// The maximum size of the messages of a block.
+        max: usize,
+    },
+
     /// Other, likely an injected L2 error.
     #[error("{0}")]
     Other(String),
@@ -4,7 +4,7 @@ use alloy_primitives::{Bytes, B256};
 use reth_chainspec::EthereumHardforks;
 use reth_node_api::{BlockTy, FullNodeComponents};
 use reth_node_builder::{rpc::RpcRegistry, NodeTypes};
-use reth_provider::BlockReader;
+use reth_provider::{BlockReader, ModifiedAccountsReader, StorageRangeProvider};
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_eth_api::{
     helpers::{EthApiSpec, EthTransactions, TraceExt},
@@ -28,7 +28,18 @@ where
         let eth_api = self.inner.eth_api();
         eth_api.send_raw_transaction(raw_tx).await
     }
+}
 
+impl<Node, EthApi> RpcTestContext<Node, EthApi>
+where
+    Node: FullNodeComponents<Types: NodeTypes<ChainSpec: EthereumHardforks>>,
+    EthApi: EthApiSpec<
+            Provider: BlockReader<Block = BlockTy<Node::Types>>
+                          + ModifiedAccountsReader
+                          + StorageRangeProvider,
+        > + EthTransactions
+        + TraceExt,
+{
     /// Retrieves a transaction envelope by its hash
     pub async fn envelope_by_hash(&self, hash: B256) -> eyre::Result<TxEnvelope> {
         let tx = self.inner.debug_api().raw_transaction(hash).await?.unwrap();
@@ -0,0 +1,177 @@
+//! A bounded, persistent store of the blocks rejected by the engine.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_execution_types::BlockExecutionOutput;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
+use reth_trie::updates::TrieUpdates;
+use std::{
+    collections::VecDeque,
+    io::Write,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use tracing::warn;
+
+/// The default number of bad blocks kept by a [`BadBlockStore`].
+pub const DEFAULT_MAX_BAD_BLOCKS: usize = 10;
+
+/// A block that was rejected by the engine.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct BadBlock {
+    /// Hash of the block.
+    pub hash: B256,
+    /// RLP encoding of the block.
+    pub rlp: Bytes,
+    /// The error the block was rejected with.
+    pub reason: String,
+}
+
+/// A bounded store of the most recent blocks rejected by the engine.
+///
+/// The store is an [`InvalidBlockHook`] that records every rejected block, evicting the oldest
+/// block once it is full. If it was opened with a path, the blocks are persisted to that file after
+/// every change, so they survive restarts.
+#[derive(Debug, Clone)]
+pub struct BadBlockStore {
+    inner: Arc<BadBlockStoreInner>,
+}
+
+#[derive(Debug)]
+struct BadBlockStoreInner {
+    /// Rejected blocks, oldest first.
+    blocks: Mutex<VecDeque<BadBlock>>,
+    /// Maximum number of blocks kept.
+    max_blocks: usize,
+    /// File the blocks are persisted to, if any.
+    path: Option<PathBuf>,
+}
+
+impl BadBlockStore {
+    /// Creates a new in-memory store that keeps at most `max_blocks` blocks.
+    pub fn new(max_blocks: usize) -> Self {
+        Self::with_blocks(VecDeque::new(), max_blocks, None)
+    }
+
+    /// Opens a store that is persisted to the file at `path`, loading the blocks that were stored
+    /// in it before.
+    pub fn open(path: impl Into<PathBuf>, max_blocks: usize) -> eyre::Result<Self> {
+        let path = path.into();
+        let mut blocks = VecDeque::new();
+        if path.exists() {
+            let data = reth_fs_util::read(&path)?;
+            blocks = Vec::<BadBlock>::decode(&mut data.as_slice())?.into();
+            while blocks.len() > max_blocks {
+                blocks.pop_front();
+            }
+        }
+        Ok(Self::with_blocks(blocks, max_blocks, Some(path)))
+    }
+
+    fn with_blocks(blocks: VecDeque<BadBlock>, max_blocks: usize, path: Option<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(BadBlockStoreInner { blocks: Mutex::new(blocks), max_blocks, path }),
+        }
+    }
+
+    /// Records a rejected block, evicting the oldest block if the store is full.
+    ///
+    /// Blocks that are already stored are ignored.
+    pub fn insert(&self, block: BadBlock) {
+        let mut blocks = self.inner.blocks.lock().unwrap();
+        if blocks.iter().any(|stored| stored.hash == block.hash) {
+            return
+        }
+        blocks.push_back(block);
+        while blocks.len() > self.inner.max_blocks {
+            blocks.pop_front();
+        }
+
+        if let Some(path) = &self.inner.path {
+            let mut data = Vec::new();
+            alloy_rlp::encode_list::<_, BadBlock>(blocks.make_contiguous(), &mut data);
+            if let Err(err) = reth_fs_util::atomic_write_file(path, |file| file.write_all(&data)) {
+                warn!(
+                    target: "engine::bad_blocks",
+                    %err,
+                    "Failed to persist bad blocks"
+                );
+            }
+        }
+    }
+
+    /// Returns the stored blocks, most recent first.
+    pub fn blocks(&self) -> Vec<BadBlock> {
+        self.inner.blocks.lock().unwrap().iter().rev().cloned().collect()
+    }
+
+    /// Returns the stored block with the given hash, if any.
+    pub fn get(&self, hash: B256) -> Option<BadBlock> {
+        self.inner.blocks.lock().unwrap().iter().find(|block| block.hash == hash).cloned()
+    }
+}
+
+impl Default for BadBlockStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BAD_BLOCKS)
+    }
+}
+
+impl<N: NodePrimitives> InvalidBlockHook<N> for BadBlockStore {
+    fn on_invalid_block(
+        &self,
+        _parent_header: &SealedHeader<N::BlockHeader>,
+        _block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        // recorded in `on_rejected_block` together with the error
+    }
+
+    fn on_rejected_block(&self, block: &SealedBlock<N::Block>, error: &dyn core::error::Error) {
+        self.insert(BadBlock {
+            hash: block.hash(),
+            rlp: alloy_rlp::encode(block.clone_block()).into(),
+            reason: error.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bad_block(n: u8) -> BadBlock {
+        BadBlock {
+            hash: B256::with_last_byte(n),
+            rlp: Bytes::from(vec![n]),
+            reason: format!("invalid block {n}"),
+        }
+    }
+
+    #[test]
+    fn evicts_oldest_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad_blocks.rlp");
+
+        let store = BadBlockStore::open(&path, 2).unwrap();
+        store.insert(bad_block(1));
+        store.insert(bad_block(2));
+        store.insert(bad_block(2));
+        store.insert(bad_block(3));
+        assert_eq!(store.blocks(), vec![bad_block(3), bad_block(2)]);
+        assert_eq!(store.get(B256::with_last_byte(1)), None);
+
+        let reopened = BadBlockStore::open(&path, 1).unwrap();
+        assert_eq!(reopened.blocks(), vec![bad_block(3)]);
+    }
+}
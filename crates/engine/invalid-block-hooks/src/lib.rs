@@ -1,5 +1,9 @@
 //! Invalid block hook implementations.
 
+mod trace;
 mod witness;
 
+pub use trace::{
+    InvalidBlockArtifactUploader, InvalidBlockTraceHook, InvalidBlockTracer, InvalidBlockTraces,
+};
 pub use witness::InvalidBlockWitnessHook;
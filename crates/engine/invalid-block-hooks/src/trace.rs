@@ -0,0 +1,363 @@
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::{
+    DefaultFrame, GethDefaultTracingOptions, GethTrace, PreStateConfig, TraceResult,
+};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::{block::BlockExecutor, ConfigureEvm, Evm};
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader, SignedTransaction};
+use reth_provider::{BlockExecutionOutput, StateProviderFactory};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{states::bundle_state::BundleRetention, CacheDB, State},
+    state::{Account, AccountStatus, EvmStorageSlot},
+    DatabaseCommit,
+};
+use reth_tasks::TaskExecutor;
+use reth_tracing::tracing::{debug, warn};
+use reth_trie::updates::TrieUpdates;
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use serde::Serialize;
+use std::{fs::File, io::Write, path::PathBuf};
+
+/// The tracer used by the [`InvalidBlockTraceHook`] to re-execute the transactions of the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidBlockTracer {
+    /// The `prestateTracer` in diff mode, records the state before and after each transaction.
+    PreState,
+    /// The default struct logger with memory, stack, storage and return data enabled.
+    Opcode,
+}
+
+impl InvalidBlockTracer {
+    /// Returns the name of the tracer, used for file names and uploaded artifacts.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::PreState => "prestate",
+            Self::Opcode => "opcode",
+        }
+    }
+
+    fn prestate_config() -> PreStateConfig {
+        PreStateConfig { diff_mode: Some(true), ..Default::default() }
+    }
+
+    fn struct_log_config() -> GethDefaultTracingOptions {
+        GethDefaultTracingOptions {
+            enable_memory: Some(true),
+            enable_return_data: Some(true),
+            disable_stack: Some(false),
+            disable_storage: Some(false),
+            ..Default::default()
+        }
+    }
+
+    fn inspector_config(&self) -> TracingInspectorConfig {
+        match self {
+            Self::PreState => {
+                TracingInspectorConfig::from_geth_prestate_config(&Self::prestate_config())
+            }
+            Self::Opcode => TracingInspectorConfig::from_geth_config(&Self::struct_log_config()),
+        }
+    }
+}
+
+/// Uploads the artifacts of the invalid block hooks to a configured URL.
+///
+/// Uploads are spawned as tasks, so they don't block the engine.
+#[derive(Debug, Clone)]
+pub struct InvalidBlockArtifactUploader {
+    /// The HTTP client.
+    client: reqwest::Client,
+    /// The URL the artifacts are posted to.
+    url: reqwest::Url,
+    /// Executor to spawn the uploads on.
+    task_executor: TaskExecutor,
+}
+
+impl InvalidBlockArtifactUploader {
+    /// Creates a new uploader for the given URL.
+    pub fn new(url: &str, task_executor: TaskExecutor) -> eyre::Result<Self> {
+        Ok(Self { client: reqwest::Client::new(), url: url.parse()?, task_executor })
+    }
+
+    /// POSTs the value as JSON to the configured URL.
+    pub fn upload<T: Serialize>(&self, value: &T) -> eyre::Result<()> {
+        let body = serde_json::to_vec(value)?;
+        let request = self
+            .client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        let url = self.url.clone();
+        self.task_executor.spawn(async move {
+            match request.send().await.and_then(|response| response.error_for_status()) {
+                Ok(_) => {
+                    debug!(target: "engine::invalid_block_hooks::trace", %url, "Uploaded artifact")
+                }
+                Err(err) => {
+                    warn!(target: "engine::invalid_block_hooks::trace", %url, %err, "Failed to upload artifact")
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+/// The traces of all transactions of an invalid block, as written by the
+/// [`InvalidBlockTraceHook`].
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidBlockTraces {
+    /// The name of the tracer, see [`InvalidBlockTracer::name`].
+    pub tracer: &'static str,
+    /// The number of the invalid block.
+    pub block_number: u64,
+    /// The hash of the invalid block.
+    pub block_hash: B256,
+    /// The traces of the transactions, in block order.
+    ///
+    /// If a transaction fails to execute, its error is the last entry.
+    pub traces: Vec<TraceResult>,
+}
+
+/// Re-executes the transactions of the invalid block with a tracer and saves the traces to a file.
+#[derive(Debug)]
+pub struct InvalidBlockTraceHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: E,
+    /// The tracer to use.
+    tracer: InvalidBlockTracer,
+    /// The directory to write the traces to.
+    output_directory: PathBuf,
+    /// Uploads the traces, if configured.
+    uploader: Option<InvalidBlockArtifactUploader>,
+}
+
+impl<P, E> InvalidBlockTraceHook<P, E> {
+    /// Creates a new trace hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        tracer: InvalidBlockTracer,
+        output_directory: PathBuf,
+        uploader: Option<InvalidBlockArtifactUploader>,
+    ) -> Self {
+        Self { provider, evm_config, tracer, output_directory, uploader }
+    }
+}
+
+impl<P, E, N> InvalidBlockTraceHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let traces = InvalidBlockTraces {
+            tracer: self.tracer.name(),
+            block_number: block.number(),
+            block_hash: block.hash(),
+            traces: self.trace_block(parent_header, block)?,
+        };
+
+        let path = self.output_directory.join(format!(
+            "{}_{}.{}.json",
+            block.number(),
+            block.hash(),
+            self.tracer.name()
+        ));
+        File::create(&path)?.write_all(serde_json::to_string(&traces)?.as_bytes())?;
+        warn!(target: "engine::invalid_block_hooks::trace", tracer = self.tracer.name(), path = %path.display(), "Saved traces of invalid block");
+
+        if let Some(uploader) = &self.uploader {
+            uploader.upload(&traces)?;
+        }
+
+        Ok(())
+    }
+
+    /// Re-executes the transactions of the block on top of the parent state and returns their
+    /// traces.
+    fn trace_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<Vec<TraceResult>> {
+        let mut db = CacheDB::new(StateProviderDatabase::new(
+            self.provider.state_by_block_hash(parent_header.hash())?,
+        ));
+        let evm_env = self.evm_config.evm_env(block.header());
+
+        // apply the pre-execution changes of the block executor before any transaction. Besides
+        // the system calls, these include the changes of wrapping executors, e.g. state overrides
+        let mut state = State::builder().with_database(&mut db).with_bundle_update().build();
+        let evm = self.evm_config.evm_with_env(&mut state, evm_env.clone());
+        let mut executor = self.evm_config.create_executor_for_block(evm, block.sealed_block());
+        executor.apply_pre_execution_changes()?;
+        drop(executor);
+        state.merge_transitions(BundleRetention::PlainState);
+        let bundle = state.take_bundle();
+
+        // the tracers read the state through the cache, so the changes are committed to it
+        db.cache.contracts.extend(bundle.contracts);
+        db.commit(
+            bundle
+                .state
+                .into_iter()
+                .map(|(address, account)| {
+                    let status = match &account.info {
+                        None => AccountStatus::Touched | AccountStatus::SelfDestructed,
+                        Some(_) if account.was_destroyed() => {
+                            AccountStatus::Touched | AccountStatus::Created
+                        }
+                        Some(_) => AccountStatus::Touched,
+                    };
+                    let storage = account
+                        .storage
+                        .into_iter()
+                        .map(|(slot, value)| {
+                            let slot_value = EvmStorageSlot::new_changed(
+                                value.previous_or_original_value,
+                                value.present_value,
+                            );
+                            (slot, slot_value)
+                        })
+                        .collect();
+                    (address, Account { info: account.info.unwrap_or_default(), storage, status })
+                })
+                .collect(),
+        );
+
+        let mut traces = Vec::with_capacity(block.transaction_count());
+        for tx in block.transactions_recovered() {
+            let tx_hash = *tx.tx_hash();
+            let gas_limit = tx.gas_limit();
+            let tx_env = self.evm_config.tx_env(tx);
+
+            let mut inspector = TracingInspector::new(self.tracer.inspector_config());
+            let mut evm = self.evm_config.evm_with_env_and_inspector(
+                &mut db,
+                evm_env.clone(),
+                &mut inspector,
+            );
+            let result = match evm.transact(tx_env) {
+                Ok(result) => result,
+                Err(err) => {
+                    // the block is invalid, so the failing transaction is likely the culprit
+                    traces.push(TraceResult::Error {
+                        error: err.to_string(),
+                        tx_hash: Some(tx_hash),
+                    });
+                    break
+                }
+            };
+            drop(evm);
+
+            inspector.set_transaction_gas_limit(gas_limit);
+            let trace: GethTrace = match self.tracer {
+                InvalidBlockTracer::PreState => inspector
+                    .geth_builder()
+                    .geth_prestate_traces(&result, &InvalidBlockTracer::prestate_config(), &db)?
+                    .into(),
+                InvalidBlockTracer::Opcode => {
+                    let gas_used = result.result.gas_used();
+                    let return_value = result.result.output().cloned().unwrap_or_default();
+                    let frame: DefaultFrame = inspector.geth_builder().geth_traces(
+                        gas_used,
+                        return_value,
+                        InvalidBlockTracer::struct_log_config(),
+                    );
+                    frame.into()
+                }
+            };
+            traces.push(TraceResult::Success { result: trace, tx_hash: Some(tx_hash) });
+
+            db.commit(result.state);
+        }
+
+        Ok(traces)
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockTraceHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::trace", tracer = self.tracer.name(), %err, "Failed to invoke hook");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, Signed, TxLegacy};
+    use alloy_primitives::{Address, Signature, TxKind, U256};
+    use reth_ethereum_primitives::{Block, BlockBody, TransactionSigned};
+    use reth_evm::overrides::{AccountStateOverride, BlockStateOverrides};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::test_utils::MockEthProvider;
+
+    #[test]
+    fn traces_with_pre_execution_changes_of_executor() {
+        let provider = MockEthProvider::default();
+        let sender = Address::random();
+        let parent = SealedHeader::seal_slow(Header::default());
+
+        let tx = TxLegacy {
+            gas_price: 1,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::random()),
+            value: U256::from(1),
+            ..Default::default()
+        };
+        let tx = TransactionSigned::from(Signed::new_unhashed(tx, Signature::test_signature()));
+        let block = RecoveredBlock::new_unhashed(
+            Block {
+                header: Header {
+                    number: 1,
+                    timestamp: 12,
+                    parent_hash: parent.hash(),
+                    gas_limit: 30_000_000,
+                    ..Default::default()
+                },
+                body: BlockBody { transactions: vec![tx], ..Default::default() },
+            },
+            vec![sender],
+        );
+
+        // the sender is only funded by the state overrides of the block
+        let funding =
+            AccountStateOverride { balance: Some(U256::from(1_000_000)), ..Default::default() };
+        let overrides = BlockStateOverrides::default();
+        overrides.insert(parent.hash(), 12, std::iter::once((sender, funding)).collect()).unwrap();
+        let evm_config = EthEvmConfig::mainnet().with_state_overrides(overrides);
+
+        let hook = InvalidBlockTraceHook::new(
+            provider,
+            evm_config,
+            InvalidBlockTracer::PreState,
+            PathBuf::new(),
+            None,
+        );
+        let traces = hook.trace_block(&parent, &block).unwrap();
+        assert_eq!(traces.len(), 1);
+        assert!(matches!(traces[0], TraceResult::Success { .. }), "{traces:?}");
+    }
+}
@@ -0,0 +1,120 @@
+//! Contains the inbound credit sources that the local miner pays out as withdrawals.
+
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::Address;
+use futures_util::Stream;
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc::{self, error::SendError, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// A credit of `amount` Gwei to `address` that is paid out as a withdrawal in a locally built
+/// block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InboundCredit {
+    /// Recipient of the credit.
+    pub address: Address,
+    /// Value of the credit in Gwei.
+    pub amount: u64,
+}
+
+impl InboundCredit {
+    /// Creates a new credit of `amount` Gwei to `address`.
+    pub const fn new(address: Address, amount: u64) -> Self {
+        Self { address, amount }
+    }
+
+    /// Converts the credit into a [`Withdrawal`] with the given withdrawal index.
+    pub const fn into_withdrawal(self, index: u64) -> Withdrawal {
+        Withdrawal { index, validator_index: 0, address: self.address, amount: self.amount }
+    }
+}
+
+/// A source of [`InboundCredit`]s that are turned into withdrawals by the local miner.
+///
+/// The miner polls the source and includes all credits received so far in the next block it
+/// builds. The stream terminating disables the source.
+pub trait InboundCreditSource:
+    Stream<Item = InboundCredit> + fmt::Debug + Send + Sync + Unpin + 'static
+{
+}
+
+impl<T> InboundCreditSource for T where
+    T: Stream<Item = InboundCredit> + fmt::Debug + Send + Sync + Unpin + 'static
+{
+}
+
+/// A channel based [`InboundCreditSource`].
+///
+/// Credits are queued through the [`CreditInboxHandle`]s of the inbox.
+#[derive(Debug)]
+pub struct CreditInbox {
+    from_handles: UnboundedReceiverStream<InboundCredit>,
+}
+
+impl CreditInbox {
+    /// Creates a new inbox and a [`CreditInboxHandle`] to queue credits into it.
+    pub fn new() -> (Self, CreditInboxHandle) {
+        let (to_inbox, from_handles) = mpsc::unbounded_channel();
+        (
+            Self { from_handles: UnboundedReceiverStream::new(from_handles) },
+            CreditInboxHandle { to_inbox },
+        )
+    }
+}
+
+impl Stream for CreditInbox {
+    type Item = InboundCredit;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().from_handles).poll_next(cx)
+    }
+}
+
+/// A cloneable handle to queue credits into a [`CreditInbox`].
+#[derive(Debug, Clone)]
+pub struct CreditInboxHandle {
+    to_inbox: UnboundedSender<InboundCredit>,
+}
+
+impl CreditInboxHandle {
+    /// Queues a credit for the next block built by the miner.
+    ///
+    /// Returns an error if the inbox has been dropped.
+    pub fn enqueue(&self, credit: InboundCredit) -> Result<(), SendError<InboundCredit>> {
+        self.to_inbox.send(credit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn inbox_yields_credits_in_order() {
+        let (mut inbox, handle) = CreditInbox::new();
+        let first = InboundCredit::new(Address::with_last_byte(1), 1);
+        let second = InboundCredit::new(Address::with_last_byte(2), 2);
+        handle.enqueue(first).unwrap();
+        handle.clone().enqueue(second).unwrap();
+        drop(handle);
+
+        assert_eq!(inbox.next().await, Some(first));
+        assert_eq!(inbox.next().await, Some(second));
+        assert_eq!(inbox.next().await, None);
+    }
+
+    #[test]
+    fn credit_into_withdrawal() {
+        let credit = InboundCredit::new(Address::with_last_byte(1), 1_000_000_000);
+        let withdrawal = credit.into_withdrawal(7);
+        assert_eq!(withdrawal.index, 7);
+        assert_eq!(withdrawal.validator_index, 0);
+        assert_eq!(withdrawal.address, credit.address);
+        assert_eq!(withdrawal.amount, credit.amount);
+    }
+}
@@ -0,0 +1,259 @@
+//! A handle to control a running local miner.
+
+use crate::MiningMode;
+use alloy_primitives::{Address, B256};
+use reth_evm::overrides::StateOverride;
+use tokio::sync::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
+
+/// Commands sent from a [`LocalMinerHandle`] to the local miner.
+///
+/// Every command that returns a value carries the sender for the response.
+#[derive(Debug)]
+pub enum LocalMinerCommand {
+    /// Build the given number of blocks, applying the given overrides in the first one.
+    Mine {
+        /// Number of blocks to build.
+        blocks: u64,
+        /// Interval between the timestamps of the blocks, if any.
+        interval: Option<u64>,
+        /// Overrides applied in the first block.
+        overrides: StateOverride,
+        /// Sender for the hashes of the built blocks.
+        tx: oneshot::Sender<eyre::Result<Vec<B256>>>,
+    },
+    /// Replace the mining mode.
+    SetMode {
+        /// The new mining mode.
+        mode: MiningMode,
+    },
+    /// Return whether blocks are built as soon as transactions reach the pool.
+    IsInstant {
+        /// Sender for the response.
+        tx: oneshot::Sender<bool>,
+    },
+    /// Return the hash and timestamp of the head block of the miner.
+    Head {
+        /// Sender for the response.
+        tx: oneshot::Sender<(B256, u64)>,
+    },
+    /// Use the given timestamp for the next block.
+    SetNextTimestamp {
+        /// Timestamp of the next block.
+        timestamp: u64,
+        /// Sender for the response.
+        tx: oneshot::Sender<eyre::Result<()>>,
+    },
+    /// Shift the clock of the miner by the given number of seconds.
+    IncreaseTime {
+        /// Seconds added to the clock.
+        seconds: i64,
+        /// Sender for the new offset to the system time.
+        tx: oneshot::Sender<i64>,
+    },
+    /// Set the clock of the miner to the given timestamp.
+    SetTime {
+        /// The new time.
+        timestamp: u64,
+        /// Sender for the new offset to the system time.
+        tx: oneshot::Sender<i64>,
+    },
+    /// Use a fixed interval between the timestamps of consecutive blocks.
+    SetTimestampInterval {
+        /// The interval, or `None` to remove it.
+        interval: Option<u64>,
+        /// Sender for the previous interval.
+        tx: oneshot::Sender<Option<u64>>,
+    },
+    /// Use the given `prevRandao` for the next block.
+    SetNextPrevRandao {
+        /// `prevRandao` of the next block.
+        prev_randao: B256,
+        /// Sender for the response.
+        tx: oneshot::Sender<eyre::Result<()>>,
+    },
+    /// Use the given fee recipient for all following blocks.
+    SetFeeRecipient {
+        /// The fee recipient.
+        recipient: Address,
+        /// Sender for the response.
+        tx: oneshot::Sender<eyre::Result<()>>,
+    },
+    /// Use the given base fee for the next block.
+    SetNextBaseFee {
+        /// Base fee of the next block.
+        base_fee: u64,
+        /// Sender for the response.
+        tx: oneshot::Sender<eyre::Result<()>>,
+    },
+    /// Use the given gas limit for all following blocks.
+    SetGasLimit {
+        /// The gas limit.
+        gas_limit: u64,
+        /// Sender for the response.
+        tx: oneshot::Sender<eyre::Result<()>>,
+    },
+    /// Make the given ancestor of the head the new head, dropping all blocks after it.
+    Rewind {
+        /// The ancestor that becomes the new head.
+        head: B256,
+        /// Timestamp of the ancestor.
+        timestamp: u64,
+        /// Sender for the response.
+        tx: oneshot::Sender<eyre::Result<()>>,
+    },
+}
+
+/// The commands sent to a local miner by its [`LocalMinerHandle`]s.
+#[derive(Debug)]
+pub struct LocalMinerCommands {
+    from_handles: UnboundedReceiver<LocalMinerCommand>,
+}
+
+impl LocalMinerCommands {
+    /// Creates a new command channel and a [`LocalMinerHandle`] to send commands into it.
+    pub fn new() -> (Self, LocalMinerHandle) {
+        let (to_miner, from_handles) = mpsc::unbounded_channel();
+        (Self { from_handles }, LocalMinerHandle { to_miner })
+    }
+
+    /// Receives the next command, or `None` if all handles have been dropped.
+    pub async fn recv(&mut self) -> Option<LocalMinerCommand> {
+        self.from_handles.recv().await
+    }
+}
+
+/// A cloneable handle to control a local miner.
+///
+/// All methods fail if the miner is no longer running.
+#[derive(Debug, Clone)]
+pub struct LocalMinerHandle {
+    to_miner: UnboundedSender<LocalMinerCommand>,
+}
+
+impl LocalMinerHandle {
+    /// Builds `blocks` blocks regardless of the mining mode and returns their hashes.
+    ///
+    /// If an `interval` is given, the timestamps of consecutive blocks are `interval` seconds
+    /// apart.
+    pub async fn mine(&self, blocks: u64, interval: Option<u64>) -> eyre::Result<Vec<B256>> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::Mine { blocks, interval, overrides: Default::default(), tx })?;
+        rx.await?
+    }
+
+    /// Builds a block that applies the given overrides before its transactions and returns its
+    /// hash.
+    ///
+    /// Fails if the EVM of the node does not support state overrides.
+    pub async fn mine_with_overrides(&self, overrides: StateOverride) -> eyre::Result<B256> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::Mine { blocks: 1, interval: None, overrides, tx })?;
+        let hashes = rx.await??;
+        hashes.last().copied().ok_or_else(|| eyre::eyre!("no block built"))
+    }
+
+    /// Replaces the mining mode of the miner.
+    pub fn set_mode(&self, mode: MiningMode) -> eyre::Result<()> {
+        self.send(LocalMinerCommand::SetMode { mode })
+    }
+
+    /// Returns `true` if the miner builds a block as soon as a transaction reaches the pool.
+    pub async fn is_instant(&self) -> eyre::Result<bool> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::IsInstant { tx })?;
+        Ok(rx.await?)
+    }
+
+    /// Returns the hash and timestamp of the latest block built by the miner.
+    pub async fn head(&self) -> eyre::Result<(B256, u64)> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::Head { tx })?;
+        Ok(rx.await?)
+    }
+
+    /// Sets the timestamp of the next block.
+    ///
+    /// Fails if the timestamp is not greater than the timestamp of the current head.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::SetNextTimestamp { timestamp, tx })?;
+        rx.await?
+    }
+
+    /// Moves the clock of the miner forward by `seconds` and returns the total offset to the
+    /// system time.
+    pub async fn increase_time(&self, seconds: i64) -> eyre::Result<i64> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::IncreaseTime { seconds, tx })?;
+        Ok(rx.await?)
+    }
+
+    /// Sets the clock of the miner to `timestamp` and returns the offset to the system time.
+    pub async fn set_time(&self, timestamp: u64) -> eyre::Result<i64> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::SetTime { timestamp, tx })?;
+        Ok(rx.await?)
+    }
+
+    /// Sets a fixed interval between the timestamps of consecutive blocks, or removes it if
+    /// `None`. Returns the previously configured interval.
+    pub async fn set_block_timestamp_interval(
+        &self,
+        interval: Option<u64>,
+    ) -> eyre::Result<Option<u64>> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::SetTimestampInterval { interval, tx })?;
+        Ok(rx.await?)
+    }
+
+    /// Sets the `prevRandao` of the next block.
+    ///
+    /// Fails if the payload attributes of the node do not support setting it.
+    pub async fn set_next_prev_randao(&self, prev_randao: B256) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::SetNextPrevRandao { prev_randao, tx })?;
+        rx.await?
+    }
+
+    /// Sets the fee recipient of all following blocks.
+    ///
+    /// Fails if the payload attributes of the node do not support setting it.
+    pub async fn set_fee_recipient(&self, recipient: Address) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::SetFeeRecipient { recipient, tx })?;
+        rx.await?
+    }
+
+    /// Sets the base fee of the next block.
+    ///
+    /// Fails if the EVM of the node does not support block environment overrides.
+    pub async fn set_next_block_base_fee(&self, base_fee: u64) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::SetNextBaseFee { base_fee, tx })?;
+        rx.await?
+    }
+
+    /// Sets the gas limit of all following blocks.
+    ///
+    /// Fails if the EVM of the node does not support block environment overrides.
+    pub async fn set_block_gas_limit(&self, gas_limit: u64) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::SetGasLimit { gas_limit, tx })?;
+        rx.await?
+    }
+
+    /// Makes `head`, a recent ancestor of the current head with the given `timestamp`, the head of
+    /// the chain again, dropping all blocks after it.
+    pub async fn rewind(&self, head: B256, timestamp: u64) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(LocalMinerCommand::Rewind { head, timestamp, tx })?;
+        rx.await?
+    }
+
+    fn send(&self, command: LocalMinerCommand) -> eyre::Result<()> {
+        self.to_miner.send(command).map_err(|_| eyre::eyre!("local miner is not running"))
+    }
+}
@@ -8,8 +8,12 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod credits;
+pub mod handle;
 pub mod miner;
 pub mod payload;
 
+pub use credits::{CreditInbox, CreditInboxHandle, InboundCredit, InboundCreditSource};
+pub use handle::{LocalMinerCommand, LocalMinerCommands, LocalMinerHandle};
 pub use miner::{LocalMiner, MiningMode};
 pub use payload::LocalPayloadAttributesBuilder;
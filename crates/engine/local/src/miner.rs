@@ -1,24 +1,30 @@
 //! Contains the implementation of the mining mode for the local engine.
 
-use alloy_consensus::BlockHeader;
-use alloy_primitives::{TxHash, B256};
+use crate::{
+    credits::{InboundCredit, InboundCreditSource},
+    handle::{LocalMinerCommand, LocalMinerCommands},
+};
+use alloy_consensus::{constants::EMPTY_WITHDRAWALS, BlockHeader};
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::{map::HashMap, Address, TxHash, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use eyre::OptionExt;
-use futures_util::{stream::Fuse, StreamExt};
+use futures_util::{future, stream::Fuse, FutureExt, StreamExt};
 use reth_engine_primitives::BeaconConsensusEngineHandle;
-use reth_payload_builder::PayloadBuilderHandle;
+use reth_evm::overrides::{BlockEnvOverride, BlockStateOverrides, StateOverride};
+use reth_payload_builder::{PayloadBuilderHandle, PayloadId};
 use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, PayloadAttributesBuilder, PayloadKind, PayloadTypes,
 };
-use reth_provider::BlockReader;
-use reth_transaction_pool::TransactionPool;
+use reth_primitives_traits::{Block, BlockBody};
+use reth_provider::{BlockReader, ProviderError, ProviderResult};
 use std::{
     future::Future,
     pin::Pin,
     task::{Context, Poll},
     time::{Duration, UNIX_EPOCH},
 };
-use tokio::time::Interval;
+use tokio::{sync::mpsc::Receiver, time::Interval};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::error;
 
@@ -30,13 +36,16 @@ pub enum MiningMode {
     Instant(Fuse<ReceiverStream<TxHash>>),
     /// In this mode a block is built at a fixed interval.
     Interval(Interval),
+    /// In this mode blocks are only built on request of a
+    /// [`LocalMinerHandle`](crate::LocalMinerHandle).
+    Manual,
 }
 
 impl MiningMode {
-    /// Constructor for a [`MiningMode::Instant`]
-    pub fn instant<Pool: TransactionPool>(pool: Pool) -> Self {
-        let rx = pool.pending_transactions_listener();
-        Self::Instant(ReceiverStream::new(rx).fuse())
+    /// Constructor for a [`MiningMode::Instant`] from a listener for the pending transactions of
+    /// the pool.
+    pub fn instant(pending_transactions: Receiver<TxHash>) -> Self {
+        Self::Instant(ReceiverStream::new(pending_transactions).fuse())
     }
 
     /// Constructor for a [`MiningMode::Interval`]
@@ -44,6 +53,11 @@ impl MiningMode {
         let start = tokio::time::Instant::now() + duration;
         Self::Interval(tokio::time::interval_at(start, duration))
     }
+
+    /// Returns true if this is [`MiningMode::Instant`].
+    pub const fn is_instant(&self) -> bool {
+        matches!(self, Self::Instant(_))
+    }
 }
 
 impl Future for MiningMode {
@@ -65,6 +79,7 @@ impl Future for MiningMode {
                 }
                 Poll::Pending
             }
+            Self::Manual => Poll::Pending,
         }
     }
 }
@@ -84,6 +99,34 @@ pub struct LocalMiner<T: PayloadTypes, B> {
     last_timestamp: u64,
     /// Stores latest mined blocks.
     last_block_hashes: Vec<B256>,
+    /// Source of credits that are paid out as withdrawals, if any.
+    credit_source: Option<Box<dyn InboundCreditSource>>,
+    /// Credits received from the source that have not been included in a block yet.
+    pending_credits: Vec<InboundCredit>,
+    /// Index of the next withdrawal included by the miner.
+    next_withdrawal_index: u64,
+    /// Credits paid out by the recent blocks of the miner, by block hash.
+    paid_credits: HashMap<B256, Vec<InboundCredit>>,
+    /// Commands sent by the handles of the miner, if any.
+    commands: Option<LocalMinerCommands>,
+    /// Registry of the state overrides applied by the EVM, if it supports them.
+    state_overrides: Option<BlockStateOverrides>,
+    /// Offset in seconds that is added to the system time when picking block timestamps.
+    time_offset: i64,
+    /// Timestamp of the next block, if it was set explicitly.
+    next_timestamp: Option<u64>,
+    /// Fixed interval between the timestamps of consecutive blocks, if any.
+    timestamp_interval: Option<u64>,
+    /// `prevRandao` of the next block, if it was set explicitly.
+    next_prev_randao: Option<B256>,
+    /// Fee recipient of all blocks, if it was set explicitly.
+    fee_recipient: Option<Address>,
+    /// Base fee of the next block, if it was set explicitly.
+    next_base_fee: Option<u64>,
+    /// Gas limit of all blocks, if it was set explicitly.
+    gas_limit: Option<u64>,
+    /// Whether the engine unwinds the canonical chain on a FCU to an ancestor of the head.
+    unwind_canonical_header: bool,
 }
 
 impl<T, B> LocalMiner<T, B>
@@ -91,25 +134,73 @@ where
     T: PayloadTypes,
     B: PayloadAttributesBuilder<<T as PayloadTypes>::PayloadAttributes>,
 {
-    /// Spawns a new [`LocalMiner`] with the given parameters.
+    /// Creates a new [`LocalMiner`] on top of the latest block of the provider.
+    ///
+    /// The withdrawal indices of the miner continue the withdrawals of the most recent block that
+    /// contains any.
     pub fn new(
         provider: impl BlockReader,
         payload_attributes_builder: B,
         to_engine: BeaconConsensusEngineHandle<T>,
         mode: MiningMode,
         payload_builder: PayloadBuilderHandle<T>,
-    ) -> Self {
+    ) -> ProviderResult<Self> {
+        let number = provider.best_block_number()?;
         let latest_header =
-            provider.sealed_header(provider.best_block_number().unwrap()).unwrap().unwrap();
+            provider.sealed_header(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        let next_withdrawal_index = next_withdrawal_index(&provider, number)?;
 
-        Self {
+        Ok(Self {
             payload_attributes_builder,
             to_engine,
             mode,
             payload_builder,
             last_timestamp: latest_header.timestamp(),
             last_block_hashes: vec![latest_header.hash()],
-        }
+            credit_source: None,
+            pending_credits: Vec::new(),
+            next_withdrawal_index,
+            paid_credits: HashMap::default(),
+            commands: None,
+            state_overrides: None,
+            time_offset: 0,
+            next_timestamp: None,
+            timestamp_interval: None,
+            next_prev_randao: None,
+            fee_recipient: None,
+            next_base_fee: None,
+            gas_limit: None,
+            unwind_canonical_header: false,
+        })
+    }
+
+    /// Configures the source of credits that are paid out as withdrawals in the built blocks.
+    ///
+    /// In [`MiningMode::Instant`] a block is built as soon as a credit is received.
+    pub fn with_credit_source(mut self, credit_source: impl InboundCreditSource) -> Self {
+        self.credit_source = Some(Box::new(credit_source));
+        self
+    }
+
+    /// Configures the commands sent by [`LocalMinerHandle`](crate::LocalMinerHandle)s that control
+    /// the miner.
+    pub fn with_commands(mut self, commands: LocalMinerCommands) -> Self {
+        self.commands = Some(commands);
+        self
+    }
+
+    /// Configures the registry of state overrides of the EVM, which is required to build blocks
+    /// that change the state of accounts.
+    pub fn with_state_overrides(mut self, state_overrides: BlockStateOverrides) -> Self {
+        self.state_overrides = Some(state_overrides);
+        self
+    }
+
+    /// Configures whether the engine unwinds the canonical chain on a FCU to an ancestor of the
+    /// head, which is required to rewind the chain.
+    pub const fn with_unwind_canonical_header(mut self, unwind_canonical_header: bool) -> Self {
+        self.unwind_canonical_header = unwind_canonical_header;
+        self
     }
 
     /// Runs the [`LocalMiner`] in a loop, polling the miner and building payloads.
@@ -123,6 +214,27 @@ where
                         error!(target: "engine::local", "Error advancing the chain: {:?}", e);
                     }
                 }
+                // Wait for the credit source to receive a credit
+                credit = next_credit(&mut self.credit_source) => {
+                    let Some(credit) = credit else {
+                        self.credit_source = None;
+                        continue
+                    };
+                    self.pending_credits.push(credit);
+                    if self.mode.is_instant() {
+                        if let Err(e) = self.advance().await {
+                            error!(target: "engine::local", "Error advancing the chain: {:?}", e);
+                        }
+                    }
+                }
+                // Wait for a handle to send a command
+                command = next_command(&mut self.commands) => {
+                    let Some(command) = command else {
+                        self.commands = None;
+                        continue
+                    };
+                    self.on_command(command).await;
+                }
                 // send FCU once in a while
                 _ = fcu_interval.tick() => {
                     if let Err(e) = self.update_forkchoice_state().await {
@@ -133,10 +245,163 @@ where
         }
     }
 
+    /// Handles a command sent by a [`LocalMinerHandle`](crate::LocalMinerHandle).
+    async fn on_command(&mut self, command: LocalMinerCommand) {
+        match command {
+            LocalMinerCommand::Mine { blocks, interval, overrides, tx } => {
+                let _ = tx.send(self.mine(blocks, interval, overrides).await);
+            }
+            LocalMinerCommand::SetMode { mode } => self.mode = mode,
+            LocalMinerCommand::IsInstant { tx } => {
+                let _ = tx.send(self.mode.is_instant());
+            }
+            LocalMinerCommand::Head { tx } => {
+                let _ = tx.send((self.head(), self.last_timestamp));
+            }
+            LocalMinerCommand::SetNextTimestamp { timestamp, tx } => {
+                let res = if timestamp > self.last_timestamp {
+                    self.next_timestamp = Some(timestamp);
+                    Ok(())
+                } else {
+                    Err(eyre::eyre!(
+                        "timestamp {timestamp} is not greater than the head timestamp {}",
+                        self.last_timestamp
+                    ))
+                };
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::IncreaseTime { seconds, tx } => {
+                self.time_offset = self.time_offset.saturating_add(seconds);
+                let _ = tx.send(self.time_offset);
+            }
+            LocalMinerCommand::SetTime { timestamp, tx } => {
+                self.time_offset = (timestamp as i64).saturating_sub(system_time() as i64);
+                let _ = tx.send(self.time_offset);
+            }
+            LocalMinerCommand::SetTimestampInterval { interval, tx } => {
+                let _ = tx.send(std::mem::replace(&mut self.timestamp_interval, interval));
+            }
+            LocalMinerCommand::SetNextPrevRandao { prev_randao, tx } => {
+                let mut attributes = self.payload_attributes_builder.build(self.last_timestamp);
+                let res = if self
+                    .payload_attributes_builder
+                    .set_prev_randao(&mut attributes, prev_randao)
+                {
+                    self.next_prev_randao = Some(prev_randao);
+                    Ok(())
+                } else {
+                    Err(eyre::eyre!("payload attributes do not support setting prevRandao"))
+                };
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::SetFeeRecipient { recipient, tx } => {
+                let mut attributes = self.payload_attributes_builder.build(self.last_timestamp);
+                let res = if self
+                    .payload_attributes_builder
+                    .set_suggested_fee_recipient(&mut attributes, recipient)
+                {
+                    self.fee_recipient = Some(recipient);
+                    Ok(())
+                } else {
+                    Err(eyre::eyre!("payload attributes do not support setting the fee recipient"))
+                };
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::SetNextBaseFee { base_fee, tx } => {
+                let res = self.ensure_env_overrides().map(|_| self.next_base_fee = Some(base_fee));
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::SetGasLimit { gas_limit, tx } => {
+                let res = self.ensure_env_overrides().map(|_| self.gas_limit = Some(gas_limit));
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::Rewind { head, timestamp, tx } => {
+                let _ = tx.send(self.rewind(head, timestamp).await);
+            }
+        }
+    }
+
+    /// Builds `blocks` blocks, applying the `overrides` in the first one, and makes the last one
+    /// canonical.
+    async fn mine(
+        &mut self,
+        blocks: u64,
+        interval: Option<u64>,
+        mut overrides: StateOverride,
+    ) -> eyre::Result<Vec<B256>> {
+        let mut hashes = Vec::new();
+        for _ in 0..blocks {
+            if let Some(interval) = interval.filter(|_| !hashes.is_empty()) {
+                self.next_timestamp = Some(self.last_timestamp + interval);
+            }
+            self.advance_with(std::mem::take(&mut overrides)).await?;
+            hashes.push(self.head());
+        }
+        self.update_forkchoice_state().await?;
+        Ok(hashes)
+    }
+
+    /// Makes `head`, which must be one of the recent blocks of the miner and have the given
+    /// `timestamp`, the head of the canonical chain again.
+    ///
+    /// All blocks after `head` are dropped from the canonical chain and the credits they paid out
+    /// are queued again, in front of the pending credits. This relies on the engine
+    /// unwinding the canonical chain on a FCU to an ancestor of the head, see
+    /// [`TreeConfig::with_unwind_canonical_header`](reth_engine_primitives::TreeConfig::with_unwind_canonical_header).
+    async fn rewind(&mut self, head: B256, timestamp: u64) -> eyre::Result<()> {
+        if !self.unwind_canonical_header {
+            eyre::bail!("rewinding the chain requires --engine.allow-unwind-canonical-header")
+        }
+        let position = self
+            .last_block_hashes
+            .iter()
+            .rposition(|hash| *hash == head)
+            .ok_or_eyre("block is not a recent block of the miner")?;
+
+        let dropped = self.last_block_hashes.split_off(position + 1);
+        let last_timestamp = std::mem::replace(&mut self.last_timestamp, timestamp);
+        if let Err(err) = self.update_forkchoice_state().await {
+            // the dropped blocks are still canonical
+            self.last_block_hashes.extend(dropped);
+            self.last_timestamp = last_timestamp;
+            return Err(err)
+        }
+
+        let requeued = dropped
+            .iter()
+            .filter_map(|hash| self.paid_credits.remove(hash))
+            .flatten()
+            .collect::<Vec<_>>();
+        self.next_withdrawal_index -= requeued.len() as u64;
+        self.pending_credits.splice(..0, requeued);
+
+        Ok(())
+    }
+
+    /// Returns the hash of the latest block built by the miner.
+    fn head(&self) -> B256 {
+        *self.last_block_hashes.last().expect("at least 1 block exists")
+    }
+
+    /// Returns the timestamp of the next block.
+    ///
+    /// Unless set explicitly, this is the system time shifted by the configured offset, or the
+    /// configured interval after the last block, and always after the last block.
+    fn next_block_timestamp(&self) -> u64 {
+        if let Some(timestamp) = self.next_timestamp {
+            return timestamp
+        }
+        if let Some(interval) = self.timestamp_interval {
+            return self.last_timestamp + interval
+        }
+        let now = system_time().saturating_add_signed(self.time_offset);
+        std::cmp::max(self.last_timestamp + 1, now)
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
-            head_block_hash: *self.last_block_hashes.last().expect("at least 1 block exists"),
+            head_block_hash: self.head(),
             safe_block_hash: *self
                 .last_block_hashes
                 .get(self.last_block_hashes.len().saturating_sub(32))
@@ -162,22 +427,72 @@ where
         Ok(())
     }
 
+    /// Returns the payload attributes for a new block with the given timestamp and the number of
+    /// pending credits they include.
+    ///
+    /// All credits that are ready are included as withdrawals, if the attributes support them.
+    /// The configured `prevRandao` and fee recipient replace the ones picked by the attributes
+    /// builder.
+    fn payload_attributes(&mut self, timestamp: u64) -> (T::PayloadAttributes, usize) {
+        if let Some(source) = &mut self.credit_source {
+            while let Some(Some(credit)) = source.next().now_or_never() {
+                self.pending_credits.push(credit);
+            }
+        }
+
+        let mut credited = 0;
+        let mut attributes = None;
+        if !self.pending_credits.is_empty() {
+            let withdrawals = self.pending_withdrawals();
+            attributes =
+                self.payload_attributes_builder.build_with_withdrawals(timestamp, withdrawals);
+            if attributes.is_some() {
+                credited = self.pending_credits.len();
+            }
+        }
+        let mut attributes =
+            attributes.unwrap_or_else(|| self.payload_attributes_builder.build(timestamp));
+
+        // support for both values is checked when they are configured
+        if let Some(prev_randao) = self.next_prev_randao {
+            self.payload_attributes_builder.set_prev_randao(&mut attributes, prev_randao);
+        }
+        if let Some(recipient) = self.fee_recipient {
+            self.payload_attributes_builder.set_suggested_fee_recipient(&mut attributes, recipient);
+        }
+
+        (attributes, credited)
+    }
+
+    /// Returns the pending credits as withdrawals, continuing the tracked withdrawal index.
+    fn pending_withdrawals(&self) -> Vec<Withdrawal> {
+        self.pending_credits
+            .iter()
+            .zip(self.next_withdrawal_index..)
+            .map(|(credit, index)| credit.into_withdrawal(index))
+            .collect()
+    }
+
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
     /// through newPayload.
     async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
-            self.last_timestamp + 1,
-            std::time::SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("cannot be earlier than UNIX_EPOCH")
-                .as_secs(),
-        );
+        self.advance_with(Default::default()).await
+    }
+
+    /// Builds a new block like [`Self::advance`] that applies the given state overrides before its
+    /// transactions.
+    async fn advance_with(&mut self, overrides: StateOverride) -> eyre::Result<()> {
+        let timestamp = self.next_block_timestamp();
+        let parent = self.head();
+        self.register_overrides(parent, timestamp, overrides)?;
+
+        let (attributes, credited) = self.payload_attributes(timestamp);
 
         let res = self
             .to_engine
             .fork_choice_updated(
                 self.forkchoice_state(),
-                Some(self.payload_attributes_builder.build(timestamp)),
+                Some(attributes),
                 EngineApiMessageVersion::default(),
             )
             .await?;
@@ -188,13 +503,62 @@ where
 
         let payload_id = res.payload_id.ok_or_eyre("No payload id")?;
 
+        let block_hash = self.insert_payload(payload_id).await;
+        self.remove_env_override(parent, timestamp);
+        self.on_block_inserted(block_hash?, timestamp, credited);
+
+        Ok(())
+    }
+
+    /// Returns an error if the EVM does not support overriding the block environment.
+    fn ensure_env_overrides(&self) -> eyre::Result<()> {
+        self.state_overrides
+            .as_ref()
+            .map(|_| ())
+            .ok_or_eyre("EVM does not support block environment overrides")
+    }
+
+    /// Registers the given state overrides and the configured block environment for the block
+    /// with the given parent and timestamp.
+    fn register_overrides(
+        &self,
+        parent: B256,
+        timestamp: u64,
+        overrides: StateOverride,
+    ) -> eyre::Result<()> {
+        if !overrides.is_empty() {
+            let state_overrides =
+                self.state_overrides.as_ref().ok_or_eyre("EVM does not support state overrides")?;
+            state_overrides.insert(parent, timestamp, overrides)?;
+        }
+
+        let env =
+            BlockEnvOverride { base_fee_per_gas: self.next_base_fee, gas_limit: self.gas_limit };
+        // support for block environment overrides is checked when they are configured
+        if let Some(state_overrides) = self.state_overrides.as_ref().filter(|_| !env.is_empty()) {
+            state_overrides.insert_env(parent, timestamp, env);
+        }
+        Ok(())
+    }
+
+    /// Removes the block environment override of a block once it was built, since executing the
+    /// block reads the values from its header.
+    fn remove_env_override(&self, parent: B256, timestamp: u64) {
+        if let Some(state_overrides) = &self.state_overrides {
+            state_overrides.remove_env(parent, timestamp);
+        }
+    }
+
+    /// Resolves the payload of the given job and inserts it through newPayload. Returns the hash
+    /// of the inserted block.
+    async fn insert_payload(&self, payload_id: PayloadId) -> eyre::Result<B256> {
         let Some(Ok(payload)) =
             self.payload_builder.resolve_kind(payload_id, PayloadKind::WaitForPending).await
         else {
             eyre::bail!("No payload")
         };
 
-        let block = payload.block();
+        let block_hash = payload.block().hash();
 
         let payload = T::block_to_payload(payload.block().clone());
         let res = self.to_engine.new_payload(payload).await?;
@@ -203,14 +567,119 @@ where
             eyre::bail!("Invalid payload")
         }
 
+        Ok(block_hash)
+    }
+
+    /// Records a block that was built with the given timestamp and pays out the first `credited`
+    /// pending credits.
+    fn on_block_inserted(&mut self, block_hash: B256, timestamp: u64, credited: usize) {
         self.last_timestamp = timestamp;
-        self.last_block_hashes.push(block.hash());
+        self.next_timestamp = None;
+        self.next_prev_randao = None;
+        self.next_base_fee = None;
+        let paid = self.pending_credits.drain(..credited).collect::<Vec<_>>();
+        self.next_withdrawal_index += credited as u64;
+        if !paid.is_empty() {
+            self.paid_credits.insert(block_hash, paid);
+        }
+        self.last_block_hashes.push(block_hash);
         // ensure we keep at most 64 blocks
         if self.last_block_hashes.len() > 64 {
-            self.last_block_hashes =
-                self.last_block_hashes.split_off(self.last_block_hashes.len() - 64);
+            let kept = self.last_block_hashes.split_off(self.last_block_hashes.len() - 64);
+            for hash in std::mem::replace(&mut self.last_block_hashes, kept) {
+                self.paid_credits.remove(&hash);
+            }
         }
+    }
+}
 
-        Ok(())
+/// Returns the current system time in seconds since the UNIX epoch.
+fn system_time() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("cannot be earlier than UNIX_EPOCH")
+        .as_secs()
+}
+
+/// Returns the index following the last withdrawal included at or below block `number`.
+///
+/// Walks back the headers from `number` to the most recent block whose withdrawals root is not
+/// the empty root, stopping at the first block that predates withdrawals altogether. Only the
+/// body of that block is read.
+fn next_withdrawal_index(provider: &impl BlockReader, number: u64) -> ProviderResult<u64> {
+    for number in (0..=number).rev() {
+        let header = provider
+            .header_by_number(number)?
+            .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        match header.withdrawals_root() {
+            None => return Ok(0),
+            Some(EMPTY_WITHDRAWALS) => continue,
+            Some(_) => {}
+        }
+        let block = provider
+            .block_by_number(number)?
+            .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        let last = block.body().withdrawals().and_then(|withdrawals| withdrawals.last());
+        return Ok(last.map_or(0, |withdrawal| withdrawal.index + 1))
+    }
+    Ok(0)
+}
+
+/// Returns the next command of the given handles, or never resolves if there are no handles.
+async fn next_command(commands: &mut Option<LocalMinerCommands>) -> Option<LocalMinerCommand> {
+    match commands {
+        Some(commands) => commands.recv().await,
+        None => future::pending().await,
+    }
+}
+
+/// Returns the next credit of the given source, or never resolves if there is no source.
+async fn next_credit(source: &mut Option<Box<dyn InboundCreditSource>>) -> Option<InboundCredit> {
+    match source {
+        Some(source) => source.next().await,
+        None => future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{proofs::calculate_withdrawals_root, Header};
+    use alloy_eips::eip4895::Withdrawals;
+    use reth_ethereum_primitives::{Block, BlockBody};
+    use reth_provider::test_utils::MockEthProvider;
+
+    fn block(number: u64, withdrawals: Option<Vec<u64>>) -> (B256, Block) {
+        let withdrawals = withdrawals.map(|indices| {
+            Withdrawals::new(
+                indices
+                    .into_iter()
+                    .map(|index| Withdrawal { index, ..Default::default() })
+                    .collect(),
+            )
+        });
+        let withdrawals_root =
+            withdrawals.as_ref().map(|withdrawals| calculate_withdrawals_root(withdrawals));
+        let body = BlockBody { withdrawals, ..Default::default() };
+        (
+            B256::with_last_byte(number as u8),
+            Block::new(Header { number, withdrawals_root, ..Default::default() }, body),
+        )
+    }
+
+    #[test]
+    fn withdrawal_index_continues_last_block_with_withdrawals() {
+        let provider = MockEthProvider::default();
+        provider.extend_blocks([
+            block(0, None),
+            block(1, Some(vec![])),
+            block(2, Some(vec![0, 1])),
+            block(3, Some(vec![])),
+            block(4, Some(vec![])),
+        ]);
+        assert_eq!(next_withdrawal_index(&provider, 4).unwrap(), 2);
+        assert_eq!(next_withdrawal_index(&provider, 2).unwrap(), 2);
+        assert_eq!(next_withdrawal_index(&provider, 1).unwrap(), 0);
+        assert_eq!(next_withdrawal_index(&provider, 0).unwrap(), 0);
     }
 }
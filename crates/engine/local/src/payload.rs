@@ -1,6 +1,7 @@
 //! The implementation of the [`PayloadAttributesBuilder`] for the
 //! [`LocalMiner`](super::LocalMiner).
 
+use alloy_eips::eip4895::Withdrawal;
 use alloy_primitives::{Address, B256};
 use reth_chainspec::EthereumHardforks;
 use reth_ethereum_engine_primitives::EthPayloadAttributes;
@@ -41,6 +42,30 @@ where
                 .then(B256::random),
         }
     }
+
+    fn build_with_withdrawals(
+        &self,
+        timestamp: u64,
+        withdrawals: Vec<Withdrawal>,
+    ) -> Option<EthPayloadAttributes> {
+        let mut attributes: EthPayloadAttributes = self.build(timestamp);
+        *attributes.withdrawals.as_mut()? = withdrawals;
+        Some(attributes)
+    }
+
+    fn set_prev_randao(&self, attributes: &mut EthPayloadAttributes, prev_randao: B256) -> bool {
+        attributes.prev_randao = prev_randao;
+        true
+    }
+
+    fn set_suggested_fee_recipient(
+        &self,
+        attributes: &mut EthPayloadAttributes,
+        recipient: Address,
+    ) -> bool {
+        attributes.suggested_fee_recipient = recipient;
+        true
+    }
 }
 
 #[cfg(feature = "op")]
@@ -62,6 +87,24 @@ where
             eip_1559_params: None,
         }
     }
+
+    fn set_prev_randao(
+        &self,
+        attributes: &mut op_alloy_rpc_types_engine::OpPayloadAttributes,
+        prev_randao: B256,
+    ) -> bool {
+        attributes.payload_attributes.prev_randao = prev_randao;
+        true
+    }
+
+    fn set_suggested_fee_recipient(
+        &self,
+        attributes: &mut op_alloy_rpc_types_engine::OpPayloadAttributes,
+        recipient: Address,
+    ) -> bool {
+        attributes.payload_attributes.suggested_fee_recipient = recipient;
+        true
+    }
 }
 
 /// A temporary workaround to support local payload engine launcher for arbitrary payload
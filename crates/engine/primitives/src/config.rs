@@ -79,6 +79,9 @@ pub struct TreeConfig {
     precompile_cache_enabled: bool,
     /// Whether to use state root fallback for testing
     state_root_fallback: bool,
+    /// Whether a forkchoice update to an ancestor of the canonical head unwinds the canonical
+    /// chain to that ancestor, instead of being skipped.
+    unwind_canonical_header: bool,
 }
 
 impl Default for TreeConfig {
@@ -99,6 +102,7 @@ impl Default for TreeConfig {
             reserved_cpu_cores: DEFAULT_RESERVED_CPU_CORES,
             precompile_cache_enabled: false,
             state_root_fallback: false,
+            unwind_canonical_header: false,
         }
     }
 }
@@ -122,6 +126,7 @@ impl TreeConfig {
         reserved_cpu_cores: usize,
         precompile_cache_enabled: bool,
         state_root_fallback: bool,
+        unwind_canonical_header: bool,
     ) -> Self {
         Self {
             persistence_threshold,
@@ -139,6 +144,7 @@ impl TreeConfig {
             reserved_cpu_cores,
             precompile_cache_enabled,
             state_root_fallback,
+            unwind_canonical_header,
         }
     }
 
@@ -214,6 +220,12 @@ impl TreeConfig {
         self.state_root_fallback
     }
 
+    /// Returns whether a forkchoice update to an ancestor of the canonical head unwinds the
+    /// canonical chain.
+    pub const fn unwind_canonical_header(&self) -> bool {
+        self.unwind_canonical_header
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -323,6 +335,13 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for whether a forkchoice update to an ancestor of the canonical head unwinds the
+    /// canonical chain, e.g. to revert the chain of a dev node.
+    pub const fn with_unwind_canonical_header(mut self, unwind_canonical_header: bool) -> Self {
+        self.unwind_canonical_header = unwind_canonical_header;
+        self
+    }
+
     /// Whether or not to use state root task
     pub const fn use_state_root_task(&self) -> bool {
         self.has_enough_parallelism && !self.legacy_state_root
@@ -1,6 +1,6 @@
 use alloy_primitives::B256;
 use reth_execution_types::BlockExecutionOutput;
-use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
 use reth_trie::updates::TrieUpdates;
 
 /// An invalid block hook.
@@ -13,6 +13,14 @@ pub trait InvalidBlockHook<N: NodePrimitives>: Send + Sync {
         output: &BlockExecutionOutput<N::Receipt>,
         trie_updates: Option<(&TrieUpdates, B256)>,
     );
+
+    /// Invoked when a block is rejected by the engine, with the error it was rejected with.
+    ///
+    /// Unlike [`Self::on_invalid_block`], this is also invoked for blocks that are rejected before
+    /// they are executed, for example because of an invalid header.
+    fn on_rejected_block(&self, block: &SealedBlock<N::Block>, error: &dyn core::error::Error) {
+        let _ = (block, error);
+    }
 }
 
 impl<F, N> InvalidBlockHook<N> for F
@@ -17,7 +17,7 @@ use reth_payload_primitives::{
     EngineApiMessageVersion, EngineObjectValidationError, InvalidPayloadAttributesError,
     NewPayloadError, PayloadAttributes, PayloadOrAttributes, PayloadTypes,
 };
-use reth_primitives_traits::{Block, RecoveredBlock};
+use reth_primitives_traits::{Block, RecoveredBlock, SealedBlock};
 use reth_trie_common::HashedPostState;
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -126,6 +126,19 @@ pub trait PayloadValidator: Send + Sync + Unpin + 'static {
         payload: Self::ExecutionData,
     ) -> Result<RecoveredBlock<Self::Block>, NewPayloadError>;
 
+    /// Converts the given payload into a block without checking that it is well-formed.
+    ///
+    /// This is used to keep track of payloads that are rejected by
+    /// [`Self::ensure_well_formed_payload`], returns `None` if the payload can't be decoded into a
+    /// block.
+    fn convert_payload_to_block(
+        &self,
+        payload: Self::ExecutionData,
+    ) -> Option<SealedBlock<Self::Block>> {
+        let _ = payload;
+        None
+    }
+
     /// Verifies payload post-execution w.r.t. hashed state updates.
     fn validate_block_post_execution_with_hashed_state(
         &self,
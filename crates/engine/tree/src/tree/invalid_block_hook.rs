@@ -1,6 +1,6 @@
 use alloy_primitives::B256;
 use reth_engine_primitives::InvalidBlockHook;
-use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
 use reth_provider::BlockExecutionOutput;
 use reth_trie::updates::TrieUpdates;
 
@@ -41,4 +41,10 @@ impl<N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockHooks<N> {
             hook.on_invalid_block(parent_header, block, output, trie_updates);
         }
     }
+
+    fn on_rejected_block(&self, block: &SealedBlock<N::Block>, error: &dyn core::error::Error) {
+        for hook in &self.0 {
+            hook.on_rejected_block(block, error);
+        }
+    }
 }
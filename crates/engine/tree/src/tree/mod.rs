@@ -550,10 +550,18 @@ where
         //
         // This validation **MUST** be instantly run in all cases even during active sync process.
         let parent_hash = payload.parent_hash();
+        // the payload is kept to record it if it is rejected, this is cheap because the
+        // transactions are reference counted
+        let rejected_payload = payload.clone();
         let block = match self.payload_validator.ensure_well_formed_payload(payload) {
             Ok(block) => block,
             Err(error) => {
                 error!(target: "engine::tree", %error, "Invalid payload");
+                if let Some(block) =
+                    self.payload_validator.convert_payload_to_block(rejected_payload)
+                {
+                    self.invalid_block_hook.on_rejected_block(&block, &error);
+                }
                 // we need to convert the error to a payload status (response to the CL)
 
                 let latest_valid_hash =
@@ -884,6 +892,24 @@ where
         if let Ok(Some(canonical_header)) = self.find_canonical_header(state.head_block_hash) {
             debug!(target: "engine::tree", head = canonical_header.number(), "fcu head block is already canonical");
 
+            // Dev nodes may unwind their own chain, e.g. to revert to a snapshot, so the ancestor
+            // becomes the new canonical head.
+            if self.config.unwind_canonical_header() {
+                self.unwind_canonical_head(&canonical_header)?;
+
+                if let Err(outcome) = self.ensure_consistent_forkchoice_state(state) {
+                    return Ok(TreeOutcome::new(outcome))
+                }
+
+                if let Some(attr) = attrs {
+                    let updated =
+                        self.process_payload_attributes(attr, &canonical_header, state, version);
+                    return Ok(TreeOutcome::new(updated))
+                }
+
+                return Ok(valid_outcome(state.head_block_hash))
+            }
+
             // For OpStack the proposers are allowed to reorg their own chain at will, so we need to
             // always trigger a new payload job if requested.
             if self.engine_kind.is_opstack() {
@@ -1939,6 +1965,68 @@ where
         ));
     }
 
+    /// Makes the given ancestor of the canonical head the new canonical head, dropping all blocks
+    /// after it from the canonical chain.
+    ///
+    /// This is notified like a reorg to the ancestor. Dropped blocks that were already persisted
+    /// are removed from disk by the next persistence run, see [`Self::find_disk_reorg`].
+    fn unwind_canonical_head(
+        &mut self,
+        new_head: &SealedHeader<N::BlockHeader>,
+    ) -> ProviderResult<()> {
+        debug!(target: "engine::tree", head = ?new_head.num_hash(), "Unwinding canonical chain to ancestor");
+        let start = Instant::now();
+
+        // collect the dropped blocks, oldest first
+        let mut old = Vec::new();
+        let mut current = self.state.tree_state.current_canonical_head;
+        while current.number > new_head.number() {
+            let block = self
+                .canonical_block_by_hash(current.hash)?
+                .ok_or(ProviderError::BlockHashNotFound(current.hash))?;
+            current = block.recovered_block().parent_num_hash();
+            old.push(block);
+        }
+        old.reverse();
+
+        self.update_reorg_metrics(old.len());
+        self.state.tree_state.set_canonical_head(new_head.num_hash());
+
+        // an ancestor that is not persisted yet stays in memory, a persisted one is only part of
+        // the notification because the in-memory state must not start at a persisted block
+        let notification = match self.state.tree_state.executed_block_by_hash(new_head.hash()) {
+            Some(block) => {
+                let chain_update = NewCanonicalChain::Reorg { new: vec![block.clone()], old };
+                let notification = chain_update.to_chain_notification();
+                self.canonical_in_memory_state.update_chain(chain_update);
+                notification
+            }
+            None => {
+                let block = ExecutedBlockWithTrieUpdates {
+                    block: self
+                        .canonical_block_by_hash(new_head.hash())?
+                        .ok_or(ProviderError::BlockHashNotFound(new_head.hash()))?,
+                    trie: ExecutedTrieUpdates::Missing,
+                };
+                let notification = NewCanonicalChain::Reorg { new: vec![block], old: old.clone() }
+                    .to_chain_notification();
+                self.canonical_in_memory_state
+                    .update_chain(NewCanonicalChain::Reorg { new: Vec::new(), old });
+                notification
+            }
+        };
+        self.canonical_in_memory_state.set_canonical_head(new_head.clone());
+        self.metrics.tree.canonical_chain_height.set(new_head.number() as f64);
+        self.canonical_in_memory_state.notify_canon_state(notification);
+
+        self.emit_event(BeaconConsensusEngineEvent::CanonicalChainCommitted(
+            Box::new(new_head.clone()),
+            start.elapsed(),
+        ));
+
+        Ok(())
+    }
+
     /// This updates metrics based on the given reorg length.
     fn update_reorg_metrics(&self, old_chain_length: usize) {
         self.metrics.tree.reorgs.increment(1);
@@ -2584,7 +2672,8 @@ where
         );
         let latest_valid_hash = self.latest_valid_hash_for_invalid_payload(block.parent_hash())?;
 
-        // keep track of the invalid header
+        // record the rejected block and keep track of the invalid header
+        self.invalid_block_hook.on_rejected_block(&block, &validation_err);
         self.state.invalid_headers.insert(block.block_with_parent());
         self.emit_event(EngineApiEvent::BeaconConsensus(BeaconConsensusEngineEvent::InvalidBlock(
             Box::new(block),
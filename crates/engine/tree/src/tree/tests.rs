@@ -585,6 +585,49 @@ fn test_disconnected_payload() {
     assert_eq!(buffered.clone_sealed_block(), sealed);
 }
 
+#[test]
+fn test_malformed_payload_is_rejected() {
+    /// Records the hashes of rejected blocks.
+    #[derive(Default, Clone)]
+    struct RejectedBlocks(Arc<std::sync::Mutex<Vec<B256>>>);
+
+    impl InvalidBlockHook<EthPrimitives> for RejectedBlocks {
+        fn on_invalid_block(
+            &self,
+            _parent_header: &SealedHeader,
+            _block: &RecoveredBlock<Block>,
+            _output: &BlockExecutionOutput<reth_ethereum_primitives::Receipt>,
+            _trie_updates: Option<(&TrieUpdates, B256)>,
+        ) {
+        }
+
+        fn on_rejected_block(&self, block: &SealedBlock<Block>, _error: &dyn core::error::Error) {
+            self.0.lock().unwrap().push(block.hash());
+        }
+    }
+
+    let s = include_str!("../../test-data/holesky/2.rlp");
+    let data = Bytes::from_str(s).unwrap();
+    let block = Block::decode(&mut data.as_ref()).unwrap();
+    let sealed = block.seal_slow();
+    let payload =
+        ExecutionPayloadV1::from_block_unchecked(B256::ZERO, &sealed.clone().into_block());
+
+    let mut test_harness = TestHarness::new(HOLESKY.clone());
+    let rejected = RejectedBlocks::default();
+    test_harness.tree.set_invalid_block_hook(Box::new(rejected.clone()));
+
+    let outcome = test_harness
+        .tree
+        .on_new_payload(ExecutionData {
+            payload: payload.into(),
+            sidecar: ExecutionPayloadSidecar::none(),
+        })
+        .unwrap();
+    assert!(outcome.outcome.is_invalid());
+    assert_eq!(*rejected.0.lock().unwrap(), vec![sealed.hash()]);
+}
+
 #[test]
 fn test_disconnected_block() {
     let s = include_str!("../../test-data/holesky/2.rlp");
@@ -941,6 +984,36 @@ async fn test_engine_tree_fcu_reorg_with_all_blocks() {
     test_harness.check_canon_head(fork_chain_last_hash);
 }
 
+#[tokio::test]
+async fn test_engine_tree_fcu_unwinds_to_canonical_ancestor() {
+    let chain_spec = MAINNET.clone();
+    let mut test_harness = TestHarness::new(chain_spec.clone());
+    test_harness.tree.config = test_harness.tree.config.clone().with_unwind_canonical_header(true);
+
+    let main_chain: Vec<_> = test_harness.block_builder.get_executed_blocks(0..5).collect();
+    test_harness = test_harness.with_blocks(main_chain.clone());
+
+    let ancestor_hash = main_chain[2].recovered_block().hash();
+    test_harness.send_fcu(ancestor_hash, ForkchoiceStatus::Valid).await;
+
+    // check for CanonicalChainCommitted event
+    test_harness.check_canon_commit(ancestor_hash).await;
+
+    test_harness.check_fcu(ancestor_hash, ForkchoiceStatus::Valid).await;
+
+    // the ancestor is the new head and the blocks after it are no longer canonical
+    test_harness.check_canon_head(ancestor_hash);
+    assert_eq!(
+        test_harness.tree.canonical_in_memory_state.get_canonical_head().hash(),
+        ancestor_hash
+    );
+    assert!(test_harness
+        .tree
+        .canonical_in_memory_state
+        .state_by_hash(main_chain[3].recovered_block().hash())
+        .is_none());
+}
+
 #[tokio::test]
 async fn test_engine_tree_live_sync_transition_required_blocks_requested() {
     reth_tracing::init_test_tracing();
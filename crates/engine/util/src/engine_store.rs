@@ -1,20 +1,29 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
-use alloy_rpc_types_engine::ForkchoiceState;
-use futures::{Stream, StreamExt};
-use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload};
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus};
+use futures::{Future, FutureExt, Stream, StreamExt};
+use reth_engine_primitives::{
+    BeaconEngineMessage, BeaconOnNewPayloadError, ExecutionPayload, ForkchoiceStatus,
+    OnForkChoiceUpdated,
+};
+use reth_errors::RethResult;
 use reth_fs_util as fs;
-use reth_payload_primitives::PayloadTypes;
+use reth_payload_primitives::{EngineApiMessageVersion, PayloadTypes};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    fmt,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
+/// The suffix of the files that store the response to an engine API message.
+const RESPONSE_FILE_SUFFIX: &str = ".response.json";
+
 /// A message from the engine API that has been stored to disk.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -25,6 +34,11 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
         state: ForkchoiceState,
         /// The payload attributes sent in the persisted call, if any.
         payload_attrs: Option<T::PayloadAttributes>,
+        /// The engine API version of the persisted call.
+        ///
+        /// Messages stored without a version are replayed with the latest version.
+        #[serde(default)]
+        version: EngineApiMessageVersion,
     },
     /// The on-disk representation of an `engine_newPayload` method call.
     NewPayload {
@@ -34,8 +48,88 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
     },
 }
 
+impl<T: PayloadTypes> StoredEngineApiMessage<T> {
+    /// Creates the on-disk representation of the given message.
+    pub fn from_message(msg: &BeaconEngineMessage<T>) -> Self {
+        match msg {
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx: _tx } => {
+                Self::ForkchoiceUpdated {
+                    state: *state,
+                    payload_attrs: payload_attrs.clone(),
+                    version: *version,
+                }
+            }
+            BeaconEngineMessage::NewPayload { payload, tx: _tx } => {
+                Self::NewPayload { payload: payload.clone() }
+            }
+        }
+    }
+}
+
+/// The response of the engine to a stored engine API message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredEngineApiResponse {
+    /// The [`PayloadStatus`] returned for an `engine_newPayload` call.
+    NewPayload(PayloadStatus),
+    /// The status of the forkchoice state returned for an `engine_forkchoiceUpdated` call.
+    ForkchoiceUpdated(StoredForkchoiceStatus),
+    /// The error returned for the call.
+    Error(String),
+}
+
+impl StoredEngineApiResponse {
+    /// Creates the response of an `engine_newPayload` call.
+    pub fn from_new_payload(response: &Result<PayloadStatus, BeaconOnNewPayloadError>) -> Self {
+        match response {
+            Ok(status) => Self::NewPayload(status.clone()),
+            Err(err) => Self::Error(err.to_string()),
+        }
+    }
+
+    /// Creates the response of an `engine_forkchoiceUpdated` call.
+    pub fn from_forkchoice_updated(response: &RethResult<OnForkChoiceUpdated>) -> Self {
+        match response {
+            Ok(on_updated) => Self::ForkchoiceUpdated(on_updated.forkchoice_status().into()),
+            Err(err) => Self::Error(err.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for StoredEngineApiResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NewPayload(status) => write!(f, "{:?}", status.status),
+            Self::ForkchoiceUpdated(status) => write!(f, "{status:?}"),
+            Self::Error(err) => write!(f, "error: {err}"),
+        }
+    }
+}
+
+/// The on-disk representation of a [`ForkchoiceStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredForkchoiceStatus {
+    /// The forkchoice state is valid.
+    Valid,
+    /// The forkchoice state is invalid.
+    Invalid,
+    /// The forkchoice state is unknown.
+    Syncing,
+}
+
+impl From<ForkchoiceStatus> for StoredForkchoiceStatus {
+    fn from(status: ForkchoiceStatus) -> Self {
+        match status {
+            ForkchoiceStatus::Valid => Self::Valid,
+            ForkchoiceStatus::Invalid => Self::Invalid,
+            ForkchoiceStatus::Syncing => Self::Syncing,
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EngineMessageStore {
     /// The path to the directory that stores the engine API messages.
     path: PathBuf,
@@ -51,51 +145,79 @@ impl EngineMessageStore {
 
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
+    ///
+    /// Returns the path of the stored message.
     pub fn on_message<T>(
         &self,
         msg: &BeaconEngineMessage<T>,
         received_at: SystemTime,
-    ) -> eyre::Result<()>
+    ) -> eyre::Result<PathBuf>
+    where
+        T: PayloadTypes,
+    {
+        self.store_message(&StoredEngineApiMessage::from_message(msg), received_at)
+    }
+
+    /// Stores the [`StoredEngineApiMessage`] to disk, appending the `received_at` time to the
+    /// path.
+    ///
+    /// Returns the path of the stored message.
+    pub fn store_message<T>(
+        &self,
+        msg: &StoredEngineApiMessage<T>,
+        received_at: SystemTime,
+    ) -> eyre::Result<PathBuf>
     where
         T: PayloadTypes,
     {
         fs::create_dir_all(&self.path)?; // ensure that store path had been created
         let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
-        match msg {
-            BeaconEngineMessage::ForkchoiceUpdated {
-                state,
-                payload_attrs,
-                tx: _tx,
-                version: _version,
-            } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
-                fs::write(
-                    self.path.join(filename),
-                    serde_json::to_vec(&StoredEngineApiMessage::<T>::ForkchoiceUpdated {
-                        state: *state,
-                        payload_attrs: payload_attrs.clone(),
-                    })?,
-                )?;
+        let filename = match msg {
+            StoredEngineApiMessage::ForkchoiceUpdated { state, .. } => {
+                format!("{}-fcu-{}.json", timestamp, state.head_block_hash)
             }
-            BeaconEngineMessage::NewPayload { payload, tx: _tx } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
-                fs::write(
-                    self.path.join(filename),
-                    serde_json::to_vec(&StoredEngineApiMessage::<T>::NewPayload {
-                        payload: payload.clone(),
-                    })?,
-                )?;
+            StoredEngineApiMessage::NewPayload { payload } => {
+                format!("{}-new_payload-{}.json", timestamp, payload.block_hash())
             }
         };
+        let path = self.path.join(filename);
+        fs::write(&path, serde_json::to_vec(msg)?)?;
+        Ok(path)
+    }
+
+    /// Returns the path of the file that stores the response to the message at the given path.
+    pub fn response_path(message_path: &Path) -> PathBuf {
+        let stem = message_path.file_stem().unwrap_or_default().to_string_lossy();
+        message_path.with_file_name(format!("{stem}{RESPONSE_FILE_SUFFIX}"))
+    }
+
+    /// Stores the response to the message at the given path.
+    pub fn on_response(
+        message_path: &Path,
+        response: &StoredEngineApiResponse,
+    ) -> eyre::Result<()> {
+        fs::write(Self::response_path(message_path), serde_json::to_vec(response)?)?;
         Ok(())
     }
 
+    /// Reads the stored response to the message at the given path, if any.
+    pub fn read_response(message_path: &Path) -> eyre::Result<Option<StoredEngineApiResponse>> {
+        let path = Self::response_path(message_path);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let filename = entry.file_name();
+            if filename.to_str().is_some_and(|n| n.ends_with(RESPONSE_FILE_SUFFIX)) {
+                continue
+            }
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
                 if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
@@ -107,12 +229,16 @@ impl EngineMessageStore {
                 tracing::warn!(target: "engine::store", ?filename, "Skipping non json file");
             }
         }
-        Ok(filenames_by_ts.into_iter().flat_map(|(_, paths)| paths))
+        Ok(filenames_by_ts.into_values().flatten())
     }
 }
 
-/// A wrapper stream that stores Engine API messages in
-/// the specified directory.
+/// A wrapper stream that stores Engine API messages and the responses of the engine in the
+/// specified directory.
+///
+/// The files are written by a task spawned for every message, which also forwards the response
+/// of the engine to the original sender, so the engine is not blocked on disk access and responses
+/// outlive the stream.
 #[derive(Debug)]
 #[pin_project::pin_project]
 pub struct EngineStoreStream<S> {
@@ -130,6 +256,64 @@ impl<S> EngineStoreStream<S> {
     }
 }
 
+/// Replaces the response sender of the message with one that stores the response before
+/// forwarding it to the original sender.
+///
+/// Returns the message and a future that stores the message and then its response.
+fn store_message_and_response<T: PayloadTypes>(
+    msg: BeaconEngineMessage<T>,
+    store: EngineMessageStore,
+    received_at: SystemTime,
+) -> (BeaconEngineMessage<T>, impl Future<Output = ()> + Send + 'static) {
+    let stored = StoredEngineApiMessage::from_message(&msg);
+    let store_message = move || {
+        store
+            .store_message(&stored, received_at)
+            .inspect_err(|error| {
+                error!(target: "engine::stream::store", ?stored, %error, "Error handling Engine API message");
+            })
+            .ok()
+    };
+    let store_response = |message_path: Option<PathBuf>, response: StoredEngineApiResponse| {
+        let Some(message_path) = message_path else { return };
+        if let Err(error) = EngineMessageStore::on_response(&message_path, &response) {
+            error!(target: "engine::stream::store", %error, "Error storing Engine API response");
+        }
+    };
+
+    match msg {
+        BeaconEngineMessage::NewPayload { payload, tx } => {
+            let (response_tx, response_rx) = oneshot::channel();
+            let fut = async move {
+                let message_path = store_message();
+                let Ok(response) = response_rx.await else { return };
+                store_response(message_path, StoredEngineApiResponse::from_new_payload(&response));
+                let _ = tx.send(response);
+            };
+            (BeaconEngineMessage::NewPayload { payload, tx: response_tx }, fut.left_future())
+        }
+        BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx } => {
+            let (response_tx, response_rx) = oneshot::channel();
+            let fut = async move {
+                let message_path = store_message();
+                let Ok(response) = response_rx.await else { return };
+                store_response(
+                    message_path,
+                    StoredEngineApiResponse::from_forkchoice_updated(&response),
+                );
+                let _ = tx.send(response);
+            };
+            let msg = BeaconEngineMessage::ForkchoiceUpdated {
+                state,
+                payload_attrs,
+                version,
+                tx: response_tx,
+            };
+            (msg, fut.right_future())
+        }
+    }
+}
+
 impl<S, T> Stream for EngineStoreStream<S>
 where
     S: Stream<Item = BeaconEngineMessage<T>>,
@@ -140,11 +324,167 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
         let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next {
-            if let Err(error) = this.store.on_message(msg, SystemTime::now()) {
-                error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+        let Some(msg) = next else { return Poll::Ready(None) };
+
+        let (msg, store) = store_message_and_response(msg, this.store.clone(), SystemTime::now());
+        tokio::spawn(store);
+        Poll::Ready(Some(msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Block;
+    use alloy_primitives::B256;
+    use alloy_rpc_types_engine::{ExecutionData, ExecutionPayload, PayloadStatusEnum};
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_ethereum_primitives::TransactionSigned;
+
+    type NewPayloadResponse = oneshot::Receiver<Result<PayloadStatus, BeaconOnNewPayloadError>>;
+    type ForkchoiceUpdatedResponse = oneshot::Receiver<RethResult<OnForkChoiceUpdated>>;
+
+    fn new_payload() -> (BeaconEngineMessage<EthEngineTypes>, NewPayloadResponse) {
+        let (payload, sidecar) =
+            ExecutionPayload::from_block_slow(&Block::<TransactionSigned>::default());
+        let (tx, rx) = oneshot::channel();
+        (BeaconEngineMessage::NewPayload { payload: ExecutionData { payload, sidecar }, tx }, rx)
+    }
+
+    fn forkchoice_updated(
+        version: EngineApiMessageVersion,
+    ) -> (BeaconEngineMessage<EthEngineTypes>, ForkchoiceUpdatedResponse) {
+        let (tx, rx) = oneshot::channel();
+        let state = ForkchoiceState {
+            head_block_hash: B256::with_last_byte(1),
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+        (BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs: None, version, tx }, rx)
+    }
+
+    fn read_message(path: &Path) -> StoredEngineApiMessage<EthEngineTypes> {
+        serde_json::from_slice(&fs::read(path).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn stores_responses_after_stream_ends() {
+        let dir = tempfile::tempdir().unwrap();
+        let (new_payload, new_payload_rx) = new_payload();
+        let (forkchoice_updated, forkchoice_updated_rx) =
+            forkchoice_updated(EngineApiMessageVersion::V3);
+        let stream = EngineStoreStream::new(
+            futures::stream::iter([new_payload, forkchoice_updated]),
+            dir.path().to_path_buf(),
+        );
+
+        // the engine responds only after the stream ended
+        let messages = stream.collect::<Vec<_>>().await;
+        for msg in messages {
+            match msg {
+                BeaconEngineMessage::NewPayload { tx, .. } => {
+                    tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))).unwrap()
+                }
+                BeaconEngineMessage::ForkchoiceUpdated { tx, .. } => tx
+                    .send(Ok(OnForkChoiceUpdated::valid(PayloadStatus::from_status(
+                        PayloadStatusEnum::Valid,
+                    ))))
+                    .unwrap(),
+            }
+        }
+
+        // the responses are forwarded to the original senders
+        let new_payload_status = new_payload_rx.await.unwrap().unwrap();
+        assert_eq!(new_payload_status.status, PayloadStatusEnum::Syncing);
+        let on_updated = forkchoice_updated_rx.await.unwrap().unwrap();
+        assert_eq!(on_updated.forkchoice_status(), ForkchoiceStatus::Valid);
+
+        // and stored alongside the messages, which are iterated without the responses
+        let store = EngineMessageStore::new(dir.path().to_path_buf());
+        let paths = store.engine_messages_iter().unwrap().collect::<Vec<_>>();
+        assert_eq!(paths.len(), 2);
+        for path in paths {
+            let response = EngineMessageStore::read_response(&path).unwrap();
+            match read_message(&path) {
+                StoredEngineApiMessage::NewPayload { .. } => {
+                    assert_eq!(
+                        response,
+                        Some(StoredEngineApiResponse::NewPayload(new_payload_status.clone()))
+                    );
+                }
+                StoredEngineApiMessage::ForkchoiceUpdated { version, .. } => {
+                    assert_eq!(version, EngineApiMessageVersion::V3);
+                    assert_eq!(
+                        response,
+                        Some(StoredEngineApiResponse::ForkchoiceUpdated(
+                            StoredForkchoiceStatus::Valid
+                        ))
+                    );
+                }
             }
         }
-        Poll::Ready(next)
+    }
+
+    #[tokio::test]
+    async fn stores_message_without_response() {
+        let dir = tempfile::tempdir().unwrap();
+        let (new_payload, new_payload_rx) = new_payload();
+        let mut stream =
+            EngineStoreStream::new(futures::stream::iter([new_payload]), dir.path().to_path_buf());
+
+        // the engine drops the message without responding
+        drop(stream.next().await.unwrap());
+        assert!(new_payload_rx.await.is_err());
+
+        let store = EngineMessageStore::new(dir.path().to_path_buf());
+        let paths = store.engine_messages_iter().unwrap().collect::<Vec<_>>();
+        assert_eq!(paths.len(), 1);
+        assert!(matches!(read_message(&paths[0]), StoredEngineApiMessage::NewPayload { .. }));
+        assert_eq!(EngineMessageStore::read_response(&paths[0]).unwrap(), None);
+    }
+
+    #[test]
+    fn read_response() {
+        let dir = tempfile::tempdir().unwrap();
+        let message_path = dir.path().join(format!("1-fcu-{}.json", B256::ZERO));
+        assert_eq!(EngineMessageStore::read_response(&message_path).unwrap(), None);
+
+        // the stored response reads back equal, so the diff of a replay only reports responses
+        // that actually differ
+        let response = StoredEngineApiResponse::NewPayload(PayloadStatus::new(
+            PayloadStatusEnum::Invalid { validation_error: "invalid state root".into() },
+            Some(B256::with_last_byte(1)),
+        ));
+        EngineMessageStore::on_response(&message_path, &response).unwrap();
+        let stored = EngineMessageStore::read_response(&message_path).unwrap().unwrap();
+        assert_eq!(stored, response);
+        assert_ne!(
+            stored,
+            StoredEngineApiResponse::NewPayload(PayloadStatus::new(
+                PayloadStatusEnum::Invalid { validation_error: "invalid state root".into() },
+                Some(B256::ZERO),
+            ))
+        );
+        assert_ne!(stored, StoredEngineApiResponse::Error("invalid state root".into()));
+    }
+
+    #[test]
+    fn forkchoice_updated_without_version() {
+        let message: StoredEngineApiMessage<EthEngineTypes> =
+            serde_json::from_value(serde_json::json!({
+                "forkchoiceUpdated": {
+                    "state": {
+                        "headBlockHash": B256::ZERO,
+                        "safeBlockHash": B256::ZERO,
+                        "finalizedBlockHash": B256::ZERO,
+                    },
+                    "payload_attrs": null,
+                }
+            }))
+            .unwrap();
+        let StoredEngineApiMessage::ForkchoiceUpdated { version, .. } = message else {
+            panic!("unexpected message {message:?}")
+        };
+        assert_eq!(version, EngineApiMessageVersion::default());
     }
 }
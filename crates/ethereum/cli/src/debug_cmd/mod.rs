@@ -13,6 +13,7 @@ mod build_block;
 mod execution;
 mod in_memory_merkle;
 mod merkle;
+mod replay_engine;
 
 /// `reth debug` command
 #[derive(Debug, Parser)]
@@ -32,6 +33,8 @@ pub enum Subcommands<C: ChainSpecParser> {
     InMemoryMerkle(in_memory_merkle::Command<C>),
     /// Debug block building.
     BuildBlock(build_block::Command<C>),
+    /// Debug engine API by replaying stored messages.
+    ReplayEngine(replay_engine::Command<C>),
 }
 
 impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
@@ -51,6 +54,7 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
             Subcommands::Merkle(command) => command.execute::<N>(ctx).await,
             Subcommands::InMemoryMerkle(command) => command.execute::<N>(ctx).await,
             Subcommands::BuildBlock(command) => command.execute::<N>(ctx).await,
+            Subcommands::ReplayEngine(command) => command.execute::<N>(ctx).await,
         }
     }
 }
@@ -63,6 +67,7 @@ impl<C: ChainSpecParser> Command<C> {
             Subcommands::Merkle(command) => command.chain_spec(),
             Subcommands::InMemoryMerkle(command) => command.chain_spec(),
             Subcommands::BuildBlock(command) => command.chain_spec(),
+            Subcommands::ReplayEngine(command) => command.chain_spec(),
         }
     }
 }
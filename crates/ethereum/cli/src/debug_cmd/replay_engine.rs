@@ -0,0 +1,187 @@
+//! Command for replaying stored engine API messages.
+
+use clap::Parser;
+use futures::StreamExt;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_consensus::FullConsensus;
+use reth_engine_service::service::EngineService;
+use reth_engine_tree::tree::{NoopInvalidBlockHook, TreeConfig};
+use reth_engine_util::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredEngineApiResponse,
+};
+use reth_errors::ConsensusError;
+use reth_ethereum_primitives::EthPrimitives;
+use reth_fs_util as fs;
+use reth_network_p2p::full_block::NoopFullBlockClient;
+use reth_node_api::BeaconEngineMessage;
+use reth_node_ethereum::{
+    consensus::EthBeaconConsensus, EthEngineTypes, EthEvmConfig, EthereumEngineValidator,
+};
+use reth_payload_builder::noop::NoopPayloadBuilderService;
+use reth_provider::{providers::BlockchainProvider, ChainSpecProvider};
+use reth_prune::PrunerBuilder;
+use reth_stages::Pipeline;
+use reth_static_file::StaticFileProducer;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::*;
+
+/// `reth debug replay-engine` command
+///
+/// Replays the engine API messages stored with `--debug.engine-api-store` through the engine tree.
+/// Blocks are not downloaded from the network, so the replay is deterministic: all blocks have to
+/// be part of the stored messages or of the datadir.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The path to read engine API messages from.
+    #[arg(long = "engine-api-store", value_name = "PATH")]
+    engine_api_store: PathBuf,
+
+    /// The number of milliseconds between engine API messages.
+    #[arg(long = "interval", default_value_t = 1_000)]
+    interval: u64,
+
+    /// The index of the last message to replay, counting from zero.
+    #[arg(long = "stop-at-index", value_name = "INDEX")]
+    stop_at_index: Option<usize>,
+
+    /// Compares the responses of the engine with the responses stored alongside the messages.
+    ///
+    /// The command fails if any response differs.
+    #[arg(long = "diff")]
+    diff: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `debug replay-engine` command
+    pub async fn execute<
+        N: CliNodeTypes<
+            Payload = EthEngineTypes,
+            Primitives = EthPrimitives,
+            ChainSpec = C::ChainSpec,
+        >,
+    >(
+        self,
+        ctx: CliContext,
+    ) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let chain_spec = provider_factory.chain_spec();
+        let consensus: Arc<dyn FullConsensus<EthPrimitives, Error = ConsensusError>> =
+            Arc::new(EthBeaconConsensus::new(chain_spec.clone()));
+        let evm_config = EthEvmConfig::ethereum(chain_spec.clone());
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        // the stored messages contain all blocks, there is nothing to backfill
+        let pipeline = Pipeline::builder().build(
+            provider_factory.clone(),
+            StaticFileProducer::new(provider_factory.clone(), Default::default()),
+        );
+        let pruner = PrunerBuilder::new(Default::default())
+            .build_with_provider_factory(provider_factory.clone());
+
+        // payloads are not built during the replay, only the forkchoice status is compared
+        let (payload_service, payload_builder) = NoopPayloadBuilderService::<EthEngineTypes>::new();
+        ctx.task_executor.spawn_critical("payload builder service", payload_service);
+        let (sync_metrics_tx, _sync_metrics_rx) = mpsc::unbounded_channel();
+
+        let (engine_tx, engine_rx) = mpsc::unbounded_channel();
+        let client: NoopFullBlockClient = NoopFullBlockClient::default();
+        let mut engine_service = EngineService::new(
+            consensus,
+            chain_spec.clone(),
+            client,
+            Box::pin(UnboundedReceiverStream::new(engine_rx)),
+            pipeline,
+            Box::new(ctx.task_executor.clone()),
+            provider_factory,
+            blockchain_db,
+            pruner,
+            payload_builder,
+            EthereumEngineValidator::new(chain_spec),
+            TreeConfig::default(),
+            Box::new(NoopInvalidBlockHook::default()),
+            sync_metrics_tx,
+            evm_config,
+        );
+        ctx.task_executor.spawn_critical("engine service", async move {
+            while let Some(event) = engine_service.next().await {
+                debug!(target: "reth::cli", ?event, "Engine event");
+            }
+        });
+
+        let store = EngineMessageStore::new(self.engine_api_store.clone());
+        let mut replayed = 0;
+        let mut mismatches = 0;
+        for (index, filepath) in store.engine_messages_iter()?.enumerate() {
+            if self.stop_at_index.is_some_and(|stop_at| index > stop_at) {
+                break
+            }
+
+            let message: StoredEngineApiMessage<EthEngineTypes> =
+                serde_json::from_slice(&fs::read(&filepath)?)?;
+            debug!(target: "reth::cli", index, filepath = %filepath.display(), "Forwarding Engine API message");
+
+            let response = match message {
+                StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs, version } => {
+                    let (tx, rx) = oneshot::channel();
+                    engine_tx
+                        .send(BeaconEngineMessage::ForkchoiceUpdated {
+                            state,
+                            payload_attrs,
+                            version,
+                            tx,
+                        })
+                        .map_err(|_| eyre::eyre!("engine service stopped"))?;
+                    StoredEngineApiResponse::from_forkchoice_updated(&rx.await?)
+                }
+                StoredEngineApiMessage::NewPayload { payload } => {
+                    let (tx, rx) = oneshot::channel();
+                    engine_tx
+                        .send(BeaconEngineMessage::NewPayload { payload, tx })
+                        .map_err(|_| eyre::eyre!("engine service stopped"))?;
+                    StoredEngineApiResponse::from_new_payload(&rx.await?)
+                }
+            };
+            info!(target: "reth::cli", index, filepath = %filepath.display(), %response, "Replayed Engine API message");
+            replayed += 1;
+
+            if self.diff {
+                match EngineMessageStore::read_response(&filepath)? {
+                    Some(original) if original != response => {
+                        mismatches += 1;
+                        warn!(target: "reth::cli", index, filepath = %filepath.display(), %original, replayed = %response, "Response differs from the original");
+                    }
+                    Some(_) => {}
+                    None => {
+                        warn!(target: "reth::cli", index, filepath = %filepath.display(), "No original response stored")
+                    }
+                }
+            }
+
+            // Throttle
+            tokio::time::sleep(Duration::from_millis(self.interval)).await;
+        }
+
+        info!(target: "reth::cli", replayed, "Finished replaying Engine API messages");
+        if mismatches > 0 {
+            eyre::bail!("{mismatches} of {replayed} responses differ from the original responses")
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
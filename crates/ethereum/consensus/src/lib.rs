@@ -16,11 +16,15 @@ use alloy_consensus::EMPTY_OMMER_ROOT_HASH;
 use alloy_eips::eip7840::BlobParams;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
-use reth_consensus_common::validation::{
-    validate_4844_header_standalone, validate_against_parent_4844,
-    validate_against_parent_eip1559_base_fee, validate_against_parent_hash_number,
-    validate_against_parent_timestamp, validate_block_pre_execution, validate_body_against_header,
-    validate_header_base_fee, validate_header_extra_data, validate_header_gas,
+use reth_consensus_common::{
+    bridge::{validate_against_parent_bridge_range, validate_bridge_withdrawals, BridgeConfig},
+    validation::{
+        validate_4844_header_standalone, validate_against_parent_4844,
+        validate_against_parent_eip1559_base_fee, validate_against_parent_hash_number,
+        validate_against_parent_timestamp, validate_block_pre_execution,
+        validate_body_against_header, validate_extra_data_sections, validate_header_base_fee,
+        validate_header_gas,
+    },
 };
 use reth_execution_types::BlockExecutionResult;
 use reth_primitives_traits::{
@@ -38,12 +42,44 @@ pub use validation::validate_block_post_execution;
 pub struct EthBeaconConsensus<ChainSpec> {
     /// Configuration
     chain_spec: Arc<ChainSpec>,
+    /// Committee that attests the withdrawals of the chain, if bridge credits are validated.
+    bridge: Option<Arc<BridgeConfig>>,
+    /// Whether the blocks of the chain carry their inbound messages in an inbox section.
+    inbox: bool,
+    /// Whether the gas limit and base fee may be set freely, as dev nodes do on request.
+    dev: bool,
 }
 
 impl<ChainSpec: EthChainSpec + EthereumHardforks> EthBeaconConsensus<ChainSpec> {
     /// Create a new instance of [`EthBeaconConsensus`]
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self { chain_spec, bridge: None, inbox: false, dev: false }
+    }
+
+    /// Accepts any gas limit and base fee instead of requiring them to follow from the parent
+    /// block.
+    ///
+    /// Dev nodes change both on request, e.g. through `anvil_setBlockGasLimit`.
+    pub const fn with_dev_mode(mut self) -> Self {
+        self.dev = true;
+        self
+    }
+
+    /// Validates the withdrawals of the chain as bridge credits attested by the given committee.
+    ///
+    /// See [`reth_consensus_common::bridge`] for the rules.
+    pub fn with_bridge_config(mut self, config: Arc<BridgeConfig>) -> Self {
+        self.bridge = Some(config);
+        self
+    }
+
+    /// Requires the extra-data of every block to carry an inbox section with the inbound messages
+    /// of the block.
+    ///
+    /// See [`reth_consensus_common::inbox`] for the section.
+    pub const fn with_inbox(mut self) -> Self {
+        self.inbox = true;
+        self
     }
 
     /// Checks the gas limit for consistency between parent and self headers.
@@ -126,7 +162,11 @@ where
     }
 
     fn validate_block_pre_execution(&self, block: &SealedBlock<B>) -> Result<(), Self::Error> {
-        validate_block_pre_execution(block, &self.chain_spec)
+        validate_block_pre_execution(block, &self.chain_spec)?;
+        if let Some(bridge) = &self.bridge {
+            validate_bridge_withdrawals(block, self.chain_spec.chain_id(), bridge)?;
+        }
+        Ok(())
     }
 }
 
@@ -169,7 +209,7 @@ where
                 }
             }
         }
-        validate_header_extra_data(header)?;
+        validate_extra_data_sections(header, self.bridge.as_deref(), self.inbox)?;
         validate_header_gas(header)?;
         validate_header_base_fee(header, &self.chain_spec)?;
 
@@ -222,19 +262,25 @@ where
 
         // TODO Check difficulty increment between parent and self
         // Ace age did increment it by some formula that we need to follow.
-        self.validate_against_parent_gas_limit(header, parent)?;
+        if !self.dev {
+            self.validate_against_parent_gas_limit(header, parent)?;
 
-        validate_against_parent_eip1559_base_fee(
-            header.header(),
-            parent.header(),
-            &self.chain_spec,
-        )?;
+            validate_against_parent_eip1559_base_fee(
+                header.header(),
+                parent.header(),
+                &self.chain_spec,
+            )?;
+        }
 
         // ensure that the blob gas fields for this block
         if let Some(blob_params) = self.chain_spec.blob_params_at_timestamp(header.timestamp()) {
             validate_against_parent_4844(header.header(), parent.header(), blob_params)?;
         }
 
+        if let Some(bridge) = &self.bridge {
+            validate_against_parent_bridge_range(header.header(), parent.header(), bridge)?;
+        }
+
         Ok(())
     }
 }
@@ -321,6 +367,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dev_mode_accepts_any_gas_limit() {
+        let parent_header = reth_primitives_traits::Header {
+            number: 1,
+            timestamp: 1,
+            gas_limit: GAS_LIMIT_BOUND_DIVISOR * 10,
+            ..Default::default()
+        };
+        let parent = SealedHeader::new(parent_header, B256::with_last_byte(1));
+        let child = SealedHeader::seal_slow(reth_primitives_traits::Header {
+            parent_hash: parent.hash(),
+            number: 2,
+            timestamp: 2,
+            gas_limit: parent.gas_limit * 2,
+            ..Default::default()
+        });
+
+        let consensus = EthBeaconConsensus::new(Arc::new(ChainSpec::default()));
+        assert_eq!(
+            consensus.validate_header_against_parent(&child, &parent),
+            Err(ConsensusError::GasLimitInvalidIncrease {
+                parent_gas_limit: parent.gas_limit,
+                child_gas_limit: child.gas_limit,
+            })
+        );
+        assert_eq!(
+            consensus.with_dev_mode().validate_header_against_parent(&child, &parent),
+            Ok(())
+        );
+    }
+
     #[test]
     fn shanghai_block_zero_withdrawals() {
         // ensures that if shanghai is activated, and we include a block with a withdrawals root,
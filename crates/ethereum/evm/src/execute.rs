@@ -6,7 +6,10 @@ pub type EthExecutorProvider = crate::EthEvmConfig;
 
 #[cfg(test)]
 mod tests {
-    use crate::EthEvmConfig;
+    use crate::{
+        inbox::{inbox_calldata, Inbox},
+        EthEvmConfig,
+    };
     use alloy_consensus::{constants::ETH_TO_WEI, Header, TxLegacy};
     use alloy_eips::{
         eip2935::{HISTORY_SERVE_WINDOW, HISTORY_STORAGE_ADDRESS, HISTORY_STORAGE_CODE},
@@ -18,6 +21,7 @@ mod tests {
     use alloy_evm::block::BlockValidationError;
     use alloy_primitives::{b256, fixed_bytes, keccak256, Bytes, TxKind, B256, U256};
     use reth_chainspec::{ChainSpec, ChainSpecBuilder, EthereumHardfork, ForkCondition, MAINNET};
+    use reth_consensus_common::inbox::InboxSection;
     use reth_ethereum_primitives::{Block, BlockBody, Transaction};
     use reth_evm::{execute::Executor, ConfigureEvm};
     use reth_execution_types::BlockExecutionResult;
@@ -183,6 +187,62 @@ mod tests {
             );
     }
 
+    #[test]
+    fn inbox_delivers_carried_messages() {
+        // the inbox stores the size of its input: CALLDATASIZE PUSH1 0 SSTORE STOP
+        let inbox_address = address!("0x00000000000000000000000000000000000ba5e5");
+        let inbox_code = Bytes::from_static(&[0x36, 0x60, 0x00, 0x55, 0x00]);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            inbox_address,
+            AccountInfo {
+                nonce: 1,
+                code_hash: keccak256(&inbox_code),
+                code: Some(Bytecode::new_raw(inbox_code)),
+                ..Default::default()
+            },
+        );
+
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+        let provider =
+            evm_config(chain_spec).with_inbox(Inbox { address: inbox_address, trailing_len: 0 });
+
+        let block = |extra_data: Vec<u8>| {
+            RecoveredBlock::new_unhashed(
+                Block {
+                    header: Header {
+                        number: 1,
+                        extra_data: extra_data.into(),
+                        ..Header::default()
+                    },
+                    body: BlockBody { transactions: vec![], ommers: vec![], withdrawals: None },
+                },
+                vec![],
+            )
+        };
+        let delivered = |extra_data| {
+            let mut executor = provider.batch_executor(db.clone());
+            executor.execute_one(&block(extra_data)).unwrap();
+            executor.with_state_mut(|state| {
+                // the account has to be loaded before its storage is read
+                state.basic(inbox_address).unwrap();
+                state.storage(inbox_address, U256::ZERO).unwrap()
+            })
+        };
+
+        // blocks without messages don't call the inbox
+        assert_eq!(delivered(InboxSection::encode(&[]).unwrap()), U256::ZERO);
+
+        let messages = vec![Bytes::from_static(b"mint"), Bytes::from_static(b"call")];
+        let mut extra_data = b"reth".to_vec();
+        extra_data.extend(InboxSection::encode(&messages).unwrap());
+        assert_eq!(delivered(extra_data), U256::from(inbox_calldata(&messages).len()));
+
+        // blocks without an inbox section can't be executed
+        let mut executor = provider.batch_executor(db.clone());
+        assert!(executor.execute_one(&block(b"reth".to_vec())).is_err());
+    }
+
     #[test]
     fn eip_4788_empty_account_call() {
         // This test ensures that we do not increment the nonce of an empty SYSTEM_ADDRESS account
@@ -0,0 +1,256 @@
+//! Delivery of inbound cross-chain messages through a pre-execution system call.
+//!
+//! Chains with an inbox contract, see [`INBOX_CONTRACT_FIELD`], deliver the inbound messages of a
+//! block to it before the transactions of the block are executed, analogous to the [EIP-4788]
+//! beacon root call. The block carries its messages in the inbox section of its extra-data, see
+//! [`reth_consensus_common::inbox`], so the messages are delivered by every node that executes it.
+//!
+//! The node that builds a block takes the messages from its [`InboxMessages`], a queue of the
+//! batches that are waiting to be delivered.
+//!
+//! [EIP-4788]: https://eips.ethereum.org/EIPS/eip-4788
+
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec::Vec};
+use alloy_eips::eip4788::SYSTEM_ADDRESS;
+use alloy_evm::{
+    block::{
+        BlockExecutionError, BlockExecutor, CommitChanges, ExecutableTx, OnStateHook,
+        StateChangeSource,
+    },
+    Evm,
+};
+use alloy_primitives::{keccak256, Address, Bytes, B256};
+use alloy_sol_types::{sol, SolCall, SolValue};
+use core::fmt;
+use reth_consensus::ConsensusError;
+use reth_consensus_common::inbox::InboxSection;
+pub use reth_consensus_common::inbox::INBOX_CONTRACT_FIELD;
+use reth_execution_types::BlockExecutionResult;
+use revm::{
+    context::result::{ExecutionResult, ResultAndState},
+    database::State,
+    state::EvmState,
+    DatabaseCommit,
+};
+use std::sync::{Mutex, RwLock};
+
+/// Source of the state changes of the message delivery that are reported to the state hook.
+///
+/// [`StateChangePreBlockSource`](alloy_evm::block::StateChangePreBlockSource) has no variant for
+/// the inbox contract, so the delivery is reported as a transaction with an index that no
+/// transaction of a block can have.
+pub const INBOX_STATE_CHANGE_SOURCE: StateChangeSource = StateChangeSource::Transaction(usize::MAX);
+
+sol! {
+    /// Entry point of the inbox contract.
+    function deliverMessages(bytes[] messages);
+}
+
+/// Returns the commitment to a batch of messages, `keccak256(abi.encode(messages))`.
+pub fn inbox_commitment(messages: &[Bytes]) -> B256 {
+    keccak256(messages.abi_encode())
+}
+
+/// Returns the input of the system call that delivers the messages to the inbox contract.
+pub fn inbox_calldata(messages: &[Bytes]) -> Bytes {
+    deliverMessagesCall { messages: messages.to_vec() }.abi_encode().into()
+}
+
+/// Delivers the messages to the inbox contract and commits the resulting state changes.
+///
+/// The state changes are passed to `on_state` before they are committed. Nothing is delivered if
+/// there are no messages.
+pub fn deliver_messages<'db, DB, E>(
+    evm: &mut E,
+    inbox: Address,
+    messages: &[Bytes],
+    on_state: impl FnOnce(&EvmState),
+) -> Result<(), BlockExecutionError>
+where
+    DB: revm::Database + 'db,
+    E: Evm<DB = &'db mut State<DB>>,
+{
+    if messages.is_empty() {
+        return Ok(())
+    }
+
+    // a reverted delivery is not fatal, the inbox contract is expected to handle every message on
+    // its own
+    let ResultAndState { state, .. } = evm
+        .transact_system_call(SYSTEM_ADDRESS, inbox, inbox_calldata(messages))
+        .map_err(|err| BlockExecutionError::msg(format!("inbox call failed: {err}")))?;
+    on_state(&state);
+    evm.db_mut().commit(state);
+
+    Ok(())
+}
+
+/// Shared queue of the message batches that are waiting to be delivered in a block built by the
+/// node.
+#[derive(Debug, Clone, Default)]
+pub struct InboxMessages {
+    batches: Arc<RwLock<VecDeque<Arc<Vec<Bytes>>>>>,
+}
+
+impl InboxMessages {
+    /// Queues a batch of messages and returns its [`inbox_commitment`].
+    ///
+    /// Returns [`ConsensusError::InboxMessagesExceedMax`] if the batch doesn't fit into a block.
+    pub fn insert(&self, messages: Vec<Bytes>) -> Result<B256, ConsensusError> {
+        InboxSection::encode(&messages)?;
+        let commitment = inbox_commitment(&messages);
+        self.batches.write().unwrap().push_back(Arc::new(messages));
+        Ok(commitment)
+    }
+
+    /// Returns the oldest queued batch, skipping the batch with the given commitment.
+    ///
+    /// Batches are removed once they are delivered in a canonical block, the builder of a child
+    /// block passes the commitment of the batch of the parent so it is not delivered twice.
+    pub fn next_batch(&self, skip: Option<B256>) -> Option<Arc<Vec<Bytes>>> {
+        self.batches
+            .read()
+            .unwrap()
+            .iter()
+            .find(|messages| skip != Some(inbox_commitment(messages)))
+            .cloned()
+    }
+
+    /// Removes the batch with the given commitment, once it is delivered in a canonical block.
+    pub fn remove(&self, commitment: &B256) -> Option<Arc<Vec<Bytes>>> {
+        let mut batches = self.batches.write().unwrap();
+        let index =
+            batches.iter().position(|messages| inbox_commitment(messages) == *commitment)?;
+        batches.remove(index)
+    }
+
+    /// Returns the number of queued batches.
+    pub fn len(&self) -> usize {
+        self.batches.read().unwrap().len()
+    }
+
+    /// Returns `true` if no batch is queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl PartialEq for InboxMessages {
+    /// Two handles are equal if they share their queue.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.batches, &other.batches)
+    }
+}
+
+impl Eq for InboxMessages {}
+
+/// The inbox contract of a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inbox {
+    /// Address of the inbox contract.
+    pub address: Address,
+    /// Number of bytes at the end of the extra-data that follow the inbox section, the bridge
+    /// section on chains with a bridge committee.
+    pub trailing_len: usize,
+}
+
+impl Inbox {
+    /// Returns the messages that the inbox section of the given extra-data carries.
+    pub fn messages(&self, extra_data: &[u8]) -> Result<Vec<Bytes>, ConsensusError> {
+        let extra_data = &extra_data[..extra_data.len().saturating_sub(self.trailing_len)];
+        Ok(InboxSection::split_extra_data(extra_data)?.1.messages())
+    }
+}
+
+/// A [`BlockExecutor`] that delivers the messages of a block to the inbox contract after the
+/// pre-execution changes of the inner executor.
+pub struct InboxExecutor<E> {
+    inner: E,
+    /// The inbox contract and the messages of the block, if the block delivers messages.
+    delivery: Option<(Address, Result<Vec<Bytes>, ConsensusError>)>,
+    /// State hook that is shared with the inner executor.
+    hook: Arc<Mutex<Option<Box<dyn OnStateHook>>>>,
+}
+
+impl<E: fmt::Debug> fmt::Debug for InboxExecutor<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InboxExecutor")
+            .field("inner", &self.inner)
+            .field("delivery", &self.delivery)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> InboxExecutor<E> {
+    /// Creates a new executor that delivers no messages, e.g. for a block that is being built.
+    pub fn new(inner: E) -> Self {
+        Self { inner, delivery: None, hook: Default::default() }
+    }
+
+    /// Creates a new executor that delivers the messages carried in the extra-data of the block
+    /// to the inbox contract.
+    ///
+    /// The execution fails if the extra-data has no well-formed inbox section.
+    pub fn for_block(inner: E, inbox: Option<&Inbox>, extra_data: &[u8]) -> Self {
+        let delivery = inbox.map(|inbox| (inbox.address, inbox.messages(extra_data)));
+        Self { delivery, ..Self::new(inner) }
+    }
+}
+
+impl<'db, DB, E> BlockExecutor for InboxExecutor<E>
+where
+    DB: revm::Database + 'db,
+    E: BlockExecutor<Evm: Evm<DB = &'db mut State<DB>>>,
+{
+    type Transaction = E::Transaction;
+    type Receipt = E::Receipt;
+    type Evm = E::Evm;
+
+    fn apply_pre_execution_changes(&mut self) -> Result<(), BlockExecutionError> {
+        self.inner.apply_pre_execution_changes()?;
+
+        let Some((inbox, messages)) = self.delivery.take() else { return Ok(()) };
+        let messages = messages.map_err(BlockExecutionError::other)?;
+        let hook = self.hook.clone();
+        deliver_messages(self.inner.evm_mut(), inbox, &messages, |state| {
+            if let Some(hook) = hook.lock().unwrap().as_mut() {
+                hook.on_state(INBOX_STATE_CHANGE_SOURCE, state);
+            }
+        })
+    }
+
+    fn execute_transaction_with_commit_condition(
+        &mut self,
+        tx: impl ExecutableTx<Self>,
+        f: impl FnOnce(&ExecutionResult<<Self::Evm as Evm>::HaltReason>) -> CommitChanges,
+    ) -> Result<Option<u64>, BlockExecutionError> {
+        self.inner.execute_transaction_with_commit_condition(tx, f)
+    }
+
+    fn finish(
+        self,
+    ) -> Result<(Self::Evm, BlockExecutionResult<Self::Receipt>), BlockExecutionError> {
+        self.inner.finish()
+    }
+
+    fn set_state_hook(&mut self, hook: Option<Box<dyn OnStateHook>>) {
+        let forward = hook.is_some().then(|| {
+            let hook = self.hook.clone();
+            Box::new(move |source: StateChangeSource, state: &EvmState| {
+                if let Some(hook) = hook.lock().unwrap().as_mut() {
+                    hook.on_state(source, state);
+                }
+            }) as Box<dyn OnStateHook>
+        });
+        *self.hook.lock().unwrap() = hook;
+        self.inner.set_state_hook(forward);
+    }
+
+    fn evm(&self) -> &Self::Evm {
+        self.inner.evm()
+    }
+
+    fn evm_mut(&mut self) -> &mut Self::Evm {
+        self.inner.evm_mut()
+    }
+}
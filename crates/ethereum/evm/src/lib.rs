@@ -21,6 +21,7 @@ use alloc::{borrow::Cow, sync::Arc};
 use alloy_consensus::{BlockHeader, Header};
 pub use alloy_evm::EthEvm;
 use alloy_evm::{
+    block::{BlockExecutorFactory, BlockExecutorFor},
     eth::{EthBlockExecutionCtx, EthBlockExecutorFactory},
     EthEvmFactory, FromRecoveredTx, FromTxWithEncoded,
 };
@@ -28,14 +29,20 @@ use alloy_primitives::{Bytes, U256};
 use core::{convert::Infallible, fmt::Debug};
 use reth_chainspec::{ChainSpec, EthChainSpec, MAINNET};
 use reth_ethereum_primitives::{Block, EthPrimitives, TransactionSigned};
+#[cfg(feature = "std")]
 use reth_evm::{
-    precompiles::PrecompilesMap, ConfigureEvm, EvmEnv, EvmFactory, NextBlockEnvAttributes,
-    TransactionEnv,
+    overrides::{BlockStateOverrides, StateOverridesExecutor},
+    Evm,
+};
+use reth_evm::{
+    precompiles::PrecompilesMap, ConfigureEvm, Database, EvmEnv, EvmFactory, EvmFor, InspectorFor,
+    NextBlockEnvAttributes, TransactionEnv,
 };
 use reth_primitives_traits::{SealedBlock, SealedHeader};
 use revm::{
     context::{BlockEnv, CfgEnv},
     context_interface::block::BlobExcessGasAndPrice,
+    database::State,
     primitives::hardfork::SpecId,
 };
 
@@ -46,6 +53,9 @@ use reth_ethereum_forks::EthereumHardfork;
 
 pub mod execute;
 
+#[cfg(feature = "std")]
+pub mod inbox;
+
 mod build;
 pub use build::EthBlockAssembler;
 
@@ -64,6 +74,12 @@ pub struct EthEvmConfig<EvmFactory = EthEvmFactory> {
     pub executor_factory: EthBlockExecutorFactory<RethReceiptBuilder, Arc<ChainSpec>, EvmFactory>,
     /// Ethereum block assembler.
     pub block_assembler: EthBlockAssembler<ChainSpec>,
+    /// State overrides applied to the blocks registered in it, used by dev nodes.
+    #[cfg(feature = "std")]
+    pub state_overrides: Option<BlockStateOverrides>,
+    /// Inbox contract that receives the inbound messages of a block before its transactions.
+    #[cfg(feature = "std")]
+    pub inbox: Option<inbox::Inbox>,
 }
 
 impl EthEvmConfig {
@@ -93,6 +109,10 @@ impl<EvmFactory> EthEvmConfig<EvmFactory> {
                 chain_spec,
                 evm_factory,
             ),
+            #[cfg(feature = "std")]
+            state_overrides: None,
+            #[cfg(feature = "std")]
+            inbox: None,
         }
     }
 
@@ -106,6 +126,22 @@ impl<EvmFactory> EthEvmConfig<EvmFactory> {
         self.block_assembler.extra_data = extra_data;
         self
     }
+
+    /// Sets the registry of state overrides that are applied to the blocks registered in it.
+    #[cfg(feature = "std")]
+    pub fn with_state_overrides(mut self, state_overrides: BlockStateOverrides) -> Self {
+        self.state_overrides = Some(state_overrides);
+        self
+    }
+
+    /// Sets the inbox contract that the messages carried by a block are delivered to.
+    ///
+    /// See [`inbox`] for how blocks carry their messages.
+    #[cfg(feature = "std")]
+    pub const fn with_inbox(mut self, inbox: inbox::Inbox) -> Self {
+        self.inbox = Some(inbox);
+        self
+    }
 }
 
 impl<EvmF> ConfigureEvm for EthEvmConfig<EvmF>
@@ -137,6 +173,11 @@ where
         &self.block_assembler
     }
 
+    #[cfg(feature = "std")]
+    fn state_overrides(&self) -> Option<&BlockStateOverrides> {
+        self.state_overrides.as_ref()
+    }
+
     fn evm_env(&self, header: &Header) -> EvmEnv {
         let blob_params = self.chain_spec().blob_params_at_timestamp(header.timestamp);
         let spec = config::revm_spec(self.chain_spec(), header);
@@ -226,6 +267,22 @@ where
             basefee = Some(INITIAL_BASE_FEE)
         }
 
+        // dev nodes can pick the base fee and gas limit of the next block
+        #[cfg(feature = "std")]
+        if let Some(env) = self
+            .state_overrides
+            .as_ref()
+            .filter(|overrides| overrides.has_env_overrides())
+            .and_then(|overrides| overrides.env(parent.hash_slow(), attributes.timestamp))
+        {
+            if let Some(base_fee) = env.base_fee_per_gas {
+                basefee = Some(base_fee);
+            }
+            if let Some(limit) = env.gas_limit {
+                gas_limit = limit;
+            }
+        }
+
         let block_env = BlockEnv {
             number: parent.number + 1,
             beneficiary: attributes.suggested_fee_recipient,
@@ -263,6 +320,64 @@ where
             withdrawals: attributes.withdrawals.map(Cow::Owned),
         }
     }
+
+    fn create_executor<'a, DB, I>(
+        &'a self,
+        evm: EvmFor<Self, &'a mut State<DB>, I>,
+        ctx: EthBlockExecutionCtx<'a>,
+    ) -> impl BlockExecutorFor<'a, Self::BlockExecutorFactory, DB, I>
+    where
+        DB: Database,
+        I: InspectorFor<Self, &'a mut State<DB>> + 'a,
+    {
+        #[cfg(feature = "std")]
+        {
+            let (parent_hash, timestamp) = (ctx.parent_hash, evm.block().timestamp);
+            let executor = StateOverridesExecutor::new(
+                self.executor_factory.create_executor(evm, ctx),
+                self.state_overrides.as_ref(),
+                parent_hash,
+                timestamp,
+            );
+            // the messages of a block that is being built are delivered by its builder
+            inbox::InboxExecutor::new(executor)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            self.executor_factory.create_executor(evm, ctx)
+        }
+    }
+
+    fn create_executor_for_block<'a, DB, I>(
+        &'a self,
+        evm: EvmFor<Self, &'a mut State<DB>, I>,
+        block: &'a SealedBlock<Block>,
+    ) -> impl BlockExecutorFor<'a, Self::BlockExecutorFactory, DB, I>
+    where
+        DB: Database,
+        I: InspectorFor<Self, &'a mut State<DB>> + 'a,
+    {
+        #[cfg(feature = "std")]
+        {
+            let (parent_hash, timestamp) = (block.header().parent_hash, block.header().timestamp);
+            let executor = StateOverridesExecutor::new(
+                self.executor_factory.create_executor(evm, self.context_for_block(block)),
+                self.state_overrides.as_ref(),
+                parent_hash,
+                timestamp,
+            );
+            // the block carries its inbound messages in its extra-data
+            inbox::InboxExecutor::for_block(
+                executor,
+                self.inbox.as_ref(),
+                &block.header().extra_data,
+            )
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            self.executor_factory.create_executor(evm, self.context_for_block(block))
+        }
+    }
 }
 
 #[cfg(test)]
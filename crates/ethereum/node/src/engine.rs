@@ -1,11 +1,14 @@
 //! Validates execution payload wrt Ethereum Execution Engine API version.
 
+use crate::node::chain_inbox;
 use alloy_rpc_types_engine::ExecutionData;
 pub use alloy_rpc_types_engine::{
     ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3, ExecutionPayloadEnvelopeV4,
     ExecutionPayloadV1, PayloadAttributes as EthPayloadAttributes,
 };
 use reth_chainspec::ChainSpec;
+use reth_consensus_clique::seal::EXTRA_SEAL;
+use reth_consensus_common::bridge::BridgeConfig;
 use reth_engine_primitives::{EngineValidator, PayloadValidator};
 use reth_ethereum_payload_builder::EthereumExecutionPayloadValidator;
 use reth_ethereum_primitives::Block;
@@ -14,7 +17,7 @@ use reth_payload_primitives::{
     validate_execution_requests, validate_version_specific_fields, EngineApiMessageVersion,
     EngineObjectValidationError, NewPayloadError, PayloadOrAttributes,
 };
-use reth_primitives_traits::RecoveredBlock;
+use reth_primitives_traits::{RecoveredBlock, SealedBlock};
 use std::sync::Arc;
 
 /// Validator for the ethereum engine API.
@@ -25,8 +28,31 @@ pub struct EthereumEngineValidator {
 
 impl EthereumEngineValidator {
     /// Instantiates a new validator.
-    pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { inner: EthereumExecutionPayloadValidator::new(chain_spec) }
+    ///
+    /// Payloads of clique chains carry the signer seal at the end of their extra-data, payloads of
+    /// chains with a bridge committee the bridge section, preceded by the inbox section on chains
+    /// with an inbox contract. The validator allows all of them, even though the
+    /// [`CliqueConsensusBuilder`](crate::node::CliqueConsensusBuilder) rejects clique chains with a
+    /// bridge committee or an inbox contract.
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        let clique_seal_len =
+            if chain_spec.genesis.config.clique.is_some() { EXTRA_SEAL } else { 0 };
+        // an invalid bridge committee is rejected when the consensus is built
+        let bridge_section_len = BridgeConfig::from_chain_spec(chain_spec.as_ref())
+            .ok()
+            .flatten()
+            .map_or(0, |bridge| bridge.section_len());
+        let seal_len = clique_seal_len + bridge_section_len;
+        // an invalid inbox contract is rejected when the consensus is built
+        let inbox = chain_inbox(chain_spec.as_ref()).ok().flatten().is_some();
+        let mut inner = EthereumExecutionPayloadValidator::new(chain_spec);
+        if seal_len > 0 {
+            inner = inner.with_extra_data_seal(seal_len);
+        }
+        if inbox {
+            inner = inner.with_inbox_section();
+        }
+        Self { inner }
     }
 
     /// Returns the chain spec used by the validator.
@@ -47,6 +73,10 @@ impl PayloadValidator for EthereumEngineValidator {
         let sealed_block = self.inner.ensure_well_formed_payload(payload)?;
         sealed_block.try_recover().map_err(|e| NewPayloadError::Other(e.into()))
     }
+
+    fn convert_payload_to_block(&self, payload: ExecutionData) -> Option<SealedBlock<Self::Block>> {
+        self.inner.convert_payload_to_block(payload).ok()
+    }
 }
 
 impl<Types> EngineValidator<Types> for EthereumEngineValidator
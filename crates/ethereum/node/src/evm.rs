@@ -3,4 +3,6 @@
 #[doc(inline)]
 pub use reth_evm_ethereum::execute::EthExecutorProvider;
 #[doc(inline)]
+pub use reth_evm_ethereum::inbox;
+#[doc(inline)]
 pub use reth_evm_ethereum::{EthEvm, EthEvmConfig};
@@ -3,15 +3,21 @@
 pub use crate::{payload::EthereumPayloadBuilder, EthereumEngineValidator};
 use crate::{EthEngineTypes, EthEvmConfig};
 use alloy_eips::{eip7840::BlobParams, merge::EPOCH_SLOTS};
+use alloy_primitives::Address;
 use reth_chainspec::{ChainSpec, EthChainSpec, EthereumHardforks};
 use reth_consensus::{ConsensusError, FullConsensus};
+use reth_consensus_clique::{CliqueBlockImport, CliqueConsensus};
+use reth_consensus_common::bridge::BridgeConfig;
 use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_primitives::{EthPrimitives, PooledTransactionVariant, TransactionSigned};
-use reth_evm::{ConfigureEvm, EvmFactory, EvmFactoryFor, NextBlockEnvAttributes};
-use reth_network::{EthNetworkPrimitives, NetworkHandle, PeersInfo};
+use reth_ethereum_primitives::{Block, EthPrimitives, PooledTransactionVariant, TransactionSigned};
+use reth_evm::{
+    overrides::BlockStateOverrides, ConfigureEvm, EvmFactory, EvmFactoryFor, NextBlockEnvAttributes,
+};
+use reth_evm_ethereum::inbox::{Inbox, InboxMessages, INBOX_CONTRACT_FIELD};
+use reth_network::{EthNetworkPrimitives, NetworkHandle, NetworkManager, PeersInfo};
 use reth_node_api::{AddOnsContext, FullNodeComponents, NodeAddOns, NodePrimitives, TxTy};
 use reth_node_builder::{
     components::{
@@ -26,16 +32,25 @@ use reth_node_builder::{
     BuilderContext, DebugNode, Node, NodeAdapter, NodeComponentsBuilder, PayloadBuilderConfig,
     PayloadTypes,
 };
-use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
-use reth_rpc::{eth::core::EthApiFor, ValidationApi};
-use reth_rpc_api::{eth::FullEthApiServer, servers::BlockSubmissionValidationApiServer};
+use reth_provider::{providers::ProviderFactoryBuilder, CanonStateSubscriptions, EthStorage};
+use reth_rpc::{
+    eth::{core::EthApiFor, EthConditionalApi, ImpersonatedAccounts},
+    AnvilApi, BridgeInboxApi, HardhatApi, ValidationApi,
+};
+use reth_rpc_api::{
+    eth::{helpers::EthApiSpec, FullEthApiServer},
+    servers::{
+        AnvilApiServer, BlockSubmissionValidationApiServer, BridgeInboxApiServer, DebugApiServer,
+        HardhatApiServer, L2EthApiExtServer,
+    },
+};
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, PoolTransaction, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, conditional::MaybeConditionalTransaction, EthTransactionPool,
+    PoolTransaction, TransactionPool, TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
 use revm::context::TxEnv;
@@ -73,6 +88,35 @@ impl EthereumNode {
             .consensus(EthereumConsensusBuilder::default())
     }
 
+    /// Returns a [`ComponentsBuilder`] configured for a clique proof-of-authority node.
+    ///
+    /// Blocks are propagated over devp2p through the given block import. Its
+    /// [`CliqueBlockImportHandle`](reth_consensus_clique::CliqueBlockImportHandle) is passed to
+    /// the [`CliqueSealer`](reth_consensus_clique::CliqueSealer) of the node, which is spawned once
+    /// the node is launched.
+    pub fn clique_components<Node>(
+        import: CliqueBlockImport<Block>,
+    ) -> ComponentsBuilder<
+        Node,
+        EthereumPoolBuilder,
+        BasicPayloadServiceBuilder<EthereumPayloadBuilder>,
+        CliqueNetworkBuilder,
+        EthereumExecutorBuilder,
+        CliqueConsensusBuilder,
+    >
+    where
+        Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
+        <Node::Types as NodeTypes>::Payload: PayloadTypes<
+            BuiltPayload = EthBuiltPayload,
+            PayloadAttributes = EthPayloadAttributes,
+            PayloadBuilderAttributes = EthPayloadBuilderAttributes,
+        >,
+    {
+        Self::components()
+            .network(CliqueNetworkBuilder::new(import))
+            .consensus(CliqueConsensusBuilder::default())
+    }
+
     /// Instantiates the [`ProviderFactoryBuilder`] for an ethereum node.
     ///
     /// # Open a Providerfactory in read-only mode from a datadir
@@ -155,6 +199,8 @@ where
     EthApiFor<N>: FullEthApiServer<Provider = N::Provider, Pool = N::Pool>,
 {
     inner: RpcAddOns<N, EthereumEthApiBuilder, EthereumEngineValidatorBuilder>,
+    /// The message batches that are queued through the `bridgeInbox` namespace.
+    inbox_messages: Option<InboxMessages>,
 }
 
 impl<N: FullNodeComponents> Default for EthereumAddOns<N>
@@ -162,7 +208,21 @@ where
     EthApiFor<N>: FullEthApiServer<Provider = N::Provider, Pool = N::Pool>,
 {
     fn default() -> Self {
-        Self { inner: Default::default() }
+        Self { inner: Default::default(), inbox_messages: None }
+    }
+}
+
+impl<N: FullNodeComponents> EthereumAddOns<N>
+where
+    EthApiFor<N>: FullEthApiServer<Provider = N::Provider, Pool = N::Pool>,
+{
+    /// Serves `bridgeInbox_queueMessages`, which queues message batches into the given queue.
+    ///
+    /// The queue is expected to be the one of the payload builder, see
+    /// [`EthereumPayloadBuilder::with_inbox_messages`].
+    pub fn with_inbox_messages(mut self, messages: InboxMessages) -> Self {
+        self.inbox_messages = Some(messages);
+        self
     }
 }
 
@@ -175,6 +235,7 @@ where
             Payload = EthEngineTypes,
         >,
         Evm: ConfigureEvm<NextBlockEnvCtx = NextBlockEnvAttributes>,
+        Pool: TransactionPool<Transaction: MaybeConditionalTransaction>,
     >,
     EthApiError: FromEvmError<N::Evm>,
     EvmFactoryFor<N::Evm>: EvmFactory<Tx = TxEnv>,
@@ -193,6 +254,14 @@ where
             Box::new(ctx.node.task_executor().clone()),
             Arc::new(EthereumEngineValidator::new(ctx.config.chain.clone())),
         );
+        let bridge_inbox_api = (ctx.credit_inbox.is_some() || self.inbox_messages.is_some())
+            .then(|| BridgeInboxApi::new(ctx.credit_inbox.clone(), self.inbox_messages.clone()));
+        let tx_conditional_api =
+            ctx.config.rpc.enable_tx_conditional.then(|| {
+                EthConditionalApi::new(ctx.node.pool().clone(), ctx.node.provider().clone())
+            });
+        let local_miner = ctx.local_miner.clone();
+        let bad_blocks = ctx.bad_blocks.clone();
 
         self.inner
             .launch_add_ons_with(ctx, move |container| {
@@ -201,6 +270,47 @@ where
                     validation_api.into_rpc(),
                 )?;
 
+                // serve the blocks rejected by the engine through `debug_getBadBlocks`
+                let debug_api = container.registry.debug_api().with_bad_blocks(bad_blocks);
+                container
+                    .modules
+                    .replace_if_module_configured(RethRpcModule::Debug, debug_api.into_rpc())?;
+
+                if let Some(bridge_inbox_api) = bridge_inbox_api {
+                    container.modules.merge_if_module_configured(
+                        RethRpcModule::BridgeInbox,
+                        bridge_inbox_api.into_rpc(),
+                    )?;
+                }
+
+                if let Some(tx_conditional_api) = tx_conditional_api {
+                    // extend the eth namespace with `eth_sendRawTransactionConditional`
+                    container.modules.merge_if_module_configured(
+                        RethRpcModule::Eth,
+                        tx_conditional_api.into_rpc(),
+                    )?;
+                }
+
+                if let Some(local_miner) = local_miner {
+                    let eth_api = container.registry.eth_api().clone();
+
+                    // transactions of impersonated accounts are sent through the eth API, the
+                    // impersonated accounts are the last signer so dev accounts keep their keys
+                    let impersonated = ImpersonatedAccounts::default();
+                    EthApiSpec::signers(&eth_api).write().push(Box::new(impersonated.clone()));
+
+                    let hardhat_api =
+                        HardhatApi::new(eth_api.clone(), local_miner.clone(), impersonated.clone());
+                    let anvil_api = AnvilApi::new(eth_api, local_miner, impersonated);
+                    container
+                        .modules
+                        .merge_if_module_configured(RethRpcModule::Anvil, anvil_api.into_rpc())?;
+                    container.modules.merge_if_module_configured(
+                        RethRpcModule::Hardhat,
+                        hardhat_api.into_rpc(),
+                    )?;
+                }
+
                 Ok(())
             })
             .await
@@ -216,6 +326,7 @@ where
             Payload = EthEngineTypes,
         >,
         Evm: ConfigureEvm<NextBlockEnvCtx = NextBlockEnvAttributes>,
+        Pool: TransactionPool<Transaction: MaybeConditionalTransaction>,
     >,
     EthApiError: FromEvmError<N::Evm>,
     EvmFactoryFor<N::Evm>: EvmFactory<Tx = TxEnv>,
@@ -292,12 +403,40 @@ where
     type EVM = EthEvmConfig;
 
     async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
-        let evm_config = EthEvmConfig::new(ctx.chain_spec())
+        let mut evm_config = EthEvmConfig::new(ctx.chain_spec())
             .with_extra_data(ctx.payload_builder_config().extra_data_bytes());
+        if ctx.is_dev() {
+            // the local miner changes state through overrides of the blocks it builds
+            let state_overrides =
+                BlockStateOverrides::open(ctx.config().datadir().state_overrides())?;
+            evm_config = evm_config.with_state_overrides(state_overrides);
+        }
+        if let Some(inbox) = chain_inbox(ctx.chain_spec().as_ref())? {
+            evm_config = evm_config.with_inbox(inbox);
+        }
         Ok(evm_config)
     }
 }
 
+/// Reads the inbox contract of the chain from the [`INBOX_CONTRACT_FIELD`] of the genesis config.
+///
+/// Returns `None` if the chain has no inbox contract.
+pub fn chain_inbox<ChainSpec: EthChainSpec>(chain_spec: &ChainSpec) -> eyre::Result<Option<Inbox>> {
+    let Some(address) = chain_spec
+        .genesis()
+        .config
+        .extra_fields
+        .get_deserialized::<Address>(INBOX_CONTRACT_FIELD)
+        .transpose()?
+    else {
+        return Ok(None)
+    };
+    // the inbox section precedes the bridge section
+    let trailing_len =
+        BridgeConfig::from_chain_spec(chain_spec)?.map_or(0, |bridge| bridge.section_len());
+    Ok(Some(Inbox { address, trailing_len }))
+}
+
 /// A basic ethereum transaction pool.
 ///
 /// This contains various settings that can be configured and take precedence over the node's
@@ -356,6 +495,18 @@ where
         info!(target: "reth::cli", "Transaction pool initialized");
         debug!(target: "reth::cli", "Spawned txpool maintenance task");
 
+        if ctx.config().rpc.enable_tx_conditional {
+            ctx.task_executor().spawn_critical(
+                "txpool conditional maintenance task",
+                reth_transaction_pool::maintain::maintain_transaction_pool_conditional_future(
+                    ctx.provider().clone(),
+                    transaction_pool.clone(),
+                    ctx.provider().canonical_state_stream(),
+                ),
+            );
+            debug!(target: "reth::cli", "Spawned txpool conditional maintenance task");
+        }
+
         Ok(transaction_pool)
     }
 }
@@ -404,7 +555,87 @@ where
     type Consensus = Arc<dyn FullConsensus<EthPrimitives, Error = ConsensusError>>;
 
     async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
-        Ok(Arc::new(EthBeaconConsensus::new(ctx.chain_spec())))
+        let mut consensus = EthBeaconConsensus::new(ctx.chain_spec());
+        if ctx.is_dev() {
+            // the local miner sets the gas limit and base fee requested through the anvil API
+            consensus = consensus.with_dev_mode();
+        }
+        if let Some(bridge) = BridgeConfig::from_chain_spec(ctx.chain_spec().as_ref())? {
+            consensus = consensus.with_bridge_config(Arc::new(bridge));
+        }
+        if chain_inbox(ctx.chain_spec().as_ref())?.is_some() {
+            consensus = consensus.with_inbox();
+        }
+        Ok(Arc::new(consensus))
+    }
+}
+
+/// Network builder of a clique node, which propagates blocks through the [`CliqueBlockImport`].
+#[derive(Debug)]
+pub struct CliqueNetworkBuilder {
+    /// The block import that hands the blocks of peers to the sealer.
+    import: CliqueBlockImport<Block>,
+}
+
+impl CliqueNetworkBuilder {
+    /// Creates a new builder with the given block import.
+    pub const fn new(import: CliqueBlockImport<Block>) -> Self {
+        Self { import }
+    }
+}
+
+impl<Node, Pool> NetworkBuilder<Node, Pool> for CliqueNetworkBuilder
+where
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
+    Pool: TransactionPool<
+            Transaction: PoolTransaction<
+                Consensus = TxTy<Node::Types>,
+                Pooled = PooledTransactionVariant,
+            >,
+        > + Unpin
+        + 'static,
+{
+    type Network = NetworkHandle<EthNetworkPrimitives>;
+
+    async fn build_network(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+    ) -> eyre::Result<Self::Network> {
+        // block propagation is only allowed in proof-of-work mode
+        let config = ctx
+            .network_config_builder::<EthNetworkPrimitives>()?
+            .with_pow()
+            .block_import(Box::new(self.import));
+        let network = NetworkManager::builder(ctx.build_network_config(config)).await?;
+        let handle = ctx.start_network(network, pool);
+        info!(target: "reth::cli", enode=%handle.local_node_record(), "P2P networking initialized");
+        Ok(handle)
+    }
+}
+
+/// Consensus builder of a clique node, see [`CliqueConsensus`].
+///
+/// Clique chains can't have a bridge committee or an inbox contract, the extra-data of clique
+/// blocks only carries the vanity and the signer seal.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct CliqueConsensusBuilder;
+
+impl<Node> ConsensusBuilder<Node> for CliqueConsensusBuilder
+where
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
+{
+    type Consensus = Arc<dyn FullConsensus<EthPrimitives, Error = ConsensusError>>;
+
+    async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
+        if BridgeConfig::from_chain_spec(ctx.chain_spec().as_ref())?.is_some() {
+            eyre::bail!("clique chains don't support a bridge committee")
+        }
+        if chain_inbox(ctx.chain_spec().as_ref())?.is_some() {
+            eyre::bail!("clique chains don't support an inbox contract")
+        }
+        Ok(Arc::new(CliqueConsensus::new(ctx.chain_spec())?))
     }
 }
 
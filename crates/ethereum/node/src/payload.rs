@@ -1,22 +1,54 @@
 //! Payload component configuration for the Ethereum node.
 
+use crate::node::chain_inbox;
+use futures::StreamExt;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_consensus_common::bridge::BridgeConfig;
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
-use reth_ethereum_primitives::EthPrimitives;
+use reth_ethereum_payload_builder::{BridgeAttestor, EthereumBuilderConfig};
+use reth_ethereum_primitives::{Block, EthPrimitives};
 use reth_evm::ConfigureEvm;
+use reth_evm_ethereum::inbox::{inbox_commitment, Inbox, InboxMessages};
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
 use reth_node_builder::{
     components::PayloadBuilderBuilder, BuilderContext, PayloadBuilderConfig, PayloadTypes,
 };
+use reth_primitives_traits::RecoveredBlock;
+use reth_provider::{CanonStateNotificationStream, CanonStateSubscriptions};
+use reth_tracing::tracing::warn;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use std::sync::Arc;
 
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder {
+    /// Attests the withdrawals of built blocks on chains with a bridge committee.
+    bridge_attestor: Option<Arc<dyn BridgeAttestor>>,
+    /// The message batches that are delivered in built blocks on chains with an inbox contract.
+    inbox_messages: Option<InboxMessages>,
+}
+
+impl EthereumPayloadBuilder {
+    /// Configures the attestor that signs the withdrawals of built blocks on chains with a bridge
+    /// committee, see [`reth_consensus_common::bridge`].
+    pub fn with_bridge_attestor(mut self, attestor: Arc<dyn BridgeAttestor>) -> Self {
+        self.bridge_attestor = Some(attestor);
+        self
+    }
+
+    /// Configures the queue of message batches that are delivered in built blocks on chains with an
+    /// inbox contract, see [`reth_evm_ethereum::inbox`].
+    ///
+    /// The queue is shared with the `bridgeInbox` RPC namespace through
+    /// [`EthereumAddOns::with_inbox_messages`](crate::node::EthereumAddOns::with_inbox_messages).
+    pub fn with_inbox_messages(mut self, messages: InboxMessages) -> Self {
+        self.inbox_messages = Some(messages);
+        self
+    }
+}
 
 impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for EthereumPayloadBuilder
 where
@@ -47,12 +79,65 @@ where
         let conf = ctx.payload_builder_config();
         let chain = ctx.chain_spec().chain();
         let gas_limit = conf.gas_limit_for(chain);
+        let builder_args = &ctx.config().builder;
+
+        let mut builder_config = EthereumBuilderConfig::new()
+            .with_gas_limit(gas_limit)
+            .with_priority_lane(builder_args.priority_lane(), builder_args.priority_lane_gas)
+            .with_executed_block(ctx.is_dev());
+        if let Some(bridge) = BridgeConfig::from_chain_spec(ctx.chain_spec().as_ref())? {
+            match self.bridge_attestor {
+                Some(attestor) => {
+                    builder_config = builder_config.with_bridge(Arc::new(bridge), attestor)
+                }
+                None => {
+                    warn!(target: "reth::cli", "No bridge attestor configured, built blocks lack the bridge section")
+                }
+            }
+        }
+        if let Some(inbox) = chain_inbox(ctx.chain_spec().as_ref())? {
+            let messages = self.inbox_messages.unwrap_or_else(|| {
+                warn!(target: "reth::cli", "No inbox messages configured, built blocks deliver no messages");
+                InboxMessages::default()
+            });
+            ctx.task_executor().spawn(Box::pin(track_delivered_messages(
+                ctx.provider().canonical_state_stream(),
+                inbox,
+                messages.clone(),
+            )));
+            builder_config = builder_config.with_inbox(inbox, messages);
+        }
 
         Ok(reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(gas_limit),
+            builder_config,
         ))
     }
 }
+
+/// Keeps the queued message batches in sync with the canonical chain: a batch is removed once it
+/// is delivered in a canonical block, and queued again if its block is reorged out.
+async fn track_delivered_messages(
+    mut notifications: CanonStateNotificationStream,
+    inbox: Inbox,
+    messages: InboxMessages,
+) {
+    let delivered = |block: &RecoveredBlock<Block>| {
+        inbox.messages(&block.header().extra_data).ok().filter(|messages| !messages.is_empty())
+    };
+    while let Some(notification) = notifications.next().await {
+        for block in notification.reverted().iter().flat_map(|chain| chain.blocks().values()) {
+            if let Some(batch) = delivered(block) {
+                // the batch was delivered in a block, so it fits into one
+                let _ = messages.insert(batch);
+            }
+        }
+        for block in notification.committed().blocks().values() {
+            if let Some(batch) = delivered(block) {
+                messages.remove(&inbox_commitment(&batch));
+            }
+        }
+    }
+}
@@ -1,18 +1,39 @@
-use alloy_eips::eip2718::Encodable2718;
-use alloy_genesis::Genesis;
-use alloy_primitives::{b256, hex};
+use alloy_consensus::BlockHeader;
+use alloy_eips::{eip2718::Encodable2718, BlockNumberOrTag};
+use alloy_genesis::{Genesis, GenesisAccount};
+use alloy_primitives::{address, b256, hex, Address, Bytes, B256, U256, U64};
+use alloy_provider::{network::TransactionBuilder, Provider, ProviderBuilder};
+use alloy_rpc_types_engine::{ExecutionData, ExecutionPayload};
+use alloy_rpc_types_eth::{Block, TransactionRequest};
+use alloy_signer_local::PrivateKeySigner;
 use futures::StreamExt;
 use reth_chainspec::ChainSpec;
+use reth_consensus_common::{
+    bridge::{validate_bridge_withdrawals, BridgeConfig, BridgeSection, BRIDGE_COMMITTEE_FIELD},
+    inbox::{InboxSection, INBOX_CONTRACT_FIELD},
+};
+use reth_engine_primitives::TreeConfig;
+use reth_ethereum_payload_builder::LocalBridgeAttestor;
+use reth_evm_ethereum::inbox::{inbox_calldata, inbox_commitment, InboxMessages};
 use reth_node_api::{BlockBody, FullNodeComponents, FullNodePrimitives, NodeTypes};
 use reth_node_builder::{
-    rpc::RethRpcAddOns, EngineNodeLauncher, FullNode, NodeBuilder, NodeConfig, NodeHandle,
+    components::BasicPayloadServiceBuilder, rpc::RethRpcAddOns, EngineNodeLauncher, FullNode,
+    NodeBuilder, NodeConfig, NodeHandle,
+};
+use reth_node_core::args::{DevArgs, RpcServerArgs};
+use reth_node_ethereum::{
+    node::{EthereumAddOns, EthereumPayloadBuilder},
+    EthereumNode,
+};
+use reth_primitives_traits::SealedBlock;
+use reth_provider::{
+    providers::BlockchainProvider, CanonStateSubscriptions, StateProvider, StateProviderFactory,
 };
-use reth_node_core::args::DevArgs;
-use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
-use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
+use reth_rpc_api::clients::{AnvilApiClient, DebugApiClient};
 use reth_rpc_eth_api::helpers::EthTransactions;
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
 use reth_tasks::TaskManager;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 #[tokio::test]
 async fn can_run_dev_node() -> eyre::Result<()> {
@@ -43,6 +64,590 @@ async fn can_run_dev_node() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_pay_out_bridge_credits() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+            RpcModuleSelection::from([
+                RethRpcModule::Eth,
+                RethRpcModule::BridgeInbox,
+                RethRpcModule::Anvil,
+            ]),
+        ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                TreeConfig::default().with_unwind_canonical_header(true),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let mut notifications = node.provider.canonical_state_stream();
+    let provider =
+        ProviderBuilder::new().connect_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+
+    let snapshot: U256 = provider.raw_request("anvil_snapshot".into(), ()).await?;
+    let recipient = address!("0x00000000000000000000000000000000000b71d9");
+    let amount = 1_000_000_000u64;
+    for index in 0..2 {
+        provider
+            .raw_request::<_, ()>(
+                "bridgeInbox_enqueueCredit".into(),
+                (recipient, U64::from(amount)),
+            )
+            .await?;
+
+        let head = notifications.next().await.unwrap();
+        let withdrawals = head.tip().body().withdrawals().cloned().unwrap_or_default();
+        assert_eq!(withdrawals.len(), 1);
+        assert_eq!(withdrawals[0].address, recipient);
+        assert_eq!(withdrawals[0].amount, amount);
+        assert_eq!(withdrawals[0].index, index);
+    }
+
+    let balance = provider.get_balance(recipient).await?;
+    assert_eq!(balance, U256::from(2 * amount) * U256::from(1_000_000_000u64));
+
+    // the credits of reverted blocks are paid out again
+    let reverted: bool = provider.raw_request("anvil_revert".into(), (snapshot,)).await?;
+    assert!(reverted);
+    notifications.next().await.unwrap();
+    assert_eq!(provider.get_balance(recipient).await?, U256::ZERO);
+
+    provider.raw_request::<_, ()>("anvil_mine".into(), (U256::from(1),)).await?;
+    let head = notifications.next().await.unwrap();
+    let withdrawals = head.tip().body().withdrawals().cloned().unwrap_or_default();
+    assert_eq!(withdrawals.iter().map(|withdrawal| withdrawal.index).collect::<Vec<_>>(), [0, 1]);
+    assert_eq!(provider.get_balance(recipient).await?, balance);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_attest_bridge_credits() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let committee: Vec<_> = (0..3).map(|_| PrivateKeySigner::random()).collect();
+    let mut genesis = custom_chain().genesis.clone();
+    genesis.config.extra_fields.insert(
+        BRIDGE_COMMITTEE_FIELD.to_string(),
+        serde_json::json!({
+            "members": committee.iter().map(|signer| signer.address()).collect::<Vec<_>>(),
+            "threshold": 2,
+        }),
+    );
+    let chain_spec: Arc<ChainSpec> = Arc::new(genesis.into());
+    let bridge = BridgeConfig::from_chain_spec(chain_spec.as_ref())?.unwrap();
+
+    let node_config = NodeConfig::test()
+        .with_chain(chain_spec.clone())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+            RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::BridgeInbox]),
+        ));
+    let attestor = LocalBridgeAttestor::new(committee[1..].to_vec());
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components().payload(BasicPayloadServiceBuilder::new(
+            EthereumPayloadBuilder::default().with_bridge_attestor(Arc::new(attestor)),
+        )))
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let mut notifications = node.provider.canonical_state_stream();
+    let provider =
+        ProviderBuilder::new().connect_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+
+    // the attested blocks pass the bridge rules of the engine and become canonical
+    let recipient = address!("0x00000000000000000000000000000000000b71d9");
+    for index in 0..2 {
+        provider
+            .raw_request::<_, ()>("bridgeInbox_enqueueCredit".into(), (recipient, U64::from(1)))
+            .await?;
+
+        let head = notifications.next().await.unwrap();
+        let block = head.tip();
+        let section = BridgeSection::from_extra_data(block.header().extra_data(), &bridge)?;
+        assert_eq!((section.from, section.to), (index, index + 1));
+        validate_bridge_withdrawals(block.sealed_block(), chain_spec.chain.id(), &bridge)?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_build_and_validate_inbox_messages() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    // the inbox stores the size of its input: CALLDATASIZE PUSH1 0 SSTORE STOP
+    let inbox = address!("0x00000000000000000000000000000000000ba5e5");
+    let mut genesis = custom_chain().genesis.clone();
+    genesis.alloc.insert(
+        inbox,
+        GenesisAccount::default()
+            .with_code(Some(Bytes::from_static(&[0x36, 0x60, 0x00, 0x55, 0x00]))),
+    );
+    genesis.config.extra_fields.insert(INBOX_CONTRACT_FIELD.to_string(), serde_json::json!(inbox));
+    let chain_spec: Arc<ChainSpec> = Arc::new(genesis.into());
+
+    let messages = InboxMessages::default();
+    let node_config = NodeConfig::test()
+        .with_chain(chain_spec.clone())
+        .with_unused_ports()
+        .with_dev(DevArgs {
+            dev: true,
+            block_time: Some(Duration::from_millis(100)),
+            ..Default::default()
+        })
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+            RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::BridgeInbox]),
+        ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config)
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components().payload(BasicPayloadServiceBuilder::new(
+            EthereumPayloadBuilder::default().with_inbox_messages(messages.clone()),
+        )))
+        .with_add_ons(EthereumAddOns::default().with_inbox_messages(messages.clone()))
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let mut notifications = node.provider.canonical_state_stream();
+    let provider =
+        ProviderBuilder::new().connect_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+
+    let batch = vec![Bytes::from_static(b"mint"), Bytes::from_static(b"call")];
+    let commitment: B256 =
+        provider.raw_request("bridgeInbox_queueMessages".into(), (batch.clone(),)).await?;
+    assert_eq!(commitment, inbox_commitment(&batch));
+
+    // every block carries an inbox section, the batch is delivered in one of them
+    let mut blocks = Vec::new();
+    loop {
+        let notification = notifications.next().await.unwrap();
+        blocks.extend(
+            notification.committed().blocks().values().map(|block| block.clone_sealed_block()),
+        );
+        let (_, section) = InboxSection::split_extra_data(blocks.last().unwrap().extra_data())?;
+        if !section.is_empty() {
+            assert_eq!(section.messages(), batch);
+            break
+        }
+    }
+    let delivered = node.provider.latest()?.storage(inbox, B256::ZERO)?.unwrap_or_default();
+    assert_eq!(delivered, U256::from(inbox_calldata(&batch).len()));
+
+    // the batch is removed from the queue once its block is canonical
+    while !messages.is_empty() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    // a node that doesn't know the batch executes the blocks with the messages they carry
+    let NodeHandle { node: validator, .. } =
+        NodeBuilder::new(NodeConfig::test().with_chain(chain_spec).with_unused_ports())
+            .testing_node(exec)
+            .node(EthereumNode::default())
+            .launch()
+            .await?;
+    let engine = &validator.add_ons_handle.beacon_engine_handle;
+    let payload = |block: &SealedBlock<reth_ethereum_primitives::Block>| {
+        let (payload, sidecar) =
+            ExecutionPayload::from_block_unchecked(block.hash(), &block.clone_block());
+        ExecutionData { payload, sidecar }
+    };
+
+    let (delivering, ancestors) = blocks.split_last().unwrap();
+    for block in ancestors {
+        assert!(engine.new_payload(payload(block)).await?.is_valid());
+    }
+
+    // a block that carries other messages than the ones it was executed with is invalid
+    let mut forged = delivering.clone_block();
+    let vanity_len = forged.header.extra_data.len() -
+        InboxSection::split_extra_data(&forged.header.extra_data)?.1.len();
+    let mut extra_data = forged.header.extra_data[..vanity_len].to_vec();
+    extra_data.extend(InboxSection::encode(&[Bytes::from_static(b"forged")])?);
+    forged.header.extra_data = extra_data.into();
+    assert!(engine.new_payload(payload(&SealedBlock::seal_slow(forged))).await?.is_invalid());
+
+    assert!(engine.new_payload(payload(delivering)).await?.is_valid());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_set_balance_and_revert() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config =
+        NodeConfig::test()
+            .with_chain(custom_chain())
+            .with_dev(DevArgs { dev: true, ..Default::default() })
+            .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+                RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Anvil]),
+            ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                TreeConfig::default().with_unwind_canonical_header(true),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let provider =
+        ProviderBuilder::new().connect_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+
+    let account = address!("0x00000000000000000000000000000000000a4b11");
+    let snapshot: U256 = provider.raw_request("anvil_snapshot".into(), ()).await?;
+
+    provider.raw_request::<_, ()>("anvil_setBalance".into(), (account, U256::from(42))).await?;
+    assert_eq!(provider.get_balance(account).await?, U256::from(42));
+
+    let reverted: bool = provider.raw_request("anvil_revert".into(), (snapshot,)).await?;
+    assert!(reverted);
+    assert_eq!(provider.get_balance(account).await?, U256::ZERO);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_set_block_env_and_load_state() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config =
+        NodeConfig::test()
+            .with_chain(custom_chain())
+            .with_dev(DevArgs { dev: true, ..Default::default() })
+            .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+                RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Anvil]),
+            ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let mut notifications = node.provider.canonical_state_stream();
+    let provider =
+        ProviderBuilder::new().connect_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+    let client = node.rpc_server_handle().http_client().unwrap();
+
+    // the base fee applies to the next block only, the gas limit to all following blocks
+    let base_fee = 12_345_678u64;
+    let gas_limit = 45_000_000u64;
+    provider
+        .raw_request::<_, ()>("anvil_setNextBlockBaseFeePerGas".into(), (U256::from(base_fee),))
+        .await?;
+    let set: bool =
+        provider.raw_request("anvil_setBlockGasLimit".into(), (U256::from(gas_limit),)).await?;
+    assert!(set);
+    AnvilApiClient::<Block>::anvil_mine(&client, Some(U256::from(2)), None).await?;
+
+    let first = notifications.next().await.unwrap();
+    assert_eq!(first.tip().header().base_fee_per_gas, Some(base_fee));
+    assert_eq!(first.tip().header().gas_limit, gas_limit);
+    let second = notifications.next().await.unwrap();
+    assert_ne!(second.tip().header().base_fee_per_gas, Some(base_fee));
+    assert_eq!(second.tip().header().gas_limit, gas_limit);
+
+    let info = AnvilApiClient::<Block>::anvil_node_info(&client).await?;
+    assert_eq!(info.current_block_number, 2);
+    assert_eq!(info.environment.gas_limit, gas_limit);
+    let snapshot = AnvilApiClient::<Block>::anvil_snapshot(&client).await?;
+    let metadata = AnvilApiClient::<Block>::anvil_metadata(&client).await?;
+    assert_eq!(metadata.latest_block_number, 2);
+    assert_eq!(metadata.snapshots.get(&snapshot), Some(&(2, second.tip().hash())));
+
+    // loading a dump restores the dumped accounts
+    let account = address!("0x00000000000000000000000000000000000a4b11");
+    provider.raw_request::<_, ()>("anvil_setBalance".into(), (account, U256::from(42))).await?;
+    let dump = AnvilApiClient::<Block>::anvil_dump_state(&client).await?;
+    provider.raw_request::<_, ()>("anvil_setBalance".into(), (account, U256::from(7))).await?;
+    assert_eq!(provider.get_balance(account).await?, U256::from(7));
+    assert!(AnvilApiClient::<Block>::anvil_load_state(&client, dump).await?);
+    assert_eq!(provider.get_balance(account).await?, U256::from(42));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_set_prev_randao_and_coinbase() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config =
+        NodeConfig::test()
+            .with_chain(custom_chain())
+            .with_dev(DevArgs { dev: true, ..Default::default() })
+            .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+                RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Hardhat]),
+            ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let mut notifications = node.provider.canonical_state_stream();
+    let provider =
+        ProviderBuilder::new().connect_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+
+    let prev_randao = B256::with_last_byte(0x42);
+    let coinbase = address!("0x00000000000000000000000000000000000c0b1e");
+    provider.raw_request::<_, ()>("hardhat_setPrevRandao".into(), (prev_randao,)).await?;
+    provider.raw_request::<_, ()>("hardhat_setCoinbase".into(), (coinbase,)).await?;
+    provider.raw_request::<_, ()>("hardhat_mine".into(), (U256::from(2),)).await?;
+
+    let first = notifications.next().await.unwrap();
+    assert_eq!(first.tip().header().mix_hash, prev_randao);
+    assert_eq!(first.tip().header().beneficiary, coinbase);
+
+    // the prevRandao only applies to the next block
+    let second = notifications.next().await.unwrap();
+    assert_ne!(second.tip().header().mix_hash, prev_randao);
+    assert_eq!(second.tip().header().beneficiary, coinbase);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_impersonate_account() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config =
+        NodeConfig::test()
+            .with_chain(custom_chain())
+            .with_dev(DevArgs { dev: true, ..Default::default() })
+            .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+                RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Hardhat]),
+            ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let provider =
+        ProviderBuilder::new().connect_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+
+    let account = address!("0x00000000000000000000000000000000000a4b11");
+    let recipient = address!("0x0000000000000000000000000000000000000b0b");
+    let balance = U256::from(10).pow(U256::from(18));
+    provider.raw_request::<_, ()>("hardhat_setBalance".into(), (account, balance)).await?;
+    provider.raw_request::<_, ()>("hardhat_impersonateAccount".into(), (account,)).await?;
+
+    let transfer = TransactionRequest::default()
+        .with_from(account)
+        .with_to(recipient)
+        .with_value(U256::from(1000));
+    let receipt = provider.send_transaction(transfer.clone()).await?.get_receipt().await?;
+    assert!(receipt.status());
+    assert_eq!(receipt.from, account);
+    assert_eq!(provider.get_balance(recipient).await?, U256::from(1000));
+
+    provider.raw_request::<_, ()>("hardhat_stopImpersonatingAccount".into(), (account,)).await?;
+    assert!(provider.send_transaction(transfer).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_trace_chain() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_ws().with_ws_api(
+            RpcModuleSelection::from([
+                RethRpcModule::Eth,
+                RethRpcModule::Debug,
+                RethRpcModule::Anvil,
+            ]),
+        ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let mut notifications = node.provider.canonical_state_stream();
+    let client = node.rpc_server_handle().ws_client().await.unwrap();
+
+    AnvilApiClient::<Block>::anvil_mine(&client, Some(U256::from(3)), None).await?;
+    for _ in 0..3 {
+        notifications.next().await.unwrap();
+    }
+
+    let mut traces = DebugApiClient::debug_trace_chain(
+        &client,
+        BlockNumberOrTag::Number(0),
+        BlockNumberOrTag::Number(3),
+        None,
+    )
+    .await?;
+    for number in 1..=3 {
+        let block = traces.next().await.unwrap()?;
+        assert_eq!(block.block, U256::from(number));
+        assert!(block.traces.is_empty());
+    }
+
+    // the start block must be before the end block
+    assert!(DebugApiClient::debug_trace_chain(
+        &client,
+        BlockNumberOrTag::Number(3),
+        BlockNumberOrTag::Number(3),
+        None,
+    )
+    .await
+    .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_compute_intermediate_roots() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+            RpcModuleSelection::from([
+                RethRpcModule::Eth,
+                RethRpcModule::Debug,
+                RethRpcModule::Anvil,
+            ]),
+        ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let mut notifications = node.provider.canonical_state_stream();
+    let provider =
+        ProviderBuilder::new().connect_http(node.rpc_server_handle().http_url().unwrap().parse()?);
+
+    // both transfers are included in the same block
+    let sender = address!("0x6Be02d1d3665660d22FF9624b7BE0551ee1Ac91b");
+    provider.raw_request::<_, ()>("anvil_setAutomine".into(), (false,)).await?;
+    provider.raw_request::<_, ()>("anvil_impersonateAccount".into(), (sender,)).await?;
+    for recipient in [address!("0x0000000000000000000000000000000000000b0b"), Address::ZERO] {
+        let transfer = TransactionRequest::default()
+            .with_from(sender)
+            .with_to(recipient)
+            .with_value(U256::from(1000));
+        let _ = provider.send_transaction(transfer).await?;
+    }
+    provider.raw_request::<_, ()>("anvil_mine".into(), (U256::from(1),)).await?;
+    let block = notifications.next().await.unwrap().tip().clone_sealed_header();
+
+    let roots: Vec<B256> =
+        provider.raw_request("debug_intermediateRoots".into(), (block.hash(),)).await?;
+    assert_eq!(roots.len(), 2);
+    assert_ne!(roots[0], roots[1]);
+    assert_eq!(roots[1], block.state_root);
+
+    Ok(())
+}
+
 async fn assert_chain_advances<N, AddOns>(node: FullNode<N, AddOns>)
 where
     N: FullNodeComponents<Provider: CanonStateSubscriptions>,
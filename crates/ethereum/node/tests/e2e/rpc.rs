@@ -3,16 +3,20 @@ use alloy_eips::{calc_next_block_base_fee, eip2718::Encodable2718};
 use alloy_primitives::{Address, B256, U256};
 use alloy_provider::{network::EthereumWallet, Provider, ProviderBuilder, SendableTx};
 use alloy_rpc_types_beacon::relay::{
-    BidTrace, BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
+    BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV3,
+    BuilderBlockValidationRequestV4, SignedBidSubmissionV1, SignedBidSubmissionV2,
     SignedBidSubmissionV3, SignedBidSubmissionV4,
 };
-use alloy_rpc_types_engine::{BlobsBundleV1, ExecutionPayloadV3};
+use alloy_rpc_types_engine::{
+    BlobsBundleV1, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
+};
 use alloy_rpc_types_eth::TransactionRequest;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use reth_chainspec::{ChainSpecBuilder, MAINNET};
 use reth_e2e_test_utils::setup_engine;
 use reth_node_ethereum::EthereumNode;
 use reth_payload_primitives::BuiltPayload;
+use reth_rpc_api::BuilderBlockValidationRequestV2;
 use std::sync::Arc;
 
 alloy_sol_types::sol! {
@@ -121,6 +125,148 @@ async fn test_fee_history() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_flashbots_validate_v1() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .paris_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec.clone(),
+        false,
+        Default::default(),
+        |timestamp| {
+            let mut attributes = eth_payload_attributes(timestamp);
+            attributes.withdrawals = Default::default();
+            attributes.parent_beacon_block_root = None;
+            attributes
+        },
+    )
+    .await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.wallet_gen().swap_remove(0)))
+        .connect_http(node.rpc_url());
+
+    let _ = provider.send_transaction(TransactionRequest::default().to(Address::ZERO)).await?;
+    let payload = node.new_payload().await?;
+
+    let mut request = BuilderBlockValidationRequest {
+        request: SignedBidSubmissionV1 {
+            message: BidTrace {
+                parent_hash: payload.block().parent_hash,
+                block_hash: payload.block().hash(),
+                gas_used: payload.block().gas_used,
+                gas_limit: payload.block().gas_limit,
+                ..Default::default()
+            },
+            execution_payload: ExecutionPayloadV1::from_block_unchecked(
+                payload.block().hash(),
+                &payload.block().clone().into_block(),
+            ),
+            signature: Default::default(),
+        },
+        registered_gas_limit: payload.block().gas_limit,
+    };
+
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_ok());
+
+    request.registered_gas_limit -= 1;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_err());
+    request.registered_gas_limit += 1;
+
+    request.request.execution_payload.state_root = B256::ZERO;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_flashbots_validate_v2() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .shanghai_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec.clone(),
+        false,
+        Default::default(),
+        |timestamp| {
+            let mut attributes = eth_payload_attributes(timestamp);
+            attributes.parent_beacon_block_root = None;
+            attributes
+        },
+    )
+    .await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.wallet_gen().swap_remove(0)))
+        .connect_http(node.rpc_url());
+
+    let _ = provider.send_transaction(TransactionRequest::default().to(Address::ZERO)).await?;
+    let payload = node.new_payload().await?;
+
+    let mut request = BuilderBlockValidationRequestV2 {
+        request: SignedBidSubmissionV2 {
+            message: BidTrace {
+                parent_hash: payload.block().parent_hash,
+                block_hash: payload.block().hash(),
+                gas_used: payload.block().gas_used,
+                gas_limit: payload.block().gas_limit,
+                ..Default::default()
+            },
+            execution_payload: ExecutionPayloadV2::from_block_unchecked(
+                payload.block().hash(),
+                &payload.block().clone().into_block(),
+            ),
+            signature: Default::default(),
+        },
+        registered_gas_limit: payload.block().gas_limit,
+        withdrawals_root: payload.block().withdrawals_root.unwrap(),
+    };
+
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .is_ok());
+
+    request.withdrawals_root = B256::ZERO;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .is_err());
+    request.withdrawals_root = payload.block().withdrawals_root.unwrap();
+
+    request.registered_gas_limit -= 1;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .is_err());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_flashbots_validate_v3() -> eyre::Result<()> {
     reth_tracing::init_test_tracing();
@@ -0,0 +1,152 @@
+//! Production of the bridge section of chains with a bridge committee.
+//!
+//! See [`reth_consensus_common::bridge`] for the section and the rules it is validated against.
+
+use alloy_consensus::{proofs::calculate_withdrawals_root, Header};
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::{Signature, B256, U256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use reth_consensus_common::bridge::{
+    attestation_hash, next_bridge_withdrawal_index, BridgeConfig, BridgeSection,
+};
+use reth_errors::RethError;
+use reth_payload_builder_primitives::PayloadBuilderError;
+use std::{fmt::Debug, sync::Arc};
+
+/// Signs the withdrawals of built blocks on behalf of the bridge committee.
+pub trait BridgeAttestor: Debug + Send + Sync {
+    /// Returns the signatures of distinct committee members over the
+    /// [`attestation_hash`](reth_consensus_common::bridge::attestation_hash), at least as many as
+    /// the threshold of the committee.
+    fn attest(&self, hash: B256) -> Result<Vec<Signature>, PayloadBuilderError>;
+}
+
+/// A [`BridgeAttestor`] that holds the keys of the committee members, e.g. on dev chains.
+#[derive(Debug, Clone)]
+pub struct LocalBridgeAttestor {
+    signers: Vec<PrivateKeySigner>,
+}
+
+impl LocalBridgeAttestor {
+    /// Creates a new attestor that signs with all of the given keys.
+    pub const fn new(signers: Vec<PrivateKeySigner>) -> Self {
+        Self { signers }
+    }
+}
+
+impl BridgeAttestor for LocalBridgeAttestor {
+    fn attest(&self, hash: B256) -> Result<Vec<Signature>, PayloadBuilderError> {
+        self.signers
+            .iter()
+            .map(|signer| signer.sign_hash_sync(&hash).map_err(PayloadBuilderError::other))
+            .collect()
+    }
+}
+
+/// Settings for the bridge section of the blocks built by the Ethereum builder.
+#[derive(Clone, Debug)]
+pub struct BridgeProducerConfig {
+    /// The committee of the chain.
+    pub config: Arc<BridgeConfig>,
+    /// Signs the withdrawals of the built blocks.
+    pub attestor: Arc<dyn BridgeAttestor>,
+}
+
+impl BridgeProducerConfig {
+    /// Appends the [`BridgeSection`] of the block to the extra-data of its header.
+    ///
+    /// The section continues the withdrawal index range of the parent and covers the given
+    /// withdrawals of the block, which are expected to have strictly increasing indices. Blocks
+    /// without withdrawals need no attestation, their signatures are left empty.
+    pub fn append_section(
+        &self,
+        header: &mut Header,
+        parent: &Header,
+        withdrawals: &[Withdrawal],
+        chain_id: u64,
+    ) -> Result<(), PayloadBuilderError> {
+        let from = next_bridge_withdrawal_index(parent, &self.config)
+            .map_err(PayloadBuilderError::other)?;
+        let to = withdrawals.last().map_or(from, |withdrawal| withdrawal.index + 1);
+
+        let threshold = self.config.threshold;
+        let signatures = if withdrawals.is_empty() {
+            vec![Signature::new(U256::ZERO, U256::ZERO, false); threshold]
+        } else {
+            let withdrawals_root = calculate_withdrawals_root(withdrawals);
+            let mut signatures =
+                self.attestor.attest(attestation_hash(chain_id, from, to, withdrawals_root))?;
+            if signatures.len() < threshold {
+                return Err(PayloadBuilderError::Internal(RethError::msg(format!(
+                    "bridge attestor returned {} signatures, the committee threshold is {threshold}",
+                    signatures.len()
+                ))))
+            }
+            signatures.truncate(threshold);
+            signatures
+        };
+
+        let mut extra_data = header.extra_data.to_vec();
+        extra_data.extend(BridgeSection::encode(from, to, &signatures));
+        header.extra_data = extra_data.into();
+        Ok(())
+    }
+}
+
+impl PartialEq for BridgeProducerConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.config == other.config && Arc::ptr_eq(&self.attestor, &other.attestor)
+    }
+}
+
+impl Eq for BridgeProducerConfig {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4895::Withdrawals;
+    use reth_consensus_common::bridge::validate_bridge_withdrawals;
+    use reth_ethereum_primitives::{Block, BlockBody};
+    use reth_primitives_traits::SealedBlock;
+
+    #[test]
+    fn appends_valid_section() {
+        let signers: Vec<_> = (0..3).map(|_| PrivateKeySigner::random()).collect();
+        let config = Arc::new(
+            BridgeConfig::new(signers.iter().map(|signer| signer.address()).collect(), 2).unwrap(),
+        );
+        let producer = BridgeProducerConfig {
+            config: config.clone(),
+            attestor: Arc::new(LocalBridgeAttestor::new(signers)),
+        };
+
+        let withdrawals: Vec<_> = [0, 2]
+            .into_iter()
+            .map(|index| Withdrawal { index, amount: 1, ..Default::default() })
+            .collect();
+        let mut header = Header {
+            number: 1,
+            extra_data: b"reth".into(),
+            withdrawals_root: Some(calculate_withdrawals_root(&withdrawals)),
+            ..Default::default()
+        };
+        producer.append_section(&mut header, &Header::default(), &withdrawals, 1).unwrap();
+        assert_eq!(header.extra_data.len(), 4 + config.section_len());
+
+        let block = SealedBlock::seal_slow(Block {
+            header: header.clone(),
+            body: BlockBody {
+                withdrawals: Some(Withdrawals::new(withdrawals)),
+                ..Default::default()
+            },
+        });
+        assert_eq!(validate_bridge_withdrawals(&block, 1, &config), Ok(()));
+
+        // the next block continues the range without withdrawals
+        let mut child = Header { number: 2, ..Default::default() };
+        producer.append_section(&mut child, &header, &[], 1).unwrap();
+        let section = BridgeSection::from_extra_data(&child.extra_data, &config).unwrap();
+        assert_eq!((section.from, section.to), (3, 3));
+    }
+}
@@ -1,5 +1,10 @@
+use crate::{BridgeAttestor, BridgeProducerConfig, InboxProducerConfig};
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+use reth_consensus_common::bridge::BridgeConfig;
+use reth_evm_ethereum::inbox::{Inbox, InboxMessages};
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
+use reth_transaction_pool::PriorityLane;
+use std::sync::Arc;
 
 /// Settings for the Ethereum builder.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -9,6 +14,29 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// Transactions that are included at the top of the block, before all other transactions.
+    pub priority_lane: Option<PriorityLaneConfig>,
+    /// Attaches the executed block to built payloads, so the engine can insert them without
+    /// executing them again.
+    pub attach_executed_block: bool,
+    /// Appends the attested bridge section to the extra-data of built blocks, on chains with a
+    /// bridge committee.
+    pub bridge: Option<BridgeProducerConfig>,
+    /// Delivers the queued inbound messages in built blocks and carries them in the inbox section
+    /// of the extra-data, on chains with an inbox contract.
+    pub inbox: Option<InboxProducerConfig>,
+}
+
+/// Settings for the priority lane of the Ethereum builder.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct PriorityLaneConfig {
+    /// The contract addresses and senders of the lane.
+    pub lane: Arc<PriorityLane>,
+    /// Gas reserved for the transactions of the lane.
+    ///
+    /// The lane's transactions are included before all other transactions until their total gas
+    /// limit reaches it, bundles and other transactions can't use the rest of it.
+    pub reserved_gas: u64,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +48,14 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            priority_lane: None,
+            attach_executed_block: false,
+            bridge: None,
+            inbox: None,
+        }
     }
 
     /// Set desired gas limit.
@@ -35,6 +70,40 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Configures whether built payloads carry their executed block.
+    ///
+    /// Dev nodes need this for transactions of impersonated senders, whose placeholder signatures
+    /// don't recover to the sender when the block is executed again.
+    pub const fn with_executed_block(mut self, attach_executed_block: bool) -> Self {
+        self.attach_executed_block = attach_executed_block;
+        self
+    }
+
+    /// Reserves the given amount of gas at the top of the block for the transactions of the lane.
+    ///
+    /// An empty lane or a reservation of zero gas disables the priority lane.
+    pub fn with_priority_lane(mut self, lane: PriorityLane, reserved_gas: u64) -> Self {
+        self.priority_lane = (!lane.is_empty() && reserved_gas > 0)
+            .then(|| PriorityLaneConfig { lane: Arc::new(lane), reserved_gas });
+        self
+    }
+
+    /// Attests the withdrawals of built blocks for the given bridge committee.
+    pub fn with_bridge(
+        mut self,
+        config: Arc<BridgeConfig>,
+        attestor: Arc<dyn BridgeAttestor>,
+    ) -> Self {
+        self.bridge = Some(BridgeProducerConfig { config, attestor });
+        self
+    }
+
+    /// Delivers the batches queued in the given messages to the inbox contract in built blocks.
+    pub fn with_inbox(mut self, inbox: Inbox, messages: InboxMessages) -> Self {
+        self.inbox = Some(InboxProducerConfig { inbox, messages });
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -43,6 +112,11 @@ impl EthereumBuilderConfig {
     pub fn gas_limit(&self, parent_gas_limit: u64) -> u64 {
         calculate_block_gas_limit(parent_gas_limit, self.desired_gas_limit)
     }
+
+    /// Returns the gas reserved for the priority lane, or zero if there is none.
+    pub fn reserved_gas(&self) -> u64 {
+        self.priority_lane.as_ref().map_or(0, |lane| lane.reserved_gas)
+    }
 }
 
 /// Calculate the gas limit for the next block based on parent and desired gas limits.
@@ -0,0 +1,78 @@
+//! Delivery of the inbound messages of chains with an inbox contract.
+//!
+//! See [`reth_evm_ethereum::inbox`] for how the messages are delivered and
+//! [`reth_consensus_common::inbox`] for the section that carries them.
+
+use alloy_consensus::Header;
+use alloy_primitives::Bytes;
+use reth_consensus_common::inbox::InboxSection;
+use reth_evm_ethereum::inbox::{inbox_commitment, Inbox, InboxMessages};
+use reth_payload_builder_primitives::PayloadBuilderError;
+use std::sync::Arc;
+
+/// Settings for the inbound messages of the blocks built by the Ethereum builder.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InboxProducerConfig {
+    /// The inbox contract of the chain.
+    pub inbox: Inbox,
+    /// The batches that are waiting to be delivered.
+    pub messages: InboxMessages,
+}
+
+impl InboxProducerConfig {
+    /// Returns the messages that are delivered in the child of the given parent: the oldest
+    /// queued batch that the parent doesn't deliver already.
+    pub fn next_batch(&self, parent: &Header) -> Arc<Vec<Bytes>> {
+        // the batch of the parent stays queued until the parent is canonical
+        let delivered = self
+            .inbox
+            .messages(&parent.extra_data)
+            .ok()
+            .filter(|messages| !messages.is_empty())
+            .map(|messages| inbox_commitment(&messages));
+        self.messages.next_batch(delivered).unwrap_or_default()
+    }
+
+    /// Appends the inbox section with the given messages to the extra-data of the header.
+    pub fn append_section(
+        &self,
+        header: &mut Header,
+        messages: &[Bytes],
+    ) -> Result<(), PayloadBuilderError> {
+        let mut extra_data = header.extra_data.to_vec();
+        extra_data.extend(InboxSection::encode(messages).map_err(PayloadBuilderError::other)?);
+        header.extra_data = extra_data.into();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+
+    #[test]
+    fn skips_batch_of_parent() {
+        let config = InboxProducerConfig {
+            inbox: Inbox { address: Address::ZERO, trailing_len: 0 },
+            messages: InboxMessages::default(),
+        };
+        let (first, second) =
+            (vec![Bytes::from_static(b"first")], vec![Bytes::from_static(b"second")]);
+        config.messages.insert(first.clone()).unwrap();
+        config.messages.insert(second.clone()).unwrap();
+
+        let mut parent = Header::default();
+        config.append_section(&mut parent, &[]).unwrap();
+        assert_eq!(*config.next_batch(&parent), first);
+
+        let mut parent = Header::default();
+        config.append_section(&mut parent, &first).unwrap();
+        assert_eq!(*config.next_batch(&parent), second);
+
+        // once the batches are delivered in canonical blocks, nothing is left to deliver
+        config.messages.remove(&inbox_commitment(&first));
+        config.messages.remove(&inbox_commitment(&second));
+        assert!(config.next_batch(&parent).is_empty());
+    }
+}
@@ -15,29 +15,40 @@ use reth_basic_payload_builder::{
     is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
 };
+use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_errors::{BlockExecutionError, BlockValidationError};
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{
     execute::{BlockBuilder, BlockBuilderOutcome},
-    ConfigureEvm, Evm, NextBlockEnvAttributes,
+    ConfigureEvm, Evm, EvmError, FromRecoveredTx, NextBlockEnvAttributes,
 };
-use reth_evm_ethereum::EthEvmConfig;
+use reth_evm_ethereum::{inbox::deliver_messages, EthEvmConfig};
+use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{BlobSidecars, EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
-use reth_primitives_traits::transaction::error::InvalidTransactionError;
-use reth_revm::{database::StateProviderDatabase, db::State};
+use reth_primitives_traits::{
+    transaction::error::InvalidTransactionError, RecoveredBlock, SealedBlock,
+};
+use reth_revm::{database::StateProviderDatabase, db::State, Database};
 use reth_storage_api::StateProviderFactory;
 use reth_transaction_pool::{
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
-    BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
+    pool::BestTransactionsWithPriorityLane,
+    BestTransactions, BestTransactionsAttributes, PoolBundle, PoolTransaction, TransactionPool,
     ValidPoolTransaction,
 };
-use revm::context_interface::Block as _;
+use revm::{context::result::ResultAndState, context_interface::Block as _, DatabaseCommit};
 use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
+mod bridge;
+pub use bridge::*;
+
+mod inbox;
+pub use inbox::*;
+
 mod config;
 pub use config::*;
 
@@ -132,6 +143,13 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// If a priority lane is configured, the lane's transactions are included at the top of the block
+/// and its gas is reserved: bundles and other pool transactions can't use the part of the
+/// reservation that the lane doesn't use.
+///
+/// Bundles and private transactions of the pool that target the new block are included after the
+/// lane, in submission order, if all of their transactions succeed or are allowed to revert.
 #[inline]
 pub fn default_ethereum_payload<EvmConfig, Client, Pool, F>(
     evm_config: EvmConfig,
@@ -151,24 +169,25 @@ where
     let PayloadConfig { parent_header, attributes } = config;
 
     let state_provider = client.state_by_block_hash(parent_header.hash())?;
+    let next_block_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        suggested_fee_recipient: attributes.suggested_fee_recipient(),
+        prev_randao: attributes.prev_randao(),
+        gas_limit: builder_config.gas_limit(parent_header.gas_limit),
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: Some(attributes.withdrawals().clone()),
+    };
+
     let state = StateProviderDatabase::new(&state_provider);
     let mut db =
         State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
 
-    let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient(),
-                prev_randao: attributes.prev_randao(),
-                gas_limit: builder_config.gas_limit(parent_header.gas_limit),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: Some(attributes.withdrawals().clone()),
-            },
-        )
+    let evm_env = evm_config
+        .next_evm_env(&parent_header, &next_block_attributes)
         .map_err(PayloadBuilderError::other)?;
+    let evm = evm_config.evm_with_env(&mut db, evm_env);
+    let ctx = evm_config.context_for_next_block(&parent_header, next_block_attributes);
+    let mut builder = evm_config.create_block_builder(evm, &parent_header, ctx);
 
     let chain_spec = client.chain_spec();
 
@@ -177,10 +196,18 @@ where
     let block_gas_limit: u64 = builder.evm_mut().block().gas_limit;
     let base_fee = builder.evm_mut().block().basefee;
 
-    let mut best_txs = best_txs(BestTransactionsAttributes::new(
+    let best_txs_attributes = BestTransactionsAttributes::new(
         base_fee,
         builder.evm_mut().block().blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    );
+    let mut best_txs = BestTransactionsWithPriorityLane::new(best_txs(best_txs_attributes));
+    if let Some(priority_lane) = &builder_config.priority_lane {
+        best_txs = best_txs.with_lane(
+            priority_lane.lane.clone(),
+            priority_lane.reserved_gas,
+            pool.best_transactions_with_attributes(best_txs_attributes),
+        );
+    }
     let mut total_fees = U256::ZERO;
 
     builder.apply_pre_execution_changes().map_err(|err| {
@@ -188,6 +215,18 @@ where
         PayloadBuilderError::Internal(err.into())
     })?;
 
+    // the inbound messages are delivered right after the system calls, as the executor of the
+    // block delivers them
+    let inbox_messages = match &builder_config.inbox {
+        Some(inbox) => {
+            let messages = inbox.next_batch(&parent_header);
+            deliver_messages(builder.evm_mut(), inbox.inbox.address, &messages, |_| {})
+                .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+            Some(messages)
+        }
+        None => None,
+    };
+
     // initialize empty blob sidecars at first. If cancun is active then this will be populated by
     // blob sidecars if any.
     let mut blob_sidecars = BlobSidecars::Empty;
@@ -198,15 +237,58 @@ where
     let max_blob_count =
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
-    while let Some(pool_tx) = best_txs.next() {
+    // the gas limit of bundles and transactions outside of the priority lane, set once the lane's
+    // transactions are included
+    let mut non_lane_gas_limit = None;
+    loop {
+        let pool_tx = best_txs.next();
+
+        if non_lane_gas_limit.is_none() && best_txs.is_lane_exhausted() {
+            // the unused part of the reservation stays free for lane transactions that arrive
+            // before the payload is resolved
+            let gas_limit = block_gas_limit
+                .saturating_sub(builder_config.reserved_gas().saturating_sub(cumulative_gas_used));
+            non_lane_gas_limit = Some(gas_limit);
+
+            // bundles go right after the lane, each one is dry-run on top of the bundles included
+            // before it so that a bundle that can't be included atomically is skipped without
+            // touching the block
+            for bundle in
+                pool.bundles().bundles_for_block(parent_header.number + 1, attributes.timestamp)
+            {
+                if cancel.is_cancelled() {
+                    return Ok(BuildOutcome::Cancelled)
+                }
+                if cumulative_gas_used + bundle.gas_limit() > gas_limit {
+                    continue
+                }
+                if !simulate_bundle(builder.evm_mut(), &bundle)? {
+                    continue
+                }
+
+                match execute_bundle(&mut builder, &bundle, base_fee)? {
+                    Some((gas_used, fees)) => {
+                        cumulative_gas_used += gas_used;
+                        total_fees += fees;
+                    }
+                    None => {
+                        warn!(target: "payload_builder", bundle=?bundle.hash, "bundle failed after a successful dry run")
+                    }
+                }
+            }
+        }
+
+        let Some(pool_tx) = pool_tx else { break };
+
         // ensure we still have capacity for this transaction
-        if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+        let gas_limit = non_lane_gas_limit.unwrap_or(block_gas_limit);
+        if cumulative_gas_used + pool_tx.gas_limit() > gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
             // which also removes all dependent transaction from the iterator before we can
             // continue
             best_txs.mark_invalid(
                 &pool_tx,
-                InvalidPoolTransactionError::ExceedsGasLimit(pool_tx.gas_limit(), block_gas_limit),
+                InvalidPoolTransactionError::ExceedsGasLimit(pool_tx.gas_limit(), gas_limit),
             );
             continue
         }
@@ -327,7 +409,26 @@ where
         return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
     }
 
-    let BlockBuilderOutcome { execution_result, block, .. } = builder.finish(&state_provider)?;
+    let BlockBuilderOutcome { execution_result, mut block, hashed_state, trie_updates } =
+        builder.finish(&state_provider)?;
+
+    if inbox_messages.is_some() || builder_config.bridge.is_some() {
+        // the extra-data doesn't affect the execution, the block only needs to be sealed again
+        let (sealed_block, senders) = block.split_sealed();
+        let (mut header, body) = sealed_block.split_header_body();
+        if let Some((inbox, messages)) = builder_config.inbox.as_ref().zip(inbox_messages) {
+            inbox.append_section(&mut header, &messages)?;
+        }
+        if let Some(bridge) = &builder_config.bridge {
+            bridge.append_section(
+                &mut header,
+                &parent_header,
+                attributes.withdrawals(),
+                chain_spec.chain_id(),
+            )?;
+        }
+        block = RecoveredBlock::new_sealed(SealedBlock::seal_parts(header, body), senders);
+    }
 
     let requests = chain_spec
         .is_prague_active_at_timestamp(attributes.timestamp)
@@ -336,9 +437,103 @@ where
     let sealed_block = Arc::new(block.sealed_block().clone());
     debug!(target: "payload_builder", id=%attributes.id, sealed_block_header = ?sealed_block.sealed_header(), "sealed built block");
 
-    let payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests)
-        // add blob sidecars from the executed txs
-        .with_sidecars(blob_sidecars);
+    let mut payload =
+        EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests.clone())
+            // add blob sidecars from the executed txs
+            .with_sidecars(blob_sidecars);
+
+    if builder_config.attach_executed_block {
+        let execution_outcome = ExecutionOutcome::new(
+            db.take_bundle(),
+            vec![execution_result.receipts],
+            block.header().number,
+            requests.into_iter().collect(),
+        );
+
+        // create the executed block data, which lets the engine insert the block without
+        // executing it again, so that transactions of impersonated senders keep their sender
+        payload = payload.with_executed_block(ExecutedBlockWithTrieUpdates {
+            block: ExecutedBlock {
+                recovered_block: Arc::new(block),
+                execution_output: Arc::new(execution_outcome),
+                hashed_state: Arc::new(hashed_state),
+            },
+            trie: ExecutedTrieUpdates::Present(Arc::new(trie_updates)),
+        });
+    }
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+/// Executes all transactions of the bundle in order without changing the state of the block.
+///
+/// Returns `true` if every transaction of the bundle is valid and either succeeds or is allowed to
+/// revert.
+fn simulate_bundle<'a, DB, E>(
+    evm: &mut E,
+    bundle: &PoolBundle<TransactionSigned>,
+) -> Result<bool, PayloadBuilderError>
+where
+    DB: Database + 'a,
+    E: Evm<DB = &'a mut State<DB>, Tx: FromRecoveredTx<TransactionSigned>>,
+{
+    // the state is rolled back to the checkpoint once the bundle was simulated
+    let checkpoint = (evm.db_mut().cache.clone(), evm.db_mut().transition_state.clone());
+    let mut included = true;
+    for tx in &bundle.transactions {
+        let ResultAndState { result, state } = match evm.transact(tx) {
+            Ok(result) => result,
+            Err(error) if error.is_invalid_tx_err() => {
+                trace!(target: "payload_builder", %error, bundle=?bundle.hash, "skipping bundle with invalid transaction");
+                included = false;
+                break
+            }
+            Err(err) => return Err(PayloadBuilderError::evm(err)),
+        };
+        if !result.is_success() && !bundle.can_revert(tx.tx_hash()) {
+            trace!(target: "payload_builder", tx=?tx.tx_hash(), bundle=?bundle.hash, "skipping bundle with reverted transaction");
+            included = false;
+            break
+        }
+        evm.db_mut().commit(state);
+    }
+    (evm.db_mut().cache, evm.db_mut().transition_state) = checkpoint;
+
+    Ok(included)
+}
+
+/// Executes all transactions of the bundle in order.
+///
+/// Returns the gas used and the fees paid by the bundle, or `None` if a transaction of the bundle
+/// is invalid or reverted without being allowed to revert.
+fn execute_bundle(
+    builder: &mut impl BlockBuilder<Primitives = EthPrimitives>,
+    bundle: &PoolBundle<TransactionSigned>,
+    base_fee: u64,
+) -> Result<Option<(u64, U256)>, PayloadBuilderError> {
+    let mut bundle_gas_used = 0;
+    let mut bundle_fees = U256::ZERO;
+    for tx in &bundle.transactions {
+        let mut success = false;
+        let gas_used = match builder.execute_transaction_with_result_closure(tx.clone(), |result| {
+            success = result.is_success()
+        }) {
+            Ok(gas_used) => gas_used,
+            Err(BlockExecutionError::Validation(error)) => {
+                trace!(target: "payload_builder", %error, bundle=?bundle.hash, "skipping bundle with invalid transaction");
+                return Ok(None)
+            }
+            Err(err) => return Err(PayloadBuilderError::evm(err)),
+        };
+        if !success && !bundle.can_revert(tx.hash()) {
+            trace!(target: "payload_builder", tx=?tx.hash(), bundle=?bundle.hash, "skipping bundle with reverted transaction");
+            return Ok(None)
+        }
+
+        let miner_fee =
+            tx.effective_tip_per_gas(base_fee).expect("fee is always valid; execution succeeded");
+        bundle_fees += U256::from(miner_fee) * U256::from(gas_used);
+        bundle_gas_used += gas_used;
+    }
+    Ok(Some((bundle_gas_used, bundle_fees)))
+}
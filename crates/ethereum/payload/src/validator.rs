@@ -1,8 +1,10 @@
 //! Validates execution payload wrt Ethereum consensus rules
 
 use alloy_consensus::Block;
+use alloy_primitives::Bytes;
 use alloy_rpc_types_engine::{ExecutionData, PayloadError};
 use reth_chainspec::EthereumHardforks;
+use reth_consensus_common::inbox::InboxSection;
 use reth_payload_validator::{cancun, prague, shanghai};
 use reth_primitives_traits::{Block as _, SealedBlock, SignedTransaction};
 use std::sync::Arc;
@@ -12,12 +14,32 @@ use std::sync::Arc;
 pub struct EthereumExecutionPayloadValidator<ChainSpec> {
     /// Chain spec to validate against.
     chain_spec: Arc<ChainSpec>,
+    /// Number of bytes at the end of the extra-data that are exempt from the maximum extra-data
+    /// size.
+    extra_data_seal_len: usize,
+    /// Whether the extra-data carries an inbox section in front of the seal, which is exempt from
+    /// the maximum extra-data size as well.
+    inbox_section: bool,
 }
 
 impl<ChainSpec> EthereumExecutionPayloadValidator<ChainSpec> {
     /// Create a new validator.
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self { chain_spec, extra_data_seal_len: 0, inbox_section: false }
+    }
+
+    /// Allows payloads to carry a seal of the given length at the end of their extra-data, on top
+    /// of the maximum extra-data size, like the signer seal of clique.
+    pub const fn with_extra_data_seal(mut self, len: usize) -> Self {
+        self.extra_data_seal_len = len;
+        self
+    }
+
+    /// Allows payloads to carry an [`InboxSection`] in front of the seal of their extra-data, on
+    /// top of the maximum extra-data size.
+    pub const fn with_inbox_section(mut self) -> Self {
+        self.inbox_section = true;
+        self
     }
 
     /// Returns the chain spec used by the validator.
@@ -73,12 +95,11 @@ impl<ChainSpec: EthereumHardforks> EthereumExecutionPayloadValidator<ChainSpec>
         &self,
         payload: ExecutionData,
     ) -> Result<SealedBlock<Block<T>>, PayloadError> {
-        let ExecutionData { payload, sidecar } = payload;
-
-        let expected_hash = payload.block_hash();
+        let expected_hash = payload.payload.block_hash();
+        let sidecar = payload.sidecar.clone();
 
         // First parse the block
-        let sealed_block = payload.try_into_block_with_sidecar(&sidecar)?.seal_slow();
+        let sealed_block = self.convert_payload_to_block(payload)?;
 
         // Ensure the hash included in the payload matches the block hash
         if expected_hash != sealed_block.hash() {
@@ -107,4 +128,31 @@ impl<ChainSpec: EthereumHardforks> EthereumExecutionPayloadValidator<ChainSpec>
 
         Ok(sealed_block)
     }
+
+    /// Converts the given payload into a sealed block without checking that it is well-formed.
+    pub fn convert_payload_to_block<T: SignedTransaction>(
+        &self,
+        payload: ExecutionData,
+    ) -> Result<SealedBlock<Block<T>>, PayloadError> {
+        let ExecutionData { mut payload, sidecar } = payload;
+
+        if self.extra_data_seal_len == 0 && !self.inbox_section {
+            return Ok(payload.try_into_block_with_sidecar(&sidecar)?.seal_slow())
+        }
+
+        // the seal and the inbox section are split off for the size check of the conversion and
+        // restored afterwards, so that they are covered by the block hash
+        let extra_data = payload.as_v1().extra_data.clone();
+        let mut unsealed_len = extra_data.len().saturating_sub(self.extra_data_seal_len);
+        if self.inbox_section {
+            // a malformed section is left in place and rejected by the consensus
+            if let Ok((vanity, _)) = InboxSection::split_extra_data(&extra_data[..unsealed_len]) {
+                unsealed_len = vanity.len();
+            }
+        }
+        payload.as_v1_mut().extra_data = Bytes::copy_from_slice(&extra_data[..unsealed_len]);
+        let mut block = payload.try_into_block_with_sidecar(&sidecar)?;
+        block.header.extra_data = extra_data;
+        Ok(block.seal_slow())
+    }
 }
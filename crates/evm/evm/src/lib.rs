@@ -46,6 +46,8 @@ pub use aliases::*;
 #[cfg(feature = "metrics")]
 pub mod metrics;
 pub mod noop;
+#[cfg(feature = "std")]
+pub mod overrides;
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking executor
 pub mod test_utils;
@@ -160,6 +162,13 @@ pub trait ConfigureEvm: Clone + Debug + Send + Sync + Unpin {
         attributes: Self::NextBlockEnvCtx,
     ) -> ExecutionCtxFor<'_, Self>;
 
+    /// Returns the registry of state overrides this configuration applies to the blocks it builds
+    /// and executes, if any.
+    #[cfg(feature = "std")]
+    fn state_overrides(&self) -> Option<&overrides::BlockStateOverrides> {
+        None
+    }
+
     /// Returns a [`TxEnv`] from a transaction and [`Address`].
     fn tx_env(&self, transaction: impl IntoTxEnv<TxEnvFor<Self>>) -> TxEnvFor<Self> {
         transaction.into_tx_env()
@@ -226,6 +235,23 @@ pub trait ConfigureEvm: Clone + Debug + Send + Sync + Unpin {
         self.block_executor_factory().create_executor(evm, ctx)
     }
 
+    /// Creates a strategy for execution of a given block with the given EVM, e.g. one with an
+    /// inspector.
+    ///
+    /// Configurations that execute blocks depending on more than their
+    /// [`BlockExecutorFactory::ExecutionCtx`] override this.
+    fn create_executor_for_block<'a, DB, I>(
+        &'a self,
+        evm: EvmFor<Self, &'a mut State<DB>, I>,
+        block: &'a SealedBlock<<Self::Primitives as NodePrimitives>::Block>,
+    ) -> impl BlockExecutorFor<'a, Self::BlockExecutorFactory, DB, I>
+    where
+        DB: Database,
+        I: InspectorFor<Self, &'a mut State<DB>> + 'a,
+    {
+        self.create_executor(evm, self.context_for_block(block))
+    }
+
     /// Creates a strategy for execution of a given block.
     fn executor_for_block<'a, DB: Database>(
         &'a self,
@@ -233,8 +259,7 @@ pub trait ConfigureEvm: Clone + Debug + Send + Sync + Unpin {
         block: &'a SealedBlock<<Self::Primitives as NodePrimitives>::Block>,
     ) -> impl BlockExecutorFor<'a, Self::BlockExecutorFactory, DB> {
         let evm = self.evm_for_block(db, block.header());
-        let ctx = self.context_for_block(block);
-        self.create_executor(evm, ctx)
+        self.create_executor_for_block(evm, block)
     }
 
     /// Creates a [`BlockBuilder`]. Should be used when building a new block.
@@ -0,0 +1,460 @@
+//! State overrides that are applied to locally built blocks.
+//!
+//! Dev nodes use these to change the state of accounts through regular blocks: the local miner
+//! registers the overrides for the block it is about to build, and the EVM configuration applies
+//! them before the pre-execution changes whenever that block is built or executed.
+//!
+//! The registry also carries [`BlockEnvOverride`]s, which replace the base fee and gas limit that
+//! the EVM configuration would derive from the parent when it builds the next block.
+
+use alloy_evm::{
+    block::{BlockExecutionError, BlockExecutor, CommitChanges, ExecutableTx, OnStateHook},
+    Evm,
+};
+use alloy_primitives::{map::HashMap, Address, Bytes, B256, U256};
+use reth_execution_types::BlockExecutionResult;
+use reth_fs_util::FsPathError;
+use revm::{
+    context::result::ExecutionResult,
+    database::State,
+    state::{Account, AccountStatus, Bytecode, EvmState, EvmStorageSlot},
+    Database, DatabaseCommit,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+/// Changes to a single account that are applied before the transactions of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountStateOverride {
+    /// New balance of the account.
+    pub balance: Option<U256>,
+    /// New nonce of the account.
+    pub nonce: Option<u64>,
+    /// New code of the account.
+    pub code: Option<Bytecode>,
+    /// Storage slots to overwrite.
+    pub storage: HashMap<U256, U256>,
+}
+
+impl AccountStateOverride {
+    /// Merges `other` into `self`, with the values of `other` taking precedence.
+    pub fn merge(&mut self, other: Self) {
+        let Self { balance, nonce, code, storage } = other;
+        if balance.is_some() {
+            self.balance = balance;
+        }
+        if nonce.is_some() {
+            self.nonce = nonce;
+        }
+        if code.is_some() {
+            self.code = code;
+        }
+        self.storage.extend(storage);
+    }
+}
+
+/// Account overrides applied to a single block.
+pub type StateOverride = HashMap<Address, AccountStateOverride>;
+
+/// Values of the block environment that replace the ones derived from the parent block when the
+/// next block is built.
+///
+/// Executing a block reads these values from its header, so they are only needed until the block
+/// is built and are not persisted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockEnvOverride {
+    /// Base fee of the block.
+    pub base_fee_per_gas: Option<u64>,
+    /// Gas limit of the block.
+    pub gas_limit: Option<u64>,
+}
+
+impl BlockEnvOverride {
+    /// Returns `true` if no value is overridden.
+    pub const fn is_empty(&self) -> bool {
+        self.base_fee_per_gas.is_none() && self.gas_limit.is_none()
+    }
+}
+
+/// A shared registry of [`StateOverride`]s keyed by the parent hash and timestamp of the block they
+/// are applied to.
+///
+/// The registry is shared between the component that decides on the overrides and the EVM
+/// configuration, so that the payload builder and the block executor apply the same changes and
+/// agree on the resulting state root.
+///
+/// Blocks with overrides can only be executed again with the same overrides, so a registry that
+/// was opened with a path appends every registered override to that file, one JSON object per
+/// line. Later lines replace earlier lines of the same block, the file is compacted when it is
+/// opened.
+#[derive(Debug, Clone, Default)]
+pub struct BlockStateOverrides {
+    inner: PerBlock<Arc<StateOverride>>,
+    /// Block environment overrides, which are kept in memory only.
+    env: PerBlock<BlockEnvOverride>,
+    /// File the overrides are persisted to, if any.
+    path: Option<Arc<PathBuf>>,
+}
+
+impl BlockStateOverrides {
+    /// Opens a registry that is persisted to the file at `path`, loading the overrides that were
+    /// stored in it before.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, FsPathError> {
+        let path = path.into();
+        let mut overrides = HashMap::default();
+        if path.exists() {
+            let file = reth_fs_util::open(&path)?;
+            let mut lines = 0;
+            for line in BufReader::new(file).lines() {
+                let line = line.map_err(|err| FsPathError::read(err, &path))?;
+                if line.is_empty() {
+                    continue
+                }
+                let stored: StoredBlockOverride = serde_json::from_str(&line)
+                    .map_err(|source| FsPathError::ReadJson { source, path: path.clone() })?;
+                let (key, block_overrides) = stored.into_parts();
+                overrides.insert(key, Arc::new(block_overrides));
+                lines += 1;
+            }
+
+            // drop the lines of overrides that were replaced later
+            if lines > overrides.len() {
+                reth_fs_util::atomic_write_file(&path, |file| {
+                    overrides.iter().try_for_each(|(key, block_overrides)| {
+                        write_line(file, *key, block_overrides)
+                    })
+                })?;
+            }
+        }
+        Ok(Self {
+            inner: Arc::new(RwLock::new(overrides)),
+            env: Default::default(),
+            path: Some(Arc::new(path)),
+        })
+    }
+
+    /// Registers the overrides for the block with the given parent and timestamp, replacing any
+    /// overrides registered for that block before.
+    ///
+    /// Returns an error if the overrides could not be persisted, they are registered regardless.
+    pub fn insert(
+        &self,
+        parent_hash: B256,
+        timestamp: u64,
+        overrides: StateOverride,
+    ) -> Result<(), FsPathError> {
+        let key = (parent_hash, timestamp);
+        let overrides = Arc::new(overrides);
+        // the lock is held while appending, so the lines are in the order of the inserts
+        let mut inner = self.inner.write().unwrap();
+        inner.insert(key, overrides.clone());
+        match &self.path {
+            Some(path) => append(path, key, &overrides),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the overrides of the block with the given parent and timestamp, if any.
+    pub fn get(&self, parent_hash: B256, timestamp: u64) -> Option<Arc<StateOverride>> {
+        self.inner.read().unwrap().get(&(parent_hash, timestamp)).cloned()
+    }
+
+    /// Registers the block environment override for the block with the given parent and
+    /// timestamp, replacing any override registered for that block before.
+    pub fn insert_env(&self, parent_hash: B256, timestamp: u64, env: BlockEnvOverride) {
+        self.env.write().unwrap().insert((parent_hash, timestamp), env);
+    }
+
+    /// Returns the block environment override of the block with the given parent and timestamp,
+    /// if any.
+    pub fn env(&self, parent_hash: B256, timestamp: u64) -> Option<BlockEnvOverride> {
+        self.env.read().unwrap().get(&(parent_hash, timestamp)).copied()
+    }
+
+    /// Returns `true` if any block environment override is registered.
+    pub fn has_env_overrides(&self) -> bool {
+        !self.env.read().unwrap().is_empty()
+    }
+
+    /// Removes the block environment override of the block with the given parent and timestamp.
+    pub fn remove_env(&self, parent_hash: B256, timestamp: u64) {
+        self.env.write().unwrap().remove(&(parent_hash, timestamp));
+    }
+
+    /// Applies the overrides of the block with the given parent and timestamp to `state`.
+    ///
+    /// The changes are committed as transitions, so they become part of the bundle state of the
+    /// block.
+    pub fn apply<DB: Database>(
+        &self,
+        parent_hash: B256,
+        timestamp: u64,
+        state: &mut State<DB>,
+    ) -> Result<(), DB::Error> {
+        match self.get(parent_hash, timestamp) {
+            Some(overrides) => apply_overrides(&overrides, state),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Overrides keyed by the parent hash and the timestamp of the block they apply to.
+type PerBlock<T> = Arc<RwLock<HashMap<(B256, u64), T>>>;
+
+/// Applies the given overrides to `state`, see [`BlockStateOverrides::apply`].
+fn apply_overrides<DB: Database>(
+    overrides: &StateOverride,
+    state: &mut State<DB>,
+) -> Result<(), DB::Error> {
+    let mut changes = EvmState::default();
+    for (address, account_override) in overrides {
+        // loads the account into the cache, which is required for committing changes
+        let mut info = state.basic(*address)?.unwrap_or_default();
+        if let Some(balance) = account_override.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = account_override.nonce {
+            info.nonce = nonce;
+        }
+        if let Some(code) = &account_override.code {
+            // we need to set both the bytecode and the codehash
+            info.code_hash = code.hash_slow();
+            info.code = Some(code.clone());
+        }
+
+        let mut account =
+            Account { info, status: AccountStatus::Touched, storage: Default::default() };
+        for (slot, value) in &account_override.storage {
+            let original = state.storage(*address, *slot)?;
+            account.storage.insert(*slot, EvmStorageSlot::new_changed(original, *value));
+        }
+        changes.insert(*address, account);
+    }
+    state.commit(changes);
+
+    Ok(())
+}
+
+/// A [`BlockExecutor`] that applies the overrides of its block before the pre-execution changes
+/// of the inner executor.
+#[derive(Debug)]
+pub struct StateOverridesExecutor<E> {
+    inner: E,
+    /// The overrides of the block, until they are applied.
+    overrides: Option<Arc<StateOverride>>,
+}
+
+impl<E> StateOverridesExecutor<E> {
+    /// Creates a new executor that applies the overrides registered for the block with the given
+    /// parent and timestamp, if any.
+    pub fn new(
+        inner: E,
+        state_overrides: Option<&BlockStateOverrides>,
+        parent_hash: B256,
+        timestamp: u64,
+    ) -> Self {
+        let overrides = state_overrides.and_then(|overrides| overrides.get(parent_hash, timestamp));
+        Self { inner, overrides }
+    }
+}
+
+impl<'db, DB, E> BlockExecutor for StateOverridesExecutor<E>
+where
+    DB: Database + 'db,
+    E: BlockExecutor<Evm: Evm<DB = &'db mut State<DB>>>,
+{
+    type Transaction = E::Transaction;
+    type Receipt = E::Receipt;
+    type Evm = E::Evm;
+
+    fn apply_pre_execution_changes(&mut self) -> Result<(), BlockExecutionError> {
+        if let Some(overrides) = self.overrides.take() {
+            apply_overrides(&overrides, self.inner.evm_mut().db_mut()).map_err(|err| {
+                BlockExecutionError::msg(format!("failed to apply state overrides: {err}"))
+            })?;
+        }
+        self.inner.apply_pre_execution_changes()
+    }
+
+    fn execute_transaction_with_commit_condition(
+        &mut self,
+        tx: impl ExecutableTx<Self>,
+        f: impl FnOnce(&ExecutionResult<<Self::Evm as Evm>::HaltReason>) -> CommitChanges,
+    ) -> Result<Option<u64>, BlockExecutionError> {
+        self.inner.execute_transaction_with_commit_condition(tx, f)
+    }
+
+    fn finish(
+        self,
+    ) -> Result<(Self::Evm, BlockExecutionResult<Self::Receipt>), BlockExecutionError> {
+        self.inner.finish()
+    }
+
+    fn set_state_hook(&mut self, hook: Option<Box<dyn OnStateHook>>) {
+        self.inner.set_state_hook(hook)
+    }
+
+    fn evm(&self) -> &Self::Evm {
+        self.inner.evm()
+    }
+
+    fn evm_mut(&mut self) -> &mut Self::Evm {
+        self.inner.evm_mut()
+    }
+}
+
+/// Appends the overrides of the block with the given parent and timestamp to the file at `path`.
+fn append(path: &Path, key: (B256, u64), overrides: &StateOverride) -> Result<(), FsPathError> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| FsPathError::open(err, path))?;
+    write_line(&mut file, key, overrides)
+        .map_err(|source| FsPathError::WriteJson { source, path: path.into() })?;
+    file.sync_data().map_err(|err| FsPathError::fsync(err, path))
+}
+
+/// Writes the overrides of the block with the given parent and timestamp as a single line.
+fn write_line(
+    file: &mut File,
+    (parent_hash, timestamp): (B256, u64),
+    overrides: &StateOverride,
+) -> Result<(), serde_json::Error> {
+    let stored = StoredBlockOverride {
+        parent_hash,
+        timestamp,
+        accounts: overrides
+            .iter()
+            .map(|(address, account)| (*address, account.clone().into()))
+            .collect(),
+    };
+    let mut line = serde_json::to_vec(&stored)?;
+    line.push(b'\n');
+    file.write_all(&line).map_err(serde_json::Error::io)
+}
+
+/// The overrides of a block as they are persisted.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredBlockOverride {
+    parent_hash: B256,
+    timestamp: u64,
+    accounts: Vec<(Address, StoredAccountOverride)>,
+}
+
+impl StoredBlockOverride {
+    /// Returns the block the overrides belong to and the overrides.
+    fn into_parts(self) -> ((B256, u64), StateOverride) {
+        let overrides =
+            self.accounts.into_iter().map(|(address, account)| (address, account.into())).collect();
+        ((self.parent_hash, self.timestamp), overrides)
+    }
+}
+
+/// An [`AccountStateOverride`] as it is persisted, with the code stored as raw bytes.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredAccountOverride {
+    balance: Option<U256>,
+    nonce: Option<u64>,
+    code: Option<Bytes>,
+    storage: Vec<(U256, U256)>,
+}
+
+impl From<AccountStateOverride> for StoredAccountOverride {
+    fn from(account: AccountStateOverride) -> Self {
+        let AccountStateOverride { balance, nonce, code, storage } = account;
+        Self {
+            balance,
+            nonce,
+            code: code.map(|code| code.original_bytes()),
+            storage: storage.into_iter().collect(),
+        }
+    }
+}
+
+impl From<StoredAccountOverride> for AccountStateOverride {
+    fn from(account: StoredAccountOverride) -> Self {
+        let StoredAccountOverride { balance, nonce, code, storage } = account;
+        Self {
+            balance,
+            nonce,
+            code: code.map(Bytecode::new_raw),
+            storage: storage.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::database::{CacheDB, EmptyDB};
+
+    #[test]
+    fn applies_registered_overrides() {
+        let overrides = BlockStateOverrides::default();
+        let address = Address::with_last_byte(1);
+        let parent_hash = B256::with_last_byte(2);
+        let mut account = AccountStateOverride {
+            balance: Some(U256::from(1)),
+            nonce: Some(2),
+            ..Default::default()
+        };
+        account.merge(AccountStateOverride {
+            balance: Some(U256::from(3)),
+            storage: HashMap::from_iter([(U256::from(4), U256::from(5))]),
+            ..Default::default()
+        });
+        overrides.insert(parent_hash, 10, HashMap::from_iter([(address, account)])).unwrap();
+
+        let mut state = State::builder()
+            .with_database(CacheDB::new(EmptyDB::default()))
+            .with_bundle_update()
+            .build();
+
+        // other blocks are not affected
+        overrides.apply(parent_hash, 11, &mut state).unwrap();
+        assert_eq!(state.basic(address).unwrap(), None);
+
+        overrides.apply(parent_hash, 10, &mut state).unwrap();
+        let info = state.basic(address).unwrap().unwrap();
+        assert_eq!(info.balance, U256::from(3));
+        assert_eq!(info.nonce, 2);
+        assert_eq!(state.storage(address, U256::from(4)).unwrap(), U256::from(5));
+    }
+
+    #[test]
+    fn persisted_overrides_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state-overrides.jsonl");
+        let parent_hash = B256::with_last_byte(1);
+        let account = AccountStateOverride {
+            balance: Some(U256::from(1)),
+            code: Some(Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]))),
+            storage: HashMap::from_iter([(U256::from(2), U256::from(3))]),
+            ..Default::default()
+        };
+        let overrides = HashMap::from_iter([(Address::with_last_byte(4), account)]);
+
+        let registry = BlockStateOverrides::open(&path).unwrap();
+        registry.insert(parent_hash, 10, HashMap::default()).unwrap();
+        registry.insert(parent_hash, 10, overrides.clone()).unwrap();
+        registry.insert(parent_hash, 12, HashMap::default()).unwrap();
+        assert_eq!(reth_fs_util::read_to_string(&path).unwrap().lines().count(), 3);
+
+        // the replaced override is dropped from the file
+        let reopened = BlockStateOverrides::open(&path).unwrap();
+        assert_eq!(*reopened.get(parent_hash, 10).unwrap(), overrides);
+        assert!(reopened.get(parent_hash, 11).is_none());
+        assert_eq!(reth_fs_util::read_to_string(&path).unwrap().lines().count(), 2);
+        assert_eq!(
+            *BlockStateOverrides::open(&path).unwrap().get(parent_hash, 10).unwrap(),
+            overrides
+        );
+    }
+}
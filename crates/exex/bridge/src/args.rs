@@ -0,0 +1,57 @@
+//! clap [Args](clap::Args) for the bridge request indexer
+
+use crate::DEFAULT_BRIDGE_ADDRESS;
+use alloy_primitives::Address;
+use clap::Args;
+
+/// Parameters for configuring the bridge request indexer.
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Bridge")]
+pub struct BridgeArgs {
+    /// Index the requests of the bridge contract and serve them over the `bridge` RPC namespace.
+    #[arg(long = "bridge.index", default_value_t = false)]
+    pub index: bool,
+
+    /// The address of the bridge contract.
+    #[arg(long = "bridge.contract", value_name = "ADDRESS", default_value_t = DEFAULT_BRIDGE_ADDRESS)]
+    pub contract: Address,
+}
+
+impl Default for BridgeArgs {
+    fn default() -> Self {
+        Self { index: false, contract: DEFAULT_BRIDGE_ADDRESS }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn bridge_args_default_sanity_check() {
+        let args = CommandParser::<BridgeArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, BridgeArgs::default());
+    }
+
+    #[test]
+    fn parse_bridge_args() {
+        let args = CommandParser::<BridgeArgs>::parse_from([
+            "reth",
+            "--bridge.index",
+            "--bridge.contract",
+            "0x0000000000000000000000000000000000002000",
+        ])
+        .args;
+        assert!(args.index);
+        assert_eq!(args.contract, address!("0x0000000000000000000000000000000000002000"));
+    }
+}
@@ -0,0 +1,360 @@
+//! Storage of indexed bridge requests.
+
+use crate::event::{block_bridge_requests, BridgeRequest};
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use alloy_rlp::{Decodable, Encodable};
+use reth_db::{
+    mdbx::{init_db_for, DatabaseArguments},
+    DatabaseEnv, DatabaseError,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    database::Database,
+    table::{Compress, Decompress, DupSort, Table, TableInfo},
+    transaction::{DbTx, DbTxMut},
+    TableSet,
+};
+use reth_execution_types::Chain;
+use reth_primitives_traits::NodePrimitives;
+use std::{fmt, path::Path, sync::Arc};
+
+/// Stores [`BridgeRequest`]s by request id.
+#[derive(Debug)]
+pub struct BridgeRequests;
+
+impl Table for BridgeRequests {
+    const NAME: &'static str = "BridgeRequests";
+    const DUPSORT: bool = false;
+
+    type Key = B256;
+    type Value = BridgeRequest;
+}
+
+/// Stores the ids of the [`BridgeRequest`]s created in a block.
+#[derive(Debug)]
+pub struct BlockBridgeRequests;
+
+impl Table for BlockBridgeRequests {
+    const NAME: &'static str = "BlockBridgeRequests";
+    const DUPSORT: bool = true;
+
+    type Key = BlockNumber;
+    type Value = B256;
+}
+
+impl DupSort for BlockBridgeRequests {
+    type SubKey = B256;
+}
+
+/// Stores the ids of the [`BridgeRequest`]s created by a sender.
+#[derive(Debug)]
+pub struct SenderBridgeRequests;
+
+impl Table for SenderBridgeRequests {
+    const NAME: &'static str = "SenderBridgeRequests";
+    const DUPSORT: bool = true;
+
+    type Key = Address;
+    type Value = B256;
+}
+
+impl DupSort for SenderBridgeRequests {
+    type SubKey = B256;
+}
+
+/// Stores the hashes of the blocks that have been indexed.
+#[derive(Debug)]
+pub struct IndexedBlocks;
+
+impl Table for IndexedBlocks {
+    const NAME: &'static str = "IndexedBlocks";
+    const DUPSORT: bool = false;
+
+    type Key = BlockNumber;
+    type Value = B256;
+}
+
+/// The tables of the bridge request database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeTables {
+    /// The [`BridgeRequests`] table.
+    BridgeRequests,
+    /// The [`BlockBridgeRequests`] table.
+    BlockBridgeRequests,
+    /// The [`SenderBridgeRequests`] table.
+    SenderBridgeRequests,
+    /// The [`IndexedBlocks`] table.
+    IndexedBlocks,
+}
+
+impl BridgeTables {
+    /// All the tables of the bridge request database.
+    pub const ALL: &'static [Self] = &[
+        Self::BridgeRequests,
+        Self::BlockBridgeRequests,
+        Self::SenderBridgeRequests,
+        Self::IndexedBlocks,
+    ];
+}
+
+impl TableInfo for BridgeTables {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::BridgeRequests => BridgeRequests::NAME,
+            Self::BlockBridgeRequests => BlockBridgeRequests::NAME,
+            Self::SenderBridgeRequests => SenderBridgeRequests::NAME,
+            Self::IndexedBlocks => IndexedBlocks::NAME,
+        }
+    }
+
+    fn is_dupsort(&self) -> bool {
+        match self {
+            Self::BridgeRequests => BridgeRequests::DUPSORT,
+            Self::BlockBridgeRequests => BlockBridgeRequests::DUPSORT,
+            Self::SenderBridgeRequests => SenderBridgeRequests::DUPSORT,
+            Self::IndexedBlocks => IndexedBlocks::DUPSORT,
+        }
+    }
+}
+
+impl TableSet for BridgeTables {
+    fn tables() -> Box<dyn Iterator<Item = Box<dyn TableInfo>>> {
+        Box::new(Self::ALL.iter().map(|table| Box::new(*table) as Box<dyn TableInfo>))
+    }
+}
+
+impl Compress for BridgeRequest {
+    type Compressed = Vec<u8>;
+
+    fn compress_to_buf<B: bytes::BufMut + AsMut<[u8]>>(&self, buf: &mut B) {
+        self.encode(buf)
+    }
+}
+
+impl Decompress for BridgeRequest {
+    fn decompress(value: &[u8]) -> Result<Self, DatabaseError> {
+        Self::decode(&mut &value[..]).map_err(|_| DatabaseError::Decode)
+    }
+}
+
+/// Database of the bridge requests of the canonical chain.
+///
+/// Requests are keyed by their request id and additionally indexed by block and sender.
+#[derive(Clone)]
+pub struct BridgeRequestsDb {
+    db: Arc<DatabaseEnv>,
+}
+
+impl fmt::Debug for BridgeRequestsDb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BridgeRequestsDb").finish_non_exhaustive()
+    }
+}
+
+impl BridgeRequestsDb {
+    /// Opens the database at the given path, creating it if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let db = init_db_for::<_, BridgeTables>(path, DatabaseArguments::default())?;
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Returns the most recent indexed block, if any.
+    pub fn head(&self) -> Result<Option<BlockNumHash>, DatabaseError> {
+        self.db.view(|tx| {
+            Ok(tx
+                .cursor_read::<IndexedBlocks>()?
+                .last()?
+                .map(|(number, hash)| BlockNumHash::new(number, hash)))
+        })?
+    }
+
+    /// Returns the request with the given id.
+    pub fn request(&self, request_id: U256) -> Result<Option<BridgeRequest>, DatabaseError> {
+        self.db.view(|tx| tx.get::<BridgeRequests>(request_id.into()))?
+    }
+
+    /// Returns the requests created in the given block, ordered by request id.
+    pub fn requests_by_block(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Vec<BridgeRequest>, DatabaseError> {
+        self.db.view(|tx| requests_by::<_, BlockBridgeRequests>(tx, block_number))?
+    }
+
+    /// Returns the requests created by the given sender, ordered by request id.
+    pub fn requests_by_sender(&self, sender: Address) -> Result<Vec<BridgeRequest>, DatabaseError> {
+        self.db.view(|tx| requests_by::<_, SenderBridgeRequests>(tx, sender))?
+    }
+
+    /// Indexes the requests of all blocks of the committed chain.
+    ///
+    /// Blocks that have been indexed before are replaced.
+    pub fn commit_chain<N: NodePrimitives>(
+        &self,
+        bridge_address: Address,
+        chain: &Chain<N>,
+    ) -> Result<(), DatabaseError> {
+        self.db.update(|tx| {
+            for (block, receipts) in chain.blocks_and_receipts() {
+                let number = block.header().number();
+                revert_block(tx, number)?;
+
+                for request in block_bridge_requests(bridge_address, block, receipts) {
+                    let request_id = B256::from(request.request_id);
+                    tx.put::<BlockBridgeRequests>(number, request_id)?;
+                    tx.put::<SenderBridgeRequests>(request.sender, request_id)?;
+                    tx.put::<BridgeRequests>(request_id, request)?;
+                }
+                tx.put::<IndexedBlocks>(number, block.hash())?;
+            }
+            Ok(())
+        })?
+    }
+
+    /// Removes the requests of all blocks of the reverted chain.
+    pub fn revert_chain<N: NodePrimitives>(&self, chain: &Chain<N>) -> Result<(), DatabaseError> {
+        self.db.update(|tx| {
+            for number in chain.range() {
+                revert_block(tx, number)?;
+            }
+            Ok(())
+        })?
+    }
+}
+
+/// Returns the requests referenced by the given dupsort index table for the given key.
+fn requests_by<TX, T>(tx: &TX, key: T::Key) -> Result<Vec<BridgeRequest>, DatabaseError>
+where
+    TX: DbTx,
+    T: DupSort<Value = B256>,
+{
+    let mut requests = Vec::new();
+    for entry in tx.cursor_dup_read::<T>()?.walk_dup(Some(key), None)? {
+        let (_, request_id) = entry?;
+        if let Some(request) = tx.get::<BridgeRequests>(request_id)? {
+            requests.push(request);
+        }
+    }
+    Ok(requests)
+}
+
+/// Removes all requests of the given block from the database.
+///
+/// Requests that have been indexed for another block since, e.g. because a reorg moved them to
+/// an earlier block, are kept.
+fn revert_block<TX: DbTxMut + DbTx>(tx: &TX, number: BlockNumber) -> Result<(), DatabaseError> {
+    let request_ids = tx
+        .cursor_dup_read::<BlockBridgeRequests>()?
+        .walk_dup(Some(number), None)?
+        .map(|entry| entry.map(|(_, request_id)| request_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for request_id in request_ids {
+        if let Some(request) =
+            tx.get::<BridgeRequests>(request_id)?.filter(|request| request.block_number == number)
+        {
+            tx.delete::<SenderBridgeRequests>(request.sender, Some(request_id))?;
+            tx.delete::<BridgeRequests>(request_id, None)?;
+        }
+    }
+    tx.delete::<BlockBridgeRequests>(number, None)?;
+    tx.delete::<IndexedBlocks>(number, None)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::BridgeRequestCreated, DEFAULT_BRIDGE_ADDRESS};
+    use alloy_primitives::Log;
+    use alloy_sol_types::SolEvent;
+    use reth_ethereum_primitives::{EthPrimitives, Receipt};
+    use reth_execution_types::ExecutionOutcome;
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+
+    fn bridge_request_log(request_id: u64, sender: Address) -> Log {
+        let event = BridgeRequestCreated {
+            requestId: U256::from(request_id),
+            sender,
+            solanaRecipient: B256::with_last_byte(1),
+            amount: U256::from(100),
+            fee: U256::from(1),
+            timestamp: U256::ZERO,
+            blockNumber: U256::ZERO,
+            nonce: U256::from(request_id),
+        };
+        Log { address: DEFAULT_BRIDGE_ADDRESS, data: event.encode_log_data() }
+    }
+
+    fn chain(number: BlockNumber, logs: Vec<Log>) -> Chain<EthPrimitives> {
+        let mut rng = generators::rng();
+        let block =
+            random_block(&mut rng, number, BlockParams { tx_count: Some(1), ..Default::default() })
+                .try_recover()
+                .unwrap();
+        let receipt = Receipt { success: true, logs, ..Default::default() };
+        Chain::new(
+            [block],
+            ExecutionOutcome::new(Default::default(), vec![vec![receipt]], number, vec![]),
+            None,
+        )
+    }
+
+    #[test]
+    fn commit_and_revert_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = BridgeRequestsDb::open(dir.path()).unwrap();
+        let sender = Address::with_last_byte(1);
+
+        let first = chain(1, vec![bridge_request_log(1, sender), bridge_request_log(2, sender)]);
+        let second = chain(2, vec![bridge_request_log(3, sender)]);
+        db.commit_chain(DEFAULT_BRIDGE_ADDRESS, &first).unwrap();
+        db.commit_chain(DEFAULT_BRIDGE_ADDRESS, &second).unwrap();
+
+        assert_eq!(db.head().unwrap(), Some(second.tip().num_hash()));
+        let request = db.request(U256::from(2)).unwrap().unwrap();
+        assert_eq!(request.sender, sender);
+        assert_eq!(request.block_number, 1);
+        assert_eq!(request.log_index, 1);
+        assert_eq!(db.requests_by_block(1).unwrap().len(), 2);
+        assert_eq!(db.requests_by_sender(sender).unwrap().len(), 3);
+
+        db.revert_chain(&second).unwrap();
+
+        assert_eq!(db.head().unwrap(), Some(first.tip().num_hash()));
+        assert_eq!(db.request(U256::from(3)).unwrap(), None);
+        assert!(db.requests_by_block(2).unwrap().is_empty());
+        assert_eq!(
+            db.requests_by_sender(sender)
+                .unwrap()
+                .into_iter()
+                .map(|request| request.request_id)
+                .collect::<Vec<_>>(),
+            vec![U256::from(1), U256::from(2)]
+        );
+    }
+
+    #[test]
+    fn reorg_moves_request_to_earlier_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = BridgeRequestsDb::open(dir.path()).unwrap();
+        let sender = Address::with_last_byte(1);
+
+        db.commit_chain(DEFAULT_BRIDGE_ADDRESS, &chain(1, vec![])).unwrap();
+        db.commit_chain(DEFAULT_BRIDGE_ADDRESS, &chain(2, vec![bridge_request_log(1, sender)]))
+            .unwrap();
+
+        // the new chain includes the request one block earlier
+        db.commit_chain(DEFAULT_BRIDGE_ADDRESS, &chain(1, vec![bridge_request_log(1, sender)]))
+            .unwrap();
+        db.commit_chain(DEFAULT_BRIDGE_ADDRESS, &chain(2, vec![])).unwrap();
+
+        assert_eq!(db.request(U256::from(1)).unwrap().unwrap().block_number, 1);
+        assert_eq!(db.requests_by_block(1).unwrap().len(), 1);
+        assert!(db.requests_by_block(2).unwrap().is_empty());
+        assert_eq!(db.requests_by_sender(sender).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,101 @@
+//! Decoding of bridge requests from the receipts of committed blocks.
+
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_primitives::{address, Address, Log, B256, U256};
+use alloy_rlp::{RlpDecodable, RlpEncodable};
+use alloy_sol_types::{sol, SolEvent};
+use reth_primitives_traits::{Block, BlockBody, RecoveredBlock, SignedTransaction};
+use serde::{Deserialize, Serialize};
+
+/// Default address of the bridge contract.
+pub const DEFAULT_BRIDGE_ADDRESS: Address = address!("0x0000000000000000000000000000000000001000");
+
+sol! {
+    /// Emitted by the bridge contract for every request to bridge funds to Solana.
+    event BridgeRequestCreated(
+        uint256 indexed requestId,
+        address indexed sender,
+        bytes32 indexed solanaRecipient,
+        uint256 amount,
+        uint256 fee,
+        uint256 timestamp,
+        uint256 blockNumber,
+        uint256 nonce
+    );
+}
+
+/// A bridge request decoded from a [`BridgeRequestCreated`] event of a canonical block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, RlpEncodable, RlpDecodable)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeRequest {
+    /// Unique id of the request assigned by the bridge contract.
+    pub request_id: U256,
+    /// Account that created the request.
+    pub sender: Address,
+    /// Recipient of the bridged funds on Solana.
+    pub solana_recipient: B256,
+    /// Bridged amount in wei.
+    pub amount: U256,
+    /// Fee paid for the request in wei.
+    pub fee: U256,
+    /// Timestamp reported by the bridge contract.
+    pub timestamp: U256,
+    /// Nonce of the request reported by the bridge contract.
+    pub nonce: U256,
+    /// Number of the block that contains the event.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Hash of the block that contains the event.
+    pub block_hash: B256,
+    /// Hash of the transaction that emitted the event.
+    pub transaction_hash: B256,
+    /// Index of the event in the logs of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub log_index: u64,
+}
+
+/// Returns all bridge requests emitted by the bridge contract at `bridge_address` in the given
+/// block, in log order.
+pub fn block_bridge_requests<B, R>(
+    bridge_address: Address,
+    block: &RecoveredBlock<B>,
+    receipts: &[R],
+) -> Vec<BridgeRequest>
+where
+    B: Block,
+    R: TxReceipt<Log = Log>,
+{
+    let mut requests = Vec::new();
+    let mut log_index = 0;
+
+    for (tx, receipt) in block.body().transactions().iter().zip(receipts) {
+        for log in receipt.logs() {
+            let index = log_index;
+            log_index += 1;
+
+            if log.address != bridge_address ||
+                log.topics().first() != Some(&BridgeRequestCreated::SIGNATURE_HASH)
+            {
+                continue
+            }
+
+            // events that don't match the expected layout are not bridge requests
+            let Ok(event) = BridgeRequestCreated::decode_log(log) else { continue };
+            requests.push(BridgeRequest {
+                request_id: event.requestId,
+                sender: event.sender,
+                solana_recipient: event.solanaRecipient,
+                amount: event.amount,
+                fee: event.fee,
+                timestamp: event.timestamp,
+                nonce: event.nonce,
+                block_number: block.header().number(),
+                block_hash: block.hash(),
+                transaction_hash: *tx.tx_hash(),
+                log_index: index,
+            });
+        }
+    }
+
+    requests
+}
@@ -0,0 +1,88 @@
+//! The execution extension that indexes bridge requests.
+
+use crate::db::BridgeRequestsDb;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::Address;
+use eyre::OptionExt;
+use futures::TryStreamExt;
+use reth_exex::{ExExContext, ExExEvent, ExExHead, ExExNotification};
+use reth_node_api::FullNodeComponents;
+use reth_provider::BlockHashReader;
+use std::fmt;
+use tracing::debug;
+
+/// Execution extension that indexes the bridge requests of the canonical chain into a
+/// [`BridgeRequestsDb`].
+///
+/// Requests of committed blocks are added to the database and requests of reverted blocks are
+/// removed from it, so the database always reflects the canonical chain.
+pub struct BridgeIndexer<Node: FullNodeComponents> {
+    /// The context of the execution extension.
+    ctx: ExExContext<Node>,
+    /// The database the requests are indexed into.
+    db: BridgeRequestsDb,
+    /// Address of the bridge contract.
+    bridge_address: Address,
+}
+
+impl<Node: FullNodeComponents> fmt::Debug for BridgeIndexer<Node> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BridgeIndexer")
+            .field("db", &self.db)
+            .field("bridge_address", &self.bridge_address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Node: FullNodeComponents> BridgeIndexer<Node> {
+    /// Creates a new indexer for the bridge contract at `bridge_address`.
+    ///
+    /// The indexer resumes from the most recent block in the database, or indexes the entire
+    /// chain if the database is empty.
+    pub fn new(
+        mut ctx: ExExContext<Node>,
+        db: BridgeRequestsDb,
+        bridge_address: Address,
+    ) -> eyre::Result<Self> {
+        let head = match db.head()? {
+            Some(head) => head,
+            None => BlockNumHash::new(
+                0,
+                ctx.provider().block_hash(0)?.ok_or_eyre("genesis block hash not found")?,
+            ),
+        };
+        ctx.set_notifications_with_head(ExExHead::new(head));
+
+        Ok(Self { ctx, db, bridge_address })
+    }
+
+    /// Runs the indexer until the notification stream ends.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        while let Some(notification) = self.ctx.notifications.try_next().await? {
+            match &notification {
+                ExExNotification::ChainCommitted { new } => {
+                    debug!(target: "exex::bridge", committed_chain = ?new.range(), "Indexing bridge requests");
+                }
+                ExExNotification::ChainReorged { old, new } => {
+                    debug!(target: "exex::bridge", from_chain = ?old.range(), to_chain = ?new.range(), "Reindexing bridge requests");
+                }
+                ExExNotification::ChainReverted { old } => {
+                    debug!(target: "exex::bridge", reverted_chain = ?old.range(), "Reverting bridge requests");
+                }
+            }
+
+            if let Some(reverted_chain) = notification.reverted_chain() {
+                self.db.revert_chain(&reverted_chain)?;
+            }
+
+            if let Some(committed_chain) = notification.committed_chain() {
+                self.db.commit_chain(self.bridge_address, &committed_chain)?;
+                self.ctx
+                    .events
+                    .send(ExExEvent::FinishedHeight(committed_chain.tip().num_hash()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
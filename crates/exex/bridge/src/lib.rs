@@ -0,0 +1,34 @@
+//! Execution extension that indexes bridge requests.
+//!
+//! The [`BridgeIndexer`] decodes the [`BridgeRequestCreated`] events emitted by the bridge
+//! contract from every committed chain and stores them in a [`BridgeRequestsDb`]. Requests of
+//! reverted blocks are removed again. The indexed requests are served over the `bridge` RPC
+//! namespace by [`BridgeRequestsRpc`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod args;
+pub use args::BridgeArgs;
+
+mod db;
+pub use db::{
+    BlockBridgeRequests, BridgeRequests, BridgeRequestsDb, BridgeTables, IndexedBlocks,
+    SenderBridgeRequests,
+};
+
+mod event;
+pub use event::{
+    block_bridge_requests, BridgeRequest, BridgeRequestCreated, DEFAULT_BRIDGE_ADDRESS,
+};
+
+mod exex;
+pub use exex::BridgeIndexer;
+
+mod rpc;
+pub use rpc::{BridgeRequestsApiServer, BridgeRequestsRpc};
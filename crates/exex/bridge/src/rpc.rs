@@ -0,0 +1,56 @@
+//! `bridge` namespace RPC serving the indexed bridge requests.
+
+use crate::{db::BridgeRequestsDb, event::BridgeRequest};
+use alloy_primitives::{Address, U256, U64};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_server_types::result::internal_rpc_err;
+
+/// Bridge namespace rpc interface that serves the bridge requests of the canonical chain.
+#[rpc(server, namespace = "bridge")]
+pub trait BridgeRequestsApi {
+    /// Returns the bridge request with the given request id.
+    #[method(name = "getRequestById")]
+    fn request_by_id(&self, request_id: U256) -> RpcResult<Option<BridgeRequest>>;
+
+    /// Returns the bridge requests created in the given block, ordered by request id.
+    #[method(name = "getRequestsByBlock")]
+    fn requests_by_block(&self, block_number: U64) -> RpcResult<Vec<BridgeRequest>>;
+
+    /// Returns the bridge requests created by the given sender, ordered by request id.
+    #[method(name = "getRequestsBySender")]
+    fn requests_by_sender(&self, sender: Address) -> RpcResult<Vec<BridgeRequest>>;
+}
+
+/// `bridge` API implementation that reads the requests indexed by the
+/// [`BridgeIndexer`](crate::BridgeIndexer).
+#[derive(Debug, Clone)]
+pub struct BridgeRequestsRpc {
+    /// The database of indexed requests.
+    db: BridgeRequestsDb,
+}
+
+impl BridgeRequestsRpc {
+    /// Creates a new instance of `BridgeRequestsRpc`.
+    pub const fn new(db: BridgeRequestsDb) -> Self {
+        Self { db }
+    }
+}
+
+impl BridgeRequestsApiServer for BridgeRequestsRpc {
+    /// Handler for `bridge_getRequestById`
+    fn request_by_id(&self, request_id: U256) -> RpcResult<Option<BridgeRequest>> {
+        self.db.request(request_id).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `bridge_getRequestsByBlock`
+    fn requests_by_block(&self, block_number: U64) -> RpcResult<Vec<BridgeRequest>> {
+        self.db
+            .requests_by_block(block_number.to())
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `bridge_getRequestsBySender`
+    fn requests_by_sender(&self, sender: Address) -> RpcResult<Vec<BridgeRequest>> {
+        self.db.requests_by_sender(sender).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
@@ -76,8 +76,11 @@ pub struct AccountRangeMessage {
 }
 
 /// Request for the storage slots of multiple accounts' storage tries.
+///
+/// The origin and limit are decoded leniently: an empty origin is the zero hash and an empty
+/// limit is the maximum hash, which is how other clients request entire storage tries.
 // https://github.com/ethereum/devp2p/blob/master/caps/snap.md#getstorageranges-0x02
-#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable)]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[add_arbitrary_tests(rlp)]
 pub struct GetStorageRangesMessage {
@@ -95,6 +98,49 @@ pub struct GetStorageRangesMessage {
     pub response_bytes: u64,
 }
 
+impl Decodable for GetStorageRangesMessage {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = alloy_rlp::Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let request_id = u64::decode(buf)?;
+        let root_hash = B256::decode(buf)?;
+        let account_hashes = Vec::<B256>::decode(buf)?;
+        let starting_hash = decode_hash_or(buf, B256::ZERO)?;
+        let limit_hash = decode_hash_or(buf, B256::repeat_byte(0xff))?;
+        let response_bytes = u64::decode(buf)?;
+
+        if started_len - buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: started_len - buf.len(),
+            })
+        }
+
+        Ok(Self {
+            request_id,
+            root_hash,
+            account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        })
+    }
+}
+
+/// Decodes a hash that may be shorter than 32 bytes, returning `default` if it's empty.
+fn decode_hash_or(buf: &mut &[u8], default: B256) -> alloy_rlp::Result<B256> {
+    let bytes = Bytes::decode(buf)?;
+    match bytes.len() {
+        0 => Ok(default),
+        len if len <= 32 => Ok(B256::left_padding_from(&bytes)),
+        _ => Err(alloy_rlp::Error::Overflow),
+    }
+}
+
 /// Storage slot data in the response.
 #[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
@@ -149,7 +195,11 @@ pub struct ByteCodesMessage {
 }
 
 /// Path in the trie for an account and its storage
-#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+///
+/// Encoded as the flat list `[account_path, slot_path_1, slot_path_2, ...]`. Without slot paths,
+/// the account path is the compact encoded path of a node in the account trie. Otherwise it is
+/// the hash of the account and the slot paths are compact encoded paths in its storage trie.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[add_arbitrary_tests(rlp)]
 pub struct TriePath {
@@ -159,6 +209,49 @@ pub struct TriePath {
     pub slot_paths: Vec<Bytes>,
 }
 
+impl TriePath {
+    fn payload_length(&self) -> usize {
+        self.account_path.length() + self.slot_paths.iter().map(Encodable::length).sum::<usize>()
+    }
+}
+
+impl Encodable for TriePath {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.account_path.encode(out);
+        for slot_path in &self.slot_paths {
+            slot_path.encode(out);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for TriePath {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = alloy_rlp::Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        if header.payload_length > buf.len() {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+
+        let mut payload = &buf[..header.payload_length];
+        let account_path = Bytes::decode(&mut payload)?;
+        let mut slot_paths = Vec::new();
+        while !payload.is_empty() {
+            slot_paths.push(Bytes::decode(&mut payload)?);
+        }
+        *buf = &buf[header.payload_length..];
+
+        Ok(Self { account_path, slot_paths })
+    }
+}
+
 /// Request a number of state (either account or storage) Merkle trie nodes by path
 // https://github.com/ethereum/devp2p/blob/master/caps/snap.md#gettrienodes-0x06
 #[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
@@ -322,6 +415,7 @@ impl SnapProtocolMessage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::hex;
 
     // Helper function to create a B256 from a u64 for testing
     fn b256_from_u64(value: u64) -> B256 {
@@ -406,6 +500,38 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn test_trie_path_is_flat_list() {
+        let path = TriePath {
+            account_path: Bytes::from(vec![0x12]),
+            slot_paths: vec![Bytes::from(vec![0x34]), Bytes::from(vec![0x00, 0x56])],
+        };
+        let encoded = alloy_rlp::encode(&path);
+        assert_eq!(encoded, hex!("c51234820056"));
+        assert_eq!(encoded.len(), path.length());
+        assert_eq!(TriePath::decode(&mut &encoded[..]).unwrap(), path);
+    }
+
+    #[test]
+    fn test_storage_ranges_request_with_empty_bounds() {
+        // [request_id, root, [account], origin = "", limit = "", response_bytes]
+        let mut payload = Vec::new();
+        1u64.encode(&mut payload);
+        B256::ZERO.encode(&mut payload);
+        vec![b256_from_u64(1)].encode(&mut payload);
+        Bytes::new().encode(&mut payload);
+        Bytes::new().encode(&mut payload);
+        1024u64.encode(&mut payload);
+        let mut encoded = Vec::new();
+        alloy_rlp::Header { list: true, payload_length: payload.len() }.encode(&mut encoded);
+        encoded.extend_from_slice(&payload);
+
+        let request = GetStorageRangesMessage::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(request.starting_hash, B256::ZERO);
+        assert_eq!(request.limit_hash, B256::repeat_byte(0xff));
+        assert_eq!(request.response_bytes, 1024);
+    }
+
     #[test]
     fn test_unknown_message_id() {
         // Create some random data
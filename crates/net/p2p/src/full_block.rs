@@ -646,10 +646,16 @@ enum RangeResponseResult<H, B> {
 }
 
 /// A headers+bodies client implementation that does nothing.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct NoopFullBlockClient<Net = EthNetworkPrimitives>(PhantomData<Net>);
 
+impl<Net> Default for NoopFullBlockClient<Net> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
 /// Implements the `DownloadClient` trait for the `NoopFullBlockClient` struct.
 impl<Net> DownloadClient for NoopFullBlockClient<Net>
 where
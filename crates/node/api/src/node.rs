@@ -2,9 +2,11 @@
 
 use crate::PayloadTypes;
 use alloy_rpc_types_engine::JwtSecret;
+use reth_bad_blocks::BadBlockStore;
 use reth_basic_payload_builder::PayloadBuilder;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
+use reth_engine_local::{CreditInboxHandle, LocalMinerHandle};
 use reth_engine_primitives::{BeaconConsensusEngineEvent, BeaconConsensusEngineHandle};
 use reth_evm::ConfigureEvm;
 use reth_network_api::FullNetwork;
@@ -118,6 +120,12 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<BeaconConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Handle to the credit inbox of the local miner, if the node runs in dev mode.
+    pub credit_inbox: Option<CreditInboxHandle>,
+    /// Handle to control the local miner, if the node runs in dev mode.
+    pub local_miner: Option<LocalMinerHandle>,
+    /// Store of the blocks rejected by the engine.
+    pub bad_blocks: BadBlockStore,
 }
 
 /// Customizable node add-on types.
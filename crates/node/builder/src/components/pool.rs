@@ -223,6 +223,38 @@ where
     Ok(())
 }
 
+/// Spawn the transaction journal task if enabled.
+///
+/// Returns `false` if the journal is disabled.
+fn spawn_transactions_journal_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> bool
+where
+    Node: FullNodeTypes,
+    Pool: TransactionPool<Transaction: reth_transaction_pool::EthPoolTransaction> + Clone + 'static,
+{
+    let txpool_args = &ctx.config().txpool;
+    if !txpool_args.journal {
+        return false
+    }
+
+    let journal_path =
+        txpool_args.journal_path.clone().unwrap_or_else(|| ctx.config().datadir().txpool_journal());
+    let journal_config =
+        reth_transaction_pool::journal::TransactionJournalConfig::new(journal_path)
+            .with_compaction_interval(txpool_args.journal_compaction_interval);
+
+    ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+        "transactions journal task",
+        |shutdown| {
+            reth_transaction_pool::journal::journal_transactions_task(
+                shutdown,
+                pool,
+                journal_config,
+            )
+        },
+    );
+    true
+}
+
 /// Spawn the main maintenance task for transaction pool.
 fn spawn_pool_maintenance_task<Node, Pool>(
     ctx: &BuilderContext<Node>,
@@ -255,7 +287,7 @@ where
     Ok(())
 }
 
-/// Spawn all maintenance tasks for a transaction pool (backup + main maintenance).
+/// Spawn all maintenance tasks for a transaction pool (journal or backup + main maintenance).
 fn spawn_maintenance_tasks<Node, Pool>(
     ctx: &BuilderContext<Node>,
     pool: Pool,
@@ -264,9 +296,13 @@ fn spawn_maintenance_tasks<Node, Pool>(
 where
     Node: FullNodeTypes,
     Pool: reth_transaction_pool::TransactionPoolExt + Clone + 'static,
-    Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>,
+    Pool::Transaction:
+        PoolTransaction<Consensus = TxTy<Node::Types>> + reth_transaction_pool::EthPoolTransaction,
 {
-    spawn_local_backup_task(ctx, pool.clone())?;
+    // the journal already covers local transactions
+    if !spawn_transactions_journal_task(ctx, pool.clone()) {
+        spawn_local_backup_task(ctx, pool.clone())?;
+    }
     spawn_pool_maintenance_task(ctx, pool, pool_config)?;
     Ok(())
 }
@@ -9,6 +9,7 @@ use alloy_eips::eip2124::Head;
 use alloy_primitives::{BlockNumber, B256};
 use eyre::{Context, OptionExt};
 use rayon::ThreadPoolBuilder;
+use reth_bad_blocks::{BadBlockStore, DEFAULT_MAX_BAD_BLOCKS};
 use reth_chainspec::{Chain, EthChainSpec, EthereumHardforks};
 use reth_config::{config::EtlConfig, PruneConfig};
 use reth_consensus::noop::NoopConsensus;
@@ -16,10 +17,13 @@ use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis, InitStorageError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_local::MiningMode;
-use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
+use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks};
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_fs_util as fs;
-use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+use reth_invalid_block_hooks::{
+    InvalidBlockArtifactUploader, InvalidBlockTraceHook, InvalidBlockTracer,
+    InvalidBlockWitnessHook,
+};
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::{
@@ -369,7 +373,7 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
         if let Some(interval) = self.node_config().dev.block_time {
             MiningMode::interval(interval)
         } else {
-            MiningMode::instant(pool)
+            MiningMode::instant(pool.pending_transactions_listener())
         }
     }
 }
@@ -841,11 +845,12 @@ where
         // Skip the first stage as we've already retrieved it and comparing all other checkpoints
         // against it.
         for stage_id in StageId::ALL.iter().skip(1) {
-            let stage_checkpoint = self
-                .blockchain_db()
-                .get_stage_checkpoint(*stage_id)?
-                .unwrap_or_default()
-                .block_number;
+            let stage_checkpoint = self.blockchain_db().get_stage_checkpoint(*stage_id)?;
+            // Optional stages that were never enabled have no checkpoint.
+            if stage_id.is_optional() && stage_checkpoint.is_none() {
+                continue
+            }
+            let stage_checkpoint = stage_checkpoint.unwrap_or_default().block_number;
 
             // If the checkpoint of any stage is less than the checkpoint of the first stage,
             // retrieve and return the block hash of the latest header and use it as the target.
@@ -888,36 +893,67 @@ where
     >,
     CB: NodeComponentsBuilder<T>,
 {
+    /// Opens the store of the blocks rejected by the engine, which is persisted in the data
+    /// directory.
+    pub fn bad_block_store(&self) -> eyre::Result<BadBlockStore> {
+        BadBlockStore::open(self.data_dir().bad_blocks(), DEFAULT_MAX_BAD_BLOCKS)
+    }
+
     /// Returns the [`InvalidBlockHook`] to use for the node.
+    ///
+    /// Rejected blocks are always recorded in the given [`BadBlockStore`], in addition to the
+    /// hooks configured for the node.
     pub fn invalid_block_hook(
         &self,
+        bad_blocks: BadBlockStore,
     ) -> eyre::Result<Box<dyn InvalidBlockHook<<T::Types as NodeTypes>::Primitives>>> {
+        let mut hooks: Vec<Box<dyn InvalidBlockHook<_>>> = vec![Box::new(bad_blocks)];
         let Some(ref hook) = self.node_config().debug.invalid_block_hook else {
-            return Ok(Box::new(NoopInvalidBlockHook::default()))
+            return Ok(Box::new(InvalidBlockHooks(hooks)))
         };
         let healthy_node_rpc_client = self.get_healthy_node_client()?;
+        let uploader = self
+            .node_config()
+            .debug
+            .invalid_block_hook_url
+            .as_deref()
+            .map(|url| InvalidBlockArtifactUploader::new(url, self.task_executor().clone()))
+            .transpose()?;
 
         let output_directory = self.data_dir().invalid_block_hooks();
-        let hooks = hook
+        let configured_hooks = hook
             .iter()
             .copied()
             .map(|hook| {
                 let output_directory = output_directory.join(hook.to_string());
                 fs::create_dir_all(&output_directory)?;
 
-                Ok(match hook {
+                let hook: Box<dyn InvalidBlockHook<_>> = match hook {
                     InvalidBlockHookType::Witness => Box::new(InvalidBlockWitnessHook::new(
                         self.blockchain_db().clone(),
                         self.components().evm_config().clone(),
                         output_directory,
                         healthy_node_rpc_client.clone(),
                     )),
-                    InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
-                        eyre::bail!("invalid block hook {hook:?} is not implemented yet")
-                    }
-                } as Box<dyn InvalidBlockHook<_>>)
+                    InvalidBlockHookType::PreState => Box::new(InvalidBlockTraceHook::new(
+                        self.blockchain_db().clone(),
+                        self.components().evm_config().clone(),
+                        InvalidBlockTracer::PreState,
+                        output_directory,
+                        uploader.clone(),
+                    )),
+                    InvalidBlockHookType::Opcode => Box::new(InvalidBlockTraceHook::new(
+                        self.blockchain_db().clone(),
+                        self.components().evm_config().clone(),
+                        InvalidBlockTracer::Opcode,
+                        output_directory,
+                        uploader.clone(),
+                    )),
+                };
+                Ok::<_, eyre::Report>(hook)
             })
-            .collect::<Result<_, _>>()?;
+            .collect::<Result<Vec<_>, _>>()?;
+        hooks.extend(configured_hooks);
 
         Ok(Box::new(InvalidBlockHooks(hooks)))
     }
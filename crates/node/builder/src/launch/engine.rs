@@ -1,10 +1,12 @@
 //! Engine node related functionality.
 
 use alloy_consensus::BlockHeader;
-use futures::{future::Either, stream, stream_select, StreamExt};
+use futures::{future::Either, stream, stream_select, FutureExt, Stream, StreamExt};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
-use reth_engine_local::{LocalMiner, LocalPayloadAttributesBuilder};
+use reth_engine_local::{
+    CreditInbox, LocalMiner, LocalMinerCommands, LocalPayloadAttributesBuilder,
+};
 use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
@@ -15,8 +17,8 @@ use reth_exex::ExExManagerHandle;
 use reth_network::{NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
-    BeaconConsensusEngineHandle, BuiltPayload, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
-    PayloadAttributesBuilder, PayloadTypes,
+    BeaconConsensusEngineHandle, BuiltPayload, ConfigureEvm, FullNodeTypes, NodeTypes,
+    NodeTypesWithDBAdapter, PayloadAttributesBuilder, PayloadTypes,
 };
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -24,11 +26,18 @@ use reth_node_core::{
     primitives::Head,
 };
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
-use reth_provider::providers::{BlockchainProvider, NodeTypesForProvider};
+use reth_provider::{
+    providers::{BlockchainProvider, NodeTypesForProvider},
+    CanonStateSubscriptions,
+};
+use reth_stages::{
+    stages::{IndexAddressAppearancesStage, IndexTracesStage},
+    StageError,
+};
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
-use reth_tracing::tracing::{debug, error, info};
-use std::sync::Arc;
+use reth_tracing::tracing::{debug, error, info, warn};
+use std::{sync::Arc, time::Duration};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
@@ -41,6 +50,14 @@ use crate::{
     NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
 };
 
+/// Delay before an optional index is caught up after the canonical chain changed, so that a burst
+/// of notifications only triggers a single run.
+const INDEXER_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Pause between two batches of an optional index, which lets the engine acquire the database
+/// writer to persist blocks while the index catches up.
+const INDEXER_BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
 /// The engine node launcher.
 #[derive(Debug)]
 pub struct EngineNodeLauncher {
@@ -188,12 +205,22 @@ where
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        // the local miner pays out credits queued into the inbox as withdrawals
+        let (credit_inbox, credit_inbox_handle) = CreditInbox::new();
+        // dev RPC namespaces control the local miner through its handle
+        let (miner_commands, local_miner) = LocalMinerCommands::new();
+        // blocks rejected by the engine are served through the debug namespace
+        let bad_blocks = ctx.bad_block_store()?;
+
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            credit_inbox: ctx.is_dev().then_some(credit_inbox_handle),
+            local_miner: ctx.is_dev().then_some(local_miner),
+            bad_blocks: bad_blocks.clone(),
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
@@ -212,6 +239,7 @@ where
             // during this run.
             .maybe_store_messages(node_config.debug.engine_api_store.clone());
 
+        let unwind_canonical_header = engine_tree_config.unwind_canonical_header();
         let mut engine_service = EngineService::new(
             consensus.clone(),
             ctx.chain_spec(),
@@ -225,22 +253,58 @@ where
             ctx.components().payload_builder_handle().clone(),
             engine_payload_validator,
             engine_tree_config,
-            ctx.invalid_block_hook()?,
+            ctx.invalid_block_hook(bad_blocks)?,
             ctx.sync_metrics_tx(),
             ctx.components().evm_config().clone(),
         );
 
         if ctx.is_dev() {
-            ctx.task_executor().spawn_critical(
-                "local engine",
-                LocalMiner::new(
-                    ctx.blockchain_db().clone(),
-                    LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
-                    beacon_engine_handle.clone(),
-                    ctx.dev_mining_mode(ctx.components().pool()),
-                    ctx.components().payload_builder_handle().clone(),
-                )
-                .run(),
+            let mut miner = LocalMiner::new(
+                ctx.blockchain_db().clone(),
+                LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
+                beacon_engine_handle.clone(),
+                ctx.dev_mining_mode(ctx.components().pool()),
+                ctx.components().payload_builder_handle().clone(),
+            )?
+            .with_credit_source(credit_inbox)
+            .with_commands(miner_commands)
+            .with_unwind_canonical_header(unwind_canonical_header);
+            if let Some(state_overrides) = ctx.components().evm_config().state_overrides() {
+                miner = miner.with_state_overrides(state_overrides.clone());
+            }
+            ctx.task_executor().spawn_critical("local engine", miner.run());
+        }
+
+        // Blocks persisted by the engine bypass the pipeline, so the optional indices are caught
+        // up in the background whenever the canonical chain changes.
+        if ctx.toml_config().stages.index_address_appearances.enabled {
+            let mut stage = IndexAddressAppearancesStage::new(
+                ctx.toml_config().stages.index_address_appearances,
+                ctx.toml_config().stages.etl.clone(),
+                ctx.prune_config().and_then(|config| config.segments.address_appearances),
+            );
+            let provider_factory = ctx.provider_factory().clone();
+            let canon_state_stream = ctx.blockchain_db().canonical_state_stream();
+            ctx.task_executor().spawn_critical_blocking(
+                "appearances indexer",
+                run_indexer(canon_state_stream, "address appearances", move || {
+                    stage.index_next_batch(&provider_factory)
+                }),
+            );
+        }
+        if ctx.toml_config().stages.index_traces.enabled {
+            let mut stage = IndexTracesStage::new(
+                ctx.toml_config().stages.index_traces,
+                ctx.prune_config().and_then(|config| config.segments.trace_index),
+                ctx.components().evm_config().clone(),
+            );
+            let provider_factory = ctx.provider_factory().clone();
+            let canon_state_stream = ctx.blockchain_db().canonical_state_stream();
+            ctx.task_executor().spawn_critical_blocking(
+                "trace indexer",
+                run_indexer(canon_state_stream, "traces", move || {
+                    stage.index_next_batch(&provider_factory)
+                }),
             );
         }
 
@@ -300,6 +364,10 @@ where
             // advance the chain and await payloads built locally to add into the engine api tree handler to prevent re-execution if that block is received as payload from the CL
             loop {
                 tokio::select! {
+                    // built payloads are inserted before the engine handles the payloads of the
+                    // local miner, which are then known and not executed again
+                    biased;
+
                     payload = built_payloads.select_next_some() => {
                         if let Some(executed_block) = payload.executed_block() {
                             debug!(target: "reth::cli", block=?executed_block.recovered_block().num_hash(),  "inserting built payload");
@@ -377,3 +445,27 @@ where
         Ok(handle)
     }
 }
+
+/// Catches up an optional index with `index_next_batch` whenever the canonical chain changes,
+/// until it returns `false`.
+async fn run_indexer<S: Stream + Unpin>(
+    mut canon_state_stream: S,
+    index: &'static str,
+    mut index_next_batch: impl FnMut() -> Result<bool, StageError>,
+) {
+    while canon_state_stream.next().await.is_some() {
+        tokio::time::sleep(INDEXER_DEBOUNCE).await;
+        while canon_state_stream.next().now_or_never().flatten().is_some() {}
+
+        loop {
+            match index_next_batch() {
+                Ok(true) => tokio::time::sleep(INDEXER_BATCH_INTERVAL).await,
+                Ok(false) => break,
+                Err(err) => {
+                    warn!(target: "reth::cli", %err, index, "Failed to update index");
+                    break
+                }
+            }
+        }
+    }
+}
@@ -87,6 +87,11 @@ pub struct DebugArgs {
         verbatim_doc_comment
     )]
     pub healthy_node_rpc_url: Option<String>,
+
+    /// The URL the artifacts of the `pre-state` and `opcode` invalid block hooks are POSTed to,
+    /// in addition to being written to the data directory.
+    #[arg(long = "debug.invalid-block-hook-url", help_heading = "Debug", value_name = "URL")]
+    pub invalid_block_hook_url: Option<String>,
 }
 
 impl Default for DebugArgs {
@@ -104,6 +109,7 @@ impl Default for DebugArgs {
             engine_api_store: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
+            invalid_block_hook_url: None,
         }
     }
 }
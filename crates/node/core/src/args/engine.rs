@@ -67,6 +67,11 @@ pub struct EngineArgs {
     /// Enable state root fallback, useful for testing
     #[arg(long = "engine.state-root-fallback", default_value = "false")]
     pub state_root_fallback: bool,
+
+    /// Allow forkchoice updates to an ancestor of the canonical head to unwind the canonical
+    /// chain, e.g. to revert the chain of a dev node through `anvil_revert`
+    #[arg(long = "engine.allow-unwind-canonical-header", default_value = "false")]
+    pub allow_unwind_canonical_header: bool,
 }
 
 impl Default for EngineArgs {
@@ -85,6 +90,7 @@ impl Default for EngineArgs {
             reserved_cpu_cores: DEFAULT_RESERVED_CPU_CORES,
             precompile_cache_enabled: false,
             state_root_fallback: false,
+            allow_unwind_canonical_header: false,
         }
     }
 }
@@ -104,6 +110,7 @@ impl EngineArgs {
             .with_reserved_cpu_cores(self.reserved_cpu_cores)
             .with_precompile_cache_enabled(self.precompile_cache_enabled)
             .with_state_root_fallback(self.state_root_fallback)
+            .with_unwind_canonical_header(self.allow_unwind_canonical_header)
     }
 }
 
@@ -12,6 +12,10 @@ pub use rpc_server::RpcServerArgs;
 mod rpc_state_cache;
 pub use rpc_state_cache::RpcStateCacheArgs;
 
+/// `RpcRateLimitArgs` struct for configuring RPC rate limits
+mod rpc_rate_limit;
+pub use rpc_rate_limit::RpcRateLimitArgs;
+
 /// DebugArgs struct for debugging purposes
 mod debug;
 pub use debug::{DebugArgs, InvalidBlockHookType, InvalidBlockSelection};
@@ -64,5 +68,9 @@ pub use engine::EngineArgs;
 mod ress_args;
 pub use ress_args::RessArgs;
 
+/// `SnapArgs` for configuring snap subprotocol.
+mod snap_args;
+pub use snap_args::SnapArgs;
+
 mod error;
 pub mod types;
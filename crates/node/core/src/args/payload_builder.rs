@@ -1,11 +1,13 @@
 use crate::{cli::config::PayloadBuilderConfig, version::default_extra_data};
 use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
 use alloy_eips::merge::SLOT_DURATION;
+use alloy_primitives::Address;
 use clap::{
     builder::{RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
 use reth_cli_util::{parse_duration_from_secs, parse_duration_from_secs_or_ms};
+use reth_transaction_pool::PriorityLane;
 use std::{borrow::Cow, ffi::OsStr, time::Duration};
 
 /// Parameters for configuring the Payload Builder
@@ -35,6 +37,30 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Comma separated contract addresses whose transactions are included ahead of other pool
+    /// transactions, within the gas reserved by `--builder.priority-lane-gas`.
+    #[arg(long = "builder.priority-lane-address", value_name = "ADDRESSES", value_delimiter = ',')]
+    pub priority_lane_addresses: Vec<Address>,
+
+    /// Comma separated senders whose transactions are included ahead of other pool transactions,
+    /// within the gas reserved by `--builder.priority-lane-gas`.
+    #[arg(long = "builder.priority-lane-sender", value_name = "ADDRESSES", value_delimiter = ',')]
+    pub priority_lane_senders: Vec<Address>,
+
+    /// Gas reserved at the top of the block for the transactions of the priority lane.
+    #[arg(long = "builder.priority-lane-gas", value_name = "GAS", default_value_t = 0)]
+    pub priority_lane_gas: u64,
+}
+
+impl PayloadBuilderArgs {
+    /// Returns the configured priority lane.
+    pub fn priority_lane(&self) -> PriorityLane {
+        PriorityLane::new(
+            self.priority_lane_addresses.iter().copied(),
+            self.priority_lane_senders.iter().copied(),
+        )
+    }
 }
 
 impl Default for PayloadBuilderArgs {
@@ -45,6 +71,9 @@ impl Default for PayloadBuilderArgs {
             gas_limit: None,
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            priority_lane_addresses: Vec::new(),
+            priority_lane_senders: Vec::new(),
+            priority_lane_gas: 0,
         }
     }
 }
@@ -173,4 +202,22 @@ mod tests {
                 .args;
         assert_eq!(args.interval, Duration::from_millis(50));
     }
+
+    #[test]
+    fn test_args_with_priority_lane() {
+        let bridge = Address::with_last_byte(1);
+        let relayer = Address::with_last_byte(2);
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.priority-lane-address",
+            &bridge.to_string(),
+            "--builder.priority-lane-sender",
+            &relayer.to_string(),
+            "--builder.priority-lane-gas",
+            "1000000",
+        ])
+        .args;
+        assert_eq!(args.priority_lane(), PriorityLane::new([bridge], [relayer]));
+        assert_eq!(args.priority_lane_gas, 1_000_000);
+    }
 }
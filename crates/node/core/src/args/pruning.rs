@@ -86,6 +86,45 @@ pub struct PruningArgs {
     /// pruned.
     #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
     pub storage_history_before: Option<BlockNumber>,
+
+    // Address Appearances
+    /// Prunes all address appearance index data.
+    #[arg(long = "prune.addressappearances.full", conflicts_with_all = &["address_appearances_distance", "address_appearances_before"])]
+    pub address_appearances_full: bool,
+    /// Prune address appearance index data before the `head-N` block number. In other words, keep
+    /// last N + 1 blocks.
+    #[arg(long = "prune.addressappearances.distance", value_name = "BLOCKS", conflicts_with_all = &["address_appearances_full", "address_appearances_before"])]
+    pub address_appearances_distance: Option<u64>,
+    /// Prune address appearance index data before the specified block number. The specified block
+    /// number is not pruned.
+    #[arg(long = "prune.addressappearances.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["address_appearances_full", "address_appearances_distance"])]
+    pub address_appearances_before: Option<BlockNumber>,
+
+    // Log Index
+    /// Prunes all log index data.
+    #[arg(long = "prune.logindex.full", conflicts_with_all = &["log_index_distance", "log_index_before"])]
+    pub log_index_full: bool,
+    /// Prune log index data before the `head-N` block number. In other words, keep last N + 1
+    /// blocks.
+    #[arg(long = "prune.logindex.distance", value_name = "BLOCKS", conflicts_with_all = &["log_index_full", "log_index_before"])]
+    pub log_index_distance: Option<u64>,
+    /// Prune log index data before the specified block number. The specified block number is not
+    /// pruned.
+    #[arg(long = "prune.logindex.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["log_index_full", "log_index_distance"])]
+    pub log_index_before: Option<BlockNumber>,
+
+    // Trace Index
+    /// Prunes all trace index data.
+    #[arg(long = "prune.traceindex.full", conflicts_with_all = &["trace_index_distance", "trace_index_before"])]
+    pub trace_index_full: bool,
+    /// Prune trace index data before the `head-N` block number. In other words, keep last N + 1
+    /// blocks.
+    #[arg(long = "prune.traceindex.distance", value_name = "BLOCKS", conflicts_with_all = &["trace_index_full", "trace_index_before"])]
+    pub trace_index_distance: Option<u64>,
+    /// Prune trace index data before the specified block number. The specified block number is
+    /// not pruned.
+    #[arg(long = "prune.traceindex.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["trace_index_full", "trace_index_distance"])]
+    pub trace_index_before: Option<BlockNumber>,
 }
 
 impl PruningArgs {
@@ -104,6 +143,9 @@ impl PruningArgs {
                     receipts: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    address_appearances: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    log_index: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    trace_index: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     receipts_log_filter: Default::default(),
                 },
             }
@@ -128,6 +170,15 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.address_appearances_prune_mode() {
+            config.segments.address_appearances = Some(mode);
+        }
+        if let Some(mode) = self.log_index_prune_mode() {
+            config.segments.log_index = Some(mode);
+        }
+        if let Some(mode) = self.trace_index_prune_mode() {
+            config.segments.trace_index = Some(mode);
+        }
         if let Some(receipt_logs) =
             self.receipts_log_filter.as_ref().filter(|c| !c.is_empty()).cloned()
         {
@@ -199,6 +250,42 @@ impl PruningArgs {
             None
         }
     }
+
+    const fn address_appearances_prune_mode(&self) -> Option<PruneMode> {
+        if self.address_appearances_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.address_appearances_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.address_appearances_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
+
+    const fn log_index_prune_mode(&self) -> Option<PruneMode> {
+        if self.log_index_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.log_index_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.log_index_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
+
+    const fn trace_index_prune_mode(&self) -> Option<PruneMode> {
+        if self.trace_index_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.trace_index_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.trace_index_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
 }
 
 /// Parses `,` separated pruning info into [`ReceiptsLogPruneConfig`].
@@ -0,0 +1,128 @@
+use clap::Args;
+use reth_rpc_server_types::{
+    rate_limit::DEFAULT_API_KEY_HEADER, ApiKeyQuotas, RateLimitConfig, RateLimitQuota,
+    RpcMethodGroup,
+};
+use std::collections::BTreeMap;
+
+/// Parameters to configure the rate limits of the HTTP and WS RPC servers.
+///
+/// Quotas are token buckets given as `<RATE>[:<BURST>]`, where `RATE` is the number of requests
+/// per second and `BURST` the number of requests that can be sent at once.
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "RPC Rate Limits")]
+pub struct RpcRateLimitArgs {
+    /// Quota per client for `eth_call`, `eth_callMany`, `eth_estimateGas`,
+    /// `eth_createAccessList` and `eth_simulateV1` requests.
+    #[arg(long = "rpc.ratelimit.call", value_name = "RATE[:BURST]")]
+    pub call: Option<RateLimitQuota>,
+
+    /// Quota per client for `eth_getLogs` requests.
+    #[arg(long = "rpc.ratelimit.logs", value_name = "RATE[:BURST]")]
+    pub logs: Option<RateLimitQuota>,
+
+    /// Quota per client for `debug_` requests.
+    #[arg(long = "rpc.ratelimit.debug", value_name = "RATE[:BURST]")]
+    pub debug: Option<RateLimitQuota>,
+
+    /// Quota per client for `trace_` requests.
+    #[arg(long = "rpc.ratelimit.trace", value_name = "RATE[:BURST]")]
+    pub trace: Option<RateLimitQuota>,
+
+    /// Quota per client for all other requests.
+    #[arg(long = "rpc.ratelimit.other", value_name = "RATE[:BURST]")]
+    pub other: Option<RateLimitQuota>,
+
+    /// Path to a JSON file with the API keys of known clients, mapping the name of each client to
+    /// its key and its quotas, e.g. `{"relayer": {"key": "...", "quotas": {"trace": "50:100"}}}`.
+    ///
+    /// Method groups without a quota for the client use the default quotas.
+    #[arg(long = "rpc.ratelimit.api-keys", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<BTreeMap<String, ApiKeyQuotas>>)]
+    pub api_keys: Option<BTreeMap<String, ApiKeyQuotas>>,
+
+    /// HTTP header that carries the API key of a client.
+    #[arg(long = "rpc.ratelimit.api-key-header", value_name = "HEADER", default_value = DEFAULT_API_KEY_HEADER)]
+    pub api_key_header: String,
+
+    /// Identify clients without an API key by the `X-Forwarded-For` or `X-Real-IP` header.
+    ///
+    /// Only enable this if the RPC server is behind a reverse proxy that sets these headers.
+    /// Otherwise all clients without an API key share the same quota.
+    #[arg(long = "rpc.ratelimit.trust-forwarded-for")]
+    pub trust_forwarded_for: bool,
+}
+
+impl RpcRateLimitArgs {
+    /// Returns the rate limit configuration, or `None` if no quota was configured.
+    pub fn rate_limit_config(&self) -> Option<RateLimitConfig> {
+        let quotas = [
+            (RpcMethodGroup::Call, self.call),
+            (RpcMethodGroup::Logs, self.logs),
+            (RpcMethodGroup::Debug, self.debug),
+            (RpcMethodGroup::Trace, self.trace),
+            (RpcMethodGroup::Other, self.other),
+        ]
+        .into_iter()
+        .filter_map(|(group, quota)| Some((group, quota?)))
+        .collect();
+
+        let config = RateLimitConfig {
+            quotas,
+            api_keys: self.api_keys.clone().unwrap_or_default(),
+            api_key_header: self.api_key_header.clone(),
+            trust_forwarded_for: self.trust_forwarded_for,
+        };
+        (!config.is_unlimited()).then_some(config)
+    }
+}
+
+impl Default for RpcRateLimitArgs {
+    fn default() -> Self {
+        Self {
+            call: None,
+            logs: None,
+            debug: None,
+            trace: None,
+            other: None,
+            api_keys: None,
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+            trust_forwarded_for: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn default_args_are_unlimited() {
+        let args = CommandParser::<RpcRateLimitArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, RpcRateLimitArgs::default());
+        assert_eq!(args.rate_limit_config(), None);
+    }
+
+    #[test]
+    fn parse_quotas() {
+        let args = CommandParser::<RpcRateLimitArgs>::parse_from([
+            "reth",
+            "--rpc.ratelimit.trace",
+            "5:20",
+            "--rpc.ratelimit.logs",
+            "10",
+        ])
+        .args;
+        let config = args.rate_limit_config().unwrap();
+        assert_eq!(config.quota(None, RpcMethodGroup::Trace), Some("5:20".parse().unwrap()));
+        assert_eq!(config.quota(None, RpcMethodGroup::Logs), Some("10:10".parse().unwrap()));
+        assert_eq!(config.quota(None, RpcMethodGroup::Call), None);
+    }
+}
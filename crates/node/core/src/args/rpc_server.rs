@@ -19,7 +19,7 @@ use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
-    GasPriceOracleArgs, RpcStateCacheArgs,
+    GasPriceOracleArgs, RpcRateLimitArgs, RpcStateCacheArgs,
 };
 
 use super::types::MaxOr;
@@ -62,6 +62,18 @@ pub struct RpcServerArgs {
     #[arg(long = "http.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub http_api: Option<RpcModuleSelection>,
 
+    /// Comma separated methods to expose on the HTTP server, e.g. `debug_traceTransaction` or
+    /// `debug_*`.
+    ///
+    /// Only restricts the namespaces of the listed methods, other namespaces are exposed
+    /// entirely.
+    #[arg(long = "http.api.allow", value_name = "METHODS", value_delimiter = ',')]
+    pub http_api_allow: Vec<String>,
+
+    /// Comma separated methods to hide on the HTTP server, e.g. `admin_addPeer` or `admin_*`.
+    #[arg(long = "http.api.deny", value_name = "METHODS", value_delimiter = ',')]
+    pub http_api_deny: Vec<String>,
+
     /// Http Corsdomain to allow request from
     #[arg(long = "http.corsdomain")]
     pub http_corsdomain: Option<String>,
@@ -86,6 +98,18 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Comma separated methods to expose on the WS server, e.g. `debug_traceTransaction` or
+    /// `debug_*`.
+    ///
+    /// Only restricts the namespaces of the listed methods, other namespaces are exposed
+    /// entirely.
+    #[arg(long = "ws.api.allow", value_name = "METHODS", value_delimiter = ',')]
+    pub ws_api_allow: Vec<String>,
+
+    /// Comma separated methods to hide on the WS server, e.g. `admin_addPeer` or `admin_*`.
+    #[arg(long = "ws.api.deny", value_name = "METHODS", value_delimiter = ',')]
+    pub ws_api_deny: Vec<String>,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
@@ -94,6 +118,18 @@ pub struct RpcServerArgs {
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Comma separated methods to expose on the IPC server, e.g. `debug_traceTransaction` or
+    /// `debug_*`.
+    ///
+    /// Only restricts the namespaces of the listed methods, other namespaces are exposed
+    /// entirely.
+    #[arg(long = "ipc.api.allow", value_name = "METHODS", value_delimiter = ',')]
+    pub ipc_api_allow: Vec<String>,
+
+    /// Comma separated methods to hide on the IPC server, e.g. `admin_addPeer` or `admin_*`.
+    #[arg(long = "ipc.api.deny", value_name = "METHODS", value_delimiter = ',')]
+    pub ipc_api_deny: Vec<String>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
     pub auth_addr: IpAddr,
@@ -211,6 +247,11 @@ pub struct RpcServerArgs {
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
     pub builder_disallow: Option<HashSet<Address>>,
 
+    /// Enables `eth_sendRawTransactionConditional`. Conditional transactions are dropped from the
+    /// pool once their conditions can no longer be met.
+    #[arg(long = "rpc.enable-tx-conditional")]
+    pub enable_tx_conditional: bool,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -218,6 +259,10 @@ pub struct RpcServerArgs {
     /// Gas price oracle configuration.
     #[command(flatten)]
     pub gas_price_oracle: GasPriceOracleArgs,
+
+    /// Rate limit configuration.
+    #[command(flatten)]
+    pub rate_limit: RpcRateLimitArgs,
 }
 
 impl RpcServerArgs {
@@ -239,6 +284,12 @@ impl RpcServerArgs {
         self
     }
 
+    /// Configures modules for the WS-RPC server.
+    pub fn with_ws_api(mut self, ws_api: RpcModuleSelection) -> Self {
+        self.ws_api = Some(ws_api);
+        self
+    }
+
     /// Enables the Auth IPC
     pub const fn with_auth_ipc(mut self) -> Self {
         self.auth_ipc = true;
@@ -322,14 +373,20 @@ impl Default for RpcServerArgs {
             http_port: constants::DEFAULT_HTTP_RPC_PORT,
             http_disable_compression: false,
             http_api: None,
+            http_api_allow: Vec::new(),
+            http_api_deny: Vec::new(),
             http_corsdomain: None,
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            ws_api_allow: Vec::new(),
+            ws_api_deny: Vec::new(),
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
+            ipc_api_allow: Vec::new(),
+            ipc_api_deny: Vec::new(),
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
@@ -350,8 +407,10 @@ impl Default for RpcServerArgs {
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
+            rate_limit: RpcRateLimitArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
             builder_disallow: Default::default(),
+            enable_tx_conditional: false,
         }
     }
 }
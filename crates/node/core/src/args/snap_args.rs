@@ -0,0 +1,39 @@
+//! clap [Args](clap::Args) for the `snap` subprotocol
+
+use clap::Args;
+
+/// The default number of maximum active connections.
+const MAX_ACTIVE_CONNECTIONS_DEFAULT: u64 = 50;
+
+/// The default number of blocks below the tip whose state is served.
+const MAX_STATE_DEPTH_DEFAULT: u64 = 128;
+
+/// Parameters for configuring the `snap` subprotocol.
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Snap")]
+pub struct SnapArgs {
+    /// Serve the state of recent blocks to peers over the `snap/1` subprotocol.
+    #[arg(long = "snap.serve", default_value_t = false)]
+    pub serve: bool,
+
+    /// The maximum number of active connections for `snap` subprotocol.
+    #[arg(long = "snap.max-active-connections", default_value_t = MAX_ACTIVE_CONNECTIONS_DEFAULT)]
+    pub max_active_connections: u64,
+
+    /// The number of blocks below the tip whose state is served.
+    ///
+    /// Older states are served by reverting the latest state, so deeper states are more expensive
+    /// to serve.
+    #[arg(long = "snap.max-state-depth", default_value_t = MAX_STATE_DEPTH_DEFAULT)]
+    pub max_state_depth: u64,
+}
+
+impl Default for SnapArgs {
+    fn default() -> Self {
+        Self {
+            serve: false,
+            max_active_connections: MAX_ACTIVE_CONNECTIONS_DEFAULT,
+            max_state_depth: MAX_STATE_DEPTH_DEFAULT,
+        }
+    }
+}
@@ -50,4 +50,16 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The optional address appearances stage within the pipeline.
+    ///
+    /// Indexes the blocks in which each address appeared.
+    AddressAppearances,
+    /// The optional log index stage within the pipeline.
+    ///
+    /// Indexes the blocks in which logs were emitted, by address and topic.
+    LogIndex,
+    /// The optional trace index stage within the pipeline.
+    ///
+    /// Indexes the blocks in which addresses appeared in call traces.
+    TraceIndex,
 }
@@ -7,6 +7,7 @@ use clap::Args;
 use reth_cli_util::parse_duration_from_secs_or_ms;
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    journal::DEFAULT_JOURNAL_COMPACTION_INTERVAL,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
@@ -122,6 +123,21 @@ pub struct TxPoolArgs {
         conflicts_with = "transactions_backup_path"
     )]
     pub disable_transactions_backup: bool,
+
+    /// Journals all pool transactions to disk as they enter and leave the pool, and replays them
+    /// on startup.
+    ///
+    /// Replaces the local transaction backup.
+    #[arg(long = "txpool.wal")]
+    pub journal: bool,
+
+    /// Path to store the transaction journal at.
+    #[arg(long = "txpool.wal-path", value_name = "PATH", requires = "journal")]
+    pub journal_path: Option<std::path::PathBuf>,
+
+    /// How often the transaction journal is compacted.
+    #[arg(long = "txpool.wal-compaction-interval", value_parser = parse_duration_from_secs_or_ms, default_value = "3600", value_name = "DURATION")]
+    pub journal_compaction_interval: Duration,
 }
 
 impl Default for TxPoolArgs {
@@ -153,6 +169,9 @@ impl Default for TxPoolArgs {
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
             transactions_backup_path: None,
             disable_transactions_backup: false,
+            journal: false,
+            journal_path: None,
+            journal_compaction_interval: DEFAULT_JOURNAL_COMPACTION_INTERVAL,
         }
     }
 }
@@ -240,6 +259,29 @@ mod tests {
         assert_eq!(args.max_queued_lifetime, Duration::from_secs(3 * 60 * 60)); // Default is 3h
     }
 
+    #[test]
+    fn txpool_parse_journal() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.wal",
+            "--txpool.wal-path",
+            "/tmp/journal.rlp",
+            "--txpool.wal-compaction-interval",
+            "600",
+        ])
+        .args;
+        assert!(args.journal);
+        assert_eq!(args.journal_path, Some("/tmp/journal.rlp".into()));
+        assert_eq!(args.journal_compaction_interval, Duration::from_secs(600));
+
+        let result = CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.wal-path",
+            "/tmp/journal.rlp",
+        ]);
+        assert!(result.is_err(), "Expected an error for a journal path without --txpool.wal");
+    }
+
     #[test]
     fn txpool_parse_max_tx_lifetime_invalid() {
         let result =
@@ -330,6 +330,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the transaction pool journal file
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-journal.rlp`
+    pub fn txpool_journal(&self) -> PathBuf {
+        self.data_dir().join("txpool-journal.rlp")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -351,10 +358,32 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the file that stores the blocks rejected by the engine for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/bad_blocks.rlp`
+    pub fn bad_blocks(&self) -> PathBuf {
+        self.data_dir().join("bad_blocks.rlp")
+    }
+
+    /// Returns the path to the file that stores the state overrides of locally built blocks for
+    /// this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/state-overrides.jsonl`
+    pub fn state_overrides(&self) -> PathBuf {
+        self.data_dir().join("state-overrides.jsonl")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
     }
+
+    /// Returns the path to the database of the bridge request indexer for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/exex/bridge`
+    pub fn bridge_requests(&self) -> PathBuf {
+        self.data_dir().join("exex/bridge")
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
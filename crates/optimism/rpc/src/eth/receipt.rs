@@ -1,6 +1,6 @@
 //! Loads and formats OP receipt RPC response.
 
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::transaction::{Recovered, TransactionMeta};
 use alloy_eips::eip2718::Encodable2718;
 use alloy_rpc_types_eth::{Log, TransactionReceipt};
 use op_alloy_consensus::{OpDepositReceipt, OpDepositReceiptWithBloom, OpReceiptEnvelope};
@@ -43,9 +43,15 @@ where
         let mut l1_block_info =
             reth_optimism_evm::extract_l1_info(block.body()).map_err(OpEthApiError::from)?;
 
+        // the block carries the senders, so they don't need to be recovered
+        let sender = match block.senders().get(meta.index as usize) {
+            Some(sender) => *sender,
+            None => tx.recover_signer_unchecked().map_err(Self::Error::from_eth_err)?,
+        };
+
         Ok(OpReceiptBuilder::new(
             &self.inner.eth_api.provider().chain_spec(),
-            &tx,
+            Recovered::new_unchecked(&tx, sender),
             meta,
             &receipt,
             &receipts,
@@ -223,7 +229,7 @@ impl OpReceiptBuilder {
     /// Returns a new builder.
     pub fn new(
         chain_spec: &impl OpHardforks,
-        transaction: &OpTransactionSigned,
+        transaction: Recovered<&OpTransactionSigned>,
         meta: TransactionMeta,
         receipt: &OpReceipt,
         all_receipts: &[OpReceipt],
@@ -252,7 +258,7 @@ impl OpReceiptBuilder {
             })?;
 
         let op_receipt_fields = OpReceiptFieldsBuilder::new(timestamp, block_number)
-            .l1_block_info(chain_spec, transaction, l1_block_info)?
+            .l1_block_info(chain_spec, transaction.inner(), l1_block_info)?
             .build();
 
         Ok(Self { core_receipt, op_receipt_fields })
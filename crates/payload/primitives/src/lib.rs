@@ -15,6 +15,7 @@ use crate::alloc::string::ToString;
 use alloy_primitives::Bytes;
 use reth_chainspec::EthereumHardforks;
 use reth_primitives_traits::{NodePrimitives, SealedBlock};
+use serde::{Deserialize, Serialize};
 
 mod error;
 pub use error::{
@@ -355,7 +356,7 @@ where
 }
 
 /// The version of Engine API message.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
 pub enum EngineApiMessageVersion {
     /// Version 1
     V1 = 1,
@@ -125,4 +125,33 @@ impl PayloadAttributes for op_alloy_rpc_types_engine::OpPayloadAttributes {
 pub trait PayloadAttributesBuilder<Attributes>: Send + Sync + 'static {
     /// Return a new payload attribute from the builder.
     fn build(&self, timestamp: u64) -> Attributes;
+
+    /// Return a new payload attribute from the builder that carries the given withdrawals.
+    ///
+    /// Returns `None` if the payload attributes for the given timestamp can not carry withdrawals,
+    /// for example because Shanghai is not active yet.
+    fn build_with_withdrawals(
+        &self,
+        timestamp: u64,
+        withdrawals: Vec<Withdrawal>,
+    ) -> Option<Attributes> {
+        let _ = (timestamp, withdrawals);
+        None
+    }
+
+    /// Replaces the `prevRandao` of payload attributes built by this builder.
+    ///
+    /// Returns `false` if the attributes do not carry a `prevRandao`.
+    fn set_prev_randao(&self, attributes: &mut Attributes, prev_randao: B256) -> bool {
+        let _ = (attributes, prev_randao);
+        false
+    }
+
+    /// Replaces the suggested fee recipient of payload attributes built by this builder.
+    ///
+    /// Returns `false` if the attributes do not carry a suggested fee recipient.
+    fn set_suggested_fee_recipient(&self, attributes: &mut Attributes, recipient: Address) -> bool {
+        let _ = (attributes, recipient);
+        false
+    }
 }
@@ -15,8 +15,8 @@ pub use static_file::{
 use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::error;
 pub use user::{
-    AccountHistory, Receipts as UserReceipts, ReceiptsByLogs, SenderRecovery, StorageHistory,
-    TransactionLookup,
+    AccountHistory, AddressAppearances, LogIndex, Receipts as UserReceipts, ReceiptsByLogs,
+    SenderRecovery, StorageHistory, TraceIndex, TransactionLookup,
 };
 
 /// A segment represents a pruning of some portion of the data.
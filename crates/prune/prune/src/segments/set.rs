@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, AddressAppearances, LogIndex, ReceiptsByLogs, Segment, SenderRecovery,
+    StorageHistory, TraceIndex, TransactionLookup, UserReceipts,
 };
 use alloy_eips::eip2718::Encodable2718;
 use reth_db_api::{table::Value, transaction::DbTxMut};
@@ -64,6 +64,9 @@ where
             receipts,
             account_history,
             storage_history,
+            address_appearances,
+            log_index,
+            trace_index,
             receipts_log_filter,
         } = prune_modes;
 
@@ -78,6 +81,12 @@ where
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Address appearances
+            .segment_opt(address_appearances.map(AddressAppearances::new))
+            // Log index
+            .segment_opt(log_index.map(LogIndex::new))
+            // Trace index
+            .segment_opt(trace_index.map(TraceIndex::new))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
@@ -0,0 +1,131 @@
+use crate::{
+    segments::{
+        user::history::{prune_sharded_index, prune_sharded_index_segment},
+        PruneInput, Segment,
+    },
+    PrunerError,
+};
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_provider::DBProvider;
+use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct AddressAppearances {
+    mode: PruneMode,
+}
+
+impl AddressAppearances {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for AddressAppearances
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressAppearances
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        prune_sharded_index_segment(provider, input, |provider, to_block, limiter| {
+            prune_sharded_index::<_, tables::AddressAppearances, _>(provider, to_block, limiter)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AddressAppearances, PruneInput, PruneLimiter, Segment, SegmentOutput};
+    use alloy_primitives::Address;
+    use assert_matches::assert_matches;
+    use reth_db_api::{
+        models::ShardedKey,
+        tables,
+        transaction::{DbTx, DbTxMut},
+        BlockNumberList,
+    };
+    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneProgress, PruneSegment};
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let first = Address::with_last_byte(1);
+        let second = Address::with_last_byte(2);
+
+        let list = |blocks: &[u64]| BlockNumberList::new(blocks.iter().copied()).unwrap();
+        db.commit(|tx| {
+            tx.put::<tables::AddressAppearances>(ShardedKey::new(first, 3), list(&[1, 2, 3]))?;
+            tx.put::<tables::AddressAppearances>(
+                ShardedKey::new(first, u64::MAX),
+                list(&[4, 20_000]),
+            )?;
+            tx.put::<tables::AddressAppearances>(ShardedKey::new(second, u64::MAX), list(&[5]))?;
+            Ok(())
+        })
+        .unwrap();
+
+        let to_block = 10;
+        let prune_mode = PruneMode::Before(to_block + 1);
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(10),
+        };
+        let segment = AddressAppearances::new(prune_mode);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(
+            result,
+            SegmentOutput { progress: PruneProgress::Finished, pruned: 3, checkpoint: Some(_) }
+        );
+        segment
+            .save_checkpoint(&provider, result.checkpoint.unwrap().as_prune_checkpoint(prune_mode))
+            .unwrap();
+        provider.commit().expect("commit");
+
+        assert_eq!(
+            db.table::<tables::AddressAppearances>().unwrap(),
+            vec![(ShardedKey::new(first, u64::MAX), list(&[20_000]))]
+        );
+        assert_eq!(
+            db.factory
+                .provider()
+                .unwrap()
+                .get_prune_checkpoint(PruneSegment::AddressAppearances)
+                .unwrap(),
+            Some(PruneCheckpoint { block_number: Some(to_block), tx_number: None, prune_mode })
+        );
+
+        // Less than a batch of blocks became prunable since the last run, so nothing is walked.
+        let input = PruneInput {
+            previous_checkpoint: Some(PruneCheckpoint {
+                block_number: Some(to_block),
+                tx_number: None,
+                prune_mode,
+            }),
+            to_block: 5_000,
+            limiter: PruneLimiter::default(),
+        };
+        let provider = db.factory.database_provider_rw().unwrap();
+        assert_matches!(
+            segment.prune(&provider, input).unwrap(),
+            SegmentOutput { progress: PruneProgress::Finished, pruned: 0, checkpoint: None }
+        );
+        assert_eq!(provider.tx_ref().entries::<tables::AddressAppearances>().unwrap(), 1);
+    }
+}
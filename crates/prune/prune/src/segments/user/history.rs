@@ -1,3 +1,4 @@
+use crate::{segments::PruneInput, PruneLimiter, PrunerError};
 use alloy_primitives::BlockNumber;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
@@ -7,8 +8,18 @@ use reth_db_api::{
     BlockNumberList, DatabaseError, RawKey, RawTable, RawValue,
 };
 use reth_provider::DBProvider;
+use reth_prune_types::{SegmentOutput, SegmentOutputCheckpoint};
+use tracing::trace;
 
-enum PruneShardOutcome {
+/// Minimum number of blocks that need to become prunable before the tables of a sharded index
+/// are walked by [`prune_sharded_index_segment`].
+///
+/// Unlike the account and storage history, these indices have no changeset table telling which
+/// keys appeared in the pruned range, so every run walks the whole index. The walk is batched to
+/// amortize its cost over many blocks.
+const SHARDED_INDEX_PRUNE_BATCH: u64 = 10_000;
+
+pub(crate) enum PruneShardOutcome {
     Deleted,
     Updated,
     Unchanged,
@@ -70,6 +81,108 @@ where
     Ok(outcomes)
 }
 
+/// Prunes a segment made of sharded index tables that have no changeset table, by walking them
+/// with `prune_tables` up to the end of the prunable range.
+///
+/// `prune_tables` returns the number of pruned shards, and whether all tables were walked before
+/// the limiter was reached.
+pub(crate) fn prune_sharded_index_segment<Provider>(
+    provider: &Provider,
+    input: PruneInput,
+    prune_tables: impl FnOnce(
+        &Provider,
+        BlockNumber,
+        &mut PruneLimiter,
+    ) -> Result<(usize, bool), DatabaseError>,
+) -> Result<SegmentOutput, PrunerError> {
+    let Some(range) = input.get_next_block_range() else {
+        trace!(target: "pruner", "No sharded index to prune");
+        return Ok(SegmentOutput::done())
+    };
+    let range_end = *range.end();
+
+    // Wait for enough blocks to accumulate, unless there's nothing pruned yet and this is the
+    // first run.
+    if input.previous_checkpoint.is_some() &&
+        range_end - range.start() + 1 < SHARDED_INDEX_PRUNE_BATCH
+    {
+        trace!(target: "pruner", ?range, "Not enough blocks to prune the sharded index");
+        return Ok(SegmentOutput::done())
+    }
+
+    let mut limiter = input.limiter;
+    if limiter.is_limit_reached() {
+        return Ok(SegmentOutput::not_done(
+            limiter.interrupt_reason(),
+            input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+        ))
+    }
+
+    let (pruned, done) = prune_tables(provider, range_end, &mut limiter)?;
+    trace!(target: "pruner", %pruned, %done, "Pruned sharded index");
+
+    let progress = limiter.progress(done);
+
+    Ok(SegmentOutput {
+        progress,
+        pruned,
+        // The walk starts from the beginning of the tables on the next run, so the checkpoint is
+        // only moved once all tables were walked.
+        checkpoint: if done {
+            Some(SegmentOutputCheckpoint { block_number: Some(range_end), tx_number: None })
+        } else {
+            input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint)
+        },
+    })
+}
+
+/// Walks the whole sharded index table `T` and removes all block numbers less than or equal to
+/// `to_block`.
+///
+/// Used for indices that have no changeset table telling which keys are affected by the pruned
+/// range.
+///
+/// Returns the number of deleted and updated shards, and whether the walk was finished before the
+/// limiter was reached.
+pub(crate) fn prune_sharded_index<Provider, T, K>(
+    provider: &Provider,
+    to_block: BlockNumber,
+    limiter: &mut PruneLimiter,
+) -> Result<(usize, bool), DatabaseError>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+    T: Table<Value = BlockNumberList>,
+    T::Key: AsRef<ShardedKey<K>>,
+    K: PartialEq,
+{
+    let mut cursor = provider.tx_ref().cursor_write::<RawTable<T>>()?;
+    let mut shard = cursor.first()?;
+    let mut pruned = 0;
+
+    let done = loop {
+        if limiter.is_limit_reached() {
+            break false
+        }
+
+        let Some((key, blocks)) = shard else { break true };
+        let key = key.key()?;
+
+        match prune_shard(&mut cursor, key, blocks, to_block, |a, b| {
+            a.as_ref().key == b.as_ref().key
+        })? {
+            PruneShardOutcome::Deleted | PruneShardOutcome::Updated => {
+                limiter.increment_deleted_entries_count();
+                pruned += 1;
+            }
+            PruneShardOutcome::Unchanged => {}
+        }
+
+        shard = cursor.next()?;
+    };
+
+    Ok((pruned, done))
+}
+
 /// Prunes one shard of a history table.
 ///
 /// 1. If the shard has `highest_block_number` less than or equal to the target block number for
@@ -77,7 +190,7 @@ where
 /// 2. If the shard has `highest_block_number` greater than the target block number for pruning,
 ///    filter block numbers inside the shard which are less than the target block number for
 ///    pruning.
-fn prune_shard<C, T, SK>(
+pub(crate) fn prune_shard<C, T, SK>(
     cursor: &mut C,
     key: T::Key,
     raw_blocks: RawValue<T::Value>,
@@ -0,0 +1,102 @@
+use crate::{
+    segments::{
+        user::history::{prune_sharded_index, prune_sharded_index_segment},
+        PruneInput, Segment,
+    },
+    PrunerError,
+};
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_provider::DBProvider;
+use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct LogIndex {
+    mode: PruneMode,
+}
+
+impl LogIndex {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for LogIndex
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::LogIndex
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        prune_sharded_index_segment(provider, input, |provider, to_block, limiter| {
+            let (pruned_addresses, done) =
+                prune_sharded_index::<_, tables::LogAddressIndex, _>(provider, to_block, limiter)?;
+            if !done {
+                return Ok((pruned_addresses, false))
+            }
+
+            let (pruned_topics, done) =
+                prune_sharded_index::<_, tables::LogTopicIndex, _>(provider, to_block, limiter)?;
+            Ok((pruned_addresses + pruned_topics, done))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{LogIndex, PruneInput, PruneLimiter, Segment, SegmentOutput};
+    use alloy_primitives::{Address, B256};
+    use assert_matches::assert_matches;
+    use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut, BlockNumberList};
+    use reth_provider::DatabaseProviderFactory;
+    use reth_prune_types::{PruneMode, PruneProgress};
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let address = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(2);
+
+        let list = |blocks: &[u64]| BlockNumberList::new(blocks.iter().copied()).unwrap();
+        db.commit(|tx| {
+            tx.put::<tables::LogAddressIndex>(ShardedKey::new(address, u64::MAX), list(&[1, 20]))?;
+            tx.put::<tables::LogTopicIndex>(ShardedKey::new(topic, u64::MAX), list(&[2, 3]))?;
+            Ok(())
+        })
+        .unwrap();
+
+        let to_block = 10;
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(10),
+        };
+        let segment = LogIndex::new(PruneMode::Before(to_block + 1));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(
+            result,
+            SegmentOutput { progress: PruneProgress::Finished, pruned: 2, checkpoint: Some(_) }
+        );
+        provider.commit().expect("commit");
+
+        assert_eq!(
+            db.table::<tables::LogAddressIndex>().unwrap(),
+            vec![(ShardedKey::new(address, u64::MAX), list(&[20]))]
+        );
+        assert!(db.table::<tables::LogTopicIndex>().unwrap().is_empty());
+    }
+}
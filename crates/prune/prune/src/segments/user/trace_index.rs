@@ -0,0 +1,171 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
+use reth_provider::DBProvider;
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use std::collections::BTreeMap;
+use tracing::{instrument, trace};
+
+/// Number of trace index tables to prune in one step.
+///
+/// Trace index consists of two tables: [`tables::BlockTraceAddresses`] and
+/// [`tables::TraceAddressIndex`]. We want to prune them to the same block number.
+const TRACE_INDEX_TABLES_TO_PRUNE: usize = 2;
+
+#[derive(Debug)]
+pub struct TraceIndex {
+    mode: PruneMode,
+}
+
+impl TraceIndex {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for TraceIndex
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::TraceIndex
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No trace index to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = if let Some(limit) = input.limiter.deleted_entries_limit() {
+            input.limiter.set_deleted_entries_limit(limit / TRACE_INDEX_TABLES_TO_PRUNE)
+        } else {
+            input.limiter
+        };
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                limiter.interrupt_reason(),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let mut last_pruned_block = None;
+        // Deleted addresses with the highest block number deleted for that address.
+        let mut highest_deleted_addresses = BTreeMap::new();
+        let (pruned_addresses, done) =
+            provider.tx_ref().prune_table_with_range::<tables::BlockTraceAddresses>(
+                range,
+                &mut limiter,
+                |_| false,
+                |(block_number, address)| {
+                    highest_deleted_addresses.insert(address, block_number);
+                    last_pruned_block = Some(block_number);
+                },
+            )?;
+        trace!(target: "pruner", pruned = %pruned_addresses, %done, "Pruned trace index (block addresses)");
+
+        let last_pruned_block = last_pruned_block
+            // If there's more block addresses to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its addresses on the next run.
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+
+        let highest_sharded_keys =
+            highest_deleted_addresses.into_iter().map(|(address, block_number)| {
+                ShardedKey::new(address, block_number.min(last_pruned_block))
+            });
+        let outcomes = prune_history_indices::<Provider, tables::TraceAddressIndex, _>(
+            provider,
+            highest_sharded_keys,
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?outcomes, %done, "Pruned trace index (indices)");
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: pruned_addresses + outcomes.deleted,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, PruneLimiter, Segment, SegmentOutput, TraceIndex};
+    use alloy_primitives::{Address, BlockNumber};
+    use assert_matches::assert_matches;
+    use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut, BlockNumberList};
+    use reth_provider::DatabaseProviderFactory;
+    use reth_prune_types::{PruneMode, PruneProgress};
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let first = Address::with_last_byte(1);
+        let second = Address::with_last_byte(2);
+
+        let entries: [(BlockNumber, Address); 4] =
+            [(1, first), (1, second), (5, first), (20, first)];
+        db.commit(|tx| {
+            for (block_number, address) in entries {
+                tx.put::<tables::BlockTraceAddresses>(block_number, address)?;
+            }
+            tx.put::<tables::TraceAddressIndex>(
+                ShardedKey::new(first, u64::MAX),
+                BlockNumberList::new([1, 5, 20]).unwrap(),
+            )?;
+            tx.put::<tables::TraceAddressIndex>(
+                ShardedKey::new(second, u64::MAX),
+                BlockNumberList::new([1]).unwrap(),
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        let to_block = 10;
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(10),
+        };
+        let segment = TraceIndex::new(PruneMode::Before(to_block + 1));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(
+            result,
+            SegmentOutput { progress: PruneProgress::Finished, pruned: 4, checkpoint: Some(_) }
+        );
+        provider.commit().expect("commit");
+
+        assert_eq!(db.table::<tables::BlockTraceAddresses>().unwrap(), vec![(20, first)]);
+        assert_eq!(
+            db.table::<tables::TraceAddressIndex>().unwrap(),
+            vec![(ShardedKey::new(first, u64::MAX), BlockNumberList::new([20]).unwrap())]
+        );
+    }
+}
@@ -26,6 +26,12 @@ pub enum PruneSegment {
     Headers,
     /// Prune segment responsible for the `Transactions` table.
     Transactions,
+    /// Prune segment responsible for the `AddressAppearances` table.
+    AddressAppearances,
+    /// Prune segment responsible for the `LogAddressIndex` and `LogTopicIndex` tables.
+    LogIndex,
+    /// Prune segment responsible for the `TraceAddressIndex` and `BlockTraceAddresses` tables.
+    TraceIndex,
 }
 
 impl PruneSegment {
@@ -36,10 +42,13 @@ impl PruneSegment {
                 0
             }
             Self::Receipts if purpose.is_static_file() => 0,
-            Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
-                MINIMUM_PRUNING_DISTANCE
-            }
-            Self::Receipts => MINIMUM_PRUNING_DISTANCE,
+            Self::Receipts |
+            Self::ContractLogs |
+            Self::AccountHistory |
+            Self::StorageHistory |
+            Self::AddressAppearances |
+            Self::LogIndex |
+            Self::TraceIndex => MINIMUM_PRUNING_DISTANCE,
         }
     }
 }
@@ -46,6 +46,33 @@ pub struct PruneModes {
         )
     )]
     pub storage_history: Option<PruneMode>,
+    /// Address Appearances pruning configuration.
+    #[cfg_attr(
+        any(test, feature = "serde"),
+        serde(
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
+        )
+    )]
+    pub address_appearances: Option<PruneMode>,
+    /// Log Index pruning configuration.
+    #[cfg_attr(
+        any(test, feature = "serde"),
+        serde(
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
+        )
+    )]
+    pub log_index: Option<PruneMode>,
+    /// Trace Index pruning configuration.
+    #[cfg_attr(
+        any(test, feature = "serde"),
+        serde(
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
+        )
+    )]
+    pub trace_index: Option<PruneMode>,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -68,6 +95,9 @@ impl PruneModes {
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
+            address_appearances: Some(PruneMode::Full),
+            log_index: Some(PruneMode::Full),
+            trace_index: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
         }
     }
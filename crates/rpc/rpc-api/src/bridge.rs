@@ -0,0 +1,22 @@
+use alloy_primitives::{Address, Bytes, B256, U64};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+/// Bridge inbox namespace rpc interface that queues inbound bridge credits on a dev node and
+/// inbound messages on chains with an inbox contract.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "bridgeInbox"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "bridgeInbox"))]
+pub trait BridgeInboxApi {
+    /// Queues a credit of `amount` Gwei to `address`.
+    ///
+    /// The credit is paid out as a withdrawal in the next block built by the local miner.
+    #[method(name = "enqueueCredit")]
+    fn enqueue_credit(&self, address: Address, amount: U64) -> RpcResult<()>;
+
+    /// Queues a batch of messages for the inbox contract and returns the commitment of the batch,
+    /// `keccak256(abi.encode(messages))`.
+    ///
+    /// The batch is delivered in one of the next blocks built by the node, and removed from the
+    /// queue once that block is canonical.
+    #[method(name = "queueMessages")]
+    fn queue_messages(&self, messages: Vec<Bytes>) -> RpcResult<B256>;
+}
@@ -2,12 +2,46 @@ use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
 use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types_debug::ExecutionWitness;
-use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, StateContext};
+use alloy_rpc_types_eth::{transaction::TransactionRequest, Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
-    BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
+    GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A block that was rejected by the node, as returned by `debug_getBadBlocks`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BadBlock {
+    /// Hash of the block.
+    pub hash: B256,
+    /// The block in its RPC representation, with transaction hashes only.
+    pub block: serde_json::Value,
+    /// RLP encoding of the block.
+    pub rlp: Bytes,
+    /// The error the block was rejected with.
+    pub reason: String,
+}
+
+/// A page of the storage of an account, as returned by `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// The storage slots of the page, keyed by the hash of the slot.
+    pub storage: BTreeMap<B256, StorageRangeSlot>,
+    /// Hashed key of the first slot of the next page, if there is one.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot returned by `debug_storageRangeAt`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeSlot {
+    /// The slot, if its preimage is known.
+    pub key: Option<B256>,
+    /// The value of the slot.
+    pub value: B256,
+}
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -35,18 +69,27 @@ pub trait DebugApi {
     #[method(name = "getRawReceipts")]
     async fn raw_receipts(&self, block_id: BlockId) -> RpcResult<Vec<Bytes>>;
 
-    /// Returns an array of recent bad blocks that the client has seen on the network.
+    /// Returns an array of recent bad blocks that the client has seen on the network, most recent
+    /// first.
     #[method(name = "getBadBlocks")]
-    async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock>>;
 
     /// Returns the structured logs created during the execution of EVM between two blocks
     /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
+    ///
+    /// The traces of each block are sent to the subscription in order, which ends once the last
+    /// block was traced.
+    #[subscription(
+        name = "traceChain",
+        unsubscribe = "traceChain_unsubscribe",
+        item = alloy_rpc_types_trace::geth::BlockTraceResult
+    )]
     async fn debug_trace_chain(
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -259,17 +302,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -282,7 +326,7 @@ pub trait DebugApi {
         &self,
         block_hash: B256,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -335,13 +379,16 @@ pub trait DebugApi {
     #[method(name = "stacks")]
     async fn debug_stacks(&self) -> RpcResult<()>;
 
-    /// Used to obtain info about a block.
+    /// Traces all transactions of a block which has been rejected as invalid, writing the trace
+    /// of each transaction to a file.
+    ///
+    /// Returns the paths of the written files.
     #[method(name = "standardTraceBadBlockToFile")]
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>>;
 
     /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
     /// about a block which has been rejected as invalid (for some reason).
@@ -388,7 +435,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -397,8 +444,8 @@ pub trait DebugApi {
     async fn debug_trace_bad_block(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
 
     /// Sets the logging verbosity ceiling. Log messages with level up to and including the given
     /// level will be printed.
@@ -16,6 +16,7 @@
 
 mod admin;
 mod anvil;
+mod bridge;
 mod debug;
 mod engine;
 mod ganache;
@@ -31,6 +32,9 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use debug::{BadBlock, StorageRangeResult, StorageRangeSlot};
+pub use validation::BuilderBlockValidationRequestV2;
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -38,8 +42,11 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::AnvilApiServer,
+        bridge::BridgeInboxApiServer,
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
+        hardhat::HardhatApiServer,
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
@@ -67,6 +74,7 @@ pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
         anvil::AnvilApiClient,
+        bridge::BridgeInboxApiClient,
         debug::{DebugApiClient, DebugExecutionWitnessApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
         ganache::GanacheApiClient,
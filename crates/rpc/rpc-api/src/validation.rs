@@ -1,10 +1,30 @@
 //! API for block submission validation.
 
+use alloy_primitives::B256;
 use alloy_rpc_types_beacon::relay::{
-    BuilderBlockValidationRequest, BuilderBlockValidationRequestV2,
-    BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
+    BuilderBlockValidationRequest, BuilderBlockValidationRequestV3,
+    BuilderBlockValidationRequestV4, SignedBidSubmissionV2,
 };
 use jsonrpsee::proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+
+/// A Request to validate a [`SignedBidSubmissionV2`].
+///
+/// In addition to the fields of the upstream request, the builder submits the withdrawals root
+/// it committed to, which must match the withdrawals of the payload.
+///
+/// <https://github.com/flashbots/builder/blob/03ee71cf0a344397204f65ff6d3a917ee8e06724/eth/block-validation/api.go#L204-L208>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuilderBlockValidationRequestV2 {
+    /// The request to be validated.
+    #[serde(flatten)]
+    pub request: SignedBidSubmissionV2,
+    /// The registered gas limit for the validation request.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub registered_gas_limit: u64,
+    /// The withdrawals root of the submitted block.
+    pub withdrawals_root: B256,
+}
 
 /// Block validation rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "flashbots"))]
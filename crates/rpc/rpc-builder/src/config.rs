@@ -3,7 +3,7 @@ use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_p
 use reth_rpc::ValidationApiConfig;
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
 use reth_rpc_layer::{JwtError, JwtSecret};
-use reth_rpc_server_types::RpcModuleSelection;
+use reth_rpc_server_types::{RpcMethodFilter, RpcModuleSelection};
 use std::{net::SocketAddr, path::PathBuf};
 use tower::layer::util::Identity;
 use tracing::{debug, warn};
@@ -138,23 +138,40 @@ impl RethRpcServerConfig for RpcServerArgs {
             .with_config(RpcModuleConfig::new(self.eth_config(), self.flashbots_config()));
 
         if self.http {
-            config = config.with_http(
-                self.http_api
-                    .clone()
-                    .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
-            );
+            config = config
+                .with_http(
+                    self.http_api
+                        .clone()
+                        .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
+                )
+                .with_http_method_filter(
+                    RpcMethodFilter::default()
+                        .with_allowed(self.http_api_allow.iter().cloned())
+                        .with_denied(self.http_api_deny.iter().cloned()),
+                );
         }
 
         if self.ws {
-            config = config.with_ws(
-                self.ws_api
-                    .clone()
-                    .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
-            );
+            config = config
+                .with_ws(
+                    self.ws_api
+                        .clone()
+                        .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
+                )
+                .with_ws_method_filter(
+                    RpcMethodFilter::default()
+                        .with_allowed(self.ws_api_allow.iter().cloned())
+                        .with_denied(self.ws_api_deny.iter().cloned()),
+                );
         }
 
         if self.is_ipc_enabled() {
-            config = config.with_ipc(RpcModuleSelection::default_ipc_modules());
+            config =
+                config.with_ipc(RpcModuleSelection::default_ipc_modules()).with_ipc_method_filter(
+                    RpcMethodFilter::default()
+                        .with_allowed(self.ipc_api_allow.iter().cloned())
+                        .with_denied(self.ipc_api_deny.iter().cloned()),
+                );
         }
 
         config
@@ -177,7 +194,9 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_rate_limit(self.rate_limit.rate_limit_config());
 
         if self.http_api.is_some() && !self.http {
             warn!(
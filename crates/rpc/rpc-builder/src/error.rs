@@ -127,6 +127,11 @@ pub enum WsHttpSamePortError {
     /// Ws and http server configured on same port but with different modules.
     #[error("{0}")]
     ConflictingModules(Box<ConflictingModules>),
+    /// Ws and http server configured on same port but with different method filters.
+    #[error(
+        "allowed and denied methods for HTTP and WS are different, but they are on the same port"
+    )]
+    ConflictingMethodFilters,
 }
 
 #[cfg(test)]
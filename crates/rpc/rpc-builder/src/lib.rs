@@ -44,13 +44,15 @@ use reth_rpc::{
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt},
-    EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcHeader, RpcNodeCore, RpcReceipt,
+    RpcTransaction,
 };
 use reth_rpc_eth_types::{EthConfig, EthSubscriptionIdProvider};
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 use reth_storage_api::{
-    AccountReader, BlockReader, BlockReaderIdExt, ChangeSetReader, FullRpcProvider, ProviderBlock,
-    StateProviderFactory,
+    AccountReader, AddressAppearancesReader, BlockReader, BlockReaderIdExt, ChangeSetReader,
+    FullRpcProvider, ModifiedAccountsReader, ProviderBlock, StateProviderFactory,
+    StorageRangeProvider, TraceIndexReader,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, PoolTransaction, TransactionPool};
@@ -73,7 +75,7 @@ use jsonrpsee::server::ServerConfigBuilder;
 pub use reth_ipc::server::{
     Builder as IpcServerBuilder, RpcServiceBuilder as IpcRpcServiceBuilder,
 };
-pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+pub use reth_rpc_server_types::{constants, RethRpcModule, RpcMethodFilter, RpcModuleSelection};
 pub use tower::layer::util::{Identity, Stack};
 
 /// Auth server utilities.
@@ -100,6 +102,10 @@ use reth_rpc::eth::sim_bundle::EthSimBundle;
 
 // Rpc rate limiter
 pub mod rate_limiter;
+use rate_limiter::{
+    RpcClientLayer, RpcClientRateLimitLayer, RpcClientRateLimitService, RpcClientRateLimiter,
+};
+use reth_rpc_server_types::RateLimitConfig;
 
 /// Convenience function for starting a server in one step.
 #[expect(clippy::too_many_arguments)]
@@ -415,7 +421,7 @@ where
         let Self { provider, pool, network, executor, consensus, evm_config, .. } = self;
 
         if !module_config.is_empty() {
-            let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
+            let TransportRpcModuleConfig { http, ws, ipc, config, .. } = module_config.clone();
 
             let mut registry = RpcRegistryInner::new(
                 provider,
@@ -432,6 +438,7 @@ where
             modules.http = registry.maybe_module(http.as_ref());
             modules.ws = registry.maybe_module(ws.as_ref());
             modules.ipc = registry.maybe_module(ipc.as_ref());
+            modules.apply_method_filters();
         }
 
         modules
@@ -723,7 +730,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn register_ots(&mut self) -> &mut Self
     where
-        EthApi: TraceExt + EthTransactions,
+        EthApi: TraceExt + EthTransactions + RpcNodeCore<Provider: AddressAppearancesReader>,
     {
         let otterscan_api = self.otterscan_api();
         self.modules.insert(RethRpcModule::Ots, otterscan_api.into_rpc().into());
@@ -737,7 +744,10 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn register_debug(&mut self) -> &mut Self
     where
-        EthApi: EthApiSpec + EthTransactions + TraceExt,
+        EthApi: EthApiSpec
+            + EthTransactions
+            + TraceExt
+            + RpcNodeCore<Provider: ModifiedAccountsReader + StorageRangeProvider>,
         EvmConfig::Primitives: NodePrimitives<Block = ProviderBlock<EthApi::Provider>>,
     {
         let debug_api = self.debug_api();
@@ -752,7 +762,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn register_trace(&mut self) -> &mut Self
     where
-        EthApi: TraceExt,
+        EthApi: TraceExt + RpcNodeCore<Provider: TraceIndexReader>,
     {
         let trace_api = self.trace_api();
         self.modules.insert(RethRpcModule::Trace, trace_api.into_rpc().into());
@@ -821,7 +831,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn trace_api(&self) -> TraceApi<EthApi>
     where
-        EthApi: TraceExt,
+        EthApi: TraceExt + RpcNodeCore<Provider: TraceIndexReader>,
     {
         TraceApi::new(self.eth_api().clone(), self.blocking_pool_guard.clone(), self.eth_config)
     }
@@ -927,6 +937,7 @@ where
         modules.http = http;
         modules.ws = ws;
         modules.ipc = ipc;
+        modules.apply_method_filters();
         modules
     }
 
@@ -983,13 +994,10 @@ where
                             module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
                             module
-                                .merge(
-                                    EthBundle::new(
-                                        eth_api.clone(),
-                                        self.blocking_pool_guard.clone(),
-                                    )
-                                    .into_rpc(),
-                                )
+                                .merge(EthBundleApiServer::into_rpc(EthBundle::new(
+                                    eth_api.clone(),
+                                    self.blocking_pool_guard.clone(),
+                                )))
                                 .expect("No conflicts");
 
                             module.into()
@@ -1035,6 +1043,12 @@ where
                                 .into_rpc()
                                 .into()
                         }
+                        // only relevant for dev nodes and configured in `EthereumAddOns`
+                        // implementation
+                        RethRpcModule::Bridge |
+                        RethRpcModule::BridgeInbox |
+                        RethRpcModule::Anvil |
+                        RethRpcModule::Hardhat => Default::default(),
                     })
                     .clone()
             })
@@ -1077,6 +1091,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     jwt_secret: Option<JwtSecret>,
     /// Configurable RPC middleware
     rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
+    /// Per-client rate limits of the http and ws servers
+    rate_limiter: Option<RpcClientRateLimiter>,
 }
 
 // === impl RpcServerConfig ===
@@ -1096,6 +1112,7 @@ impl Default for RpcServerConfig<Identity> {
             ipc_endpoint: None,
             jwt_secret: None,
             rpc_middleware: RpcServiceBuilder::new(),
+            rate_limiter: None,
         }
     }
 }
@@ -1160,6 +1177,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
             rpc_middleware,
+            rate_limiter: self.rate_limiter,
         }
     }
 
@@ -1238,6 +1256,14 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the per-client rate limits of the http and ws servers.
+    ///
+    /// The ipc server is not rate limited.
+    pub fn with_rate_limit(mut self, config: Option<RateLimitConfig>) -> Self {
+        self.rate_limiter = config.map(RpcClientRateLimiter::new);
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1272,6 +1298,14 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Returns an [`RpcClientLayer`] that identifies the client of each request if rate limits are
+    /// configured.
+    fn maybe_rate_limit_client_layer(
+        rate_limiter: Option<&RpcClientRateLimiter>,
+    ) -> Option<RpcClientLayer> {
+        rate_limiter.map(RpcClientRateLimiter::client_layer)
+    }
+
     /// Returns a [`CompressionLayer`] that adds compression support (gzip, deflate, brotli, zstd)
     /// based on the client's `Accept-Encoding` header
     fn maybe_compression_layer(disable_compression: bool) -> Option<CompressionLayer> {
@@ -1289,8 +1323,13 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
     /// Returns the [`RpcServerHandle`] with the handle to the started servers.
     pub async fn start(self, modules: &TransportRpcModules) -> Result<RpcServerHandle, RpcError>
     where
-        RpcMiddleware: Layer<RpcRequestMetricsService<RpcService>> + Clone + Send + 'static,
-        for<'a> <RpcMiddleware as Layer<RpcRequestMetricsService<RpcService>>>::Service:
+        RpcMiddleware: Layer<RpcRequestMetricsService<RpcClientRateLimitService<RpcService>>>
+            + Clone
+            + Send
+            + 'static,
+        for<'a> <RpcMiddleware as Layer<
+            RpcRequestMetricsService<RpcClientRateLimitService<RpcService>>,
+        >>::Service:
             Send
                 + Sync
                 + 'static
@@ -1354,19 +1393,25 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                         tower::ServiceBuilder::new()
                             .option_layer(Self::maybe_cors_layer(cors)?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(Self::maybe_rate_limit_client_layer(
+                                self.rate_limiter.as_ref(),
+                            ))
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
                             )),
                     )
                     .set_rpc_middleware(
-                        self.rpc_middleware.clone().layer(
-                            modules
-                                .http
-                                .as_ref()
-                                .or(modules.ws.as_ref())
-                                .map(RpcRequestMetrics::same_port)
-                                .unwrap_or_default(),
-                        ),
+                        self.rpc_middleware
+                            .clone()
+                            .layer(
+                                modules
+                                    .http
+                                    .as_ref()
+                                    .or(modules.ws.as_ref())
+                                    .map(RpcRequestMetrics::same_port)
+                                    .unwrap_or_default(),
+                            )
+                            .layer(RpcClientRateLimitLayer::new(self.rate_limiter.clone())),
                     )
                     .set_config(config.build())
                     .build(http_socket_addr)
@@ -1405,12 +1450,16 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_rate_limit_client_layer(
+                            self.rate_limiter.as_ref(),
+                        )),
                 )
                 .set_rpc_middleware(
                     self.rpc_middleware
                         .clone()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
+                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(RpcClientRateLimitLayer::new(self.rate_limiter.clone())),
                 )
                 .build(ws_socket_addr)
                 .await
@@ -1431,12 +1480,18 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_rate_limit_client_layer(
+                            self.rate_limiter.as_ref(),
+                        ))
                         .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
                 )
                 .set_rpc_middleware(
-                    self.rpc_middleware.clone().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
+                    self.rpc_middleware
+                        .clone()
+                        .layer(
+                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                        )
+                        .layer(RpcClientRateLimitLayer::new(self.rate_limiter.clone())),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1475,6 +1530,17 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
 /// let config =
 ///     TransportRpcModuleConfig::default().with_http([RethRpcModule::Eth, RethRpcModule::Admin]);
 /// ```
+///
+/// Expose only the tracing methods of the `debug` namespace over http
+///
+/// ```
+/// use reth_rpc_builder::{RethRpcModule, RpcMethodFilter, TransportRpcModuleConfig};
+/// let config = TransportRpcModuleConfig::default()
+///     .with_http([RethRpcModule::Eth, RethRpcModule::Debug])
+///     .with_http_method_filter(
+///         RpcMethodFilter::default().with_allowed(["debug_traceTransaction", "debug_traceCall"]),
+///     );
+/// ```
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct TransportRpcModuleConfig {
     /// http module configuration
@@ -1485,6 +1551,12 @@ pub struct TransportRpcModuleConfig {
     ipc: Option<RpcModuleSelection>,
     /// Config for the modules
     config: Option<RpcModuleConfig>,
+    /// Methods exposed on http
+    http_method_filter: RpcMethodFilter,
+    /// Methods exposed on ws
+    ws_method_filter: RpcMethodFilter,
+    /// Methods exposed on ipc
+    ipc_method_filter: RpcMethodFilter,
 }
 
 // === impl TransportRpcModuleConfig ===
@@ -1529,6 +1601,24 @@ impl TransportRpcModuleConfig {
         self
     }
 
+    /// Sets the [`RpcMethodFilter`] for the http transport.
+    pub fn with_http_method_filter(mut self, filter: RpcMethodFilter) -> Self {
+        self.http_method_filter = filter;
+        self
+    }
+
+    /// Sets the [`RpcMethodFilter`] for the ws transport.
+    pub fn with_ws_method_filter(mut self, filter: RpcMethodFilter) -> Self {
+        self.ws_method_filter = filter;
+        self
+    }
+
+    /// Sets the [`RpcMethodFilter`] for the ipc transport.
+    pub fn with_ipc_method_filter(mut self, filter: RpcMethodFilter) -> Self {
+        self.ipc_method_filter = filter;
+        self
+    }
+
     /// Get a mutable reference to the
     pub const fn http_mut(&mut self) -> &mut Option<RpcModuleSelection> {
         &mut self.http
@@ -1574,6 +1664,21 @@ impl TransportRpcModuleConfig {
         self.config.as_ref()
     }
 
+    /// Returns the [`RpcMethodFilter`] for the http transport
+    pub const fn http_method_filter(&self) -> &RpcMethodFilter {
+        &self.http_method_filter
+    }
+
+    /// Returns the [`RpcMethodFilter`] for the ws transport
+    pub const fn ws_method_filter(&self) -> &RpcMethodFilter {
+        &self.ws_method_filter
+    }
+
+    /// Returns the [`RpcMethodFilter`] for the ipc transport
+    pub const fn ipc_method_filter(&self) -> &RpcMethodFilter {
+        &self.ipc_method_filter
+    }
+
     /// Returns true if the given module is configured for any transport.
     pub fn contains_any(&self, module: &RethRpcModule) -> bool {
         self.contains_http(module) || self.contains_ws(module) || self.contains_ipc(module)
@@ -1597,6 +1702,10 @@ impl TransportRpcModuleConfig {
     /// Ensures that both http and ws are configured and that they are configured to use the same
     /// port.
     fn ensure_ws_http_identical(&self) -> Result<(), WsHttpSamePortError> {
+        if self.http_method_filter != self.ws_method_filter {
+            return Err(WsHttpSamePortError::ConflictingMethodFilters)
+        }
+
         if RpcModuleSelection::are_identical(self.http.as_ref(), self.ws.as_ref()) {
             Ok(())
         } else {
@@ -1667,6 +1776,30 @@ impl TransportRpcModules {
         &self.config
     }
 
+    /// Removes the methods that the [`RpcMethodFilter`]s of the config don't expose from the
+    /// installed modules.
+    fn apply_method_filters(&mut self) {
+        let TransportRpcModuleConfig {
+            http_method_filter,
+            ws_method_filter,
+            ipc_method_filter,
+            ..
+        } = &self.config;
+        for (module, filter) in [
+            (&mut self.http, http_method_filter),
+            (&mut self.ws, ws_method_filter),
+            (&mut self.ipc, ipc_method_filter),
+        ] {
+            if let Some(module) = module {
+                let denied: Vec<_> =
+                    module.method_names().filter(|name| !filter.is_allowed(name)).collect();
+                for name in denied {
+                    module.remove_method(name);
+                }
+            }
+        }
+    }
+
     /// Merge the given [`Methods`] in all configured transport modules if the given
     /// [`RethRpcModule`] is configured for the transport.
     ///
@@ -1697,7 +1830,8 @@ impl TransportRpcModules {
     /// Returns [Ok(false)] if no http transport is configured.
     pub fn merge_http(&mut self, other: impl Into<Methods>) -> Result<bool, RegisterMethodError> {
         if let Some(ref mut http) = self.http {
-            return http.merge(other.into()).map(|_| true)
+            let other = allowed_methods(other.into(), &self.config.http_method_filter);
+            return http.merge(other).map(|_| true)
         }
         Ok(false)
     }
@@ -1709,7 +1843,8 @@ impl TransportRpcModules {
     /// Returns [Ok(false)] if no ws transport is configured.
     pub fn merge_ws(&mut self, other: impl Into<Methods>) -> Result<bool, RegisterMethodError> {
         if let Some(ref mut ws) = self.ws {
-            return ws.merge(other.into()).map(|_| true)
+            let other = allowed_methods(other.into(), &self.config.ws_method_filter);
+            return ws.merge(other).map(|_| true)
         }
         Ok(false)
     }
@@ -1721,7 +1856,8 @@ impl TransportRpcModules {
     /// Returns [Ok(false)] if no ipc transport is configured.
     pub fn merge_ipc(&mut self, other: impl Into<Methods>) -> Result<bool, RegisterMethodError> {
         if let Some(ref mut ipc) = self.ipc {
-            return ipc.merge(other.into()).map(|_| true)
+            let other = allowed_methods(other.into(), &self.config.ipc_method_filter);
+            return ipc.merge(other).map(|_| true)
         }
         Ok(false)
     }
@@ -1943,9 +2079,47 @@ impl TransportRpcModules {
         self.replace_ipc(other)?;
         Ok(true)
     }
+
+    /// Replaces the given [`Methods`] in all configured transport modules if the given
+    /// [`RethRpcModule`] is configured for the transport.
+    ///
+    /// Methods that are already installed are removed before the new methods are merged.
+    pub fn replace_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.module_config().contains_http(&module) {
+            self.replace_http(other.clone())?;
+        }
+        if self.module_config().contains_ws(&module) {
+            self.replace_ws(other.clone())?;
+        }
+        if self.module_config().contains_ipc(&module) {
+            self.replace_ipc(other)?;
+        }
+
+        Ok(())
+    }
 }
 
 /// Returns the methods installed in the given module that match the given filter.
+/// Returns the given methods without the methods that the filter doesn't expose.
+fn allowed_methods(methods: Methods, filter: &RpcMethodFilter) -> Methods {
+    if filter.is_empty() {
+        return methods
+    }
+
+    let mut allowed = Methods::new();
+    for name in methods.method_names().filter(|name| filter.is_allowed(name)) {
+        if let Some(method) = methods.method(name).cloned() {
+            let _ = allowed.verify_and_insert(name, method);
+        }
+    }
+    allowed
+}
+
 fn methods_by<T, F>(module: &RpcModule<T>, mut filter: F) -> Methods
 where
     F: FnMut(&str) -> bool,
@@ -2263,6 +2437,7 @@ mod tests {
                 ws: None,
                 ipc: None,
                 config: None,
+                ..Default::default()
             }
         )
     }
@@ -2277,6 +2452,7 @@ mod tests {
                 ws: None,
                 ipc: None,
                 config: None,
+                ..Default::default()
             }
         )
     }
@@ -2287,6 +2463,38 @@ mod tests {
         module
     }
 
+    #[test]
+    fn test_method_filters() {
+        let config = TransportRpcModuleConfig::default().with_http_method_filter(
+            RpcMethodFilter::default().with_allowed(["debug_traceTransaction"]),
+        );
+        let mut module = RpcModule::new(());
+        module.register_method("debug_traceTransaction", |_, _, _| "succeed").unwrap();
+        module.register_method("debug_setHead", |_, _, _| "succeed").unwrap();
+        let mut modules = TransportRpcModules {
+            config,
+            http: Some(module.clone()),
+            ws: Some(module),
+            ..Default::default()
+        };
+        modules.apply_method_filters();
+
+        let http = modules.http.as_ref().unwrap();
+        assert!(http.method("debug_traceTransaction").is_some());
+        assert!(http.method("debug_setHead").is_none());
+        assert!(modules.ws.as_ref().unwrap().method("debug_setHead").is_some());
+
+        // methods merged later are filtered as well
+        let mut other = RpcModule::new(());
+        other.register_method("debug_getRawBlock", |_, _, _| "succeed").unwrap();
+        other.register_method("eth_chainId", |_, _, _| "succeed").unwrap();
+        modules.merge_http(other).unwrap();
+
+        let http = modules.http.as_ref().unwrap();
+        assert!(http.method("debug_getRawBlock").is_none());
+        assert!(http.method("eth_chainId").is_some());
+    }
+
     #[test]
     fn test_remove_http_method() {
         let mut modules =
@@ -1,15 +1,34 @@
-//! [`jsonrpsee`] helper layer for rate limiting certain methods.
+//! [`jsonrpsee`] helper layers for rate limiting certain methods and clients.
 
-use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request};
+use http::HeaderMap;
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObjectOwned, Request},
+    MethodResponse,
+};
+use parking_lot::Mutex;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_rpc_server_types::{
+    rate_limit::RATE_LIMIT_EXCEEDED_CODE, RateLimitConfig, RateLimitQuota, RpcMethodGroup,
+};
+use serde::Serialize;
 use std::{
+    collections::HashMap,
     future::Future,
+    net::IpAddr,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
+    time::{Duration, Instant},
 };
+use strum::VariantArray;
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio_util::sync::PollSemaphore;
-use tower::Layer;
+use tower::{Layer, Service};
+
+/// Number of token buckets after which the buckets that are full again are dropped.
+const MAX_TRACKED_BUCKETS: usize = 100_000;
 
 /// Rate limiter for the RPC server.
 ///
@@ -130,3 +149,389 @@ impl<F: Future> Future for RateLimitingRequestFuture<F> {
         res
     }
 }
+
+/// Rate limiter for the HTTP and WS RPC servers that enforces the token bucket quotas of a
+/// [`RateLimitConfig`].
+///
+/// Requests are counted per client and per [`RpcMethodGroup`]. The client of a request is
+/// identified by the HTTP layer returned from [`Self::client_layer`], requests that weren't
+/// identified count towards the quota of the anonymous client.
+#[derive(Debug, Clone)]
+pub struct RpcClientRateLimiter {
+    inner: Arc<RpcClientRateLimiterInner>,
+}
+
+impl RpcClientRateLimiter {
+    /// Creates a new rate limiter with the given configuration.
+    pub fn new(config: RateLimitConfig) -> Self {
+        let names = config.api_keys.keys().map(|name| Arc::<str>::from(name.as_str()));
+        let clients = names.clone().map(Some).chain([None]);
+        let metrics = clients
+            .flat_map(|client| {
+                RpcMethodGroup::VARIANTS.iter().map(move |group| {
+                    let label = client.as_deref().unwrap_or("anonymous").to_string();
+                    let metrics = RpcRateLimitMetrics::new_with_labels(&[
+                        ("client", label),
+                        ("group", group.as_ref().to_string()),
+                    ]);
+                    ((client.clone(), *group), metrics)
+                })
+            })
+            .collect();
+        let api_keys = config
+            .api_keys
+            .iter()
+            .zip(names)
+            .map(|((_, api_key), name)| (api_key.key.clone(), name));
+
+        Self {
+            inner: Arc::new(RpcClientRateLimiterInner {
+                api_keys: api_keys.collect(),
+                config,
+                buckets: Default::default(),
+                metrics,
+            }),
+        }
+    }
+
+    /// Returns the HTTP layer that identifies the client of each request.
+    pub fn client_layer(&self) -> RpcClientLayer {
+        RpcClientLayer { limiter: self.clone() }
+    }
+
+    /// Identifies the client by the API key header, or by the headers of the reverse proxy if
+    /// they are trusted.
+    fn identify(&self, headers: &HeaderMap) -> RpcClient {
+        let config = &self.inner.config;
+        let api_key = headers.get(config.api_key_header.as_str()).and_then(|key| key.to_str().ok());
+        if let Some(name) = api_key.and_then(|key| self.inner.api_keys.get(key)) {
+            return RpcClient::ApiKey(name.clone())
+        }
+
+        if config.trust_forwarded_for {
+            let forwarded_for = headers
+                .get("x-forwarded-for")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(',').next());
+            let real_ip = headers.get("x-real-ip").and_then(|value| value.to_str().ok());
+            if let Some(ip) = forwarded_for.or(real_ip).and_then(|ip| ip.trim().parse().ok()) {
+                return RpcClient::Ip(ip)
+            }
+        }
+
+        RpcClient::Anonymous
+    }
+
+    /// Takes a token from the bucket of the client and method group of the request.
+    ///
+    /// Returns the error that is sent to the client if the bucket is empty.
+    fn acquire(&self, req: &Request<'_>) -> Result<(), ErrorObjectOwned> {
+        let group = RpcMethodGroup::of(req.method_name());
+        let client = req.extensions().get::<RpcClient>().cloned().unwrap_or(RpcClient::Anonymous);
+        let name = match &client {
+            RpcClient::ApiKey(name) => Some(name.clone()),
+            RpcClient::Ip(_) | RpcClient::Anonymous => None,
+        };
+        let Some(quota) = self.inner.config.quota(name.as_deref(), group) else { return Ok(()) };
+
+        let now = Instant::now();
+        let result = {
+            let mut buckets = self.inner.buckets.lock();
+            if buckets.len() >= MAX_TRACKED_BUCKETS &&
+                !buckets.contains_key(&(client.clone(), group))
+            {
+                // Clients whose buckets are full again are indistinguishable from new clients.
+                buckets.retain(|(client, group), bucket| {
+                    let name = match client {
+                        RpcClient::ApiKey(name) => Some(&**name),
+                        RpcClient::Ip(_) | RpcClient::Anonymous => None,
+                    };
+                    self.inner
+                        .config
+                        .quota(name, *group)
+                        .is_some_and(|quota| !bucket.is_full(quota, now))
+                });
+            }
+            buckets
+                .entry((client, group))
+                .or_insert_with(|| TokenBucket::full(quota, now))
+                .try_take(quota, now)
+        };
+
+        let metrics = self.inner.metrics.get(&(name, group));
+        match result {
+            Ok(()) => {
+                if let Some(metrics) = metrics {
+                    metrics.allowed_total.increment(1);
+                }
+                Ok(())
+            }
+            Err(retry_after) => {
+                if let Some(metrics) = metrics {
+                    metrics.limited_total.increment(1);
+                }
+                Err(ErrorObjectOwned::owned(
+                    RATE_LIMIT_EXCEEDED_CODE,
+                    format!("rate limit exceeded for {} requests", group.as_ref()),
+                    Some(RateLimitExceededData {
+                        group,
+                        retry_after_ms: retry_after.as_millis() as u64,
+                    }),
+                ))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RpcClientRateLimiterInner {
+    config: RateLimitConfig,
+    /// The names of the clients by their API key.
+    api_keys: HashMap<String, Arc<str>>,
+    /// The token buckets by client and method group.
+    buckets: Mutex<HashMap<(RpcClient, RpcMethodGroup), TokenBucket>>,
+    /// Metrics by the name of the client and method group. Clients without an API key share the
+    /// metrics of the anonymous client.
+    metrics: HashMap<(Option<Arc<str>>, RpcMethodGroup), RpcRateLimitMetrics>,
+}
+
+/// The client of a request, as identified by [`RpcClientLayer`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum RpcClient {
+    /// A client with a known API key, by its name.
+    ApiKey(Arc<str>),
+    /// A client without a known API key, by the IP reported by the reverse proxy.
+    Ip(IpAddr),
+    /// Any other client.
+    Anonymous,
+}
+
+/// The `data` of the error returned to clients that exhausted their quota.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RateLimitExceededData {
+    /// The method group whose quota was exhausted.
+    group: RpcMethodGroup,
+    /// Milliseconds until the next request of the group is accepted.
+    retry_after_ms: u64,
+}
+
+/// A token bucket that holds up to `burst` tokens and is refilled with `per_second` tokens per
+/// second.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket.
+    fn full(quota: RateLimitQuota, now: Instant) -> Self {
+        Self { tokens: quota.burst.get() as f64, refilled_at: now }
+    }
+
+    fn refill(&mut self, quota: RateLimitQuota, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * quota.per_second.get() as f64).min(quota.burst.get() as f64);
+        self.refilled_at = now;
+    }
+
+    /// Returns `true` if the bucket is full at the given time.
+    fn is_full(&mut self, quota: RateLimitQuota, now: Instant) -> bool {
+        self.refill(quota, now);
+        self.tokens >= quota.burst.get() as f64
+    }
+
+    /// Takes a token from the bucket, or returns the time until the next token is available.
+    fn try_take(&mut self, quota: RateLimitQuota, now: Instant) -> Result<(), Duration> {
+        self.refill(quota, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / quota.per_second.get() as f64))
+        }
+    }
+}
+
+/// An HTTP middleware layer that identifies the client of each request for the
+/// [`RpcClientRateLimiter`].
+#[derive(Debug, Clone)]
+pub struct RpcClientLayer {
+    limiter: RpcClientRateLimiter,
+}
+
+impl<S> Layer<S> for RpcClientLayer {
+    type Service = RpcClientService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientService { limiter: self.limiter.clone(), inner }
+    }
+}
+
+/// An HTTP middleware that adds the client of the request to the request extensions, which are
+/// passed on to the RPC middleware.
+#[derive(Debug, Clone)]
+pub struct RpcClientService<S> {
+    limiter: RpcClientRateLimiter,
+    inner: S,
+}
+
+impl<S, B> Service<http::Request<B>> for RpcClientService<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let client = self.limiter.identify(req.headers());
+        req.extensions_mut().insert(client);
+        self.inner.call(req)
+    }
+}
+
+/// An RPC middleware layer that rejects requests of clients that exhausted their quota.
+///
+/// If no [`RpcClientRateLimiter`] is configured, requests are passed through.
+#[derive(Debug, Clone, Default)]
+pub struct RpcClientRateLimitLayer {
+    limiter: Option<RpcClientRateLimiter>,
+}
+
+impl RpcClientRateLimitLayer {
+    /// Creates a new layer with the given rate limiter.
+    pub const fn new(limiter: Option<RpcClientRateLimiter>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RpcClientRateLimitLayer {
+    type Service = RpcClientRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientRateLimitService { limiter: self.limiter.clone(), inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that rejects requests of clients that exhausted their quota with a
+/// [`RATE_LIMIT_EXCEEDED_CODE`] error.
+#[derive(Debug, Clone)]
+pub struct RpcClientRateLimitService<S> {
+    limiter: Option<RpcClientRateLimiter>,
+    inner: S,
+}
+
+impl<S> RpcServiceT for RpcClientRateLimitService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let acquired = self.limiter.as_ref().map_or(Ok(()), |limiter| limiter.acquire(&req));
+        let call = match acquired {
+            Ok(()) => Ok(self.inner.call(req)),
+            Err(err) => Err(MethodResponse::error(req.id, err)),
+        };
+        async move {
+            match call {
+                Ok(fut) => fut.await,
+                Err(response) => response,
+            }
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut batch: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        if let Some(limiter) = &self.limiter {
+            for entry in batch.iter_mut() {
+                let Ok(BatchEntry::Call(req)) = entry else { continue };
+                let Err(err) = limiter.acquire(req) else { continue };
+                let id = req.id.clone();
+                *entry = Err(BatchEntryErr::new(id, err));
+            }
+        }
+        self.inner.batch(batch)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Rate limit metrics per client and method group.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.rate_limit")]
+struct RpcRateLimitMetrics {
+    /// The number of requests within the quota
+    allowed_total: Counter,
+    /// The number of requests rejected because the quota was exhausted
+    limited_total: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_server_types::ApiKeyQuotas;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn token_bucket() {
+        let quota: RateLimitQuota = "2:4".parse().unwrap();
+        let now = Instant::now();
+        let mut bucket = TokenBucket::full(quota, now);
+
+        for _ in 0..4 {
+            assert_eq!(bucket.try_take(quota, now), Ok(()));
+        }
+        assert_eq!(bucket.try_take(quota, now), Err(Duration::from_millis(500)));
+
+        // half a second refills a single token
+        let now = now + Duration::from_millis(500);
+        assert_eq!(bucket.try_take(quota, now), Ok(()));
+        assert!(bucket.try_take(quota, now).is_err());
+
+        // the bucket doesn't hold more than the burst
+        let now = now + Duration::from_secs(10);
+        assert!(bucket.is_full(quota, now));
+        assert_eq!(bucket.tokens, 4.0);
+    }
+
+    #[test]
+    fn identify_client() {
+        let limiter = RpcClientRateLimiter::new(RateLimitConfig {
+            api_keys: BTreeMap::from([(
+                "relayer".to_string(),
+                ApiKeyQuotas { key: "secret".to_string(), quotas: BTreeMap::new() },
+            )]),
+            ..Default::default()
+        });
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "10.0.0.1, 10.0.0.2".parse().unwrap());
+        assert_eq!(limiter.identify(&headers), RpcClient::Anonymous);
+
+        headers.insert("x-api-key", "secret".parse().unwrap());
+        assert_eq!(limiter.identify(&headers), RpcClient::ApiKey("relayer".into()));
+
+        let limiter = RpcClientRateLimiter::new(RateLimitConfig {
+            trust_forwarded_for: true,
+            ..Default::default()
+        });
+        assert_eq!(limiter.identify(&headers), RpcClient::Ip("10.0.0.1".parse().unwrap()));
+    }
+}
@@ -478,28 +478,23 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_before(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_after(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    // the noop provider has no address appearances index
+    OtterscanClient::<Transaction, Header>::search_transactions_before(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap_err();
+    OtterscanClient::<Transaction, Header>::search_transactions_after(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap_err();
     assert!(OtterscanClient::<Transaction, Header>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
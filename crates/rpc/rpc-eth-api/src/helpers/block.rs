@@ -12,7 +12,8 @@ use alloy_rpc_types_eth::{Block, BlockTransactions, Header, Index};
 use futures::Future;
 use reth_evm::ConfigureEvm;
 use reth_node_api::BlockBody;
-use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedBlock};
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock};
+use reth_rpc_eth_types::EthApiError;
 use reth_rpc_types_compat::block::from_block;
 use reth_storage_api::{BlockIdReader, BlockReader, ProviderHeader, ProviderReceipt, ProviderTx};
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
@@ -23,7 +24,7 @@ pub type BlockReceiptsResult<N, E> = Result<Option<Vec<RpcReceipt<N>>>, E>;
 /// Result type of the fetched block and its receipts.
 pub type BlockAndReceiptsResult<Eth> = Result<
     Option<(
-        SealedBlock<<<Eth as RpcNodeCore>::Provider as BlockReader>::Block>,
+        Arc<RecoveredBlock<<<Eth as RpcNodeCore>::Provider as BlockReader>::Block>>,
         Arc<Vec<ProviderReceipt<<Eth as RpcNodeCore>::Provider>>>,
     )>,
     <Eth as EthApiTypes>::Error,
@@ -128,12 +129,16 @@ pub trait EthBlocks: LoadBlock {
                     .pending_block_and_receipts()
                     .map_err(Self::Error::from_eth_err)?
                 {
-                    return Ok(Some((block, Arc::new(receipts))));
+                    let block = block
+                        .try_recover()
+                        .map_err(|_| EthApiError::InvalidTransactionSignature)
+                        .map_err(Self::Error::from_eth_err)?;
+                    return Ok(Some((Arc::new(block), Arc::new(receipts))));
                 }
 
                 // If no pending block from provider, build the pending block locally.
                 if let Some((block, receipts)) = self.local_pending_block().await? {
-                    return Ok(Some((block.into_sealed_block(), Arc::new(receipts))));
+                    return Ok(Some((Arc::new(block), Arc::new(receipts))));
                 }
             }
 
@@ -145,7 +150,6 @@ pub trait EthBlocks: LoadBlock {
                     .get_block_and_receipts(block_hash)
                     .await
                     .map_err(Self::Error::from_eth_err)
-                    .map(|b| b.map(|(b, r)| (b.clone_sealed_block(), r)))
             }
 
             Ok(None)
@@ -7,7 +7,7 @@ use crate::{
     RpcNodeCore, RpcNodeCoreExt, RpcReceipt, RpcTransaction,
 };
 use alloy_consensus::{
-    transaction::{SignerRecoverable, TransactionMeta},
+    transaction::{Recovered, SignerRecoverable, TransactionMeta},
     BlockHeader, Transaction,
 };
 use alloy_dyn_abi::TypedData;
@@ -482,12 +482,30 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
                     {
                         None => Ok(None),
                         Some((tx, meta)) => {
+                            // Prefer the stored sender, which is cheaper than recovering it and
+                            // the only source for transactions of impersonated accounts on dev
+                            // nodes, which carry a signature that does not recover to their
+                            // sender.
+                            let provider = this.provider();
+                            let sender = match provider
+                                .transaction_id(hash)
+                                .map_err(Self::Error::from_eth_err)?
+                            {
+                                Some(id) => provider
+                                    .transaction_sender(id)
+                                    .map_err(Self::Error::from_eth_err)?,
+                                None => None,
+                            };
+
                             // Note: we assume this transaction is valid, because it's mined (or
                             // part of pending block) and already. We don't need to
                             // check for pre EIP-2 because this transaction could be pre-EIP-2.
-                            let transaction = tx
-                                .try_into_recovered_unchecked()
-                                .map_err(|_| EthApiError::InvalidTransactionSignature)?;
+                            let transaction = match sender {
+                                Some(sender) => Recovered::new_unchecked(tx, sender),
+                                None => tx
+                                    .try_into_recovered_unchecked()
+                                    .map_err(|_| EthApiError::InvalidTransactionSignature)?,
+                            };
 
                             let tx = TransactionSource::Block {
                                 transaction,
@@ -13,6 +13,9 @@ pub mod constants;
 pub mod result;
 
 mod module;
-pub use module::{RethRpcModule, RpcModuleSelection};
+pub use module::{RethRpcModule, RpcMethodFilter, RpcModuleSelection};
+
+pub mod rate_limit;
+pub use rate_limit::{ApiKeyQuotas, RateLimitConfig, RateLimitQuota, RpcMethodGroup};
 
 pub use result::ToRpcResult;
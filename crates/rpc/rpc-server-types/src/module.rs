@@ -229,6 +229,92 @@ impl fmt::Display for RpcModuleSelection {
     }
 }
 
+/// Method-level access control for the methods installed on a transport.
+///
+/// Entries are either method names, e.g. `debug_traceTransaction`, or all methods of a namespace,
+/// e.g. `debug_*`.
+///
+/// A method is exposed if it doesn't match any denied entry and, if there are allowed entries in
+/// the namespace of the method, it matches one of them. Namespaces without allowed entries are
+/// exposed entirely, so that only the namespaces listed in the allow list are restricted.
+///
+/// # Example
+///
+/// Expose the tracing methods of the `debug` namespace, but none of the other `debug` methods.
+///
+/// ```
+/// use reth_rpc_server_types::RpcMethodFilter;
+/// let filter = RpcMethodFilter::default()
+///     .with_allowed(["debug_traceTransaction", "debug_traceCall"])
+///     .with_denied(["admin_*"]);
+/// assert!(filter.is_allowed("debug_traceTransaction"));
+/// assert!(!filter.is_allowed("debug_setHead"));
+/// assert!(!filter.is_allowed("admin_addPeer"));
+/// assert!(filter.is_allowed("eth_call"));
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct RpcMethodFilter {
+    /// Allowed methods or namespaces
+    allowed: HashSet<String>,
+    /// Denied methods or namespaces
+    denied: HashSet<String>,
+}
+
+// === impl RpcMethodFilter ===
+
+impl RpcMethodFilter {
+    /// Adds the given methods or namespaces to the allow list.
+    pub fn with_allowed<I, T>(mut self, allowed: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.allowed.extend(allowed.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds the given methods or namespaces to the deny list.
+    pub fn with_denied<I, T>(mut self, denied: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.denied.extend(denied.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns true if the filter exposes all methods.
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty()
+    }
+
+    /// Returns true if the method with the given name is exposed.
+    pub fn is_allowed(&self, method: &str) -> bool {
+        if self.denied.iter().any(|entry| Self::matches(entry, method)) {
+            return false
+        }
+
+        let namespace = Self::namespace(method);
+        let mut allowed_in_namespace =
+            self.allowed.iter().filter(|entry| Self::namespace(entry) == namespace).peekable();
+        allowed_in_namespace.peek().is_none() ||
+            allowed_in_namespace.any(|entry| Self::matches(entry, method))
+    }
+
+    /// Returns the namespace of a method, e.g. `debug` for `debug_traceTransaction`.
+    fn namespace(method: &str) -> &str {
+        method.split_once('_').map_or(method, |(namespace, _)| namespace)
+    }
+
+    /// Returns true if the entry is the method or the namespace of the method.
+    fn matches(entry: &str, method: &str) -> bool {
+        match entry.strip_suffix('*') {
+            Some(prefix) => method.starts_with(prefix),
+            None => entry == method,
+        }
+    }
+}
+
 /// Represents RPC modules that are supported by reth
 #[derive(
     Debug,
@@ -273,6 +359,14 @@ pub enum RethRpcModule {
     Miner,
     /// `mev_` module
     Mev,
+    /// `bridge_` module
+    Bridge,
+    /// `bridgeInbox_` module
+    BridgeInbox,
+    /// `anvil_` module
+    Anvil,
+    /// `hardhat_` module
+    Hardhat,
 }
 
 // === impl RethRpcModule ===
@@ -324,6 +418,10 @@ impl FromStr for RethRpcModule {
             "flashbots" => Self::Flashbots,
             "miner" => Self::Miner,
             "mev" => Self::Mev,
+            "bridge" => Self::Bridge,
+            "bridge-inbox" => Self::BridgeInbox,
+            "anvil" => Self::Anvil,
+            "hardhat" => Self::Hardhat,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -575,4 +673,26 @@ mod test {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ParseError::VariantNotFound);
     }
+
+    #[test]
+    fn test_method_filter() {
+        let filter = RpcMethodFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.is_allowed("debug_setHead"));
+
+        // Allowed entries only restrict their own namespace
+        let filter = RpcMethodFilter::default().with_allowed(["admin_peers", "admin_nodeInfo"]);
+        assert!(filter.is_allowed("admin_peers"));
+        assert!(!filter.is_allowed("admin_addPeer"));
+        assert!(filter.is_allowed("debug_setHead"));
+
+        // Denied entries take precedence over allowed entries
+        let filter = RpcMethodFilter::default()
+            .with_allowed(["debug_*"])
+            .with_denied(["debug_setHead", "admin_*"]);
+        assert!(filter.is_allowed("debug_traceTransaction"));
+        assert!(!filter.is_allowed("debug_setHead"));
+        assert!(!filter.is_allowed("admin_peers"));
+        assert!(filter.is_allowed("eth_blockNumber"));
+    }
 }
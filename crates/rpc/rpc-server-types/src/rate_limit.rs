@@ -0,0 +1,248 @@
+//! Types for configuring the rate limits of the RPC server.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    num::{NonZeroU32, ParseIntError},
+    str::FromStr,
+};
+use strum::{AsRefStr, VariantArray};
+
+/// The JSON-RPC error code returned to clients that exhausted their quota.
+///
+/// This is the `Limit exceeded` code of EIP-1474.
+pub const RATE_LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The default HTTP header that carries the API key of a client.
+pub const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+
+/// A group of RPC methods that share a quota.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    AsRefStr,
+    VariantArray,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RpcMethodGroup {
+    /// Methods that execute a call on top of the state: `eth_call`, `eth_callMany`,
+    /// `eth_estimateGas`, `eth_createAccessList` and `eth_simulateV1`.
+    Call,
+    /// `eth_getLogs`
+    Logs,
+    /// `debug_` methods
+    Debug,
+    /// `trace_` methods
+    Trace,
+    /// All other methods
+    Other,
+}
+
+impl RpcMethodGroup {
+    /// Returns the group of the given method.
+    pub fn of(method: &str) -> Self {
+        match method {
+            "eth_call" |
+            "eth_callMany" |
+            "eth_estimateGas" |
+            "eth_createAccessList" |
+            "eth_simulateV1" => Self::Call,
+            "eth_getLogs" => Self::Logs,
+            _ if method.starts_with("debug_") => Self::Debug,
+            _ if method.starts_with("trace_") => Self::Trace,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// A token bucket quota.
+///
+/// A client can send up to `burst` requests at once, after which the bucket is refilled with
+/// `per_second` requests per second.
+///
+/// The quota is parsed from `<RATE>` or `<RATE>:<BURST>`, e.g. `10:50`. Without a burst, the
+/// bucket holds one second of requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RateLimitQuota {
+    /// The number of requests the bucket is refilled with per second.
+    pub per_second: NonZeroU32,
+    /// The maximum number of requests the bucket holds.
+    pub burst: NonZeroU32,
+}
+
+impl RateLimitQuota {
+    /// Creates a quota of `per_second` requests per second, with a burst of one second of
+    /// requests.
+    pub const fn per_second(per_second: NonZeroU32) -> Self {
+        Self { per_second, burst: per_second }
+    }
+
+    /// Sets the maximum number of requests the bucket holds.
+    pub const fn with_burst(mut self, burst: NonZeroU32) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+impl FromStr for RateLimitQuota {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((per_second, burst)) => {
+                Ok(Self::per_second(per_second.trim().parse()?).with_burst(burst.trim().parse()?))
+            }
+            None => Ok(Self::per_second(s.trim().parse()?)),
+        }
+    }
+}
+
+impl TryFrom<String> for RateLimitQuota {
+    type Error = ParseIntError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<RateLimitQuota> for String {
+    fn from(quota: RateLimitQuota) -> Self {
+        quota.to_string()
+    }
+}
+
+impl fmt::Display for RateLimitQuota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.per_second, self.burst)
+    }
+}
+
+/// The quotas of a client that identifies itself with an API key.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeyQuotas {
+    /// The API key that is sent in the API key header.
+    pub key: String,
+    /// Quotas per method group. Groups without a quota use the quotas of anonymous clients.
+    #[serde(default)]
+    pub quotas: BTreeMap<RpcMethodGroup, RateLimitQuota>,
+}
+
+impl fmt::Debug for ApiKeyQuotas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The key is a secret and must not end up in the logs.
+        f.debug_struct("ApiKeyQuotas").field("quotas", &self.quotas).finish_non_exhaustive()
+    }
+}
+
+/// Rate limit configuration of the HTTP and WS RPC servers.
+///
+/// Requests are counted per client and per [`RpcMethodGroup`]. Clients are identified by their
+/// API key, or if they don't send a known key, by their IP address as reported by a trusted
+/// reverse proxy. All other clients share the quotas of a single anonymous client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// Quotas per method group of clients without a known API key. Groups without a quota are not
+    /// rate limited.
+    pub quotas: BTreeMap<RpcMethodGroup, RateLimitQuota>,
+    /// Known API keys by the name of the client. The name labels the rate limit metrics.
+    pub api_keys: BTreeMap<String, ApiKeyQuotas>,
+    /// The HTTP header that carries the API key.
+    pub api_key_header: String,
+    /// Whether clients without a known API key are identified by the `X-Forwarded-For` or
+    /// `X-Real-IP` header.
+    ///
+    /// This must only be enabled if the server is behind a reverse proxy that sets these headers,
+    /// since clients can set them to any value otherwise.
+    pub trust_forwarded_for: bool,
+}
+
+impl RateLimitConfig {
+    /// Returns the quota of the given method group for the client with the given name, or for
+    /// anonymous clients if no name is given.
+    pub fn quota(&self, client: Option<&str>, group: RpcMethodGroup) -> Option<RateLimitQuota> {
+        client
+            .and_then(|name| self.api_keys.get(name))
+            .and_then(|api_key| api_key.quotas.get(&group))
+            .or_else(|| self.quotas.get(&group))
+            .copied()
+    }
+
+    /// Returns `true` if no method group has a quota.
+    pub fn is_unlimited(&self) -> bool {
+        self.quotas.is_empty() && self.api_keys.values().all(|api_key| api_key.quotas.is_empty())
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            quotas: BTreeMap::new(),
+            api_keys: BTreeMap::new(),
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+            trust_forwarded_for: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_quota() {
+        let quota: RateLimitQuota = "10".parse().unwrap();
+        assert_eq!(quota, RateLimitQuota::per_second(NonZeroU32::new(10).unwrap()));
+
+        let quota: RateLimitQuota = "10:50".parse().unwrap();
+        assert_eq!(quota.per_second.get(), 10);
+        assert_eq!(quota.burst.get(), 50);
+        assert_eq!(quota.to_string(), "10:50");
+
+        assert!("0".parse::<RateLimitQuota>().is_err());
+        assert!("10:".parse::<RateLimitQuota>().is_err());
+        assert!("10:5:1".parse::<RateLimitQuota>().is_err());
+    }
+
+    #[test]
+    fn method_groups() {
+        assert_eq!(RpcMethodGroup::of("eth_call"), RpcMethodGroup::Call);
+        assert_eq!(RpcMethodGroup::of("eth_estimateGas"), RpcMethodGroup::Call);
+        assert_eq!(RpcMethodGroup::of("eth_getLogs"), RpcMethodGroup::Logs);
+        assert_eq!(RpcMethodGroup::of("debug_traceTransaction"), RpcMethodGroup::Debug);
+        assert_eq!(RpcMethodGroup::of("trace_filter"), RpcMethodGroup::Trace);
+        assert_eq!(RpcMethodGroup::of("eth_blockNumber"), RpcMethodGroup::Other);
+    }
+
+    #[test]
+    fn api_key_quotas_fall_back_to_defaults() {
+        let api_keys = r#"{"relayer": {"key": "secret", "quotas": {"trace": "100:200"}}}"#;
+        let config = RateLimitConfig {
+            quotas: BTreeMap::from([
+                (RpcMethodGroup::Trace, "1".parse().unwrap()),
+                (RpcMethodGroup::Call, "5".parse().unwrap()),
+            ]),
+            api_keys: serde_json::from_str(api_keys).unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.quota(Some("relayer"), RpcMethodGroup::Trace),
+            Some("100:200".parse().unwrap())
+        );
+        assert_eq!(config.quota(Some("relayer"), RpcMethodGroup::Call), Some("5".parse().unwrap()));
+        assert_eq!(config.quota(None, RpcMethodGroup::Trace), Some("1".parse().unwrap()));
+        assert_eq!(config.quota(None, RpcMethodGroup::Logs), None);
+        assert!(!format!("{config:?}").contains("secret"));
+    }
+}
@@ -0,0 +1,616 @@
+use crate::eth::ImpersonatedAccounts;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{map::HashMap, Address, Bytes, B256, U256};
+use alloy_rpc_types_anvil::{
+    Forking, Metadata, MineOptions, NodeEnvironment, NodeForkConfig, NodeInfo,
+};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use parking_lot::Mutex;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardfork, EthereumHardforks};
+use reth_engine_local::{LocalMinerHandle, MiningMode};
+use reth_evm::overrides::{AccountStateOverride, StateOverride};
+use reth_primitives_traits::SealedHeader;
+use reth_rpc_api::AnvilApiServer;
+use reth_rpc_eth_api::{
+    helpers::{EthBlocks, EthFees},
+    FromEthApiError, FullEthApiTypes, RpcBlock, RpcNodeCore,
+};
+use reth_rpc_eth_types::EthApiError;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_storage_api::{
+    BlockNumReader, BlockReaderIdExt, HeaderProvider, ModifiedAccountsReader, StateProviderFactory,
+    StorageChangeSetReader,
+};
+use reth_transaction_pool::TransactionPool;
+use revm::state::Bytecode;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+/// `anvil` API implementation for dev nodes.
+///
+/// Mining and state changes are delegated to the local miner, so every change made through this
+/// API is part of a regular block.
+#[derive(Debug)]
+pub struct AnvilApi<Eth> {
+    /// The eth API of the node.
+    eth: Eth,
+    /// Handle to the local miner of the node.
+    miner: LocalMinerHandle,
+    /// The accounts impersonated through this API.
+    impersonated: ImpersonatedAccounts,
+    /// Snapshots taken through `anvil_snapshot`.
+    snapshots: Mutex<Snapshots>,
+    /// Random id of this instance, reported by `anvil_metadata`.
+    instance_id: B256,
+}
+
+impl<Eth> AnvilApi<Eth> {
+    /// Creates a new instance of `AnvilApi`.
+    ///
+    /// The `impersonated` accounts must be one of the signers of the eth API for transactions of
+    /// impersonated accounts to be accepted.
+    pub fn new(eth: Eth, miner: LocalMinerHandle, impersonated: ImpersonatedAccounts) -> Self {
+        Self {
+            eth,
+            miner,
+            impersonated,
+            snapshots: Default::default(),
+            instance_id: B256::random(),
+        }
+    }
+}
+
+impl<Eth> AnvilApi<Eth>
+where
+    Eth: RpcNodeCore<Pool: TransactionPool>,
+{
+    /// Builds a block that applies the given override to `address`.
+    async fn override_account(
+        &self,
+        address: Address,
+        account_override: AccountStateOverride,
+    ) -> RpcResult<()> {
+        self.miner
+            .mine_with_overrides(HashMap::from_iter([(address, account_override)]))
+            .await
+            .map_err(miner_err)?;
+        Ok(())
+    }
+
+    /// Sets the mining mode of the miner.
+    fn set_mining_mode(&self, mode: MiningMode) -> RpcResult<()> {
+        self.miner.set_mode(mode).map_err(miner_err)
+    }
+}
+
+impl<Eth> AnvilApi<Eth>
+where
+    Eth: RpcNodeCore<Provider: BlockReaderIdExt>,
+{
+    /// Returns the header of the latest block.
+    fn latest_header(&self) -> RpcResult<SealedHeader<<Eth::Provider as HeaderProvider>::Header>> {
+        self.eth
+            .provider()
+            .latest_header()
+            .map_err(EthApiError::from)?
+            .ok_or_else(|| EthApiError::HeaderNotFound(Default::default()).into())
+    }
+}
+
+#[async_trait]
+impl<Eth> AnvilApiServer<RpcBlock<Eth::NetworkTypes>> for AnvilApi<Eth>
+where
+    Eth: EthBlocks
+        + EthFees
+        + FullEthApiTypes
+        + RpcNodeCore<
+            Pool: TransactionPool,
+            Provider: BlockReaderIdExt
+                          + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+                          + ModifiedAccountsReader
+                          + StorageChangeSetReader
+                          + StateProviderFactory,
+        > + 'static,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.impersonated.impersonate(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.impersonated.stop_impersonating(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        self.impersonated.set_auto_impersonate(enabled);
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        self.miner.is_instant().await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        let blocks = blocks.map(to_u64).transpose()?.unwrap_or(1);
+        let interval = interval.map(to_u64).transpose()?;
+        self.miner.mine(blocks, interval).await.map_err(miner_err)?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        let mode = if enabled {
+            MiningMode::instant(self.eth.pool().pending_transactions_listener())
+        } else {
+            MiningMode::Manual
+        };
+        self.set_mining_mode(mode)
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        // an interval of 0 disables interval mining
+        let mode = if interval == 0 {
+            MiningMode::Manual
+        } else {
+            MiningMode::interval(Duration::from_secs(interval))
+        };
+        self.set_mining_mode(mode)
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        let removed = self.eth.pool().remove_transactions(vec![tx_hash]);
+        Ok((!removed.is_empty()).then_some(tx_hash))
+    }
+
+    /// Handler for `anvil_reset`
+    async fn anvil_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("forking is not supported").into())
+    }
+
+    /// Handler for `anvil_setRpcUrl`
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("forking is not supported").into())
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.override_account(
+            address,
+            AccountStateOverride { balance: Some(balance), ..Default::default() },
+        )
+        .await
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        let code = Bytecode::new_raw_checked(code)
+            .map_err(|err| EthApiError::InvalidBytecode(err.to_string()))?;
+        self.override_account(
+            address,
+            AccountStateOverride { code: Some(code), ..Default::default() },
+        )
+        .await
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        let nonce = to_u64(nonce)?;
+        self.override_account(
+            address,
+            AccountStateOverride { nonce: Some(nonce), ..Default::default() },
+        )
+        .await
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        let storage = HashMap::from_iter([(slot, value.into())]);
+        self.override_account(address, AccountStateOverride { storage, ..Default::default() })
+            .await?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        self.miner.set_fee_recipient(address).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_setChainId`
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("the chain id is defined by the chain spec").into())
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("logging is configured on startup").into())
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        // like anvil, the minimum gas price only applies to chains without a base fee
+        if self.latest_header()?.base_fee_per_gas().is_some() {
+            return Err(invalid_params_rpc_err(
+                "anvil_setMinGasPrice is not supported when EIP-1559 is active",
+            ))
+        }
+        Err(EthApiError::Unsupported("the minimum gas price is configured on startup").into())
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, base_fee: U256) -> RpcResult<()> {
+        let base_fee = to_u64(base_fee)?;
+        self.miner.set_next_block_base_fee(base_fee).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        let offset = self.miner.set_time(timestamp).await.map_err(miner_err)?;
+        Ok(offset.max(0) as u64)
+    }
+
+    /// Handler for `anvil_dumpState`
+    ///
+    /// The dump is the uncompressed JSON of the accounts in anvil's format.
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        let state = self
+            .eth
+            .spawn_blocking_io(|eth| dump_state(eth.provider()).map_err(Eth::Error::from_eth_err))
+            .await
+            .map_err(Into::into)?;
+        let json = serde_json::to_vec(&state).map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(json.into())
+    }
+
+    /// Handler for `anvil_loadState`
+    ///
+    /// The accounts of the dump are written through a new block, on top of the current state.
+    async fn anvil_load_state(&self, state: Bytes) -> RpcResult<bool> {
+        let state: DumpedState = serde_json::from_slice(&state)
+            .map_err(|err| invalid_params_rpc_err(format!("invalid state dump: {err}")))?;
+        self.miner.mine_with_overrides(state.into_overrides()).await.map_err(miner_err)?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        let header = self.latest_header()?;
+        let chain_spec = self.eth.provider().chain_spec();
+        let hard_fork = EthereumHardfork::VARIANTS
+            .iter()
+            .rev()
+            .find(|fork| {
+                chain_spec
+                    .ethereum_fork_activation(**fork)
+                    .active_at_timestamp_or_number(header.timestamp(), header.number())
+            })
+            .map(|fork| fork.name().to_lowercase())
+            .unwrap_or_default();
+        let gas_price = EthFees::gas_price(&self.eth).await.map_err(Into::into)?;
+
+        Ok(NodeInfo {
+            current_block_number: header.number(),
+            current_block_timestamp: header.timestamp(),
+            current_block_hash: header.hash(),
+            hard_fork,
+            // the pool orders transactions by their tip
+            transaction_order: "fees".to_string(),
+            environment: NodeEnvironment {
+                base_fee: header.base_fee_per_gas().unwrap_or_default() as u128,
+                chain_id: chain_spec.chain().id(),
+                gas_limit: header.gas_limit(),
+                gas_price: gas_price.saturating_to(),
+            },
+            fork_config: NodeForkConfig::default(),
+        })
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        let header = self.latest_header()?;
+        let heads = self.snapshots.lock().heads.clone();
+        let mut snapshots = BTreeMap::new();
+        for (id, (hash, _)) in heads {
+            // snapshots of blocks that were dropped by a revert can no longer be reverted to
+            if let Some(number) =
+                self.eth.provider().block_number(hash).map_err(EthApiError::from)?
+            {
+                snapshots.insert(id, (number, hash));
+            }
+        }
+
+        Ok(Metadata {
+            client_version: format!("reth/v{}", env!("CARGO_PKG_VERSION")),
+            chain_id: self.eth.provider().chain_spec().chain().id(),
+            instance_id: self.instance_id,
+            latest_block_number: header.number(),
+            latest_block_hash: header.hash(),
+            forked_network: None,
+            snapshots,
+        })
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        let head = self.miner.head().await.map_err(miner_err)?;
+        Ok(self.snapshots.lock().insert(head))
+    }
+
+    /// Handler for `anvil_revert`
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        let Some((hash, timestamp)) = self.snapshots.lock().remove(id) else { return Ok(false) };
+        self.miner.rewind(hash, timestamp).await.map_err(miner_err)?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        let seconds = i64::try_from(seconds)
+            .map_err(|_| invalid_params_rpc_err("seconds exceed the range of i64"))?;
+        self.miner.increase_time(seconds).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        self.miner
+            .set_next_block_timestamp(seconds)
+            .await
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, gas_limit: U256) -> RpcResult<bool> {
+        let gas_limit = to_u64(gas_limit)?;
+        self.miner.set_block_gas_limit(gas_limit).await.map_err(miner_err)?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        self.miner.set_block_timestamp_interval(Some(seconds)).await.map_err(miner_err)?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        let previous = self.miner.set_block_timestamp_interval(None).await.map_err(miner_err)?;
+        Ok(previous.is_some())
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(
+        &self,
+        opts: Option<MineOptions>,
+    ) -> RpcResult<Vec<RpcBlock<Eth::NetworkTypes>>> {
+        let (timestamp, blocks) = match opts {
+            Some(MineOptions::Options { timestamp, blocks }) => (timestamp, blocks),
+            Some(MineOptions::Timestamp(timestamp)) => (timestamp, None),
+            None => (None, None),
+        };
+        if let Some(timestamp) = timestamp {
+            self.anvil_set_next_block_timestamp(timestamp).await?;
+        }
+
+        let hashes = self.miner.mine(blocks.unwrap_or(1), None).await.map_err(miner_err)?;
+
+        let mut mined = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            let block = self
+                .eth
+                .rpc_block(hash.into(), true)
+                .await
+                .map_err(Into::into)?
+                .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+            mined.push(block);
+        }
+        Ok(mined)
+    }
+
+    /// Handler for `anvil_enableTraces`
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("call traces are not supported").into())
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        self.eth.pool().remove_transactions_by_sender(address);
+        Ok(())
+    }
+}
+
+/// Snapshots of the chain that can be reverted to, by id.
+#[derive(Debug, Default)]
+struct Snapshots {
+    /// Id of the next snapshot.
+    next_id: U256,
+    /// Hash and timestamp of the head block of each snapshot.
+    heads: BTreeMap<U256, (B256, u64)>,
+}
+
+impl Snapshots {
+    /// Records a snapshot of the given head and returns its id.
+    fn insert(&mut self, head: (B256, u64)) -> U256 {
+        let id = self.next_id;
+        self.heads.insert(id, head);
+        self.next_id += U256::from(1);
+        id
+    }
+
+    /// Removes the snapshot with the given id and all snapshots taken after it, and returns its
+    /// head.
+    fn remove(&mut self, id: U256) -> Option<(B256, u64)> {
+        let head = self.heads.remove(&id)?;
+        self.heads.retain(|snapshot, _| *snapshot < id);
+        Some(head)
+    }
+}
+
+/// The state of all accounts as dumped by `anvil_dumpState`.
+///
+/// Other fields of anvil's dumps, like the blocks of the chain, are ignored when loading.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct DumpedState {
+    /// The accounts by address.
+    accounts: BTreeMap<Address, DumpedAccount>,
+}
+
+impl DumpedState {
+    /// Converts the accounts into overrides that write their state.
+    fn into_overrides(self) -> StateOverride {
+        self.accounts
+            .into_iter()
+            .map(|(address, account)| {
+                let account_override = AccountStateOverride {
+                    balance: Some(account.balance),
+                    nonce: Some(account.nonce),
+                    code: Some(Bytecode::new_raw(account.code)),
+                    storage: account.storage.into_iter().collect(),
+                };
+                (address, account_override)
+            })
+            .collect()
+    }
+}
+
+/// A single account of a [`DumpedState`].
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct DumpedAccount {
+    nonce: u64,
+    balance: U256,
+    code: Bytes,
+    storage: BTreeMap<U256, U256>,
+}
+
+/// Collects the state of all accounts at the latest block.
+///
+/// These are the accounts allocated in the genesis and the accounts changed by any later block,
+/// with the storage slots set by either. Dev chains are short, so the changesets of all blocks
+/// are walked.
+fn dump_state<P>(provider: &P) -> Result<DumpedState, EthApiError>
+where
+    P: BlockNumReader
+        + ChainSpecProvider<ChainSpec: EthChainSpec>
+        + ModifiedAccountsReader
+        + StorageChangeSetReader
+        + StateProviderFactory,
+{
+    let mut slots = provider
+        .chain_spec()
+        .genesis()
+        .alloc
+        .iter()
+        .map(|(address, account)| {
+            (*address, account.storage.iter().flatten().map(|(slot, _)| *slot).collect())
+        })
+        .collect::<BTreeMap<Address, BTreeSet<B256>>>();
+
+    let tip = provider.best_block_number()?;
+    if tip > 0 {
+        for address in provider.modified_accounts_in_range(1..=tip)? {
+            slots.entry(address).or_default();
+        }
+        for number in 1..=tip {
+            for (key, entry) in provider.storage_changeset(number)? {
+                slots.entry(key.address()).or_default().insert(entry.key);
+            }
+        }
+    }
+
+    let state = provider.latest()?;
+    let mut accounts = BTreeMap::new();
+    for (address, slots) in slots {
+        // accounts that were destroyed no longer exist
+        let Some(account) = state.basic_account(&address)? else { continue };
+        let code = state.account_code(&address)?.map(|code| code.original_bytes());
+        let mut storage = BTreeMap::new();
+        for slot in slots {
+            let value = state.storage(address, slot)?.unwrap_or_default();
+            if !value.is_zero() {
+                storage.insert(slot.into(), value);
+            }
+        }
+        accounts.insert(
+            address,
+            DumpedAccount {
+                nonce: account.nonce,
+                balance: account.balance,
+                code: code.unwrap_or_default(),
+                storage,
+            },
+        );
+    }
+    Ok(DumpedState { accounts })
+}
+
+/// Converts the given quantity into a `u64`.
+fn to_u64(value: U256) -> RpcResult<u64> {
+    u64::try_from(value).map_err(|_| invalid_params_rpc_err("value exceeds the range of u64"))
+}
+
+/// Converts an error of the local miner into an RPC error.
+pub(crate) fn miner_err(err: eyre::Report) -> jsonrpsee::types::ErrorObject<'static> {
+    internal_rpc_err(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn revert_drops_later_snapshots() {
+        let mut snapshots = Snapshots::default();
+        let first = snapshots.insert((B256::with_last_byte(1), 1));
+        let second = snapshots.insert((B256::with_last_byte(2), 2));
+        let third = snapshots.insert((B256::with_last_byte(3), 3));
+
+        assert_eq!(snapshots.remove(second), Some((B256::with_last_byte(2), 2)));
+        assert_eq!(snapshots.remove(third), None);
+        assert_eq!(snapshots.remove(second), None);
+        assert_eq!(snapshots.remove(first), Some((B256::with_last_byte(1), 1)));
+    }
+
+    #[test]
+    fn loads_accounts_of_anvil_dump() {
+        let dump = r#"{
+            "block": null,
+            "accounts": {
+                "0x0000000000000000000000000000000000000001": {
+                    "nonce": 2,
+                    "balance": "0x3",
+                    "code": "0x6000",
+                    "storage": { "0x4": "0x5" }
+                }
+            },
+            "best_block_number": "0x0",
+            "blocks": [],
+            "transactions": []
+        }"#;
+        let state: DumpedState = serde_json::from_str(dump).unwrap();
+        let reencoded: DumpedState =
+            serde_json::from_slice(&serde_json::to_vec(&state).unwrap()).unwrap();
+        assert_eq!(reencoded, state);
+
+        let overrides = state.into_overrides();
+        let account = &overrides[&Address::with_last_byte(1)];
+        assert_eq!(account.nonce, Some(2));
+        assert_eq!(account.balance, Some(U256::from(3)));
+        assert_eq!(account.code, Some(Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]))));
+        assert_eq!(account.storage, HashMap::from_iter([(U256::from(4), U256::from(5))]));
+    }
+}
@@ -0,0 +1,43 @@
+use alloy_primitives::{Address, Bytes, B256, U64};
+use jsonrpsee::core::RpcResult;
+use reth_engine_local::{CreditInboxHandle, InboundCredit};
+use reth_evm_ethereum::inbox::InboxMessages;
+use reth_rpc_api::BridgeInboxApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+
+/// `bridgeInbox` API implementation that queues credits into the credit inbox of the local miner
+/// and message batches into the inbox messages of the payload builder.
+#[derive(Debug, Clone, Default)]
+pub struct BridgeInboxApi {
+    /// Handle to the credit inbox of the local miner.
+    credits: Option<CreditInboxHandle>,
+    /// The message batches that are delivered in the blocks built by the node.
+    messages: Option<InboxMessages>,
+}
+
+impl BridgeInboxApi {
+    /// Creates a new instance of `BridgeInboxApi`.
+    pub const fn new(credits: Option<CreditInboxHandle>, messages: Option<InboxMessages>) -> Self {
+        Self { credits, messages }
+    }
+}
+
+impl BridgeInboxApiServer for BridgeInboxApi {
+    /// Handler for `bridgeInbox_enqueueCredit`
+    fn enqueue_credit(&self, address: Address, amount: U64) -> RpcResult<()> {
+        self.credits
+            .as_ref()
+            .ok_or_else(|| internal_rpc_err("credit inbox not available"))?
+            .enqueue(InboundCredit::new(address, amount.to()))
+            .map_err(|_| internal_rpc_err("credit inbox closed"))
+    }
+
+    /// Handler for `bridgeInbox_queueMessages`
+    fn queue_messages(&self, messages: Vec<Bytes>) -> RpcResult<B256> {
+        self.messages
+            .as_ref()
+            .ok_or_else(|| internal_rpc_err("inbox messages not available"))?
+            .insert(messages)
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
+    }
+}
@@ -1,12 +1,12 @@
 use alloy_consensus::{transaction::SignerRecoverable, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{Address, Bytes, B256, U256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
-    state::EvmOverrides, transaction::TransactionRequest, Block as RpcBlock, BlockError, Bundle,
-    StateContext, TransactionInfo,
+    state::EvmOverrides, transaction::TransactionRequest, BlockError, Bundle, StateContext,
+    TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
     call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
@@ -14,27 +14,40 @@ use alloy_rpc_types_trace::geth::{
     NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
+use jsonrpsee_types::ErrorObject;
+use reth_bad_blocks::{BadBlock as StoredBadBlock, BadBlockStore};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_errors::RethError;
 use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
 use reth_primitives_traits::{
     Block as _, BlockBody, NodePrimitives, ReceiptWithBloom, RecoveredBlock, SignedTransaction,
 };
 use reth_revm::{
     database::StateProviderDatabase,
-    db::{CacheDB, State},
+    db::{states::bundle_state::BundleRetention, AccountState, CacheDB, State},
     witness::ExecutionWitnessRecord,
 };
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{BadBlock, DebugApiServer, StorageRangeResult, StorageRangeSlot};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    EthApiTypes, FromEthApiError, RpcNodeCore,
+    EthApiTypes, FromEthApiError, RpcNodeCore, RpcTransaction,
+};
+use reth_rpc_eth_types::{
+    revm_utils::{apply_block_overrides, apply_state_overrides},
+    EthApiError, StateCacheDb,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_types_compat::block::from_block_with_tx_hashes;
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
-    StateProofProvider, StateProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
+    BlockIdReader, BlockNumReader, BlockReaderIdExt, HashedPostStateProvider, HeaderProvider,
+    ModifiedAccountsReader, ProviderBlock, ReceiptProviderIdExt, StateProofProvider, StateProvider,
+    StateProviderFactory, StateRootProvider, StorageOverlay, StorageRangeProvider,
+    TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
@@ -42,14 +55,19 @@ use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
+use std::{ops::RangeInclusive, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// The maximum number of blocks traced in parallel by `debug_traceChain`.
+const TRACE_CHAIN_CONCURRENCY: usize = 8;
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Eth, BlockExecutor> {
     inner: Arc<DebugApiInner<Eth, BlockExecutor>>,
+    /// Blocks rejected by the engine.
+    bad_blocks: BadBlockStore,
 }
 
 // === impl DebugApi ===
@@ -58,7 +76,14 @@ impl<Eth, Evm> DebugApi<Eth, Evm> {
     /// Create a new instance of the [`DebugApi`]
     pub fn new(eth: Eth, blocking_task_guard: BlockingTaskGuard, evm_config: Evm) -> Self {
         let inner = Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, evm_config });
-        Self { inner }
+        Self { inner, bad_blocks: Default::default() }
+    }
+
+    /// Configures the store of the blocks rejected by the engine, which are served by
+    /// `debug_getBadBlocks` and can be traced with `debug_traceBadBlock`.
+    pub fn with_bad_blocks(mut self, bad_blocks: BadBlockStore) -> Self {
+        self.bad_blocks = bad_blocks;
+        self
     }
 
     /// Access the underlying `Eth` API.
@@ -147,12 +172,21 @@ where
         rlp_block: Bytes,
         opts: GethDebugTracingOptions,
     ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let block = self.recover_raw_block(rlp_block)?;
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        self.trace_block(Arc::new(block), evm_env, opts).await
+    }
+
+    /// Decodes the given rlp encoded block and recovers the senders of its transactions.
+    fn recover_raw_block(
+        &self,
+        rlp_block: Bytes,
+    ) -> Result<RecoveredBlock<ProviderBlock<Eth::Provider>>, Eth::Error> {
         let block: ProviderBlock<Eth::Provider> = Decodable::decode(&mut rlp_block.as_ref())
             .map_err(BlockError::RlpDecodeRawBlock)
             .map_err(Eth::Error::from_eth_err)?;
 
-        let evm_env = self.eth_api().evm_config().evm_env(block.header());
-
         // Depending on EIP-2 we need to recover the transactions differently
         let senders =
             if self.provider().chain_spec().is_homestead_active_at_block(block.header().number()) {
@@ -175,7 +209,180 @@ where
                     .collect()
             };
 
-        self.trace_block(Arc::new(block.into_recovered_with_signers(senders)), evm_env, opts).await
+        Ok(block.into_recovered_with_signers(senders))
+    }
+
+    /// Re-executes the given block on top of the state of its parent and returns the state root
+    /// after each transaction.
+    ///
+    /// The block overrides of the options are applied to the block environment and the state
+    /// overrides on top of the state after the pre-execution changes of the block.
+    async fn intermediate_roots(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
+        opts: GethDebugTracingCallOptions,
+    ) -> Result<Vec<B256>, Eth::Error> {
+        let GethDebugTracingCallOptions { state_overrides, block_overrides, .. } = opts;
+        let mut evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = State::builder()
+                    .with_database(StateProviderDatabase::new(&state))
+                    .with_bundle_update()
+                    .build();
+
+                if let Some(block_overrides) = block_overrides {
+                    apply_block_overrides(block_overrides, &mut db, &mut evm_env.block_env);
+                }
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+                if let Some(state_overrides) = state_overrides {
+                    apply_state_overrides(state_overrides, &mut db)
+                        .map_err(Eth::Error::from_eth_err)?;
+                }
+
+                let mut roots = Vec::with_capacity(block.body().transactions().len());
+                for tx in block.transactions_recovered() {
+                    let tx_env = this.eth_api().evm_config().tx_env(tx);
+                    let res = this.eth_api().transact(&mut db, evm_env.clone(), tx_env)?;
+                    db.commit(res.state);
+
+                    // the root is computed over the changes of all transactions executed so far,
+                    // applied on top of the parent state
+                    db.merge_transitions(BundleRetention::PlainState);
+                    let hashed_state = state.hashed_post_state(&db.bundle_state);
+                    roots.push(state.state_root(hashed_state).map_err(Eth::Error::from_eth_err)?);
+                }
+
+                Ok(roots)
+            })
+            .await
+    }
+
+    /// Returns the block with the given hash that was rejected by the engine.
+    fn bad_block(&self, block_hash: B256) -> Result<StoredBadBlock, EthApiError> {
+        self.bad_blocks
+            .get(block_hash)
+            .ok_or_else(|| EthApiError::InvalidParams(format!("bad block {block_hash} not found")))
+    }
+
+    /// Traces the block with the given hash that was rejected by the engine and writes the trace of
+    /// each transaction as JSON to a file in the temporary directory.
+    ///
+    /// Returns the paths of the written files.
+    async fn standard_trace_bad_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let bad_block = self.bad_block(block_hash).map_err(Eth::Error::from_eth_err)?;
+        let traces = self.debug_trace_raw_block(bad_block.rlp, opts).await?;
+
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let mut files = Vec::with_capacity(traces.len());
+                for (index, trace) in traces.iter().enumerate() {
+                    let tx_hash = match trace {
+                        TraceResult::Success { tx_hash, .. } |
+                        TraceResult::Error { tx_hash, .. } => tx_hash.unwrap_or_default(),
+                    };
+                    let path = std::env::temp_dir()
+                        .join(format!("block_{block_hash}-{index}-{tx_hash}.json"));
+                    serde_json::to_vec(trace)
+                        .map_err(RethError::other)
+                        .and_then(|json| std::fs::write(&path, json).map_err(RethError::other))
+                        .map_err(|err| Eth::Error::from_eth_err(EthApiError::Internal(err)))?;
+                    files.push(path.display().to_string());
+                }
+                Ok(files)
+            })
+            .await
+    }
+
+    /// Returns the range of blocks traced by `debug_traceChain`, excluding the start block.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> Result<RangeInclusive<u64>, EthApiError> {
+        let start = self
+            .provider()
+            .convert_block_number(start_exclusive)?
+            .ok_or(EthApiError::HeaderNotFound(start_exclusive.into()))?;
+        let end = self
+            .provider()
+            .convert_block_number(end_inclusive)?
+            .ok_or(EthApiError::HeaderNotFound(end_inclusive.into()))?;
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block {start} must be before end block {end}"
+            )))
+        }
+        Ok(start + 1..=end)
+    }
+
+    /// Traces all blocks in the given range and sends the traces of each block to the
+    /// subscription, in order.
+    ///
+    /// Up to [`TRACE_CHAIN_CONCURRENCY`] blocks are traced in parallel, each on top of the
+    /// historical state of its parent. Traced blocks are buffered until all preceding blocks were
+    /// sent, so memory stays bounded regardless of the length of the range.
+    async fn trace_chain(
+        &self,
+        sink: SubscriptionSink,
+        range: RangeInclusive<u64>,
+        opts: GethDebugTracingOptions,
+    ) -> Result<(), ErrorObject<'static>> {
+        let mut traces = futures::stream::iter(range)
+            .map(|number| {
+                let this = self.clone();
+                let opts = opts.clone();
+                async move {
+                    let _permit = this.acquire_trace_permit().await;
+                    let hash = this
+                        .provider()
+                        .block_hash_for_id(number.into())
+                        .map_err(Eth::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                    let traces = this.debug_trace_block(hash.into(), opts).await?;
+                    Ok::<_, Eth::Error>(BlockTraceResult {
+                        block: U256::from(number),
+                        hash,
+                        traces,
+                    })
+                }
+            })
+            .buffered(TRACE_CHAIN_CONCURRENCY);
+
+        loop {
+            tokio::select! {
+                _ = sink.closed() => {
+                    // connection dropped
+                    break Ok(())
+                }
+                maybe_result = traces.next() => {
+                    let result = match maybe_result {
+                        Some(Ok(result)) => result,
+                        Some(Err(err)) => break Err(err.into()),
+                        None => {
+                            // all blocks traced
+                            break Ok(())
+                        }
+                    };
+                    let msg = SubscriptionMessage::new(
+                        sink.method_name(),
+                        sink.subscription_id(),
+                        &result,
+                    )
+                    .map_err(|err| internal_rpc_err(err.to_string()))?;
+
+                    if sink.send(msg).await.is_err() {
+                        break Ok(())
+                    }
+                }
+            }
+        }
     }
 
     /// Replays a block and returns the trace of each transaction.
@@ -884,10 +1091,154 @@ where
     }
 }
 
+impl<Eth, Evm> DebugApi<Eth, Evm>
+where
+    Eth: EthApiTypes
+        + TraceExt
+        + RpcNodeCore<Provider: ModifiedAccountsReader + StorageRangeProvider>
+        + 'static,
+    Evm: ConfigureEvm<Primitives: NodePrimitives<Block = ProviderBlock<Eth::Provider>>> + 'static,
+{
+    /// Returns the accounts modified between the given blocks, excluding the start block, or the
+    /// accounts modified in the start block if no end block is given.
+    async fn modified_accounts(
+        &self,
+        start: u64,
+        end: Option<u64>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let range = match end {
+            None => start..=start,
+            Some(end) if start < end => start + 1..=end,
+            Some(end) => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "start block ({start}) must be less than end block ({end})"
+                ))
+                .into())
+            }
+        };
+
+        let best_block = self.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+        for number in [start, *range.end()] {
+            if number > best_block {
+                return Err(EthApiError::HeaderNotFound(number.into()).into())
+            }
+        }
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let accounts = this
+                    .provider()
+                    .modified_accounts_in_range(range)
+                    .map_err(Eth::Error::from_eth_err)?;
+                Ok(accounts.into_iter().collect())
+            })
+            .await
+    }
+
+    /// Returns the number of the block with the given hash.
+    fn block_number_by_hash(&self, hash: B256) -> Result<u64, Eth::Error> {
+        self.provider()
+            .block_number(hash)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or_else(|| EthApiError::HeaderNotFound(hash.into()).into())
+    }
+
+    /// Returns a page of the storage of the given account, as it was before the transaction at
+    /// the given index of the block was executed.
+    ///
+    /// The slots are ordered by their hashed key, starting at `key_start`.
+    async fn storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let ((evm_env, _), block) = futures::try_join!(
+            self.eth_api().evm_env_at(block_hash.into()),
+            self.eth_api().recovered_block(block_hash.into()),
+        )?;
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+
+        let transactions = block.body().transactions();
+        if tx_idx >= transactions.len() && !(tx_idx == 0 && transactions.is_empty()) {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {block_hash}"
+            ))
+            .into())
+        }
+        let target_tx = transactions.get(tx_idx).map(|tx| *tx.tx_hash());
+        let parent_number = block.number().checked_sub(1).ok_or_else(|| {
+            EthApiError::InvalidParams("the genesis block has no parent state".to_string())
+        })?;
+        let limit = usize::try_from(max_result).unwrap_or(usize::MAX);
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                // replay all transactions prior to the targeted transaction
+                if let Some(target_tx) = target_tx {
+                    this.eth_api().replay_transactions_until(
+                        &mut db,
+                        evm_env,
+                        block.transactions_recovered(),
+                        target_tx,
+                    )?;
+                }
+
+                // the storage changed by the replayed transactions is applied on top of the
+                // storage at the parent block
+                let overlay = db
+                    .cache
+                    .accounts
+                    .get(&address)
+                    .map(|account| StorageOverlay {
+                        wiped: matches!(
+                            account.account_state,
+                            AccountState::StorageCleared | AccountState::NotExisting
+                        ),
+                        slots: account
+                            .storage
+                            .iter()
+                            .map(|(slot, value)| (B256::from(*slot), *value))
+                            .collect(),
+                    })
+                    .unwrap_or_default();
+
+                let range = this
+                    .provider()
+                    .storage_range(parent_number, address, overlay, key_start, limit)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                Ok(StorageRangeResult {
+                    storage: range
+                        .entries
+                        .into_iter()
+                        .map(|entry| {
+                            let value = B256::from(entry.value);
+                            (entry.hashed_key, StorageRangeSlot { key: entry.key, value })
+                        })
+                        .collect(),
+                    next_key: range.next_key,
+                })
+            })
+            .await
+    }
+}
+
 #[async_trait]
 impl<Eth, Evm> DebugApiServer for DebugApi<Eth, Evm>
 where
-    Eth: EthApiTypes + EthTransactions + TraceExt + 'static,
+    Eth: EthApiTypes
+        + EthTransactions
+        + TraceExt
+        + RpcNodeCore<Provider: ModifiedAccountsReader + StorageRangeProvider>
+        + 'static,
     Evm: ConfigureEvm<Primitives: NodePrimitives<Block = ProviderBlock<Eth::Provider>>> + 'static,
 {
     /// Handler for `debug_getRawHeader`
@@ -959,17 +1310,45 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RpcBlock>> {
-        Ok(vec![])
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock>> {
+        let mut bad_blocks = Vec::new();
+        for StoredBadBlock { hash, rlp, reason } in self.bad_blocks.blocks() {
+            let block: ProviderBlock<Eth::Provider> = Decodable::decode(&mut rlp.as_ref())
+                .map_err(BlockError::RlpDecodeRawBlock)
+                .map_err(EthApiError::from)?;
+            // the senders are not required for a block that only contains transaction hashes
+            let block = from_block_with_tx_hashes::<RpcTransaction<Eth::NetworkTypes>, _>(
+                RecoveredBlock::new_unhashed(block, Vec::new()),
+            );
+            let block = serde_json::to_value(block)
+                .map_err(|err| internal_rpc_err(format!("failed to serialize block: {err}")))?;
+            bad_blocks.push(BadBlock { hash, block, rlp, reason });
+        }
+        Ok(bad_blocks)
     }
 
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> SubscriptionResult {
+        let range = match self.trace_chain_range(start_exclusive, end_inclusive) {
+            Ok(range) => range,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+
+        let sink = pending.accept().await?;
+        self.trace_chain(sink, range, opts.unwrap_or_default())
+            .await
+            .map_err(|err| err.message().to_string())?;
+
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -1141,32 +1520,48 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        let start = self.block_number_by_hash(start_hash).map_err(Into::into)?;
+        let end =
+            end_hash.map(|hash| self.block_number_by_hash(hash)).transpose().map_err(Into::into)?;
+        self.modified_accounts(start, end).await.map_err(Into::into)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        self.modified_accounts(start_number, end_number).await.map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingCallOptions>,
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        let block =
+            match self.eth_api().recovered_block(block_hash.into()).await.map_err(Into::into)? {
+                Some(block) => block,
+                None => {
+                    // the block may have been rejected by the engine
+                    let bad_block = self.bad_block(block_hash)?;
+                    Arc::new(self.recover_raw_block(bad_block.rlp).map_err(Into::into)?)
+                }
+            };
+        self.intermediate_roots(block, opts.unwrap_or_default()).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1213,12 +1608,18 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_standardTraceBadBlockToFile`
+    ///
+    /// The trace of each transaction is written as JSON to a file in the temporary directory.
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        self.standard_trace_bad_block_to_file(block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_standard_trace_block_to_file(
@@ -1253,23 +1654,31 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        self.storage_range_at(block_hash, tx_idx, contract_address, key_start, max_result)
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let bad_block = self.bad_block(block_hash)?;
+        let _permit = self.acquire_trace_permit().await;
+        self.debug_trace_raw_block(bad_block.rlp, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -1301,7 +1710,7 @@ impl<Eth, Evm> std::fmt::Debug for DebugApi<Eth, Evm> {
 
 impl<Eth, Evm> Clone for DebugApi<Eth, Evm> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), bad_blocks: self.bad_blocks.clone() }
     }
 }
 
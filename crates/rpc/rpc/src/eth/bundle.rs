@@ -2,25 +2,34 @@
 
 use alloy_consensus::{EnvKzgSettings, Transaction as _};
 use alloy_eips::eip7840::BlobParams;
-use alloy_primitives::{Keccak256, U256};
-use alloy_rpc_types_mev::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use alloy_primitives::{Bytes, Keccak256, B256, U256};
+use alloy_rpc_types_mev::{
+    CancelBundleRequest, CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle,
+    EthCallBundleResponse, EthCallBundleTransactionResult, EthSendBundle,
+    PrivateTransactionRequest,
+};
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_evm::{ConfigureEvm, Evm};
-use reth_primitives_traits::SignedTransaction;
+use reth_primitives_traits::{Recovered, SignedTransaction};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc_eth_api::{
     helpers::{Call, EthTransactions, LoadPendingBlock},
-    EthCallBundleApiServer, FromEthApiError, FromEvmError,
+    EthBundleApiServer, EthCallBundleApiServer, FromEthApiError, FromEvmError,
 };
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError, RpcInvalidTransactionError};
+use reth_storage_api::BlockNumReader;
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{
-    EthBlobTransactionSidecar, EthPoolTransaction, PoolPooledTx, PoolTransaction, TransactionPool,
+    EthBlobTransactionSidecar, EthPoolTransaction, PoolBundle, PoolConsensusTx, PoolPooledTx,
+    PoolTransaction, TransactionPool,
 };
 use revm::{context_interface::result::ResultAndState, DatabaseCommit, DatabaseRef};
 use std::sync::Arc;
 
+/// The number of blocks a private transaction is kept for if no `maxBlockNumber` is provided.
+pub const DEFAULT_PRIVATE_TX_MAX_BLOCKS: u64 = 25;
+
 /// `Eth` bundle implementation.
 pub struct EthBundle<Eth> {
     /// All nested fields bundled together.
@@ -261,6 +270,109 @@ where
             })
             .await
     }
+
+    /// Adds a bundle to the bundle store of the pool.
+    ///
+    /// The bundle is never propagated to peers and is only included by the local payload builder
+    /// at the top of the targeted block, if all of its transactions succeed or are allowed to
+    /// revert.
+    pub fn send_bundle(&self, bundle: EthSendBundle) -> Result<EthBundleHash, Eth::Error> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+            ..
+        } = bundle;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            )
+            .into())
+        }
+        if block_number == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            )
+            .into())
+        }
+        let best_block_number =
+            self.eth_api().provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if block_number <= best_block_number {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BlockNumberInPast(block_number).to_string(),
+            )
+            .into())
+        }
+
+        let mut bundle =
+            PoolBundle::new(self.recover_transactions(&txs)?, block_number, block_number);
+        bundle.min_timestamp = min_timestamp;
+        bundle.max_timestamp = max_timestamp;
+        bundle.reverting_tx_hashes = reverting_tx_hashes.into_iter().collect();
+        bundle.replacement_uuid = replacement_uuid;
+
+        let bundle_hash = self
+            .eth_api()
+            .pool()
+            .bundles()
+            .add_bundle(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Adds a private transaction to the bundle store of the pool.
+    ///
+    /// The transaction is considered for inclusion in all blocks up to `max_block_number`, which
+    /// defaults to [`DEFAULT_PRIVATE_TX_MAX_BLOCKS`] blocks after the current head.
+    pub fn send_private_transaction(
+        &self,
+        tx: Bytes,
+        max_block_number: Option<u64>,
+    ) -> Result<B256, Eth::Error> {
+        let best_block_number =
+            self.eth_api().provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+        let max_block_number =
+            max_block_number.unwrap_or(best_block_number + DEFAULT_PRIVATE_TX_MAX_BLOCKS);
+        if max_block_number <= best_block_number {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BlockNumberInPast(max_block_number).to_string(),
+            )
+            .into())
+        }
+
+        let transactions = self.recover_transactions(&[tx])?;
+        let tx_hash = *transactions[0].tx_hash();
+        self.eth_api()
+            .pool()
+            .bundles()
+            .add_bundle(PoolBundle::new(transactions, best_block_number + 1, max_block_number))
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+        Ok(tx_hash)
+    }
+
+    /// Decodes and recovers the given raw transactions.
+    ///
+    /// Blob transactions are rejected because their sidecars are not retained by the bundle store.
+    fn recover_transactions(
+        &self,
+        txs: &[Bytes],
+    ) -> Result<Vec<Recovered<PoolConsensusTx<Eth::Pool>>>, Eth::Error> {
+        txs.iter()
+            .map(|tx| {
+                let tx = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(tx)?;
+                if tx.blob_gas_used().is_some() {
+                    return Err(EthApiError::InvalidParams(
+                        EthBundleError::BlobTransactionUnsupported.to_string(),
+                    )
+                    .into())
+                }
+                Ok(tx.map(<Eth::Pool as TransactionPool>::Transaction::pooled_into_consensus))
+            })
+            .collect()
+    }
 }
 
 #[async_trait::async_trait]
@@ -273,6 +385,44 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Self::send_bundle(self, bundle).map_err(Into::into)
+    }
+
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        Self::call_bundle(self, request).await.map_err(Into::into)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        self.eth_api().pool().bundles().cancel_bundle(&request.bundle_hash);
+        Ok(())
+    }
+
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        Self::send_private_transaction(self, request.tx, request.max_block_number)
+            .map_err(Into::into)
+    }
+
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        Self::send_private_transaction(self, bytes, None).map_err(Into::into)
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        Ok(self.eth_api().pool().bundles().remove_private_transaction(request.tx_hash))
+    }
+}
+
 /// Container type for  `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
@@ -307,4 +457,10 @@ pub enum EthBundleError {
     /// Thrown when the blob gas usage of the blob transactions in a bundle exceed the maximum.
     #[error("blob gas usage exceeds the limit of {0} gas per block.")]
     Eip4844BlobGasExceeded(u64),
+    /// Thrown if the targeted block number is not in the future.
+    #[error("block number {0} is in the past")]
+    BlockNumberInPast(u64),
+    /// Thrown if a bundle or private transaction contains a blob transaction.
+    #[error("blob transactions are not supported")]
+    BlobTransactionUnsupported,
 }
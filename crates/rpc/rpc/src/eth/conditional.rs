@@ -0,0 +1,166 @@
+//! `eth_sendRawTransactionConditional` implementation.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_types::ErrorObject;
+use reth_rpc_eth_api::L2EthApiExtServer;
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory};
+use reth_transaction_pool::{
+    conditional::{find_known_account_mismatch, KnownAccountMismatch, MaybeConditionalTransaction},
+    PoolTransaction, TransactionOrigin, TransactionPool,
+};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Maximum execution cost of a conditional, see [`TransactionConditional::cost`].
+const MAX_CONDITIONAL_EXECUTION_COST: u64 = 5000;
+
+/// Maximum number of conditionals whose known accounts are checked concurrently.
+const MAX_CONCURRENT_CONDITIONAL_VALIDATIONS: usize = 3;
+
+/// `eth` API extension that accepts transactions with a [`TransactionConditional`].
+///
+/// Conditional transactions are added to the pool as [`TransactionOrigin::Private`], they are not
+/// propagated because peers can't be expected to honor the conditional.
+#[derive(Debug, Clone)]
+pub struct EthConditionalApi<Pool, Provider> {
+    inner: Arc<EthConditionalApiInner<Pool, Provider>>,
+}
+
+impl<Pool, Provider> EthConditionalApi<Pool, Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory,
+{
+    /// Creates a new [`EthConditionalApi`].
+    pub fn new(pool: Pool, provider: Provider) -> Self {
+        Self {
+            inner: Arc::new(EthConditionalApiInner {
+                pool,
+                provider,
+                validation_semaphore: Semaphore::new(MAX_CONCURRENT_CONDITIONAL_VALIDATIONS),
+            }),
+        }
+    }
+
+    /// Validates the conditional against the latest block and state.
+    async fn validate_conditional(
+        &self,
+        condition: &TransactionConditional,
+    ) -> Result<(), TxConditionalError> {
+        let header = self
+            .inner
+            .provider
+            .latest_header()?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::Number(BlockNumberOrTag::Latest)))?;
+
+        // ensure that the condition can still be met by checking the max bounds
+        if condition.has_exceeded_block_number(header.number()) ||
+            condition.has_exceeded_timestamp(header.timestamp())
+        {
+            return Err(TxConditionalError::InvalidCondition)
+        }
+
+        if condition.known_accounts.is_empty() {
+            return Ok(())
+        }
+
+        let _permit = self
+            .inner
+            .validation_semaphore
+            .acquire()
+            .await
+            .map_err(|err| TxConditionalError::Internal(err.to_string()))?;
+        let state = self.inner.provider.state_by_block_hash(header.hash())?;
+        match find_known_account_mismatch(&*state, condition)? {
+            Some(mismatch) => Err(mismatch.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<Pool, Provider> L2EthApiExtServer for EthConditionalApi<Pool, Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + 'static,
+    Pool: TransactionPool<Transaction: MaybeConditionalTransaction> + 'static,
+{
+    /// Handler for `eth_sendRawTransactionConditional`
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256> {
+        if condition.cost() > MAX_CONDITIONAL_EXECUTION_COST {
+            return Err(TxConditionalError::ConditionalCostExceeded.into())
+        }
+
+        let recovered = recover_raw_transaction(&bytes)?;
+        self.validate_conditional(&condition).await?;
+
+        let transaction = <Pool as TransactionPool>::Transaction::from_pooled(recovered)
+            .with_conditional(condition);
+        let hash = self
+            .inner
+            .pool
+            .add_transaction(TransactionOrigin::Private, transaction)
+            .await
+            .map_err(EthApiError::from)?;
+
+        Ok(hash)
+    }
+}
+
+#[derive(Debug)]
+struct EthConditionalApiInner<Pool, Provider> {
+    /// The transaction pool of the node.
+    pool: Pool,
+    /// The provider used to check the conditionals.
+    provider: Provider,
+    /// Limits the number of concurrent known accounts checks.
+    validation_semaphore: Semaphore,
+}
+
+/// Errors thrown when a [`TransactionConditional`] is rejected.
+#[derive(Debug, thiserror::Error)]
+pub enum TxConditionalError {
+    /// The cost of the conditional exceeds the maximum allowed.
+    #[error("conditional cost exceeded maximum allowed")]
+    ConditionalCostExceeded,
+    /// The block number or timestamp bounds are already exceeded.
+    #[error("invalid conditional parameters")]
+    InvalidCondition,
+    /// The state of a known account doesn't match the latest state.
+    #[error(transparent)]
+    KnownAccountMismatch(#[from] KnownAccountMismatch),
+    /// Error looking up the latest block or state.
+    #[error(transparent)]
+    Eth(#[from] EthApiError),
+    /// Internal error
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+impl From<ProviderError> for TxConditionalError {
+    fn from(err: ProviderError) -> Self {
+        Self::Eth(err.into())
+    }
+}
+
+impl From<TxConditionalError> for ErrorObject<'static> {
+    fn from(error: TxConditionalError) -> Self {
+        match error {
+            TxConditionalError::ConditionalCostExceeded |
+            TxConditionalError::InvalidCondition |
+            TxConditionalError::KnownAccountMismatch(_) => {
+                invalid_params_rpc_err(error.to_string())
+            }
+            TxConditionalError::Eth(err) => err.into(),
+            TxConditionalError::Internal(_) => internal_rpc_err(error.to_string()),
+        }
+    }
+}
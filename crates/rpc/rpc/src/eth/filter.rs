@@ -21,13 +21,13 @@ use reth_rpc_eth_types::{
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use reth_storage_api::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderReceipt, TransactionsProvider,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, LogIndexReader,
+    ProviderBlock, ProviderReceipt, TransactionsProvider,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt,
     future::Future,
     iter::StepBy,
@@ -43,7 +43,7 @@ use tracing::{error, trace};
 
 impl<Eth> EngineEthFilter for EthFilter<Eth>
 where
-    Eth: FullEthApiTypes + RpcNodeCoreExt<Provider: BlockIdReader> + 'static,
+    Eth: FullEthApiTypes + RpcNodeCoreExt<Provider: BlockIdReader + LogIndexReader> + 'static,
 {
     /// Returns logs matching given filter object, no query limits
     fn logs(
@@ -170,7 +170,9 @@ where
 
 impl<Eth> EthFilter<Eth>
 where
-    Eth: FullEthApiTypes<Provider: BlockReader + BlockIdReader> + RpcNodeCoreExt + 'static,
+    Eth: FullEthApiTypes<Provider: BlockReader + BlockIdReader + LogIndexReader>
+        + RpcNodeCoreExt
+        + 'static,
 {
     /// Access the underlying provider.
     fn provider(&self) -> &Eth::Provider {
@@ -294,7 +296,7 @@ impl<Eth> EthFilterApiServer<RpcTransaction<Eth::NetworkTypes>> for EthFilter<Et
 where
     Eth: FullEthApiTypes
         + RpcNodeCoreExt<
-            Provider: BlockIdReader,
+            Provider: BlockIdReader + LogIndexReader,
             Primitives: NodePrimitives<
                 SignedTx = <<Eth as RpcNodeCore>::Provider as TransactionsProvider>::Transaction,
             >,
@@ -415,7 +417,9 @@ struct EthFilterInner<Eth: EthApiTypes> {
 
 impl<Eth> EthFilterInner<Eth>
 where
-    Eth: RpcNodeCoreExt<Provider: BlockIdReader, Pool: TransactionPool> + EthApiTypes + 'static,
+    Eth: RpcNodeCoreExt<Provider: BlockIdReader + LogIndexReader, Pool: TransactionPool>
+        + EthApiTypes
+        + 'static,
 {
     /// Access the underlying provider.
     fn provider(&self) -> &Eth::Provider {
@@ -534,8 +538,13 @@ where
             return Err(EthFilterError::InvalidBlockRangeParams)
         }
 
-        if let Some(max_blocks_per_filter) =
-            limits.max_blocks_per_filter.filter(|limit| to_block - from_block > *limit)
+        // Blocks covered by the log index are looked up rather than scanned, so only the
+        // remaining blocks count towards the limit.
+        let indexed = self.indexed_block_range(&filter, from_block, to_block)?;
+        let scan_from = indexed.as_ref().map_or(from_block, |indexed| indexed.end() + 1);
+        if let Some(max_blocks_per_filter) = limits
+            .max_blocks_per_filter
+            .filter(|limit| scan_from <= to_block && to_block - scan_from > *limit)
         {
             return Err(EthFilterError::QueryExceedsMaxBlocks(max_blocks_per_filter))
         }
@@ -543,16 +552,74 @@ where
         let (tx, rx) = oneshot::channel();
         let this = self.clone();
         self.task_spawner.spawn_blocking(Box::pin(async move {
-            let res =
-                this.get_logs_in_block_range_inner(&filter, from_block, to_block, limits).await;
+            let res = this
+                .get_logs_in_block_range_inner(&filter, from_block, to_block, indexed, limits)
+                .await;
             let _ = tx.send(res);
         }));
 
         rx.await.map_err(|_| EthFilterError::InternalError)?
     }
 
+    /// Returns the beginning of the given range that is covered by the log index.
+    ///
+    /// Returns `None` if the index is not available, doesn't cover the start of the range or the
+    /// filter matches logs of any address and topic.
+    fn indexed_block_range(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Option<RangeInclusive<u64>>, EthFilterError> {
+        if filter.address.is_empty() && filter.topics.iter().all(|topics| topics.is_empty()) {
+            return Ok(None)
+        }
+        let Some(index) = self.provider().log_index_range()? else { return Ok(None) };
+        if !index.contains(&from_block) {
+            return Ok(None)
+        }
+        Ok(Some(from_block..=to_block.min(*index.end())))
+    }
+
+    /// Returns the blocks in the given indexed range that contain logs matching the address and
+    /// every topic position of the filter.
+    ///
+    /// The index doesn't record the position of a topic, so the returned blocks may still contain
+    /// no matching log.
+    fn indexed_candidate_blocks(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<u64>,
+    ) -> Result<BTreeSet<u64>, EthFilterError> {
+        let mut criteria = Vec::new();
+        if !filter.address.is_empty() {
+            let mut blocks = BTreeSet::new();
+            for address in filter.address.iter() {
+                blocks.extend(self.provider().log_address_blocks(*address, range.clone())?);
+            }
+            criteria.push(blocks);
+        }
+        for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut blocks = BTreeSet::new();
+            for topic in topics.iter() {
+                blocks.extend(self.provider().log_topic_blocks(*topic, range.clone())?);
+            }
+            criteria.push(blocks);
+        }
+
+        let mut criteria = criteria.into_iter();
+        let mut candidates = criteria.next().unwrap_or_default();
+        for blocks in criteria {
+            candidates.retain(|block| blocks.contains(block));
+        }
+        Ok(candidates)
+    }
+
     /// Returns all logs in the given _inclusive_ range that match the filter
     ///
+    /// The part of the range covered by the log index, if any, is served from the index. The rest
+    /// is scanned by matching the filter against the header blooms.
+    ///
     /// Note: This function uses a mix of blocking db operations for fetching indices and header
     /// ranges and utilizes the rpc cache for optimistically fetching receipts and blocks.
     /// This function is considered blocking and should thus be spawned on a blocking task.
@@ -564,14 +631,42 @@ where
         filter: &Filter,
         from_block: u64,
         to_block: u64,
+        indexed: Option<RangeInclusive<u64>>,
         limits: QueryLimits,
     ) -> Result<Vec<Log>, EthFilterError> {
         let mut all_logs = Vec::new();
+        let mut scan_from = from_block;
+
+        if let Some(indexed) = indexed {
+            scan_from = indexed.end() + 1;
+            // the index is walked in chunks so that only the candidates of one chunk are held at a
+            // time and the response limit is hit before the whole range is looked up
+            for (from, to) in BlockRangeInclusiveIter::new(indexed, self.max_headers_range) {
+                for block_number in self.indexed_candidate_blocks(filter, from..=to)? {
+                    let header = self
+                        .provider()
+                        .sealed_header(block_number)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+                    self.append_block_logs(
+                        &mut all_logs,
+                        filter,
+                        header.num_hash(),
+                        header.timestamp(),
+                        from_block..=to_block,
+                        limits,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        if scan_from > to_block {
+            return Ok(all_logs)
+        }
 
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+        for (from, to) in BlockRangeInclusiveIter::new(scan_from..=to_block, self.max_headers_range)
         {
             let headers = self.provider().headers_range(from..=to)?;
             for (idx, header) in headers
@@ -590,39 +685,66 @@ where
                 };
 
                 let num_hash = BlockNumHash::new(header.number(), block_hash);
-                if let Some((receipts, maybe_block)) =
-                    self.eth_cache().get_receipts_and_maybe_block(num_hash.hash).await?
-                {
-                    append_matching_block_logs(
-                        &mut all_logs,
-                        maybe_block
-                            .map(ProviderOrBlock::Block)
-                            .unwrap_or_else(|| ProviderOrBlock::Provider(self.provider())),
-                        filter,
-                        num_hash,
-                        &receipts,
-                        false,
-                        header.timestamp(),
-                    )?;
-
-                    // size check but only if range is multiple blocks, so we always return all
-                    // logs of a single block
-                    let is_multi_block_range = from_block != to_block;
-                    if let Some(max_logs_per_response) = limits.max_logs_per_response {
-                        if is_multi_block_range && all_logs.len() > max_logs_per_response {
-                            return Err(EthFilterError::QueryExceedsMaxResults {
-                                max_logs: max_logs_per_response,
-                                from_block,
-                                to_block: num_hash.number.saturating_sub(1),
-                            });
-                        }
-                    }
-                }
+                self.append_block_logs(
+                    &mut all_logs,
+                    filter,
+                    num_hash,
+                    header.timestamp(),
+                    from_block..=to_block,
+                    limits,
+                )
+                .await?;
             }
         }
 
         Ok(all_logs)
     }
+
+    /// Appends the logs of the given block that match the filter.
+    ///
+    /// Returns an error if the logs exceed the configured limit for a query of the given range.
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter: &Filter,
+        num_hash: BlockNumHash,
+        timestamp: u64,
+        range: RangeInclusive<u64>,
+        limits: QueryLimits,
+    ) -> Result<(), EthFilterError> {
+        let Some((receipts, maybe_block)) =
+            self.eth_cache().get_receipts_and_maybe_block(num_hash.hash).await?
+        else {
+            return Ok(())
+        };
+
+        append_matching_block_logs(
+            all_logs,
+            maybe_block
+                .map(ProviderOrBlock::Block)
+                .unwrap_or_else(|| ProviderOrBlock::Provider(self.provider())),
+            filter,
+            num_hash,
+            &receipts,
+            false,
+            timestamp,
+        )?;
+
+        // size check but only if range is multiple blocks, so we always return all
+        // logs of a single block
+        let is_multi_block_range = range.start() != range.end();
+        if let Some(max_logs_per_response) = limits.max_logs_per_response {
+            if is_multi_block_range && all_logs.len() > max_logs_per_response {
+                return Err(EthFilterError::QueryExceedsMaxResults {
+                    max_logs: max_logs_per_response,
+                    from_block: *range.start(),
+                    to_block: num_hash.number.saturating_sub(1),
+                });
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// All active filters
@@ -758,14 +880,14 @@ enum FilterKind<T> {
 
 /// An iterator that yields _inclusive_ block ranges of a given step size
 #[derive(Debug)]
-struct BlockRangeInclusiveIter {
+pub(crate) struct BlockRangeInclusiveIter {
     iter: StepBy<RangeInclusive<u64>>,
     step: u64,
     end: u64,
 }
 
 impl BlockRangeInclusiveIter {
-    fn new(range: RangeInclusive<u64>, step: u64) -> Self {
+    pub(crate) fn new(range: RangeInclusive<u64>, step: u64) -> Self {
         Self { end: *range.end(), iter: range.step_by(step as usize + 1), step }
     }
 }
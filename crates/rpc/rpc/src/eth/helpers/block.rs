@@ -4,7 +4,7 @@ use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_rpc_types_eth::{BlockId, TransactionReceipt};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_evm::ConfigureEvm;
-use reth_primitives_traits::{BlockBody, NodePrimitives};
+use reth_primitives_traits::NodePrimitives;
 use reth_rpc_eth_api::{
     helpers::{EthBlocks, LoadBlock, LoadPendingBlock, LoadReceipt, SpawnBlocking},
     types::RpcTypes,
@@ -44,9 +44,7 @@ where
             let blob_params = self.provider().chain_spec().blob_params_at_timestamp(timestamp);
 
             return block
-                .body()
-                .transactions()
-                .iter()
+                .transactions_recovered()
                 .zip(receipts.iter())
                 .enumerate()
                 .map(|(idx, (tx, receipt))| {
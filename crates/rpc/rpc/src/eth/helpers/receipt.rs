@@ -1,8 +1,9 @@
 //! Builds an RPC receipt response w.r.t. data layout of network.
 
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::transaction::{Recovered, TransactionMeta};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
+use reth_primitives_traits::SignerRecoverable;
 use reth_rpc_eth_api::{helpers::LoadReceipt, FromEthApiError, RpcNodeCoreExt, RpcReceipt};
 use reth_rpc_eth_types::{EthApiError, EthReceiptBuilder};
 use reth_storage_api::{BlockReader, ReceiptProvider, TransactionsProvider};
@@ -24,15 +25,23 @@ where
         receipt: Receipt,
     ) -> Result<RpcReceipt<Self::NetworkTypes>, Self::Error> {
         let hash = meta.block_hash;
-        // get all receipts for the block
-        let all_receipts = self
+        // get the block and all receipts for the block
+        let (block, all_receipts) = self
             .cache()
-            .get_receipts(hash)
+            .get_block_and_receipts(hash)
             .await
             .map_err(Self::Error::from_eth_err)?
             .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
         let blob_params = self.provider().chain_spec().blob_params_at_timestamp(meta.timestamp);
 
-        Ok(EthReceiptBuilder::new(&tx, meta, &receipt, &all_receipts, blob_params)?.build())
+        // the sender is taken from the block, because transactions of impersonated accounts on dev
+        // nodes carry a signature that does not recover to their sender
+        let sender = match block.senders().get(meta.index as usize) {
+            Some(sender) => *sender,
+            None => tx.recover_signer_unchecked().map_err(Self::Error::from_eth_err)?,
+        };
+        let tx = Recovered::new_unchecked(&tx, sender);
+
+        Ok(EthReceiptBuilder::new(tx, meta, &receipt, &all_receipts, blob_params)?.build())
     }
 }
@@ -1,15 +1,20 @@
 //! An abstraction over ethereum signers.
 
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use crate::EthApi;
+use alloy_consensus::{SignableTransaction, TxEnvelope};
 use alloy_dyn_abi::TypedData;
 use alloy_eips::eip2718::Decodable2718;
 use alloy_network::{eip2718::Encodable2718, EthereumWallet, TransactionBuilder};
-use alloy_primitives::{eip191_hash_message, Address, Signature, B256};
+use alloy_primitives::{eip191_hash_message, uint, Address, Signature, B256, U256};
 use alloy_rpc_types_eth::TransactionRequest;
 use alloy_signer::SignerSync;
 use alloy_signer_local::PrivateKeySigner;
+use parking_lot::RwLock;
 use reth_rpc_eth_api::helpers::{signer::Result, AddDevSigners, EthSigner};
 use reth_rpc_eth_types::SignError;
 use reth_storage_api::BlockReader;
@@ -104,10 +109,111 @@ impl<T: Decodable2718> EthSigner<T> for DevSigner {
     }
 }
 
+/// The x coordinate of the generator point of secp256k1.
+///
+/// Used as the `r` value of the signatures of impersonated transactions: a signature with this
+/// `r` always recovers to some address, so the transactions can be decoded by everything that
+/// recovers senders.
+const GENERATOR_X: U256 =
+    uint!(0x79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798_U256);
+
+/// Accounts whose transactions are accepted without a signature of the account.
+///
+/// Transactions sent through `eth_sendTransaction` for an impersonated account carry a signature
+/// that does not recover to the account, the sender is attached when the transaction enters the
+/// pool. They are only valid in blocks built by the node itself, which are inserted into the
+/// engine together with their senders.
+///
+/// Blocks that contain impersonated transactions can not be re-executed: recovering the senders
+/// of the transactions yields different accounts. Such blocks are rejected by peers that sync
+/// the chain of the node and by unwinding and re-running the stages of the node itself, so
+/// impersonation is only meant for dev nodes that do not share their chain.
+#[derive(Debug, Clone, Default)]
+pub struct ImpersonatedAccounts {
+    inner: Arc<RwLock<ImpersonatedAccountsInner>>,
+}
+
+#[derive(Debug, Default)]
+struct ImpersonatedAccountsInner {
+    /// Accounts impersonated explicitly.
+    accounts: HashSet<Address>,
+    /// Whether all accounts are impersonated.
+    auto: bool,
+}
+
+impl ImpersonatedAccounts {
+    /// Starts impersonating the given account.
+    pub fn impersonate(&self, account: Address) {
+        self.inner.write().accounts.insert(account);
+    }
+
+    /// Stops impersonating the given account.
+    ///
+    /// The account remains impersonated if all accounts are impersonated.
+    pub fn stop_impersonating(&self, account: Address) {
+        self.inner.write().accounts.remove(&account);
+    }
+
+    /// Sets whether all accounts are impersonated.
+    pub fn set_auto_impersonate(&self, enabled: bool) {
+        self.inner.write().auto = enabled;
+    }
+
+    /// Returns `true` if the given account is impersonated.
+    pub fn is_impersonated(&self, account: &Address) -> bool {
+        let inner = self.inner.read();
+        inner.auto || inner.accounts.contains(account)
+    }
+
+    /// Returns the signature attached to the transactions of the given account.
+    ///
+    /// The `s` value is derived from the account, so transactions of different accounts with the
+    /// same fields have different hashes.
+    fn signature(account: &Address) -> Signature {
+        let s = U256::from_be_slice(account.as_slice()) + U256::from(1);
+        Signature::new(GENERATOR_X, s, false)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: Decodable2718> EthSigner<T> for ImpersonatedAccounts {
+    fn accounts(&self) -> Vec<Address> {
+        self.inner.read().accounts.iter().copied().collect()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.is_impersonated(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> Result<Signature> {
+        Err(SignError::NoAccount)
+    }
+
+    async fn sign_transaction(&self, request: TransactionRequest, address: &Address) -> Result<T> {
+        if !self.is_impersonated(address) {
+            return Err(SignError::NoAccount)
+        }
+
+        let tx = request.build_typed_tx().map_err(|_| SignError::InvalidTransactionRequest)?;
+        let txn_envelope = TxEnvelope::from(tx.into_signed(Self::signature(address)));
+
+        // decode transaction into signed transaction type
+        let encoded = txn_envelope.encoded_2718();
+        let txn_signed = T::decode_2718(&mut encoded.as_ref())
+            .map_err(|_| SignError::InvalidTransactionRequest)?;
+
+        Ok(txn_signed)
+    }
+
+    fn sign_typed_data(&self, _address: Address, _payload: &TypedData) -> Result<Signature> {
+        Err(SignError::NoAccount)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_consensus::Transaction;
+    use alloy_consensus::{transaction::SignerRecoverable, Transaction};
     use alloy_primitives::{Bytes, U256};
     use alloy_rpc_types_eth::TransactionInput;
     use reth_ethereum_primitives::TransactionSigned;
@@ -261,4 +367,44 @@ mod tests {
 
         assert_eq!(Bytes::from(message.to_vec()), txn_signed.unwrap().input().0);
     }
+
+    #[tokio::test]
+    async fn test_impersonated_transaction() {
+        let impersonated = ImpersonatedAccounts::default();
+        let alice = Address::with_last_byte(1);
+        let bob = Address::with_last_byte(2);
+        let sign = |from: Address| {
+            let request = TransactionRequest {
+                chain_id: Some(1u64),
+                from: Some(from),
+                to: Some(TxKind::Call(Address::ZERO)),
+                gas: Some(21000),
+                gas_price: Some(1000u128),
+                value: Some(U256::from(1000)),
+                nonce: Some(0u64),
+                ..Default::default()
+            };
+            let impersonated = impersonated.clone();
+            async move {
+                EthSigner::<TransactionSigned>::sign_transaction(&impersonated, request, &from)
+                    .await
+            }
+        };
+        assert!(sign(alice).await.is_err());
+
+        impersonated.impersonate(alice);
+        assert!(EthSigner::<TransactionSigned>::is_signer_for(&impersonated, &alice));
+        assert!(!EthSigner::<TransactionSigned>::is_signer_for(&impersonated, &bob));
+        let alice_tx = sign(alice).await.unwrap();
+        // the signature recovers, to an address other than the impersonated account
+        assert_ne!(alice_tx.recover_signer().unwrap(), alice);
+
+        impersonated.set_auto_impersonate(true);
+        let bob_tx = sign(bob).await.unwrap();
+        assert_ne!(alice_tx.tx_hash(), bob_tx.tx_hash());
+
+        impersonated.set_auto_impersonate(false);
+        impersonated.stop_impersonating(alice);
+        assert!(sign(alice).await.is_err());
+    }
 }
@@ -2,6 +2,7 @@
 
 pub mod builder;
 pub mod bundle;
+pub mod conditional;
 pub mod core;
 pub mod filter;
 pub mod helpers;
@@ -11,12 +12,13 @@ pub mod sim_bundle;
 /// Implementation of `eth` namespace API.
 pub use builder::EthApiBuilder;
 pub use bundle::EthBundle;
+pub use conditional::EthConditionalApi;
 pub use core::{EthApi, EthApiFor};
 pub use filter::EthFilter;
 pub use pubsub::EthPubSub;
 
 pub use helpers::{
-    signer::DevSigner,
+    signer::{DevSigner, ImpersonatedAccounts},
     types::{EthTxBuilder, EthereumEthApiTypes},
 };
 
@@ -0,0 +1,142 @@
+use crate::{anvil::miner_err, eth::ImpersonatedAccounts, AnvilApi};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types_anvil::{Forking, Metadata};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_engine_local::LocalMinerHandle;
+use reth_rpc_api::{AnvilApiServer, HardhatApiServer};
+use reth_rpc_eth_api::{
+    helpers::{EthBlocks, EthFees},
+    FullEthApiTypes, RpcNodeCore,
+};
+use reth_storage_api::{
+    BlockReaderIdExt, ModifiedAccountsReader, StateProviderFactory, StorageChangeSetReader,
+};
+use reth_transaction_pool::TransactionPool;
+
+/// `hardhat` API implementation for dev nodes.
+///
+/// The methods that Hardhat shares with Anvil are served by an [`AnvilApi`].
+#[derive(Debug)]
+pub struct HardhatApi<Eth> {
+    /// The anvil API that serves the shared methods.
+    anvil: AnvilApi<Eth>,
+    /// Handle to the local miner of the node.
+    miner: LocalMinerHandle,
+}
+
+impl<Eth> HardhatApi<Eth> {
+    /// Creates a new instance of `HardhatApi`.
+    ///
+    /// Accounts are impersonated through the given `impersonated` accounts, see
+    /// [`AnvilApi::new`].
+    pub fn new(eth: Eth, miner: LocalMinerHandle, impersonated: ImpersonatedAccounts) -> Self {
+        Self { anvil: AnvilApi::new(eth, miner.clone(), impersonated), miner }
+    }
+}
+
+#[async_trait]
+impl<Eth> HardhatApiServer for HardhatApi<Eth>
+where
+    Eth: EthBlocks
+        + EthFees
+        + FullEthApiTypes
+        + RpcNodeCore<
+            Pool: TransactionPool,
+            Provider: BlockReaderIdExt
+                          + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+                          + ModifiedAccountsReader
+                          + StorageChangeSetReader
+                          + StateProviderFactory,
+        > + 'static,
+{
+    /// Handler for `hardhat_dropTransaction`
+    async fn hardhat_drop_transaction(&self, tx_hash: B256) -> RpcResult<bool> {
+        Ok(self.anvil.anvil_drop_transaction(tx_hash).await?.is_some())
+    }
+
+    /// Handler for `hardhat_impersonateAccount`
+    async fn hardhat_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.anvil.anvil_impersonate_account(address).await
+    }
+
+    /// Handler for `hardhat_getAutomine`
+    async fn hardhat_get_automine(&self) -> RpcResult<bool> {
+        self.anvil.anvil_get_automine().await
+    }
+
+    /// Handler for `hardhat_metadata`
+    async fn hardhat_metadata(&self) -> RpcResult<Metadata> {
+        self.anvil.anvil_metadata().await
+    }
+
+    /// Handler for `hardhat_mine`
+    async fn hardhat_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.anvil.anvil_mine(blocks, interval).await
+    }
+
+    /// Handler for `hardhat_reset`
+    async fn hardhat_reset(&self, fork: Option<Forking>) -> RpcResult<()> {
+        self.anvil.anvil_reset(fork).await
+    }
+
+    /// Handler for `hardhat_setBalance`
+    async fn hardhat_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.anvil.anvil_set_balance(address, balance).await
+    }
+
+    /// Handler for `hardhat_setCode`
+    async fn hardhat_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.anvil.anvil_set_code(address, code).await
+    }
+
+    /// Handler for `hardhat_setCoinbase`
+    async fn hardhat_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        self.anvil.anvil_set_coinbase(address).await
+    }
+
+    /// Handler for `hardhat_setLoggingEnabled`
+    async fn hardhat_set_logging_enabled(&self, enabled: bool) -> RpcResult<()> {
+        self.anvil.anvil_set_logging_enabled(enabled).await
+    }
+
+    /// Handler for `hardhat_setMinGasPrice`
+    async fn hardhat_set_min_gas_price(&self, gas_price: U256) -> RpcResult<()> {
+        self.anvil.anvil_set_min_gas_price(gas_price).await
+    }
+
+    /// Handler for `hardhat_setNextBlockBaseFeePerGas`
+    async fn hardhat_set_next_block_base_fee_per_gas(
+        &self,
+        base_fee_per_gas: U256,
+    ) -> RpcResult<()> {
+        self.anvil.anvil_set_next_block_base_fee_per_gas(base_fee_per_gas).await
+    }
+
+    /// Handler for `hardhat_setPrevRandao`
+    async fn hardhat_set_prev_randao(&self, prev_randao: B256) -> RpcResult<()> {
+        self.miner.set_next_prev_randao(prev_randao).await.map_err(miner_err)
+    }
+
+    /// Handler for `hardhat_setNonce`
+    async fn hardhat_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        self.anvil.anvil_set_nonce(address, nonce).await
+    }
+
+    /// Handler for `hardhat_setStorageAt`
+    async fn hardhat_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<()> {
+        self.anvil.anvil_set_storage_at(address, slot, value).await?;
+        Ok(())
+    }
+
+    /// Handler for `hardhat_stopImpersonatingAccount`
+    async fn hardhat_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.anvil.anvil_stop_impersonating_account(address).await
+    }
+}
@@ -33,9 +33,12 @@ use pin_project as _;
 use tower as _;
 
 mod admin;
+mod anvil;
+mod bridge;
 mod debug;
 mod engine;
 pub mod eth;
+mod hardhat;
 mod miner;
 mod net;
 mod otterscan;
@@ -47,9 +50,12 @@ mod validation;
 mod web3;
 
 pub use admin::AdminApi;
+pub use anvil::AnvilApi;
+pub use bridge::BridgeInboxApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
+pub use hardhat::HardhatApi;
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
@@ -1,32 +1,37 @@
 use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
-use alloy_rpc_types_eth::{BlockTransactions, TransactionReceipt};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, B256, U256};
+use alloy_rpc_types_eth::{BlockTransactions, Transaction, TransactionReceipt};
 use alloy_rpc_types_trace::{
     otterscan::{
         BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
         OtsReceipt, OtsTransactionReceipt, TraceEntry, TransactionsWithReceipts,
     },
-    parity::{Action, CreateAction, CreateOutput, TraceOutput},
+    parity::{Action, CreateAction, CreateOutput, TraceOutput, TransactionTrace},
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    FullEthApiTypes, RpcBlock, RpcHeader, RpcNodeCore, RpcReceipt, RpcTransaction,
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
+use reth_storage_api::{errors::ProviderResult, AddressAppearancesReader};
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
     transfer::{TransferInspector, TransferKind},
 };
+use std::{collections::VecDeque, ops::RangeInclusive};
 
 const API_LEVEL: u64 = 8;
 
+/// Number of blocks fetched from the address appearances index at once.
+const APPEARANCES_BATCH_SIZE: usize = 100;
+
 /// Otterscan API.
 #[derive(Debug)]
 pub struct OtterscanApi<Eth> {
@@ -62,6 +67,287 @@ where
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+        > + EthTransactions
+        + TraceExt
+        + RpcNodeCore<Provider: AddressAppearancesReader>
+        + 'static,
+{
+    /// Searches the transactions `address` was involved in, starting from the block
+    /// `block_number` (exclusive) in the given direction.
+    ///
+    /// Candidate blocks are taken from the address appearances index and each of them is traced
+    /// to find the transactions that called, were called by or created `address`. Blocks that
+    /// are not indexed yet are traced unconditionally. A block is never split across pages, so a
+    /// page may contain more than `page_size` transactions.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        page_size: usize,
+        direction: SearchDirection,
+    ) -> RpcResult<TransactionsWithReceipts> {
+        let provider = self.eth.provider();
+        let indexed_to = provider
+            .address_appearances_indexed_to()
+            .map_err(EthApiError::from)?
+            .ok_or_else(|| internal_rpc_err("address appearances index is not enabled"))?;
+        let latest = self.eth.block_number()?.saturating_to::<BlockNumber>();
+
+        // `0` means the search starts from the chain tip, or from genesis respectively
+        let from_boundary = block_number == 0;
+        let mut candidates = match direction {
+            SearchDirection::Before => {
+                let end = if from_boundary { latest } else { block_number - 1 };
+                CandidateBlocks {
+                    direction,
+                    address,
+                    indexed_cursor: Some(end.min(indexed_to) + 1),
+                    unindexed: Some(indexed_to + 1..=end),
+                }
+            }
+            SearchDirection::After => CandidateBlocks {
+                direction,
+                address,
+                indexed_cursor: (block_number < indexed_to).then_some(block_number),
+                unindexed: Some(block_number.max(indexed_to) + 1..=latest),
+            },
+        };
+
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        let mut batch = VecDeque::new();
+        let mut exhausted = loop {
+            if batch.is_empty() {
+                batch = candidates.next_batch(provider).map_err(EthApiError::from)?.into();
+            }
+            let Some(block) = batch.pop_front() else { break true };
+
+            let mut block_txs = self.block_transactions_with_address(address, block).await?;
+            if direction == SearchDirection::Before {
+                block_txs.reverse();
+            }
+            for (tx, receipt) in block_txs {
+                txs.push(tx);
+                receipts.push(receipt);
+            }
+
+            if txs.len() >= page_size {
+                break false
+            }
+        };
+        // the page may have been filled by the last candidate block
+        if !exhausted && batch.is_empty() {
+            exhausted = candidates.next_batch(provider).map_err(EthApiError::from)?.is_empty();
+        }
+
+        // pages are always ordered from the newest to the oldest transaction
+        let (first_page, last_page) = match direction {
+            SearchDirection::Before => (from_boundary, exhausted),
+            SearchDirection::After => {
+                txs.reverse();
+                receipts.reverse();
+                (exhausted, from_boundary)
+            }
+        };
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
+    }
+
+    /// Returns the transactions of the block that involve `address`, with their receipts, ordered
+    /// by their index in the block.
+    async fn block_transactions_with_address(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> RpcResult<Vec<(Transaction, OtsTransactionReceipt)>> {
+        let tx_hashes = self
+            .eth
+            .trace_block_with(
+                block_number.into(),
+                None,
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _, _| {
+                    let tx_hash = tx_info.hash;
+                    let traces =
+                        inspector.into_parity_builder().into_localized_transaction_traces(tx_info);
+                    let involved =
+                        traces.iter().any(|trace| trace_involves_address(&trace.trace, address));
+                    Ok(tx_hash.filter(|_| involved))
+                },
+            )
+            .await
+            .map_err(Into::into)?
+            .unwrap_or_default();
+
+        let tx_hashes = tx_hashes.into_iter().flatten().collect::<Vec<_>>();
+        if tx_hashes.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let timestamp =
+            self.eth.header_by_number(block_number.into()).await?.map(|header| header.timestamp());
+
+        let mut transactions = Vec::with_capacity(tx_hashes.len());
+        for tx_hash in tx_hashes {
+            let tx = EthApiServer::transaction_by_hash(&self.eth, tx_hash)
+                .await?
+                .ok_or(EthApiError::TransactionNotFound)?;
+            let receipt = EthApiServer::transaction_receipt(&self.eth, tx_hash)
+                .await?
+                .ok_or(EthApiError::ReceiptsNotFound(block_number.into()))?;
+            let receipt = ots_receipt(&receipt, tx.ty(), timestamp);
+
+            // the otterscan response only supports ethereum transactions
+            let tx = serde_json::to_value(tx)
+                .and_then(serde_json::from_value)
+                .map_err(|err| internal_rpc_err(format!("failed to convert transaction: {err}")))?;
+            transactions.push((tx, receipt));
+        }
+
+        Ok(transactions)
+    }
+}
+
+/// Direction of `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchDirection {
+    /// Towards genesis.
+    Before,
+    /// Towards the chain tip.
+    After,
+}
+
+/// Blocks an address may have appeared in, yielded in the order they are searched.
+#[derive(Debug)]
+struct CandidateBlocks {
+    direction: SearchDirection,
+    address: Address,
+    /// Exclusive bound of the next index lookup, `None` once the index is exhausted.
+    indexed_cursor: Option<BlockNumber>,
+    /// Blocks above the index checkpoint, which are searched without consulting the index.
+    unindexed: Option<RangeInclusive<BlockNumber>>,
+}
+
+impl CandidateBlocks {
+    /// Returns the next batch of candidate blocks, or an empty batch if there are none left.
+    ///
+    /// Blocks that are not indexed yet are closer to the tip, so they're yielded first when
+    /// searching backwards and last when searching forwards.
+    fn next_batch(
+        &mut self,
+        provider: &impl AddressAppearancesReader,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        if self.direction == SearchDirection::Before {
+            let blocks = self.next_unindexed_batch();
+            if !blocks.is_empty() {
+                return Ok(blocks)
+            }
+        }
+
+        if let Some(cursor) = self.indexed_cursor.take() {
+            let (address, limit) = (self.address, APPEARANCES_BATCH_SIZE);
+            let blocks = match self.direction {
+                SearchDirection::Before => {
+                    provider.address_appearances_before(address, cursor, limit)
+                }
+                SearchDirection::After => {
+                    provider.address_appearances_after(address, cursor, limit)
+                }
+            }?;
+            if blocks.len() == APPEARANCES_BATCH_SIZE {
+                self.indexed_cursor = blocks.last().copied();
+            }
+            if !blocks.is_empty() {
+                return Ok(blocks)
+            }
+        }
+
+        Ok(self.next_unindexed_batch())
+    }
+
+    /// Takes the next [`APPEARANCES_BATCH_SIZE`] blocks in search order from the blocks that are
+    /// not indexed yet.
+    fn next_unindexed_batch(&mut self) -> Vec<BlockNumber> {
+        let Some(range) = self.unindexed.take().filter(|range| !range.is_empty()) else {
+            return Vec::new()
+        };
+        let (start, end) = range.into_inner();
+        let batch_size = APPEARANCES_BATCH_SIZE as u64;
+        match self.direction {
+            SearchDirection::Before => {
+                let batch_start = end.saturating_sub(batch_size - 1).max(start);
+                if batch_start > start {
+                    self.unindexed = Some(start..=batch_start - 1);
+                }
+                (batch_start..=end).rev().collect()
+            }
+            SearchDirection::After => {
+                let batch_end = start.saturating_add(batch_size - 1).min(end);
+                if batch_end < end {
+                    self.unindexed = Some(batch_end + 1..=end);
+                }
+                (start..=batch_end).collect()
+            }
+        }
+    }
+}
+
+/// Returns true if the trace was made by or to `address`, or created it.
+fn trace_involves_address(trace: &TransactionTrace, address: Address) -> bool {
+    let in_action = match &trace.action {
+        Action::Call(call) => call.from == address || call.to == address,
+        Action::Create(create) => create.from == address,
+        Action::Selfdestruct(selfdestruct) => {
+            selfdestruct.address == address || selfdestruct.refund_address == address
+        }
+        Action::Reward(reward) => reward.author == address,
+    };
+    in_action ||
+        matches!(
+            &trace.result,
+            Some(TraceOutput::Create(CreateOutput { address: created, .. })) if *created == address
+        )
+}
+
+/// Converts a receipt into the trimmed down receipt returned by otterscan.
+fn ots_receipt<R: ReceiptResponse>(
+    receipt: &R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
     for OtterscanApi<Eth>
@@ -73,6 +359,7 @@ where
             RpcHeader<Eth::NetworkTypes>,
         > + EthTransactions
         + TraceExt
+        + RpcNodeCore<Provider: AddressAppearancesReader>
         + 'static,
 {
     /// Handler for `ots_getHeaderByNumber` and `erigon_getHeaderByNumber`
@@ -245,32 +532,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_receipt(&receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -282,21 +544,25 @@ where
     /// Handler for `ots_searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        // tags are treated like `0`, which starts the search at the chain boundary
+        let block_number = block_number.into_inner().as_number().unwrap_or_default();
+        self.search_transactions(address, block_number, page_size, SearchDirection::Before).await
     }
 
     /// Handler for `ots_searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        // tags are treated like `0`, which starts the search at the chain boundary
+        let block_number = block_number.into_inner().as_number().unwrap_or_default();
+        self.search_transactions(address, block_number, page_size, SearchDirection::After).await
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -377,3 +643,37 @@ where
         Ok(found)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::NoopProvider;
+
+    fn candidates(direction: SearchDirection) -> CandidateBlocks {
+        CandidateBlocks {
+            direction,
+            address: Address::ZERO,
+            indexed_cursor: None,
+            unindexed: Some(1..=250),
+        }
+    }
+
+    fn all_batches(mut candidates: CandidateBlocks) -> Vec<Vec<BlockNumber>> {
+        let provider = NoopProvider::default();
+        std::iter::from_fn(|| {
+            Some(candidates.next_batch(&provider).unwrap()).filter(|batch| !batch.is_empty())
+        })
+        .collect()
+    }
+
+    #[test]
+    fn pages_unindexed_blocks() {
+        let batches = all_batches(candidates(SearchDirection::Before));
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), [100, 100, 50]);
+        assert_eq!(batches.concat(), (1..=250).rev().collect::<Vec<_>>());
+
+        let batches = all_batches(candidates(SearchDirection::After));
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), [100, 100, 50]);
+        assert_eq!(batches.concat(), (1..=250).collect::<Vec<_>>());
+    }
+}
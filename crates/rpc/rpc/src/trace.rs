@@ -1,14 +1,15 @@
+use crate::eth::filter::BlockRangeInclusiveIter;
 use alloy_consensus::BlockHeader as _;
 use alloy_eips::BlockId;
 use alloy_evm::block::calc::{base_block_reward_pre_merge, block_reward, ommer_reward};
-use alloy_primitives::{map::HashSet, Bytes, B256, U256};
+use alloy_primitives::{map::HashSet, Address, Bytes, B256, U256};
 use alloy_rpc_types_eth::{
     state::{EvmOverrides, StateOverride},
     transaction::TransactionRequest,
     BlockOverrides, Index,
 };
 use alloy_rpc_types_trace::{
-    filter::TraceFilter,
+    filter::{TraceFilter, TraceFilterMatcher, TraceFilterMode},
     opcode::{BlockOpcodeGas, TransactionOpcodeGas},
     parity::*,
     tracerequest::TraceCallRequest,
@@ -25,7 +26,7 @@ use reth_rpc_eth_api::{
     FromEthApiError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction, EthConfig};
-use reth_storage_api::{BlockNumReader, BlockReader};
+use reth_storage_api::{BlockNumReader, TraceIndexReader};
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
 use revm::DatabaseCommit;
@@ -33,7 +34,7 @@ use revm_inspectors::{
     opcode::OpcodeGasInspector,
     tracing::{parity::populate_state_diff, TracingInspector, TracingInspectorConfig},
 };
-use std::sync::Arc;
+use std::{collections::BTreeSet, ops::RangeInclusive, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `trace` API implementation.
@@ -346,12 +347,15 @@ impl<Eth> TraceApi<Eth>
 where
     // tracing methods read from mempool, hence `LoadBlock` trait bound via
     // `TraceExt`
-    Eth: TraceExt + 'static,
+    Eth: TraceExt + RpcNodeCore<Provider: TraceIndexReader> + 'static,
 {
     /// Returns all transaction traces that match the given filter.
     ///
     /// This is similar to [`Self::trace_block`] but only returns traces for transactions that match
     /// the filter.
+    ///
+    /// If the filter restricts the addresses and the trace index covers the start of the range,
+    /// only the indexed blocks in which one of the addresses appears are traced.
     pub async fn trace_filter(
         &self,
         filter: TraceFilter,
@@ -375,23 +379,159 @@ where
             .into())
         }
 
+        // Blocks covered by the trace index are looked up rather than traced one by one, so only
+        // the remaining blocks count towards the limit.
+        let indexed = self.indexed_block_range(&filter, start, end)?;
+        let trace_from = indexed.as_ref().map_or(start, |indexed| indexed.end() + 1);
+
         // ensure that the range is not too large, since we need to fetch all blocks in the range
-        let distance = end.saturating_sub(start);
-        if distance > self.inner.eth_config.max_trace_filter_blocks {
-            return Err(EthApiError::InvalidParams(
+        let max_blocks = self.inner.eth_config.max_trace_filter_blocks;
+        let range_too_large = || {
+            EthApiError::InvalidParams(
                 "Block range too large; currently limited to 100 blocks".to_string(),
             )
-            .into())
+        };
+        if trace_from <= end && end - trace_from > max_blocks {
+            return Err(range_too_large().into())
+        }
+
+        // The number of matching traces after which no more blocks need to be traced.
+        let limit = count.map(|count| after.unwrap_or(0) as usize + count as usize);
+        let limit_reached = |traces: &Vec<LocalizedTransactionTrace>| {
+            limit.is_some_and(|limit| traces.len() >= limit)
+        };
+
+        // The blocks looked up in the index count towards the same limit as the traced range. The
+        // index is walked in chunks, so a query stops looking up blocks once enough traces matched.
+        let mut traced_blocks = if trace_from <= end { end - trace_from + 1 } else { 0 };
+        let mut all_traces = Vec::new();
+        if let Some(indexed) = indexed {
+            for (from, to) in BlockRangeInclusiveIter::new(indexed, max_blocks) {
+                let block_numbers = self.indexed_candidate_blocks(&filter, from..=to)?;
+                traced_blocks += block_numbers.len() as u64;
+                if traced_blocks > max_blocks + 1 {
+                    return Err(range_too_large().into())
+                }
+                self.append_matching_traces(&mut all_traces, &block_numbers, &matcher).await?;
+                if limit_reached(&all_traces) {
+                    break
+                }
+            }
+        }
+        if trace_from <= end && !limit_reached(&all_traces) {
+            let block_numbers = (trace_from..=end).collect::<Vec<_>>();
+            self.append_matching_traces(&mut all_traces, &block_numbers, &matcher).await?;
+        }
+
+        // Skips the first `after` number of matching traces.
+        // If `after` is greater than or equal to the number of matched traces, it returns an empty
+        // array.
+        if let Some(after) = after.map(|a| a as usize) {
+            if after < all_traces.len() {
+                all_traces.drain(..after);
+            } else {
+                return Ok(vec![])
+            }
+        }
+
+        // Return at most `count` of traces
+        if let Some(count) = count {
+            let count = count as usize;
+            if count < all_traces.len() {
+                all_traces.truncate(count);
+            }
+        };
+
+        Ok(all_traces)
+    }
+
+    /// Returns the beginning of the given range that is covered by the trace index.
+    ///
+    /// Returns `None` if the index is not available, doesn't cover the start of the range or the
+    /// filter may match traces of any address.
+    fn indexed_block_range(
+        &self,
+        filter: &TraceFilter,
+        start: u64,
+        end: u64,
+    ) -> Result<Option<RangeInclusive<u64>>, Eth::Error> {
+        let restricted = match filter.mode {
+            TraceFilterMode::Union => {
+                !filter.from_address.is_empty() && !filter.to_address.is_empty()
+            }
+            TraceFilterMode::Intersection => {
+                !filter.from_address.is_empty() || !filter.to_address.is_empty()
+            }
+        };
+        if !restricted {
+            return Ok(None)
+        }
+        let Some(index) = self.provider().trace_index_range().map_err(Eth::Error::from_eth_err)?
+        else {
+            return Ok(None)
+        };
+        if !index.contains(&start) {
+            return Ok(None)
         }
+        Ok(Some(start..=end.min(*index.end())))
+    }
 
-        // fetch all blocks in that range
-        let blocks = self
-            .provider()
-            .recovered_block_range(start..=end)
-            .map_err(Eth::Error::from_eth_err)?
-            .into_iter()
-            .map(Arc::new)
-            .collect::<Vec<_>>();
+    /// Returns the blocks in the given indexed range in which the addresses of the filter appear,
+    /// in ascending order.
+    ///
+    /// The index doesn't record whether an address was the sender or the recipient of a call, so
+    /// the returned blocks may still contain no matching trace.
+    fn indexed_candidate_blocks(
+        &self,
+        filter: &TraceFilter,
+        range: RangeInclusive<u64>,
+    ) -> Result<Vec<u64>, Eth::Error> {
+        let address_blocks = |addresses: &[Address]| -> Result<Option<BTreeSet<u64>>, Eth::Error> {
+            if addresses.is_empty() {
+                return Ok(None)
+            }
+            let mut blocks = BTreeSet::new();
+            for address in addresses {
+                blocks.extend(
+                    self.provider()
+                        .trace_address_blocks(*address, range.clone())
+                        .map_err(Eth::Error::from_eth_err)?,
+                );
+            }
+            Ok(Some(blocks))
+        };
+
+        let from_blocks = address_blocks(&filter.from_address)?;
+        let to_blocks = address_blocks(&filter.to_address)?;
+        let candidates = match (from_blocks, to_blocks) {
+            (Some(from_blocks), Some(to_blocks)) => match filter.mode {
+                TraceFilterMode::Union => from_blocks.union(&to_blocks).copied().collect(),
+                TraceFilterMode::Intersection => {
+                    from_blocks.intersection(&to_blocks).copied().collect()
+                }
+            },
+            (Some(blocks), None) | (None, Some(blocks)) => blocks.into_iter().collect(),
+            (None, None) => Vec::new(),
+        };
+        Ok(candidates)
+    }
+
+    /// Traces the given blocks and appends the traces matching the filter, followed by the
+    /// matching reward traces.
+    async fn append_matching_traces(
+        &self,
+        all_traces: &mut Vec<LocalizedTransactionTrace>,
+        block_numbers: &[u64],
+        matcher: &Arc<TraceFilterMatcher>,
+    ) -> Result<(), Eth::Error> {
+        let blocks = futures::future::try_join_all(
+            block_numbers.iter().map(|number| self.eth_api().recovered_block((*number).into())),
+        )
+        .await?
+        .into_iter()
+        .zip(block_numbers)
+        .map(|(block, number)| block.ok_or(EthApiError::HeaderNotFound((*number).into())))
+        .collect::<Result<Vec<_>, _>>()?;
 
         // trace all blocks
         let mut block_traces = Vec::with_capacity(blocks.len());
@@ -413,11 +553,11 @@ where
         }
 
         let block_traces = futures::future::try_join_all(block_traces).await?;
-        let mut all_traces = block_traces
-            .into_iter()
-            .flatten()
-            .flat_map(|traces| traces.into_iter().flatten().flat_map(|traces| traces.into_iter()))
-            .collect::<Vec<_>>();
+        all_traces.extend(
+            block_traces.into_iter().flatten().flat_map(|traces| {
+                traces.into_iter().flatten().flat_map(|traces| traces.into_iter())
+            }),
+        );
 
         // add reward traces for all blocks
         for block in &blocks {
@@ -438,26 +578,7 @@ where
             }
         }
 
-        // Skips the first `after` number of matching traces.
-        // If `after` is greater than or equal to the number of matched traces, it returns an empty
-        // array.
-        if let Some(after) = after.map(|a| a as usize) {
-            if after < all_traces.len() {
-                all_traces.drain(..after);
-            } else {
-                return Ok(vec![])
-            }
-        }
-
-        // Return at most `count` of traces
-        if let Some(count) = count {
-            let count = count as usize;
-            if count < all_traces.len() {
-                all_traces.truncate(count);
-            }
-        };
-
-        Ok(all_traces)
+        Ok(())
     }
 
     /// Returns traces created at given block.
@@ -566,7 +687,7 @@ where
 #[async_trait]
 impl<Eth> TraceApiServer for TraceApi<Eth>
 where
-    Eth: TraceExt + 'static,
+    Eth: TraceExt + RpcNodeCore<Provider: TraceIndexReader> + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
     ///
@@ -644,7 +765,8 @@ where
     /// This is similar to `eth_getLogs` but for traces.
     ///
     /// # Limitations
-    /// This currently requires block filter fields, since reth does not have address indices yet.
+    /// Without the trace index, this requires block filter fields that span at most
+    /// `max_trace_filter_blocks` blocks.
     async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>> {
         Ok(Self::trace_filter(self, filter).await.map_err(Into::into)?)
     }
@@ -3,8 +3,8 @@ use alloy_consensus::{
 };
 use alloy_eips::{eip4844::kzg_to_versioned_hash, eip7685::RequestsOrHash};
 use alloy_rpc_types_beacon::relay::{
-    BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV2,
-    BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
+    BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV3,
+    BuilderBlockValidationRequestV4,
 };
 use alloy_rpc_types_engine::{
     BlobsBundleV1, CancunPayloadFields, ExecutionData, ExecutionPayload, ExecutionPayloadSidecar,
@@ -31,7 +31,7 @@ use reth_primitives_traits::{
     SealedBlock, SealedHeaderFor,
 };
 use reth_revm::{cached::CachedReads, database::StateProviderDatabase};
-use reth_rpc_api::BlockSubmissionValidationApiServer;
+use reth_rpc_api::{BlockSubmissionValidationApiServer, BuilderBlockValidationRequestV2};
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -40,7 +40,6 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::{oneshot, RwLock};
-use tracing::warn;
 
 /// The type that implements the `validation` rpc namespace trait
 #[derive(Clone, Debug, derive_more::Deref)]
@@ -366,6 +365,51 @@ where
         Ok(versioned_hashes)
     }
 
+    /// Core logic for validating the builder submission v1
+    async fn validate_builder_submission_v1(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> Result<(), ValidationApiError> {
+        let block = self.payload_validator.ensure_well_formed_payload(ExecutionData {
+            payload: ExecutionPayload::V1(request.request.execution_payload),
+            sidecar: ExecutionPayloadSidecar::none(),
+        })?;
+
+        self.validate_message_against_block(
+            block,
+            request.request.message,
+            request.registered_gas_limit,
+        )
+        .await
+    }
+
+    /// Core logic for validating the builder submission v2
+    async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequestV2,
+    ) -> Result<(), ValidationApiError> {
+        let block = self.payload_validator.ensure_well_formed_payload(ExecutionData {
+            payload: ExecutionPayload::V2(request.request.execution_payload),
+            sidecar: ExecutionPayloadSidecar::none(),
+        })?;
+
+        // the withdrawals root of the block is derived from the withdrawals of the payload
+        let withdrawals_root = block.header().withdrawals_root().unwrap_or_default();
+        if withdrawals_root != request.withdrawals_root {
+            return Err(ValidationApiError::WithdrawalsRootMismatch(GotExpected {
+                got: request.withdrawals_root,
+                expected: withdrawals_root,
+            }))
+        }
+
+        self.validate_message_against_block(
+            block,
+            request.request.message,
+            request.registered_gas_limit,
+        )
+        .await
+    }
+
     /// Core logic for validating the builder submission v3
     async fn validate_builder_submission_v3(
         &self,
@@ -426,20 +470,40 @@ where
         + 'static,
     E: ConfigureEvm + 'static,
 {
+    /// Validates a block submitted to the relay
     async fn validate_builder_submission_v1(
         &self,
-        _request: BuilderBlockValidationRequest,
+        request: BuilderBlockValidationRequest,
     ) -> RpcResult<()> {
-        warn!(target: "rpc::flashbots", "Method `flashbots_validateBuilderSubmissionV1` is not supported");
-        Err(internal_rpc_err("unimplemented"))
+        let this = self.clone();
+        let (tx, rx) = oneshot::channel();
+
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let result = Self::validate_builder_submission_v1(&this, request)
+                .await
+                .map_err(ErrorObject::from);
+            let _ = tx.send(result);
+        }));
+
+        rx.await.map_err(|_| internal_rpc_err("Internal blocking task error"))?
     }
 
+    /// Validates a block submitted to the relay
     async fn validate_builder_submission_v2(
         &self,
-        _request: BuilderBlockValidationRequestV2,
+        request: BuilderBlockValidationRequestV2,
     ) -> RpcResult<()> {
-        warn!(target: "rpc::flashbots", "Method `flashbots_validateBuilderSubmissionV2` is not supported");
-        Err(internal_rpc_err("unimplemented"))
+        let this = self.clone();
+        let (tx, rx) = oneshot::channel();
+
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let result = Self::validate_builder_submission_v2(&this, request)
+                .await
+                .map_err(ErrorObject::from);
+            let _ = tx.send(result);
+        }));
+
+        rx.await.map_err(|_| internal_rpc_err("Internal blocking task error"))?
     }
 
     /// Validates a block submitted to the relay
@@ -561,6 +625,8 @@ pub enum ValidationApiError {
     ParentHashMismatch(GotExpected<B256>),
     #[error("block hash mismatch: {_0}")]
     BlockHashMismatch(GotExpected<B256>),
+    #[error("withdrawals root mismatch: {_0}")]
+    WithdrawalsRootMismatch(GotExpected<B256>),
     #[error("missing latest block in database")]
     MissingLatestBlock,
     #[error("parent block not found")]
@@ -592,6 +658,7 @@ impl From<ValidationApiError> for ErrorObject<'static> {
             ValidationApiError::GasUsedMismatch(_) |
             ValidationApiError::ParentHashMismatch(_) |
             ValidationApiError::BlockHashMismatch(_) |
+            ValidationApiError::WithdrawalsRootMismatch(_) |
             ValidationApiError::Blacklist(_) |
             ValidationApiError::ProposerPayment |
             ValidationApiError::InvalidBlobsBundle |
@@ -0,0 +1,133 @@
+use crate::{ProtocolEvent, SnapPeerRequest};
+use alloy_primitives::map::HashMap;
+use parking_lot::Mutex;
+use reth_eth_wire::{
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage,
+};
+use reth_network_api::PeerId;
+use reth_network_p2p::error::{PeerRequestResult, RequestError, RequestResult};
+use reth_network_peers::WithPeerId;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+use tracing::*;
+
+/// Client that sends `snap/1` requests to the peers of the node.
+///
+/// The client learns about the peers from the [`ProtocolEvent`]s of a
+/// [`SnapProtocolHandler`](crate::SnapProtocolHandler) that was created with an events sender, and
+/// sends the requests to them in turn. Peers are forgotten once their connection is closed.
+#[derive(Debug, Clone)]
+pub struct SnapClient {
+    inner: Arc<Mutex<SnapPeers>>,
+}
+
+impl SnapClient {
+    /// Creates a new client for the peers of the handler the events come from.
+    pub fn new(events: mpsc::UnboundedReceiver<ProtocolEvent>) -> Self {
+        let peers = SnapPeers { events, peers: HashMap::default(), order: Vec::new(), next: 0 };
+        Self { inner: Arc::new(Mutex::new(peers)) }
+    }
+
+    /// Returns the number of peers requests can be sent to.
+    pub fn num_peers(&self) -> usize {
+        let mut peers = self.inner.lock();
+        peers.update();
+        peers.peers.len()
+    }
+
+    /// Requests a range of accounts.
+    pub async fn account_range(
+        &self,
+        request: GetAccountRangeMessage,
+    ) -> PeerRequestResult<AccountRangeMessage> {
+        self.request(|tx| SnapPeerRequest::GetAccountRange { request, tx }).await
+    }
+
+    /// Requests storage ranges of accounts.
+    pub async fn storage_ranges(
+        &self,
+        request: GetStorageRangesMessage,
+    ) -> PeerRequestResult<StorageRangesMessage> {
+        self.request(|tx| SnapPeerRequest::GetStorageRanges { request, tx }).await
+    }
+
+    /// Requests bytecodes by code hash.
+    pub async fn byte_codes(
+        &self,
+        request: GetByteCodesMessage,
+    ) -> PeerRequestResult<ByteCodesMessage> {
+        self.request(|tx| SnapPeerRequest::GetByteCodes { request, tx }).await
+    }
+
+    /// Requests trie nodes by path.
+    pub async fn trie_nodes(
+        &self,
+        request: GetTrieNodesMessage,
+    ) -> PeerRequestResult<TrieNodesMessage> {
+        self.request(|tx| SnapPeerRequest::GetTrieNodes { request, tx }).await
+    }
+
+    /// Sends the request to the next peer and waits for the response.
+    ///
+    /// Returns [`RequestError::ChannelClosed`] if there is no peer to send the request to.
+    async fn request<T>(
+        &self,
+        request: impl FnOnce(oneshot::Sender<RequestResult<T>>) -> SnapPeerRequest,
+    ) -> PeerRequestResult<T> {
+        let (tx, rx) = oneshot::channel();
+        let peer_id = self.inner.lock().send(request(tx))?;
+        let response = rx.await??;
+        Ok(WithPeerId::new(peer_id, response))
+    }
+}
+
+/// The peers of a [`SnapClient`].
+#[derive(Debug)]
+struct SnapPeers {
+    /// Events of the connections to new peers.
+    events: mpsc::UnboundedReceiver<ProtocolEvent>,
+    /// Senders for requests to the connected peers.
+    peers: HashMap<PeerId, mpsc::UnboundedSender<SnapPeerRequest>>,
+    /// The peers in the order they are sent requests.
+    order: Vec<PeerId>,
+    /// Index of the peer in `order` that is sent the next request.
+    next: usize,
+}
+
+impl SnapPeers {
+    /// Adds the peers of new connections and removes the peers of closed connections.
+    fn update(&mut self) {
+        while let Ok(ProtocolEvent::Established { peer_id, to_connection, .. }) =
+            self.events.try_recv()
+        {
+            trace!(target: "snap::client", %peer_id, "added peer");
+            if self.peers.insert(peer_id, to_connection).is_none() {
+                self.order.push(peer_id);
+            }
+        }
+
+        let peers = &mut self.peers;
+        peers.retain(|_, to_connection| !to_connection.is_closed());
+        self.order.retain(|peer_id| peers.contains_key(peer_id));
+    }
+
+    /// Sends the request to the next peer, returns the id of the peer.
+    fn send(&mut self, mut request: SnapPeerRequest) -> RequestResult<PeerId> {
+        self.update();
+        while !self.order.is_empty() {
+            let peer_id = self.order[self.next % self.order.len()];
+            self.next = self.next.wrapping_add(1);
+            match self.peers[&peer_id].send(request) {
+                Ok(()) => return Ok(peer_id),
+                Err(mpsc::error::SendError(unsent)) => {
+                    // the connection closed since the last update
+                    request = unsent;
+                    self.peers.remove(&peer_id);
+                    self.order.retain(|id| *id != peer_id);
+                }
+            }
+        }
+        Err(RequestError::ChannelClosed)
+    }
+}
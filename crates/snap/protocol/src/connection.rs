@@ -0,0 +1,419 @@
+use crate::SnapProtocolProvider;
+use alloy_primitives::bytes::BytesMut;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_eth_wire::{
+    multiplex::ProtocolConnection, AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage,
+    GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage, SnapProtocolMessage,
+    StorageRangesMessage, TrieNodesMessage,
+};
+use reth_network_api::{test_utils::PeersHandle, PeerId, ReputationChangeKind};
+use reth_network_p2p::error::{RequestError, RequestResult};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::oneshot,
+    task::JoinHandle,
+    time::{interval, Interval, MissedTickBehavior},
+};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::*;
+
+/// The maximum number of requests of a peer that are served concurrently.
+///
+/// Incoming messages are not read while this many requests are being served.
+const MAX_PENDING_REQUESTS: usize = 16;
+
+/// The default time a peer has to answer a request.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum interval at which inflight requests are checked for timeouts.
+const MAX_TIMEOUT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The connection handler for the `snap` protocol.
+#[derive(Debug)]
+pub struct SnapProtocolConnection<P> {
+    /// Provider.
+    provider: P,
+    /// Peers handle.
+    peers_handle: PeersHandle,
+    /// Peer ID.
+    peer_id: PeerId,
+    /// Protocol connection.
+    conn: ProtocolConnection,
+    /// Stream of requests to send to the peer.
+    commands: UnboundedReceiverStream<SnapPeerRequest>,
+    /// The total number of active connections.
+    active_connections: Arc<AtomicU64>,
+    /// Flag indicating whether this stream has previously been terminated.
+    terminated: bool,
+    /// Incremental counter for request ids.
+    next_id: u64,
+    /// Collection of inflight requests.
+    inflight_requests: HashMap<u64, InflightRequest>,
+    /// The time the peer has to answer a request.
+    request_timeout: Duration,
+    /// Interval at which inflight requests are checked for timeouts.
+    timeout_interval: Interval,
+    /// Requests that are being served on the blocking pool.
+    pending_responses: FuturesUnordered<JoinHandle<SnapProtocolMessage>>,
+}
+
+impl<P> SnapProtocolConnection<P> {
+    /// Create new connection.
+    pub fn new(
+        provider: P,
+        peers_handle: PeersHandle,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+        commands: UnboundedReceiverStream<SnapPeerRequest>,
+        active_connections: Arc<AtomicU64>,
+        request_timeout: Duration,
+    ) -> Self {
+        let mut timeout_interval = interval(request_timeout.min(MAX_TIMEOUT_CHECK_INTERVAL));
+        timeout_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            provider,
+            peers_handle,
+            peer_id,
+            conn,
+            commands,
+            active_connections,
+            terminated: false,
+            next_id: 0,
+            inflight_requests: HashMap::default(),
+            request_timeout,
+            timeout_interval,
+            pending_responses: FuturesUnordered::new(),
+        }
+    }
+
+    /// Returns the next request id
+    const fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Report bad message from current peer.
+    fn report_bad_message(&self) {
+        self.peers_handle.reputation_change(self.peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    fn on_command(&mut self, command: SnapPeerRequest) -> SnapProtocolMessage {
+        let request_id = self.next_id();
+        let message = match &command {
+            SnapPeerRequest::GetAccountRange { request, .. } => {
+                SnapProtocolMessage::GetAccountRange(GetAccountRangeMessage {
+                    request_id,
+                    ..request.clone()
+                })
+            }
+            SnapPeerRequest::GetStorageRanges { request, .. } => {
+                SnapProtocolMessage::GetStorageRanges(GetStorageRangesMessage {
+                    request_id,
+                    ..request.clone()
+                })
+            }
+            SnapPeerRequest::GetByteCodes { request, .. } => {
+                SnapProtocolMessage::GetByteCodes(GetByteCodesMessage {
+                    request_id,
+                    ..request.clone()
+                })
+            }
+            SnapPeerRequest::GetTrieNodes { request, .. } => {
+                SnapProtocolMessage::GetTrieNodes(GetTrieNodesMessage {
+                    request_id,
+                    ..request.clone()
+                })
+            }
+        };
+        let deadline = Instant::now() + self.request_timeout;
+        self.inflight_requests.insert(request_id, InflightRequest { request: command, deadline });
+        message
+    }
+
+    /// Fails the inflight requests that missed their deadline, returns `true` if any did.
+    fn on_timeout_check(&mut self, now: Instant) -> bool {
+        let timed_out = self
+            .inflight_requests
+            .iter()
+            .filter(|(_, request)| request.deadline <= now)
+            .map(|(request_id, _)| *request_id)
+            .collect::<Vec<_>>();
+        for request_id in &timed_out {
+            trace!(target: "snap::net::connection", peer_id = %self.peer_id, request_id, "request timed out");
+            if let Some(request) = self.inflight_requests.remove(request_id) {
+                request.request.send_err(RequestError::Timeout);
+            }
+        }
+        !timed_out.is_empty()
+    }
+
+    /// Forwards the response to the inflight request, returns `false` if there is no matching
+    /// request.
+    fn on_response(&mut self, message: SnapProtocolMessage) -> bool {
+        macro_rules! respond {
+            ($response:ident, $variant:ident) => {
+                match self.inflight_requests.remove(&$response.request_id) {
+                    Some(InflightRequest {
+                        request: SnapPeerRequest::$variant { tx, .. }, ..
+                    }) => {
+                        let _ = tx.send(Ok($response));
+                        true
+                    }
+                    Some(request) => {
+                        // a response of the wrong type fails the request
+                        request.request.send_err(RequestError::BadResponse);
+                        false
+                    }
+                    None => false,
+                }
+            };
+        }
+
+        match message {
+            SnapProtocolMessage::AccountRange(response) => respond!(response, GetAccountRange),
+            SnapProtocolMessage::StorageRanges(response) => respond!(response, GetStorageRanges),
+            SnapProtocolMessage::ByteCodes(response) => respond!(response, GetByteCodes),
+            SnapProtocolMessage::TrieNodes(response) => respond!(response, GetTrieNodes),
+            _ => false,
+        }
+    }
+}
+
+impl<P> SnapProtocolConnection<P>
+where
+    P: SnapProtocolProvider + Clone + 'static,
+{
+    /// Spawns a task serving a request or forwards a response, returns `false` if the message is
+    /// an unsolicited response.
+    fn on_snap_message(&mut self, message: SnapProtocolMessage) -> bool {
+        let message_id = message.message_id();
+        let is_request = matches!(
+            message,
+            SnapProtocolMessage::GetAccountRange(_) |
+                SnapProtocolMessage::GetStorageRanges(_) |
+                SnapProtocolMessage::GetByteCodes(_) |
+                SnapProtocolMessage::GetTrieNodes(_)
+        );
+        if !is_request {
+            if !self.on_response(message) {
+                trace!(target: "snap::net::connection", peer_id = %self.peer_id, ?message_id, "unsolicited response");
+                return false
+            }
+            return true
+        }
+
+        trace!(target: "snap::net::connection", peer_id = %self.peer_id, ?message_id, "serving request");
+        let provider = self.provider.clone();
+        let peer_id = self.peer_id;
+        self.pending_responses
+            .push(tokio::task::spawn_blocking(move || serve_request(&provider, peer_id, message)));
+        true
+    }
+}
+
+/// Serves a request, answering with an empty response if the provider fails.
+fn serve_request<P: SnapProtocolProvider>(
+    provider: &P,
+    peer_id: PeerId,
+    request: SnapProtocolMessage,
+) -> SnapProtocolMessage {
+    match request {
+        SnapProtocolMessage::GetAccountRange(request) => {
+            let request_id = request.request_id;
+            let response = provider.account_range(request).unwrap_or_else(|error| {
+                trace!(target: "snap::net::connection", %peer_id, %error, "error retrieving account range");
+                AccountRangeMessage { request_id, accounts: Vec::new(), proof: Vec::new() }
+            });
+            SnapProtocolMessage::AccountRange(response)
+        }
+        SnapProtocolMessage::GetStorageRanges(request) => {
+            let request_id = request.request_id;
+            let response = provider.storage_ranges(request).unwrap_or_else(|error| {
+                trace!(target: "snap::net::connection", %peer_id, %error, "error retrieving storage ranges");
+                StorageRangesMessage { request_id, slots: Vec::new(), proof: Vec::new() }
+            });
+            SnapProtocolMessage::StorageRanges(response)
+        }
+        SnapProtocolMessage::GetByteCodes(request) => {
+            let request_id = request.request_id;
+            let response = provider.byte_codes(request).unwrap_or_else(|error| {
+                trace!(target: "snap::net::connection", %peer_id, %error, "error retrieving bytecodes");
+                ByteCodesMessage { request_id, codes: Vec::new() }
+            });
+            SnapProtocolMessage::ByteCodes(response)
+        }
+        SnapProtocolMessage::GetTrieNodes(request) => {
+            let request_id = request.request_id;
+            let response = provider.trie_nodes(request).unwrap_or_else(|error| {
+                trace!(target: "snap::net::connection", %peer_id, %error, "error retrieving trie nodes");
+                TrieNodesMessage { request_id, nodes: Vec::new() }
+            });
+            SnapProtocolMessage::TrieNodes(response)
+        }
+        response => unreachable!("only requests are served: {:?}", response.message_id()),
+    }
+}
+
+impl<P> Drop for SnapProtocolConnection<P> {
+    fn drop(&mut self) {
+        for pending in &self.pending_responses {
+            pending.abort();
+        }
+        for (_, request) in self.inflight_requests.drain() {
+            request.request.send_err(RequestError::ConnectionDropped);
+        }
+        let _ = self
+            .active_connections
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| Some(c.saturating_sub(1)));
+    }
+}
+
+impl<P> Stream for SnapProtocolConnection<P>
+where
+    P: SnapProtocolProvider + Clone + Unpin + 'static,
+{
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.terminated {
+            return Poll::Ready(None)
+        }
+
+        'conn: loop {
+            while this.timeout_interval.poll_tick(cx).is_ready() {
+                if this.on_timeout_check(Instant::now()) {
+                    this.peers_handle
+                        .reputation_change(this.peer_id, ReputationChangeKind::Timeout);
+                }
+            }
+
+            if let Poll::Ready(Some(cmd)) = this.commands.poll_next_unpin(cx) {
+                let message = this.on_command(cmd);
+                trace!(target: "snap::net::connection", peer_id = %this.peer_id, message_id = ?message.message_id(), "Sending peer request");
+                return Poll::Ready(Some(BytesMut::from(&message.encode()[..])))
+            }
+
+            if let Poll::Ready(Some(result)) = this.pending_responses.poll_next_unpin(cx) {
+                match result {
+                    Ok(response) => {
+                        return Poll::Ready(Some(BytesMut::from(&response.encode()[..])))
+                    }
+                    Err(error) => {
+                        debug!(target: "snap::net::connection", peer_id = %this.peer_id, %error, "failed to serve request");
+                        continue
+                    }
+                }
+            }
+
+            // Stop reading requests until some of the pending ones are served.
+            if this.pending_responses.len() >= MAX_PENDING_REQUESTS {
+                return Poll::Pending
+            }
+
+            if let Poll::Ready(maybe_msg) = this.conn.poll_next_unpin(cx) {
+                let Some(next) = maybe_msg else { break 'conn };
+                let Some((&id, mut body)) = next.split_first() else {
+                    this.report_bad_message();
+                    continue
+                };
+                match SnapProtocolMessage::decode(id, &mut body) {
+                    Ok(message) => {
+                        if !this.on_snap_message(message) {
+                            this.report_bad_message();
+                        }
+                    }
+                    Err(error) => {
+                        trace!(target: "snap::net::connection", peer_id = %this.peer_id, %error, "Error decoding peer message");
+                        this.report_bad_message();
+                    }
+                }
+
+                continue
+            }
+
+            return Poll::Pending
+        }
+
+        // Terminating the connection.
+        this.terminated = true;
+        Poll::Ready(None)
+    }
+}
+
+/// Snap peer request.
+///
+/// The request id of the message is replaced by the id of the connection. The request fails with
+/// [`RequestError::Timeout`] if the peer doesn't answer in time and with
+/// [`RequestError::ConnectionDropped`] if the connection is closed before.
+#[derive(Debug)]
+pub enum SnapPeerRequest {
+    /// Get a range of accounts.
+    GetAccountRange {
+        /// The request for the account range.
+        request: GetAccountRangeMessage,
+        /// The sender for the response.
+        tx: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    },
+    /// Get storage ranges of accounts.
+    GetStorageRanges {
+        /// The request for the storage ranges.
+        request: GetStorageRangesMessage,
+        /// The sender for the response.
+        tx: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    },
+    /// Get bytecodes by code hash.
+    GetByteCodes {
+        /// The request for the bytecodes.
+        request: GetByteCodesMessage,
+        /// The sender for the response.
+        tx: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    },
+    /// Get trie nodes by path.
+    GetTrieNodes {
+        /// The request for the trie nodes.
+        request: GetTrieNodesMessage,
+        /// The sender for the response.
+        tx: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    },
+}
+
+impl SnapPeerRequest {
+    /// Fails the request with the given error.
+    fn send_err(self, err: RequestError) {
+        match self {
+            Self::GetAccountRange { tx, .. } => {
+                let _ = tx.send(Err(err));
+            }
+            Self::GetStorageRanges { tx, .. } => {
+                let _ = tx.send(Err(err));
+            }
+            Self::GetByteCodes { tx, .. } => {
+                let _ = tx.send(Err(err));
+            }
+            Self::GetTrieNodes { tx, .. } => {
+                let _ = tx.send(Err(err));
+            }
+        }
+    }
+}
+
+/// A request that was sent to the peer and waits for its response.
+#[derive(Debug)]
+struct InflightRequest {
+    /// The request with the sender for the response.
+    request: SnapPeerRequest,
+    /// The time the response has to arrive by.
+    deadline: Instant,
+}
@@ -0,0 +1,183 @@
+use crate::{
+    connection::{SnapPeerRequest, SnapProtocolConnection, DEFAULT_REQUEST_TIMEOUT},
+    SnapProtocolProvider,
+};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_network::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use reth_network_api::{test_utils::PeersHandle, Direction, PeerId};
+use std::{
+    fmt,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::*;
+
+/// The events that can be emitted by the `snap` protocol.
+#[derive(Debug)]
+pub enum ProtocolEvent {
+    /// Connection established.
+    Established {
+        /// Connection direction.
+        direction: Direction,
+        /// Peer ID.
+        peer_id: PeerId,
+        /// Sender part for sending requests to the peer.
+        to_connection: mpsc::UnboundedSender<SnapPeerRequest>,
+    },
+}
+
+/// The protocol handler takes care of incoming and outgoing connections.
+#[derive(Clone)]
+pub struct SnapProtocolHandler<P> {
+    /// Provider.
+    pub provider: P,
+    /// Peers handle.
+    pub peers_handle: PeersHandle,
+    /// The maximum number of active connections.
+    pub max_active_connections: u64,
+    /// The number of active connections.
+    pub active_connections: Arc<AtomicU64>,
+    /// Protocol event sender, set if the node sends requests to its peers.
+    pub events_sender: Option<mpsc::UnboundedSender<ProtocolEvent>>,
+    /// The time a peer has to answer a request.
+    pub request_timeout: Duration,
+}
+
+impl<P> SnapProtocolHandler<P> {
+    /// Create new protocol handler.
+    pub fn new(provider: P, peers_handle: PeersHandle, max_active_connections: u64) -> Self {
+        Self {
+            provider,
+            peers_handle,
+            max_active_connections,
+            active_connections: Arc::default(),
+            events_sender: None,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Emits a [`ProtocolEvent::Established`] for every connection, which carries the sender for
+    /// requests to the peer.
+    ///
+    /// See [`SnapClient`](crate::SnapClient) for a client that sends requests to these peers.
+    pub fn with_events_sender(
+        mut self,
+        events_sender: mpsc::UnboundedSender<ProtocolEvent>,
+    ) -> Self {
+        self.events_sender = Some(events_sender);
+        self
+    }
+
+    /// Sets the time a peer has to answer a request, [`DEFAULT_REQUEST_TIMEOUT`] by default.
+    pub const fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Returns the current number of active connections.
+    pub fn num_active_connections(&self) -> u64 {
+        self.active_connections.load(Ordering::Relaxed)
+    }
+
+    fn has_capacity(&self, socket_addr: SocketAddr) -> bool {
+        let num_active = self.num_active_connections();
+        if num_active >= self.max_active_connections {
+            trace!(
+                target: "snap::net",
+                num_active, max_connections = self.max_active_connections, %socket_addr,
+                "ignoring connection, max active reached"
+            );
+            return false
+        }
+        true
+    }
+}
+
+impl<P> fmt::Debug for SnapProtocolHandler<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapProtocolHandler")
+            .field("peers_handle", &self.peers_handle)
+            .field("max_active_connections", &self.max_active_connections)
+            .field("active_connections", &self.active_connections)
+            .field("events_sender", &self.events_sender)
+            .field("request_timeout", &self.request_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> ProtocolHandler for SnapProtocolHandler<P>
+where
+    P: SnapProtocolProvider + Clone + Unpin + 'static,
+{
+    type ConnectionHandler = Self;
+
+    fn on_incoming(&self, socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        self.has_capacity(socket_addr).then(|| self.clone())
+    }
+
+    fn on_outgoing(
+        &self,
+        socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        self.has_capacity(socket_addr).then(|| self.clone())
+    }
+}
+
+impl<P> ConnectionHandler for SnapProtocolHandler<P>
+where
+    P: SnapProtocolProvider + Clone + Unpin + 'static,
+{
+    type Connection = SnapProtocolConnection<P>;
+
+    fn protocol(&self) -> Protocol {
+        crate::protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        // `snap` is optional, peers that don't support it are still useful for `eth`.
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        // Emit connection established event.
+        if let Some(events_sender) = &self.events_sender {
+            events_sender
+                .send(ProtocolEvent::Established { direction, peer_id, to_connection: tx })
+                .ok();
+        }
+
+        // Increment the number of active sessions.
+        self.active_connections.fetch_add(1, Ordering::Relaxed);
+
+        SnapProtocolConnection::new(
+            self.provider,
+            self.peers_handle,
+            peer_id,
+            conn,
+            UnboundedReceiverStream::from(rx),
+            self.active_connections,
+            self.request_timeout,
+        )
+    }
+}
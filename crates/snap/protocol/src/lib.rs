@@ -0,0 +1,46 @@
+//! `snap` protocol is an `RLPx` subprotocol for the exchange of state snapshots.
+//! following [snap/1 specs](https://github.com/ethereum/devp2p/blob/master/caps/snap.md)
+//!
+//! The protocol runs side by side with `eth` and lets peers download the state of a recent block
+//! as contiguous ranges of accounts and storage slots, together with the proofs that the ranges
+//! belong to the requested state root.
+//!
+//! Requests to a peer are sent with the [`SnapPeerRequest`] sender of the
+//! [`ProtocolEvent::Established`] event of its connection, [`SnapClient`] sends requests to the
+//! peers of these events in turn.
+//!
+//! The crate covers the exchange of the messages only. Syncing the state of a pivot block with
+//! these requests, healing the trie and verifying it against the state root of the pivot is left
+//! to the user of the [`SnapClient`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use reth_eth_wire::{protocol::Protocol, Capability};
+
+mod provider;
+pub use provider::*;
+
+mod handlers;
+pub use handlers::*;
+
+mod connection;
+pub use connection::{SnapPeerRequest, SnapProtocolConnection, DEFAULT_REQUEST_TIMEOUT};
+
+mod client;
+pub use client::SnapClient;
+
+/// Returns the capability of the `snap/1` protocol.
+pub const fn capability() -> Capability {
+    Capability::new_static("snap", 1)
+}
+
+/// Returns the `snap/1` protocol.
+pub const fn protocol() -> Protocol {
+    Protocol::new(capability(), 8)
+}
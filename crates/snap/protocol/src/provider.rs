@@ -0,0 +1,60 @@
+use alloy_primitives::{Bytes, B256};
+use reth_eth_wire::{
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage,
+};
+use reth_network::eth_requests::SOFT_RESPONSE_LIMIT;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Maximum number of bytecodes to serve in a single response.
+pub const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie nodes to serve in a single response.
+pub const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Returns the number of bytes to serve for a request with the given soft limit.
+///
+/// Peers can ask for less than [`SOFT_RESPONSE_LIMIT`] but not for more.
+pub fn response_limit(response_bytes: u64) -> usize {
+    usize::try_from(response_bytes).unwrap_or(usize::MAX).min(SOFT_RESPONSE_LIMIT)
+}
+
+/// A provider trait for snap protocol.
+///
+/// Requests for a state root the provider does not serve are answered with empty responses, which
+/// tells the peer that the state is not available.
+pub trait SnapProtocolProvider: Send + Sync {
+    /// Return a range of accounts of the requested state, with the proof of the range.
+    fn account_range(&self, request: GetAccountRangeMessage)
+        -> ProviderResult<AccountRangeMessage>;
+
+    /// Return storage ranges of the requested accounts, with the proof of the last range if it
+    /// doesn't cover the entire storage trie.
+    fn storage_ranges(
+        &self,
+        request: GetStorageRangesMessage,
+    ) -> ProviderResult<StorageRangesMessage>;
+
+    /// Return bytecode by code hash.
+    fn bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytes>>;
+
+    /// Return bytecodes. Unknown code hashes are skipped.
+    fn byte_codes(&self, request: GetByteCodesMessage) -> ProviderResult<ByteCodesMessage> {
+        let limit = response_limit(request.response_bytes);
+        let mut total_bytes = 0;
+        let mut codes = Vec::new();
+        for code_hash in request.hashes.into_iter().take(MAX_CODE_LOOKUPS) {
+            if let Some(code) = self.bytecode(code_hash)? {
+                total_bytes += code.len();
+                codes.push(code);
+                if total_bytes > limit {
+                    break
+                }
+            }
+        }
+        Ok(ByteCodesMessage { request_id: request.request_id, codes })
+    }
+
+    /// Return trie nodes of the requested state by path.
+    fn trie_nodes(&self, request: GetTrieNodesMessage) -> ProviderResult<TrieNodesMessage>;
+}
@@ -0,0 +1,218 @@
+use alloy_primitives::{Bytes, B256};
+use reth_eth_wire::{
+    AccountData, AccountRangeMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, StorageData, StorageRangesMessage,
+    TrieNodesMessage, TriePath,
+};
+use reth_network::{test_utils::Testnet, Peers};
+use reth_network_api::test_utils::PeersHandleProvider;
+use reth_network_p2p::error::RequestError;
+use reth_provider::test_utils::MockEthProvider;
+use reth_snap_protocol::{SnapClient, SnapProtocolHandler, SnapProtocolProvider};
+use reth_storage_errors::provider::ProviderResult;
+use reth_transaction_pool::test_utils::TestPool;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Provider that answers every request with the same state, after an optional delay.
+#[derive(Clone, Copy, Debug, Default)]
+struct MockSnapProvider {
+    delay: Option<Duration>,
+}
+
+impl MockSnapProvider {
+    const CODE: Bytes = Bytes::from_static(&[0x60, 0x00]);
+
+    fn wait(&self) {
+        if let Some(delay) = self.delay {
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+impl SnapProtocolProvider for MockSnapProvider {
+    fn account_range(
+        &self,
+        request: GetAccountRangeMessage,
+    ) -> ProviderResult<AccountRangeMessage> {
+        self.wait();
+        Ok(AccountRangeMessage {
+            request_id: request.request_id,
+            accounts: vec![AccountData {
+                hash: request.starting_hash,
+                body: Bytes::from_static(&[0xc4, 0x01, 0x02, 0x80, 0x80]),
+            }],
+            proof: vec![Bytes::from_static(&[0xc0])],
+        })
+    }
+
+    fn storage_ranges(
+        &self,
+        request: GetStorageRangesMessage,
+    ) -> ProviderResult<StorageRangesMessage> {
+        self.wait();
+        Ok(StorageRangesMessage {
+            request_id: request.request_id,
+            slots: request
+                .account_hashes
+                .iter()
+                .map(|_| vec![StorageData { hash: request.starting_hash, data: Bytes::from([1]) }])
+                .collect(),
+            proof: Vec::new(),
+        })
+    }
+
+    fn bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytes>> {
+        self.wait();
+        Ok((code_hash == B256::ZERO).then_some(Self::CODE))
+    }
+
+    fn trie_nodes(&self, request: GetTrieNodesMessage) -> ProviderResult<TrieNodesMessage> {
+        self.wait();
+        Ok(TrieNodesMessage {
+            request_id: request.request_id,
+            nodes: request.paths.iter().map(|_| Bytes::from_static(&[0xc0])).collect(),
+        })
+    }
+}
+
+/// Connects a node that sends requests with the returned client to a node that serves them with
+/// the given provider.
+async fn connect_client(
+    provider: MockSnapProvider,
+    request_timeout: Duration,
+) -> (SnapClient, Testnet<MockEthProvider, TestPool>) {
+    let mut net = Testnet::create_with(2, MockEthProvider::default()).await;
+
+    let (tx, events) = mpsc::unbounded_channel();
+    let peer0 = &mut net.peers_mut()[0];
+    peer0.add_rlpx_sub_protocol(
+        SnapProtocolHandler::new(provider, peer0.handle().peers_handle().clone(), 100)
+            .with_events_sender(tx)
+            .with_request_timeout(request_timeout),
+    );
+
+    let peer1 = &mut net.peers_mut()[1];
+    peer1.add_rlpx_sub_protocol(SnapProtocolHandler::new(
+        provider,
+        peer1.handle().peers_handle().clone(),
+        100,
+    ));
+
+    (SnapClient::new(events), net)
+}
+
+/// Waits until the client has the given number of peers.
+async fn wait_for_peers(client: &SnapClient, num_peers: usize) {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while client.num_peers() != num_peers {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn message_exchange() {
+    reth_tracing::init_test_tracing();
+    let (client, net) = connect_client(MockSnapProvider::default(), Duration::from_secs(10)).await;
+
+    // spawn and connect all the peers
+    let handle = net.spawn();
+    handle.connect_peers().await;
+    wait_for_peers(&client, 1).await;
+    let peer1 = *handle.peers()[1].peer_id();
+
+    let starting_hash = B256::repeat_byte(0x11);
+    let response = client
+        .account_range(GetAccountRangeMessage {
+            request_id: 0,
+            root_hash: B256::ZERO,
+            starting_hash,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: 1024,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.peer_id(), peer1);
+    assert_eq!(response.data().accounts.len(), 1);
+    assert_eq!(response.data().accounts[0].hash, starting_hash);
+    assert_eq!(response.data().proof, vec![Bytes::from_static(&[0xc0])]);
+
+    let response = client
+        .storage_ranges(GetStorageRangesMessage {
+            request_id: 0,
+            root_hash: B256::ZERO,
+            account_hashes: vec![B256::repeat_byte(1), B256::repeat_byte(2)],
+            starting_hash,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: 1024,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.data().slots.len(), 2);
+    assert_eq!(response.data().slots[1][0].hash, starting_hash);
+
+    // unknown code hashes are skipped
+    let response = client
+        .byte_codes(GetByteCodesMessage {
+            request_id: 0,
+            hashes: vec![B256::ZERO, B256::repeat_byte(1)],
+            response_bytes: 1024,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.data().codes, vec![MockSnapProvider::CODE]);
+
+    let response = client
+        .trie_nodes(GetTrieNodesMessage {
+            request_id: 0,
+            root_hash: B256::ZERO,
+            paths: vec![TriePath { account_path: Bytes::new(), slot_paths: Vec::new() }],
+            response_bytes: 1024,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.data().nodes.len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn request_times_out() {
+    reth_tracing::init_test_tracing();
+    let provider = MockSnapProvider { delay: Some(Duration::from_secs(1)) };
+    let (client, net) = connect_client(provider, Duration::from_millis(100)).await;
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+    wait_for_peers(&client, 1).await;
+
+    let result = client
+        .byte_codes(GetByteCodesMessage {
+            request_id: 0,
+            hashes: vec![B256::ZERO],
+            response_bytes: 1024,
+        })
+        .await;
+    assert_eq!(result.unwrap_err(), RequestError::Timeout);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forgets_disconnected_peers() {
+    reth_tracing::init_test_tracing();
+    let (client, net) = connect_client(MockSnapProvider::default(), Duration::from_secs(10)).await;
+
+    // without peers, requests fail right away
+    let request =
+        GetByteCodesMessage { request_id: 0, hashes: vec![B256::ZERO], response_bytes: 0 };
+    assert_eq!(client.byte_codes(request.clone()).await.unwrap_err(), RequestError::ChannelClosed);
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+    wait_for_peers(&client, 1).await;
+    assert!(client.byte_codes(request.clone()).await.is_ok());
+
+    handle.peers()[0].network().disconnect_peer(*handle.peers()[1].peer_id());
+    wait_for_peers(&client, 0).await;
+    assert_eq!(client.byte_codes(request).await.unwrap_err(), RequestError::ChannelClosed);
+}
@@ -0,0 +1,5 @@
+#![allow(missing_docs)]
+
+mod e2e;
+
+const fn main() {}
@@ -0,0 +1,470 @@
+//! Reth implementation of [`reth_snap_protocol::SnapProtocolProvider`].
+//!
+//! State is served from the hashed state and trie tables of the database. The state of recent
+//! blocks is served by reverting the tables with the changesets of the blocks after them.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
+use alloy_primitives::{map::B256Set, BlockNumber, Bytes, B256, U256};
+use alloy_rlp::RlpEncodable;
+use parking_lot::Mutex;
+use reth_db_api::{tables, transaction::DbTx};
+use reth_eth_wire::{
+    AccountData, AccountRangeMessage, GetAccountRangeMessage, GetStorageRangesMessage,
+    GetTrieNodesMessage, StorageData, StorageRangesMessage, TrieNodesMessage,
+};
+use reth_primitives_traits::Account;
+use reth_snap_protocol::{response_limit, SnapProtocolProvider, MAX_TRIE_NODE_LOOKUPS};
+use reth_storage_api::{BlockNumReader, DBProvider, DatabaseProviderFactory, HeaderProvider};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    prefix_set::TriePrefixSetsMut,
+    proof::{Proof, StorageProof},
+    HashedPostState, HashedPostStateSorted, KeccakKeyHasher, MultiProofTargets, Nibbles,
+    StorageMultiProof, TrieInput, EMPTY_ROOT_HASH,
+};
+use reth_trie_db::{
+    DatabaseHashedCursorFactory, DatabaseHashedPostState, DatabaseTrieCursorFactory,
+};
+use std::sync::Arc;
+use tracing::*;
+
+/// The default number of blocks below the tip whose state is served.
+///
+/// This matches the number of recent states other clients serve.
+pub const DEFAULT_MAX_STATE_DEPTH: u64 = 128;
+
+/// Reth provider implementing [`SnapProtocolProvider`].
+#[derive(Clone)]
+pub struct RethSnapProtocolProvider<F> {
+    factory: F,
+    max_state_depth: u64,
+    /// The most recently served state, snap sync peers request the same root many times.
+    cached_state: Arc<Mutex<Option<Arc<ServedState>>>>,
+}
+
+impl<F> std::fmt::Debug for RethSnapProtocolProvider<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethSnapProtocolProvider")
+            .field("max_state_depth", &self.max_state_depth)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> RethSnapProtocolProvider<F>
+where
+    F: DatabaseProviderFactory<Provider: BlockNumReader + HeaderProvider>,
+{
+    /// Create new snap protocol provider serving the state of the last `max_state_depth` blocks.
+    pub fn new(factory: F, max_state_depth: u64) -> Self {
+        Self { factory, max_state_depth, cached_state: Default::default() }
+    }
+
+    /// Returns the state with the given state root at the tip of the database transaction, or
+    /// `None` if the state root is not one of the served states.
+    fn served_state(
+        &self,
+        provider: &F::Provider,
+        state_root: B256,
+    ) -> ProviderResult<Option<Arc<ServedState>>> {
+        let tip = provider.best_block_number()?;
+        if let Some(state) = self.cached_state.lock().as_ref() {
+            if state.state_root == state_root && state.tip == tip {
+                return Ok(Some(state.clone()))
+            }
+        }
+
+        let lowest = tip.saturating_sub(self.max_state_depth);
+        let mut block_number = tip;
+        loop {
+            let Some(header) = provider.header_by_number(block_number)? else { return Ok(None) };
+            if header.state_root() == state_root {
+                break
+            }
+            if block_number == lowest {
+                return Ok(None)
+            }
+            block_number -= 1;
+        }
+
+        trace!(target: "snap::provider", %state_root, block_number, tip, "loading served state");
+        let revert_state =
+            HashedPostState::from_reverts::<KeccakKeyHasher>(provider.tx_ref(), block_number + 1)?;
+        let TrieInput { state, prefix_sets, .. } = TrieInput::from_state(revert_state);
+        let state = Arc::new(ServedState {
+            state_root,
+            tip,
+            revert_state: state.into_sorted(),
+            prefix_sets,
+        });
+        *self.cached_state.lock() = Some(state.clone());
+        Ok(Some(state))
+    }
+}
+
+/// The state of a recent block, as an overlay over the state of the database tip.
+#[derive(Debug)]
+struct ServedState {
+    /// The state root of the block.
+    state_root: B256,
+    /// The tip of the database the overlay reverts.
+    tip: BlockNumber,
+    /// The hashed state that reverts the state of the tip to the state of the block.
+    revert_state: HashedPostStateSorted,
+    /// The trie paths that are changed by the reverts.
+    prefix_sets: TriePrefixSetsMut,
+}
+
+impl ServedState {
+    /// Returns the storage root of the account.
+    fn storage_root<TX: DbTx>(&self, tx: &TX, hashed_address: B256) -> ProviderResult<B256> {
+        Ok(self.storage_proof(tx, hashed_address, B256Set::default())?.root)
+    }
+
+    /// Returns the storage root of the account and the proof of the targeted slots.
+    fn storage_proof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+        targets: B256Set,
+    ) -> ProviderResult<StorageMultiProof> {
+        let prefix_set =
+            self.prefix_sets.storage_prefix_sets.get(&hashed_address).cloned().unwrap_or_default();
+        StorageProof::new_hashed(
+            DatabaseTrieCursorFactory::new(tx),
+            HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(tx),
+                &self.revert_state,
+            ),
+            hashed_address,
+        )
+        .with_prefix_set_mut(prefix_set)
+        .storage_multiproof(targets)
+        .map_err(ProviderError::from)
+    }
+}
+
+impl<F> SnapProtocolProvider for RethSnapProtocolProvider<F>
+where
+    F: DatabaseProviderFactory<Provider: BlockNumReader + HeaderProvider> + Send + Sync,
+{
+    fn account_range(
+        &self,
+        request: GetAccountRangeMessage,
+    ) -> ProviderResult<AccountRangeMessage> {
+        let mut response = AccountRangeMessage {
+            request_id: request.request_id,
+            accounts: Vec::new(),
+            proof: Vec::new(),
+        };
+        let provider = self.factory.database_provider_ro()?;
+        let Some(state) = self.served_state(&provider, request.root_hash)? else {
+            return Ok(response)
+        };
+        let tx = provider.tx_ref();
+
+        let limit = response_limit(request.response_bytes);
+        let mut size = 0;
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(tx),
+            &state.revert_state,
+        );
+        let mut cursor = hashed_cursor_factory.hashed_account_cursor()?;
+        let mut entry = cursor.seek(request.starting_hash)?;
+        while let Some((hashed_address, account)) = entry {
+            let storage_root = state.storage_root(tx, hashed_address)?;
+            let body = alloy_rlp::encode(SlimAccount::new(account, storage_root));
+            size += B256::len_bytes() + body.len();
+            response.accounts.push(AccountData { hash: hashed_address, body: body.into() });
+
+            // The first account past the limit is included to prove there are no accounts left
+            // in the requested range.
+            if hashed_address >= request.limit_hash || size > limit {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        // Prove the boundaries of the range: the origin and the last served account.
+        let mut targets = MultiProofTargets::account(request.starting_hash);
+        if let Some(last) = response.accounts.last() {
+            targets.insert(last.hash, B256Set::default());
+        }
+        let multiproof = Proof::new(DatabaseTrieCursorFactory::new(tx), hashed_cursor_factory)
+            .with_prefix_sets_mut(state.prefix_sets.clone())
+            .multiproof(targets)
+            .map_err(ProviderError::from)?;
+        response.proof = multiproof
+            .account_subtree
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+
+        Ok(response)
+    }
+
+    fn storage_ranges(
+        &self,
+        request: GetStorageRangesMessage,
+    ) -> ProviderResult<StorageRangesMessage> {
+        let mut response = StorageRangesMessage {
+            request_id: request.request_id,
+            slots: Vec::new(),
+            proof: Vec::new(),
+        };
+        let provider = self.factory.database_provider_ro()?;
+        let Some(state) = self.served_state(&provider, request.root_hash)? else {
+            return Ok(response)
+        };
+        let tx = provider.tx_ref();
+
+        let limit = response_limit(request.response_bytes);
+        // Storage tries can't be split between accounts, the last one may exceed the soft limit.
+        let hard_limit = limit + limit / 10;
+        let mut size = 0;
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(tx),
+            &state.revert_state,
+        );
+        for (index, hashed_address) in request.account_hashes.iter().copied().enumerate() {
+            if size >= limit {
+                break
+            }
+
+            // The origin and limit only apply to the first account.
+            let (origin, limit_hash) = if index == 0 {
+                (request.starting_hash, request.limit_hash)
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let mut slots = Vec::new();
+            let mut aborted = false;
+            let mut cursor = hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+            let mut entry = cursor.seek(origin)?;
+            while let Some((hashed_slot, value)) = entry {
+                if size >= hard_limit {
+                    aborted = true;
+                    break
+                }
+                let data = alloy_rlp::encode(value);
+                size += B256::len_bytes() + data.len();
+                slots.push(StorageData { hash: hashed_slot, data: data.into() });
+                if hashed_slot >= limit_hash {
+                    break
+                }
+                entry = cursor.next()?;
+            }
+
+            let last = slots.last().map(|slot| slot.hash);
+            if !slots.is_empty() {
+                response.slots.push(slots);
+            }
+
+            // A partial storage range is proven by the proofs of its boundaries and ends the
+            // response.
+            if origin != B256::ZERO || (aborted && last.is_some()) {
+                let targets = std::iter::once(origin).chain(last).collect();
+                let proof = state.storage_proof(tx, hashed_address, targets)?;
+                response.proof =
+                    proof.subtree.into_nodes_sorted().into_iter().map(|(_, node)| node).collect();
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytes>> {
+        let provider = self.factory.database_provider_ro()?;
+        Ok(provider
+            .tx_ref()
+            .get::<tables::Bytecodes>(code_hash)?
+            .map(|bytecode| bytecode.original_bytes()))
+    }
+
+    fn trie_nodes(&self, request: GetTrieNodesMessage) -> ProviderResult<TrieNodesMessage> {
+        let mut response = TrieNodesMessage { request_id: request.request_id, nodes: Vec::new() };
+        let provider = self.factory.database_provider_ro()?;
+        let Some(state) = self.served_state(&provider, request.root_hash)? else {
+            return Ok(response)
+        };
+        let tx = provider.tx_ref();
+
+        // Collect the requested nodes as the paths of storage tries by account, or the paths of
+        // the account trie if no account is given. The response ends at the first invalid path.
+        let mut lookups = Vec::new();
+        let mut targets = MultiProofTargets::default();
+        'paths: for path in request.paths {
+            if path.slot_paths.is_empty() {
+                let Some(nibbles) = decode_compact_path(&path.account_path) else { break };
+                targets.entry(padded_key(&nibbles)).or_default();
+                lookups.push((None, nibbles));
+            } else {
+                let Ok(hashed_address) = B256::try_from(&path.account_path[..]) else { break };
+                for slot_path in path.slot_paths {
+                    let Some(nibbles) = decode_compact_path(&slot_path) else { break 'paths };
+                    targets.entry(hashed_address).or_default().insert(padded_key(&nibbles));
+                    lookups.push((Some(hashed_address), nibbles));
+                }
+            }
+            if lookups.len() >= MAX_TRIE_NODE_LOOKUPS {
+                lookups.truncate(MAX_TRIE_NODE_LOOKUPS);
+                break
+            }
+        }
+
+        let multiproof = Proof::new(
+            DatabaseTrieCursorFactory::new(tx),
+            HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(tx),
+                &state.revert_state,
+            ),
+        )
+        .with_prefix_sets_mut(state.prefix_sets.clone())
+        .multiproof(targets)
+        .map_err(ProviderError::from)?;
+
+        let limit = response_limit(request.response_bytes);
+        let mut size = 0;
+        for (hashed_address, path) in lookups {
+            let node = match hashed_address {
+                None => multiproof.account_subtree.get(&path),
+                Some(hashed_address) => multiproof
+                    .storages
+                    .get(&hashed_address)
+                    .and_then(|proof| proof.subtree.get(&path)),
+            };
+            let Some(node) = node else { break };
+            size += node.len();
+            response.nodes.push(node.clone());
+            if size > limit {
+                break
+            }
+        }
+
+        Ok(response)
+    }
+}
+
+/// Account in the slim format of the snap protocol.
+///
+/// Empty storage roots and code hashes are encoded as empty strings.
+#[derive(Debug, RlpEncodable)]
+struct SlimAccount {
+    nonce: u64,
+    balance: U256,
+    storage_root: Bytes,
+    code_hash: Bytes,
+}
+
+impl SlimAccount {
+    fn new(account: Account, storage_root: B256) -> Self {
+        let storage_root =
+            if storage_root == EMPTY_ROOT_HASH { Bytes::new() } else { storage_root.into() };
+        let code_hash = match account.bytecode_hash {
+            Some(code_hash) if code_hash != KECCAK_EMPTY => code_hash.into(),
+            _ => Bytes::new(),
+        };
+        Self { nonce: account.nonce, balance: account.balance, storage_root, code_hash }
+    }
+}
+
+/// Decodes a trie path in compact (hex-prefix) encoding.
+///
+/// The empty path is the path of the root node.
+fn decode_compact_path(path: &[u8]) -> Option<Nibbles> {
+    let Some((&first, rest)) = path.split_first() else { return Some(Nibbles::default()) };
+    let flag = first >> 4;
+    if flag > 3 {
+        return None
+    }
+
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // An odd number of nibbles stores the first nibble in the flag byte.
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    nibbles.extend(rest.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+    (nibbles.len() <= 64).then(|| Nibbles::from_nibbles_unchecked(nibbles))
+}
+
+/// Returns the key of the trie that starts with the path, padded with zeros.
+fn padded_key(path: &Nibbles) -> B256 {
+    let mut key = B256::ZERO;
+    for (index, nibble) in path.iter().enumerate() {
+        key[index / 2] |= if index % 2 == 0 { nibble << 4 } else { *nibble };
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, hex};
+
+    #[test]
+    fn compact_paths() {
+        assert_eq!(decode_compact_path(&[]), Some(Nibbles::default()));
+        // even path
+        assert_eq!(
+            decode_compact_path(&hex!("001234")),
+            Some(Nibbles::from_nibbles_unchecked([1, 2, 3, 4]))
+        );
+        // odd path
+        assert_eq!(
+            decode_compact_path(&hex!("1a2b")),
+            Some(Nibbles::from_nibbles_unchecked([0xa, 2, 0xb]))
+        );
+        // leaf flags are ignored
+        assert_eq!(
+            decode_compact_path(&hex!("3a2b")),
+            Some(Nibbles::from_nibbles_unchecked([0xa, 2, 0xb]))
+        );
+        assert_eq!(decode_compact_path(&hex!("4a")), None);
+        assert_eq!(decode_compact_path(&[0; 34]), None);
+    }
+
+    #[test]
+    fn padded_keys() {
+        let key = padded_key(&Nibbles::from_nibbles_unchecked([0xa, 2, 0xb]));
+        assert_eq!(
+            key,
+            b256!("0xa2b0000000000000000000000000000000000000000000000000000000000000")
+        );
+        let full = B256::repeat_byte(0x12);
+        assert_eq!(padded_key(&Nibbles::unpack(full)), full);
+    }
+
+    #[test]
+    fn slim_account_encoding() {
+        let account = Account { nonce: 1, balance: U256::from(2), bytecode_hash: None };
+        assert_eq!(
+            alloy_rlp::encode(SlimAccount::new(account, EMPTY_ROOT_HASH)),
+            hex!("c401028080")
+        );
+
+        let account = Account { bytecode_hash: Some(KECCAK_EMPTY), ..account };
+        assert_eq!(
+            alloy_rlp::encode(SlimAccount::new(account, EMPTY_ROOT_HASH)),
+            hex!("c401028080")
+        );
+
+        let code_hash = B256::repeat_byte(0xcc);
+        let storage_root = B256::repeat_byte(0x55);
+        let account = Account { bytecode_hash: Some(code_hash), ..account };
+        let encoded = alloy_rlp::encode(SlimAccount::new(account, storage_root));
+        assert_eq!(encoded.len(), 2 + 1 + 1 + 33 + 33);
+        assert_eq!(&encoded[5..37], storage_root.as_slice());
+        assert_eq!(&encoded[38..], code_hash.as_slice());
+    }
+}
@@ -39,7 +39,8 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage,
+        IndexAccountHistoryStage, IndexAddressAppearancesStage, IndexLogsStage,
+        IndexStorageHistoryStage, IndexTracesStage, MerkleStage, PruneSenderRecoveryStage,
         PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
@@ -77,6 +78,9 @@ use tokio::sync::watch;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`IndexAddressAppearancesStage`] (if enabled)
+/// - [`IndexLogsStage`] (if enabled)
+/// - [`IndexTracesStage`] (if enabled)
 /// - [`PruneStage`] (execute)
 /// - [`FinishStage`]
 #[derive(Debug)]
@@ -280,6 +284,7 @@ where
 /// - [`PruneSenderRecoveryStage`]
 /// - [`HashingStages`]
 /// - [`HistoryIndexingStages`]
+/// - [`IndexTracesStage`] (if enabled)
 /// - [`PruneStage`]
 #[derive(Debug)]
 #[non_exhaustive]
@@ -308,14 +313,24 @@ impl<E: ConfigureEvm> OfflineStages<E> {
 
 impl<E, Provider> StageSet<Provider> for OfflineStages<E>
 where
-    E: ConfigureEvm,
+    E: ConfigureEvm + 'static,
     ExecutionStages<E>: StageSet<Provider>,
     PruneSenderRecoveryStage: Stage<Provider>,
     HashingStages: StageSet<Provider>,
     HistoryIndexingStages: StageSet<Provider>,
+    IndexTracesStage<E>: Stage<Provider>,
     PruneStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
+        // The trace index is opt-in and re-executes the blocks on top of the history indices.
+        let index_traces = self.stages_config.index_traces.enabled.then(|| {
+            IndexTracesStage::new(
+                self.stages_config.index_traces,
+                self.prune_modes.trace_index,
+                self.evm_config.clone(),
+            )
+        });
+
         ExecutionStages::new(self.evm_config, self.consensus, self.stages_config.clone())
             .builder()
             // If sender recovery prune mode is set, add the prune sender recovery stage.
@@ -327,6 +342,7 @@ where
                 stages_config: self.stages_config.clone(),
                 prune_modes: self.prune_modes.clone(),
             })
+            .add_stage_opt(index_traces)
             // If any prune modes are set, add the prune stage.
             .add_stage_opt(self.prune_modes.is_empty().not().then(|| {
                 // Prune stage should be added after all hashing stages, because otherwise it will
@@ -421,6 +437,8 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexAddressAppearancesStage: Stage<Provider>,
+    IndexLogsStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -439,5 +457,21 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.storage_history,
             ))
+            // The address appearances index is opt-in, add the stage only if it was enabled.
+            .add_stage_opt(self.stages_config.index_address_appearances.enabled.then(|| {
+                IndexAddressAppearancesStage::new(
+                    self.stages_config.index_address_appearances,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.address_appearances,
+                )
+            }))
+            // The log index is opt-in as well.
+            .add_stage_opt(self.stages_config.index_logs.enabled.then(|| {
+                IndexLogsStage::new(
+                    self.stages_config.index_logs,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.log_index,
+                )
+            }))
     }
 }
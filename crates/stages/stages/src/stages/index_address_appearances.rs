@@ -0,0 +1,277 @@
+use super::{
+    load_history_indices,
+    utils::{block_chunks, collect_sharded_blocks, sharded_index_range, skip_prunable_blocks},
+};
+use alloy_primitives::Address;
+use reth_config::config::{EtlConfig, IndexAddressAppearancesConfig};
+use reth_db_api::{models::ShardedKey, table::Decode, tables, transaction::DbTxMut};
+use reth_etl::Collector;
+use reth_provider::{
+    AddressAppearancesWriter, DBProvider, DatabaseProviderFactory, PruneCheckpointReader,
+    PruneCheckpointWriter, StageCheckpointReader, StageCheckpointWriter,
+};
+use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::fmt::Debug;
+use tracing::info;
+
+/// Number of blocks that are read into memory at once while collecting the appearances.
+const BLOCKS_PER_CHUNK: u64 = 1_000;
+
+/// Maximum number of blocks indexed in a single database transaction by
+/// [`IndexAddressAppearancesStage::index_next_batch`], so that the engine isn't blocked from
+/// persisting blocks for too long while the index catches up.
+const BLOCKS_PER_TIP_BATCH: u64 = 1_000;
+
+/// Stage that indexes the blocks in which an address appeared, either as the sender or the
+/// recipient of a transaction, or because its state was changed during execution. For more
+/// information on index sharding take a look at [`tables::AddressAppearances`].
+///
+/// The stage is optional and is only part of the pipeline if it was enabled in the config.
+#[derive(Debug)]
+pub struct IndexAddressAppearancesStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexAddressAppearancesStage {
+    /// Create new instance of [`IndexAddressAppearancesStage`].
+    pub const fn new(
+        config: IndexAddressAppearancesConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+
+    /// Indexes the next batch of blocks outside of the pipeline, up to the highest executed block.
+    ///
+    /// The provider only extends the index with persisted blocks that directly follow the
+    /// checkpoint of the stage, so the node calls this whenever the canonical chain changes to
+    /// index the blocks the pipeline didn't, e.g. after the stage was enabled on a synced node.
+    /// Each batch is committed in its own transaction.
+    ///
+    /// Returns `false` if the index already reached the highest executed block.
+    pub fn index_next_batch<F>(&mut self, factory: &F) -> Result<bool, StageError>
+    where
+        F: DatabaseProviderFactory<ProviderRW: StageCheckpointReader + StageCheckpointWriter>,
+        Self: Stage<F::ProviderRW>,
+    {
+        let provider = factory.database_provider_rw()?;
+
+        // Appearances are collected from the changesets written by the execution.
+        let target =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        let checkpoint = provider.get_stage_checkpoint(StageId::IndexAddressAppearances)?;
+        let mut input = ExecInput { target: Some(target), checkpoint };
+        if input.target_reached() {
+            return Ok(false)
+        }
+        input.target = Some(target.min(input.next_block() + BLOCKS_PER_TIP_BATCH - 1));
+
+        let output = self.execute(&provider, input)?;
+        provider.save_stage_checkpoint(StageId::IndexAddressAppearances, output.checkpoint)?;
+        provider.commit()?;
+
+        Ok(true)
+    }
+}
+
+impl Default for IndexAddressAppearancesStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexAddressAppearancesStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + AddressAppearancesWriter
+        + PruneCheckpointReader
+        + PruneCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressAppearances
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        let prune_target = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AddressAppearances,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten();
+        skip_prunable_blocks(provider, &mut input, PruneSegment::AddressAppearances, prune_target)?;
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, first_sync) = sharded_index_range(&input, || {
+            provider.tx_ref().clear::<tables::AddressAppearances>()
+        })?;
+
+        info!(target: "sync::stages::index_address_appearances::exec", ?first_sync, "Collecting indices");
+        let mut collector = Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        for chunk in block_chunks(range.clone(), BLOCKS_PER_CHUNK) {
+            collect_sharded_blocks(
+                &mut collector,
+                provider.address_appearances_with_range(chunk)?,
+            )?;
+        }
+
+        info!(target: "sync::stages::index_address_appearances::exec", "Loading indices into database");
+        load_history_indices::<_, tables::AddressAppearances, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_address_appearances_index_range(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_consensus::{transaction::SignerRecoverable, Transaction};
+    use alloy_primitives::{BlockNumber, B256};
+    use reth_ethereum_primitives::Block;
+    use reth_primitives_traits::SealedBlock;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::collections::BTreeMap;
+
+    fn expected_appearances(blocks: &[SealedBlock<Block>]) -> BTreeMap<Address, Vec<BlockNumber>> {
+        let mut appearances = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        for block in blocks {
+            for transaction in &block.body().transactions {
+                let sender = transaction.recover_signer().expect("recover signer");
+                appearances.entry(sender).or_default().push(block.number);
+                if let Some(to) = transaction.to() {
+                    appearances.entry(to).or_default().push(block.number);
+                }
+            }
+        }
+        for blocks in appearances.values_mut() {
+            blocks.dedup();
+        }
+        appearances
+    }
+
+    fn table(db: &TestStageDB) -> BTreeMap<Address, Vec<BlockNumber>> {
+        let mut appearances = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        for (key, list) in db.table::<tables::AddressAppearances>().unwrap() {
+            appearances.entry(key.key).or_default().extend(list.iter());
+        }
+        appearances
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=20,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("insert blocks");
+
+        let mut stage = IndexAddressAppearancesStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(20), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(20), done: true });
+        provider.commit().unwrap();
+
+        assert_eq!(table(&db), expected_appearances(&blocks));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output = stage
+            .unwind(
+                &provider,
+                UnwindInput {
+                    checkpoint: StageCheckpoint::new(20),
+                    unwind_to: 10,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(output, UnwindOutput { checkpoint: StageCheckpoint::new(10) });
+        provider.commit().unwrap();
+
+        assert_eq!(table(&db), expected_appearances(&blocks[..=10]));
+    }
+
+    #[test]
+    fn index_to_tip() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=20,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("insert blocks");
+
+        // the stage was enabled after the chain was executed
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(15)).unwrap();
+        provider.commit().unwrap();
+
+        let mut stage = IndexAddressAppearancesStage::default();
+        while stage.index_next_batch(&db.factory).unwrap() {}
+        assert_eq!(table(&db), expected_appearances(&blocks[..=15]));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::IndexAddressAppearances).unwrap(),
+            Some(StageCheckpoint::new(15))
+        );
+        provider.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(20)).unwrap();
+        provider.commit().unwrap();
+
+        assert!(stage.index_next_batch(&db.factory).unwrap());
+        assert!(!stage.index_next_batch(&db.factory).unwrap());
+        assert_eq!(table(&db), expected_appearances(&blocks));
+    }
+}
@@ -0,0 +1,226 @@
+use super::{
+    load_history_indices,
+    utils::{block_chunks, collect_sharded_blocks, sharded_index_range, skip_prunable_blocks},
+};
+use alloy_primitives::{Address, B256};
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db_api::{models::ShardedKey, table::Decode, tables, transaction::DbTxMut};
+use reth_etl::Collector;
+use reth_provider::{DBProvider, LogIndexWriter, PruneCheckpointReader, PruneCheckpointWriter};
+use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::fmt::Debug;
+use tracing::info;
+
+/// Number of blocks that are read into memory at once while collecting the logs.
+const BLOCKS_PER_CHUNK: u64 = 1_000;
+
+/// Stage that indexes the blocks in which logs were emitted, by the address of the emitting
+/// contract and by each of the log topics. For more information on index sharding take a look at
+/// [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`].
+///
+/// The stage is optional and is only part of the pipeline if it was enabled in the config.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(
+        config: IndexLogsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider:
+        DBProvider<Tx: DbTxMut> + LogIndexWriter + PruneCheckpointReader + PruneCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        let prune_target = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(input.target(), PruneSegment::LogIndex, PrunePurpose::User)
+            })
+            .transpose()?
+            .flatten();
+        skip_prunable_blocks(provider, &mut input, PruneSegment::LogIndex, prune_target)?;
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, first_sync) = sharded_index_range(&input, || {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            provider.tx_ref().clear::<tables::LogTopicIndex>()
+        })?;
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, "Collecting indices");
+        let mut address_collector =
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        let mut topic_collector =
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        for chunk in block_chunks(range.clone(), BLOCKS_PER_CHUNK) {
+            let entries = provider.log_index_entries_with_range(chunk)?;
+            collect_sharded_blocks(&mut address_collector, entries.addresses)?;
+            collect_sharded_blocks(&mut topic_collector, entries.topics)?;
+        }
+
+        info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider,
+            address_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicIndex, _>(
+            provider,
+            topic_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_index_range(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::BlockNumber;
+    use reth_db_api::{table::Table, BlockNumberList};
+    use reth_ethereum_primitives::Receipt;
+    use reth_provider::DatabaseProviderFactory;
+    use reth_testing_utils::generators::{self, random_block_range, random_log, BlockRangeParams};
+    use std::collections::BTreeMap;
+
+    fn table<K: Ord + Default, T>(db: &TestStageDB) -> BTreeMap<K, Vec<BlockNumber>>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut index = BTreeMap::<K, Vec<BlockNumber>>::new();
+        for (key, list) in db.table::<T>().unwrap() {
+            index.entry(key.key).or_default().extend(list.iter());
+        }
+        index
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+        let emitters = [Address::random(), Address::random(), Address::random()];
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=20,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("insert blocks");
+
+        let mut receipts = Vec::with_capacity(blocks.len());
+        let mut expected_addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut expected_topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+        let mut tx_num = 0u64;
+        for block in &blocks {
+            let mut block_receipts = Vec::new();
+            for transaction in &block.body().transactions {
+                let emitter = emitters[block.number as usize % emitters.len()];
+                let log = random_log(&mut rng, Some(emitter), Some(2));
+                if block.number <= 10 {
+                    let blocks = expected_addresses.entry(emitter).or_default();
+                    if blocks.last() != Some(&block.number) {
+                        blocks.push(block.number);
+                    }
+                    for topic in log.topics() {
+                        expected_topics.entry(*topic).or_default().push(block.number);
+                    }
+                }
+                let receipt = Receipt {
+                    tx_type: transaction.tx_type(),
+                    success: true,
+                    cumulative_gas_used: 0,
+                    logs: vec![log],
+                };
+                block_receipts.push((tx_num, receipt));
+                tx_num += 1;
+            }
+            receipts.push((block.number, block_receipts));
+        }
+        db.insert_receipts_by_block(receipts, StorageKind::Static).expect("insert receipts");
+
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(20), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(20), done: true });
+        provider.commit().unwrap();
+
+        assert_eq!(table::<_, tables::LogAddressIndex>(&db).len(), emitters.len());
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output = stage
+            .unwind(
+                &provider,
+                UnwindInput {
+                    checkpoint: StageCheckpoint::new(20),
+                    unwind_to: 10,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(output, UnwindOutput { checkpoint: StageCheckpoint::new(10) });
+        provider.commit().unwrap();
+
+        assert_eq!(table::<_, tables::LogAddressIndex>(&db), expected_addresses);
+        assert_eq!(table::<_, tables::LogTopicIndex>(&db), expected_topics);
+    }
+}
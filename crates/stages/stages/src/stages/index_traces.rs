@@ -0,0 +1,491 @@
+use super::utils::{block_chunks, skip_prunable_blocks};
+use alloy_consensus::BlockHeader;
+use alloy_eips::eip4788::SYSTEM_ADDRESS;
+use alloy_primitives::{Address, BlockNumber, U256};
+use reth_config::config::IndexTracesConfig;
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_evm::{execute::BlockExecutor, ConfigureEvm};
+use reth_primitives_traits::{BlockBody, NodePrimitives};
+use reth_provider::{
+    providers::LowestAvailableBlocks, BlockHashReader, BlockReader, DBProvider,
+    DatabaseProviderFactory, HistoricalStateProviderRef, PruneCheckpointReader,
+    PruneCheckpointWriter, StageCheckpointReader, StageCheckpointWriter, StateCommitmentProvider,
+    TraceIndexWriter,
+};
+use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment};
+use reth_revm::{
+    database::StateProviderDatabase,
+    inspector::Inspector,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    State,
+};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+use tracing::{debug, info};
+
+/// Number of blocks that are re-executed on top of the same state cache.
+const BLOCKS_PER_CHUNK: u64 = 1_000;
+
+/// Maximum number of blocks re-executed by [`IndexTracesStage::index_next_batch`] at once.
+const BLOCKS_PER_TIP_BATCH: u64 = 100;
+
+/// Stage that indexes the blocks in which addresses appeared in call traces. For more information
+/// on index sharding take a look at [`tables::TraceAddressIndex`].
+///
+/// Traces aren't stored, so the stage re-executes the blocks on top of the historical state and
+/// must run after the account and storage history indexing stages.
+///
+/// The stage is optional and is only part of the pipeline if it was enabled in the config.
+#[derive(Debug)]
+pub struct IndexTracesStage<E> {
+    /// The EVM configuration used to re-execute the blocks.
+    evm_config: E,
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+}
+
+impl<E> IndexTracesStage<E> {
+    /// Create new instance of [`IndexTracesStage`].
+    pub const fn new(
+        config: IndexTracesConfig,
+        prune_mode: Option<PruneMode>,
+        evm_config: E,
+    ) -> Self {
+        Self { evm_config, commit_threshold: config.commit_threshold, prune_mode }
+    }
+
+    /// Returns the highest block that must not be indexed, either because it's pruned from the
+    /// index or because its state history was pruned, together with the matching prune mode.
+    fn prune_target<Provider: PruneCheckpointReader>(
+        &self,
+        provider: &Provider,
+        input: &ExecInput,
+    ) -> Result<Option<(BlockNumber, PruneMode)>, StageError> {
+        let prune_target = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::TraceIndex,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten();
+        // Blocks whose state history was pruned can't be re-executed, so they are skipped as if
+        // they were pruned from the index.
+        let lowest_available_blocks = lowest_available_blocks(provider)?;
+        let history_target = lowest_available_blocks
+            .account_history_block_number
+            .max(lowest_available_blocks.storage_history_block_number)
+            .and_then(|block| block.checked_sub(1))
+            .map(|block| (block, PruneMode::Before(block + 1)));
+
+        Ok(prune_target.into_iter().chain(history_target).max_by_key(|(block, _)| *block))
+    }
+
+    /// Indexes the next batch of blocks outside of the pipeline, up to the highest block whose
+    /// state history is available.
+    ///
+    /// Blocks persisted by the engine are never seen by the pipeline, so the node calls this
+    /// whenever the canonical chain changes to keep the index up to date. The blocks are
+    /// re-executed in a read-only transaction, and the database writer is only held to write the
+    /// index. If the stage checkpoint or the indexed blocks changed in the meantime, e.g. because
+    /// of a reorg, the batch is discarded.
+    ///
+    /// Returns `false` if the index already reached the highest available block.
+    pub fn index_next_batch<F>(&mut self, factory: &F) -> Result<bool, StageError>
+    where
+        E: ConfigureEvm,
+        F: DatabaseProviderFactory<
+            Provider: BlockReader<Block = <E::Primitives as NodePrimitives>::Block>
+                          + StateCommitmentProvider
+                          + PruneCheckpointReader
+                          + StageCheckpointReader,
+            ProviderRW: BlockHashReader
+                            + PruneCheckpointReader
+                            + PruneCheckpointWriter
+                            + StageCheckpointReader
+                            + StageCheckpointWriter
+                            + TraceIndexWriter,
+        >,
+    {
+        let provider = factory.database_provider_ro()?;
+
+        // Blocks can only be re-executed once their changesets were indexed.
+        let mut target = u64::MAX;
+        for stage_id in
+            [StageId::Execution, StageId::IndexAccountHistory, StageId::IndexStorageHistory]
+        {
+            let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+            target = target.min(checkpoint.block_number);
+        }
+
+        let checkpoint = provider.get_stage_checkpoint(StageId::IndexTraces)?;
+        let mut input = ExecInput { target: Some(target), checkpoint };
+        if input.target_reached() {
+            return Ok(false)
+        }
+
+        let prune_target = self.prune_target(&provider, &input)?;
+        let mut skipped_input = input;
+        if let Some((target_prunable_block, _)) = prune_target {
+            if target_prunable_block > input.checkpoint().block_number {
+                skipped_input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+            }
+        }
+
+        let batch = if skipped_input.target_reached() {
+            None
+        } else {
+            let next_block = skipped_input.next_block();
+            let range = next_block..=target.min(next_block + BLOCKS_PER_TIP_BATCH - 1);
+            let end_hash = provider.block_hash(*range.end())?;
+            let addresses = trace_addresses_with_range(&self.evm_config, &provider, range.clone())?;
+            Some((range, end_hash, addresses))
+        };
+        drop(provider);
+
+        let provider = factory.database_provider_rw()?;
+        if provider.get_stage_checkpoint(StageId::IndexTraces)? != checkpoint {
+            debug!(target: "sync::stages::index_traces", "Checkpoint changed while tracing, discarding batch");
+            return Ok(true)
+        }
+        if let Some((range, end_hash, _)) = &batch {
+            if provider.block_hash(*range.end())? != *end_hash {
+                debug!(target: "sync::stages::index_traces", ?range, "Blocks changed while tracing, discarding batch");
+                return Ok(true)
+            }
+        }
+
+        skip_prunable_blocks(&provider, &mut input, PruneSegment::TraceIndex, prune_target)?;
+        // The stage might have been enabled and disabled before, leaving stale entries behind.
+        if input.checkpoint().block_number == 0 {
+            provider.tx_ref().clear::<tables::TraceAddressIndex>()?;
+            provider.tx_ref().clear::<tables::BlockTraceAddresses>()?;
+        }
+
+        let checkpoint = if let Some((range, _, addresses)) = batch {
+            provider.insert_trace_index(addresses)?;
+            StageCheckpoint::new(*range.end())
+        } else {
+            input.checkpoint()
+        };
+        provider.save_stage_checkpoint(StageId::IndexTraces, checkpoint)?;
+        provider.commit()?;
+
+        Ok(true)
+    }
+}
+
+impl<E, Provider> Stage<Provider> for IndexTracesStage<E>
+where
+    E: ConfigureEvm,
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader<Block = <E::Primitives as NodePrimitives>::Block>
+        + StateCommitmentProvider
+        + PruneCheckpointReader
+        + PruneCheckpointWriter
+        + TraceIndexWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexTraces
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        let prune_target = self.prune_target(provider, &input)?;
+        skip_prunable_blocks(provider, &mut input, PruneSegment::TraceIndex, prune_target)?;
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        // The stage might have been enabled and disabled before, leaving stale entries behind. We
+        // clear the tables since it's faster to rebuild from scratch. The genesis block has no
+        // traces, so the range always starts at the first block.
+        if input.checkpoint().block_number == 0 {
+            provider.tx_ref().clear::<tables::TraceAddressIndex>()?;
+            provider.tx_ref().clear::<tables::BlockTraceAddresses>()?;
+        }
+
+        info!(target: "sync::stages::index_traces::exec", ?range, "Re-executing blocks");
+        for chunk in block_chunks(range.clone(), BLOCKS_PER_CHUNK) {
+            let addresses = trace_addresses_with_range(&self.evm_config, provider, chunk)?;
+            provider.insert_trace_index(addresses)?;
+        }
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_trace_index_range(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Re-executes the given range of blocks and returns the addresses that appeared in the call
+/// traces of each block.
+///
+/// The state at the start of the range is read from the account and storage history, so the
+/// history must have been indexed up to the end of the range.
+pub fn trace_addresses_with_range<E, Provider>(
+    evm_config: &E,
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<BTreeMap<BlockNumber, BTreeSet<Address>>, StageError>
+where
+    E: ConfigureEvm,
+    Provider: DBProvider
+        + BlockReader<Block = <E::Primitives as NodePrimitives>::Block>
+        + StateCommitmentProvider
+        + PruneCheckpointReader,
+{
+    let state_provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+        provider,
+        *range.start(),
+        lowest_available_blocks(provider)?,
+    );
+    let mut db = State::builder().with_database(StateProviderDatabase::new(state_provider)).build();
+
+    let mut addresses = BTreeMap::new();
+    for block in provider.recovered_block_range(range)? {
+        let mut inspector = TraceAddressInspector::default();
+        let evm = evm_config.evm_with_env_and_inspector(
+            &mut db,
+            evm_config.evm_env(block.header()),
+            &mut inspector,
+        );
+        evm_config
+            .create_executor_for_block(evm, block.sealed_block())
+            .execute_block(block.transactions_recovered())
+            .map_err(|err| StageError::Fatal(Box::new(err)))?;
+
+        // Block and ommer rewards are traced as well.
+        inspector.addresses.insert(block.header().beneficiary());
+        for ommer in block.body().ommers().unwrap_or_default() {
+            inspector.addresses.insert(ommer.beneficiary());
+        }
+        addresses.insert(block.header().number(), inspector.addresses);
+    }
+
+    Ok(addresses)
+}
+
+/// Returns the lowest blocks at which the account and storage history are available.
+fn lowest_available_blocks<Provider: PruneCheckpointReader>(
+    provider: &Provider,
+) -> Result<LowestAvailableBlocks, StageError> {
+    let first_unpruned_block = |segment| {
+        Ok::<_, StageError>(
+            provider
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .map(|block_number| block_number + 1),
+        )
+    };
+
+    Ok(LowestAvailableBlocks {
+        account_history_block_number: first_unpruned_block(PruneSegment::AccountHistory)?,
+        storage_history_block_number: first_unpruned_block(PruneSegment::StorageHistory)?,
+    })
+}
+
+/// An [`Inspector`] that collects the senders and recipients of all calls, contract creations and
+/// selfdestructs.
+///
+/// Calls made by the system while applying the pre-execution changes of a block aren't part of
+/// any transaction trace, so they are skipped.
+#[derive(Debug, Default)]
+struct TraceAddressInspector {
+    addresses: BTreeSet<Address>,
+}
+
+impl<CTX> Inspector<CTX> for TraceAddressInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        if inputs.caller != SYSTEM_ADDRESS {
+            self.addresses.extend([inputs.caller, inputs.target_address, inputs.bytecode_address]);
+        }
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.addresses.insert(inputs.caller);
+        self.addresses.extend(outcome.address);
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        self.addresses.extend([contract, target]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::{address, hex_literal::hex, keccak256};
+    use alloy_rlp::Decodable;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_db_api::{models::ShardedKey, BlockNumberList};
+    use reth_ethereum_primitives::Block;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{Account, Bytecode, SealedBlock};
+    use reth_provider::{providers::StaticFileWriter, StaticFileProviderFactory, TraceIndexReader};
+    use reth_static_file_types::StaticFileSegment;
+    use std::sync::Arc;
+
+    /// Inserts a block with a call to a contract, and returns the addresses that appear in its
+    /// traces.
+    fn insert_traced_block(db: &TestStageDB) -> [Address; 3] {
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::<Block>::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::<Block>::decode(&mut block_rlp).unwrap();
+
+        let contract = address!("0x1000000000000000000000000000000000000000");
+        let sender = address!("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let beneficiary = address!("0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba");
+        let code = hex!("5a465a905090036002900360015500");
+        let code_hash = keccak256(code);
+
+        let provider = db.factory.provider_rw().unwrap();
+        provider.insert_historical_block(genesis.try_recover().unwrap()).unwrap();
+        provider.insert_historical_block(block.try_recover().unwrap()).unwrap();
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        let tx = provider.tx_ref();
+        tx.put::<tables::PlainAccountState>(
+            contract,
+            Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(
+            sender,
+            Account {
+                nonce: 0,
+                balance: U256::from(0x3635c9adc5dea00000u128),
+                bytecode_hash: None,
+            },
+        )
+        .unwrap();
+        tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        // The accounts were created in the genesis block, so their state at block 1 is read from
+        // the plain state.
+        for address in [contract, sender] {
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(address, u64::MAX),
+                BlockNumberList::new([0]).unwrap(),
+            )
+            .unwrap();
+        }
+        provider.commit().unwrap();
+
+        [contract, sender, beneficiary]
+    }
+
+    fn evm_config() -> EthEvmConfig {
+        EthEvmConfig::new(Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build()))
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let [contract, sender, beneficiary] = insert_traced_block(&db);
+        let mut stage = IndexTracesStage::new(IndexTracesConfig::default(), None, evm_config());
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(1), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(1), done: true });
+        provider.save_stage_checkpoint(StageId::IndexTraces, output.checkpoint).unwrap();
+        provider.commit().unwrap();
+
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(provider.trace_index_range().unwrap(), Some(0..=1));
+        for address in [contract, sender, beneficiary] {
+            assert_eq!(provider.trace_address_blocks(address, 0..=1).unwrap(), vec![1]);
+        }
+        assert!(provider.trace_address_blocks(Address::random(), 0..=1).unwrap().is_empty());
+        drop(provider);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output = stage
+            .unwind(
+                &provider,
+                UnwindInput {
+                    checkpoint: StageCheckpoint::new(1),
+                    unwind_to: 0,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(output, UnwindOutput { checkpoint: StageCheckpoint::new(0) });
+        provider.commit().unwrap();
+
+        assert!(db.table::<tables::TraceAddressIndex>().unwrap().is_empty());
+        assert!(db.table::<tables::BlockTraceAddresses>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn index_to_tip() {
+        let db = TestStageDB::default();
+        let [contract, sender, beneficiary] = insert_traced_block(&db);
+        let mut stage = IndexTracesStage::new(IndexTracesConfig::default(), None, evm_config());
+
+        // nothing to index until the history of the block was indexed
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(1)).unwrap();
+        provider.commit().unwrap();
+        assert!(!stage.index_next_batch(&db.factory).unwrap());
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        for stage_id in [StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+            provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(1)).unwrap();
+        }
+        provider.commit().unwrap();
+        assert!(stage.index_next_batch(&db.factory).unwrap());
+        assert!(!stage.index_next_batch(&db.factory).unwrap());
+
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::IndexTraces).unwrap(),
+            Some(StageCheckpoint::new(1))
+        );
+        for address in [contract, sender, beneficiary] {
+            assert_eq!(provider.trace_address_blocks(address, 0..=1).unwrap(), vec![1]);
+        }
+    }
+}
@@ -12,8 +12,14 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index blocks in which addresses appeared
+mod index_address_appearances;
+/// Index blocks in which logs were emitted
+mod index_logs;
 /// Index history of storage changes
 mod index_storage_history;
+/// Index blocks in which addresses appeared in call traces
+mod index_traces;
 /// Stage for computing state root.
 mod merkle;
 mod prune;
@@ -31,7 +37,10 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_address_appearances::*;
+pub use index_logs::*;
 pub use index_storage_history::*;
+pub use index_traces::*;
 pub use merkle::*;
 pub use prune::*;
 pub use s3::*;
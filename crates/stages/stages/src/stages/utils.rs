@@ -3,19 +3,24 @@ use alloy_primitives::{BlockNumber, TxNumber};
 use reth_config::config::EtlConfig;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
-    models::sharded_key::NUM_OF_INDICES_IN_SHARD,
-    table::{Decompress, Table},
+    models::{sharded_key::NUM_OF_INDICES_IN_SHARD, ShardedKey},
+    table::{Decompress, Key, Table},
     transaction::{DbTx, DbTxMut},
     BlockNumberList, DatabaseError,
 };
 use reth_etl::Collector;
 use reth_provider::{
-    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError,
-    StaticFileProviderFactory,
+    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError, PruneCheckpointReader,
+    PruneCheckpointWriter, StaticFileProviderFactory,
 };
-use reth_stages_api::StageError;
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+use reth_stages_api::{ExecInput, StageCheckpoint, StageError};
 use reth_static_file_types::StaticFileSegment;
-use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{RangeBounds, RangeInclusive},
+};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
@@ -248,6 +253,90 @@ impl LoadMode {
     }
 }
 
+/// Moves the checkpoint of an optional index stage past the blocks that are prunable according to
+/// `prune_target`, as returned by [`PruneMode::prune_target_block`], so that they aren't indexed.
+///
+/// The prune checkpoint of the segment is only saved if there's none yet. Otherwise, the pruner may
+/// skip the unpruned range of blocks.
+pub(crate) fn skip_prunable_blocks<Provider>(
+    provider: &Provider,
+    input: &mut ExecInput,
+    segment: PruneSegment,
+    prune_target: Option<(BlockNumber, PruneMode)>,
+) -> Result<(), StageError>
+where
+    Provider: PruneCheckpointReader + PruneCheckpointWriter,
+{
+    let Some((target_prunable_block, prune_mode)) = prune_target else { return Ok(()) };
+    if target_prunable_block <= input.checkpoint().block_number {
+        return Ok(())
+    }
+
+    input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+    if provider.get_prune_checkpoint(segment)?.is_none() {
+        provider.save_prune_checkpoint(
+            segment,
+            PruneCheckpoint {
+                block_number: Some(target_prunable_block),
+                tx_number: None,
+                prune_mode,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Returns the range of blocks an optional sharded index stage has to index, and whether the index
+/// is built from scratch.
+///
+/// The stage might have been enabled and disabled before, leaving stale entries behind. The index
+/// tables are cleared with `clear_tables` if nothing was indexed yet, since it's faster to rebuild
+/// the index from scratch.
+pub(crate) fn sharded_index_range(
+    input: &ExecInput,
+    clear_tables: impl FnOnce() -> Result<(), DatabaseError>,
+) -> Result<(RangeInclusive<BlockNumber>, bool), StageError> {
+    let range = input.next_block_range();
+    if input.checkpoint().block_number != 0 {
+        return Ok((range, false))
+    }
+
+    clear_tables()?;
+    Ok((0..=*range.end(), true))
+}
+
+/// Splits the range into consecutive chunks of at most `chunk_size` blocks.
+pub(crate) fn block_chunks(
+    range: RangeInclusive<BlockNumber>,
+    chunk_size: u64,
+) -> impl Iterator<Item = RangeInclusive<BlockNumber>> {
+    let end = *range.end();
+    range.step_by(chunk_size as usize).map(move |start| start..=end.min(start + chunk_size - 1))
+}
+
+/// Inserts the sorted block numbers of each key into the collector as a single shard, keyed by the
+/// highest block number.
+///
+/// The block numbers of successive calls must come from distinct chunks of blocks, so that the
+/// keys are unique.
+pub(crate) fn collect_sharded_blocks<K>(
+    collector: &mut Collector<ShardedKey<K>, BlockNumberList>,
+    blocks_by_key: impl IntoIterator<Item = (K, Vec<BlockNumber>)>,
+) -> Result<(), StageError>
+where
+    ShardedKey<K>: Key,
+{
+    for (key, blocks) in blocks_by_key {
+        let Some(&highest_block_number) = blocks.last() else { continue };
+        collector.insert(
+            ShardedKey::new(key, highest_block_number),
+            BlockNumberList::new_pre_sorted(blocks),
+        )?;
+    }
+    Ok(())
+}
+
 /// Called when database is ahead of static files. Attempts to find the first block we are missing
 /// transactions for.
 pub(crate) fn missing_static_data_error<Provider>(
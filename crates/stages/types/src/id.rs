@@ -20,6 +20,11 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    // Optional index stages. Their checkpoints only exist once the stages were enabled, see
+    // [`StageId::is_optional`].
+    IndexAddressAppearances,
+    IndexLogs,
+    IndexTraces,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -28,7 +33,7 @@ pub enum StageId {
 
 impl StageId {
     /// All supported Stages
-    pub const ALL: [Self; 14] = [
+    pub const ALL: [Self; 17] = [
         Self::Headers,
         Self::Bodies,
         Self::SenderRecovery,
@@ -41,6 +46,9 @@ impl StageId {
         Self::TransactionLookup,
         Self::IndexStorageHistory,
         Self::IndexAccountHistory,
+        Self::IndexAddressAppearances,
+        Self::IndexLogs,
+        Self::IndexTraces,
         Self::Prune,
         Self::Finish,
     ];
@@ -75,6 +83,9 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexAddressAppearances => "IndexAddressAppearances",
+            Self::IndexLogs => "IndexLogs",
+            Self::IndexTraces => "IndexTraces",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -91,6 +102,14 @@ impl StageId {
         matches!(self, Self::TransactionLookup)
     }
 
+    /// Returns `true` if the stage only runs when it was enabled in the config.
+    ///
+    /// The checkpoint of an optional stage is only created once the stage runs. It is advanced by
+    /// the stage and the index updates of the provider, not together with the other stages.
+    pub const fn is_optional(&self) -> bool {
+        matches!(self, Self::IndexAddressAppearances | Self::IndexLogs | Self::IndexTraces)
+    }
+
     /// Returns true indicating if it's the finish stage [`StageId::Finish`]
     pub const fn is_finish(&self) -> bool {
         matches!(self, Self::Finish)
@@ -118,6 +137,9 @@ mod tests {
         assert_eq!(StageId::StorageHashing.to_string(), "StorageHashing");
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
+        assert_eq!(StageId::IndexAddressAppearances.to_string(), "IndexAddressAppearances");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
+        assert_eq!(StageId::IndexTraces.to_string(), "IndexTraces");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
@@ -133,6 +155,14 @@ mod tests {
         assert!(!StageId::Execution.is_downloading_stage());
     }
 
+    #[test]
+    fn is_optional() {
+        assert!(StageId::IndexAddressAppearances.is_optional());
+        assert!(StageId::IndexLogs.is_optional());
+        assert!(StageId::IndexTraces.is_optional());
+        assert!(!StageId::IndexAccountHistory.is_optional());
+    }
+
     // Multiple places around the codebase assume headers is the first stage.
     // Feel free to remove this test if the assumption changes.
     #[test]
@@ -436,6 +436,66 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the blocks in which an address appeared.
+    ///
+    /// An address appears in a block if it sent or received a transaction in that block, or if
+    /// its account or storage was touched by the block's execution. Sharded the same way as
+    /// [`AccountsHistory`], with the last shard of the address keyed by `u64::MAX`.
+    ///
+    /// Only populated when the optional `IndexAddressAppearances` stage is enabled.
+    table AddressAppearances {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks in which an address emitted a log.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], with the last shard of the address keyed by
+    /// `u64::MAX`.
+    ///
+    /// Only populated when the optional `IndexLogs` stage is enabled.
+    table LogAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks in which a log with the topic was emitted, regardless of the
+    /// position of the topic in the log.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], with the last shard of the topic keyed by
+    /// `u64::MAX`.
+    ///
+    /// Only populated when the optional `IndexLogs` stage is enabled.
+    table LogTopicIndex {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks in which an address was the sender or the recipient of a
+    /// call trace, including internal calls, contract creations and selfdestructs, or received a
+    /// block reward.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], with the last shard of the address keyed by
+    /// `u64::MAX`.
+    ///
+    /// Only populated when the optional `IndexTraces` stage is enabled.
+    table TraceAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores the addresses indexed in [`TraceAddressIndex`] for each block.
+    ///
+    /// Traces can only be recomputed by re-executing the block, so the addresses are kept to
+    /// unwind and prune the index.
+    ///
+    /// Only populated when the optional `IndexTraces` stage is enabled.
+    table BlockTraceAddresses {
+        type Key = BlockNumber;
+        type Value = Address;
+        type SubKey = Address;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -138,8 +138,8 @@ where
 
     insert_genesis_state(&provider_rw, alloc.iter())?;
 
-    // insert sync stage
-    for stage in StageId::ALL {
+    // insert sync stage, optional stages create their checkpoint once they run
+    for stage in StageId::ALL.into_iter().filter(|stage| !stage.is_optional()) {
         provider_rw.save_stage_checkpoint(stage, Default::default())?;
     }
 
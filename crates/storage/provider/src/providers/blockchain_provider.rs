@@ -1,13 +1,14 @@
 #![allow(unused)]
 use crate::{
     providers::{ConsistentProvider, ProviderNodeTypes, StaticFileProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
-    ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
-    DatabaseProviderFactory, FullProvider, HashedPostStateProvider, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    AccountReader, AddressAppearancesReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker, CanonStateNotifications,
+    CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader, ChangeSetReader,
+    DatabaseProvider, DatabaseProviderFactory, FullProvider, HashedPostStateProvider,
+    HeaderProvider, LogIndexReader, ProviderError, ProviderFactory, PruneCheckpointReader,
+    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox,
+    StateProviderFactory, StateReader, StaticFileProviderFactory, TraceIndexReader,
+    TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, Header};
 use alloy_eips::{
@@ -36,14 +37,16 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, StateCommitmentProvider,
-    StorageChangeSetReader,
+    BlockBodyIndicesProvider, DBProvider, ModifiedAccountsReader, NodePrimitivesProvider,
+    StateCommitmentProvider, StorageChangeSetReader, StorageOverlay, StorageRange,
+    StorageRangeProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
 use reth_trie_db::StateCommitment;
 use revm_database::BundleState;
 use std::{
+    collections::BTreeSet,
     ops::{Add, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
     time::Instant,
@@ -467,6 +470,88 @@ impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressAppearancesReader for BlockchainProvider<N> {
+    fn address_appearances_indexed_to(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.address_appearances_indexed_to()
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.address_appearances_before(address, block, limit)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.address_appearances_after(address, block, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexReader for BlockchainProvider<N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.log_topic_blocks(topic, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> TraceIndexReader for BlockchainProvider<N> {
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.trace_index_range()
+    }
+
+    fn trace_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.trace_address_blocks(address, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> StorageRangeProvider for BlockchainProvider<N> {
+    fn storage_range(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        overlay: StorageOverlay,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        self.consistent_provider()?.storage_range(block_number, address, overlay, start, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> ModifiedAccountsReader for BlockchainProvider<N> {
+    fn modified_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.consistent_provider()?.modified_accounts_in_range(range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for BlockchainProvider<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.consistent_provider()?.get_stage_checkpoint(id)
@@ -26,12 +26,14 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader,
+    BlockBodyIndicesProvider, DatabaseProviderFactory, ModifiedAccountsReader,
+    NodePrimitivesProvider, StateProvider, StorageChangeSetReader, StorageOverlay, StorageRange,
+    StorageRangeProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use revm_database::states::PlainStorageRevert;
 use std::{
+    collections::BTreeSet,
     ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
 };
@@ -1396,6 +1398,71 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> StorageRangeProvider for ConsistentProvider<N> {
+    fn storage_range(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        overlay: StorageOverlay,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let Some(state) =
+            self.head_block.as_ref().and_then(|b| b.block_on_chain(block_number.into()))
+        else {
+            return self.storage_provider.storage_range(
+                block_number,
+                address,
+                overlay,
+                start,
+                limit,
+            )
+        };
+
+        // Apply the storage changes of the in-memory blocks up to the requested one on top of the
+        // latest database state, oldest first.
+        let mut in_memory = StorageOverlay::default();
+        for block_state in state.chain().collect::<Vec<_>>().into_iter().rev() {
+            let Some(account) = block_state.block_ref().execution_output.bundle.account(&address)
+            else {
+                continue
+            };
+            in_memory.extend(StorageOverlay {
+                wiped: account.was_destroyed(),
+                slots: account
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| (B256::from(*slot), value.present_value))
+                    .collect(),
+            });
+        }
+        in_memory.extend(overlay);
+
+        let last_block_number = self.storage_provider.last_block_number()?;
+        self.storage_provider.storage_range(last_block_number, address, in_memory, start, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> ModifiedAccountsReader for ConsistentProvider<N> {
+    fn modified_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let mut accounts = BTreeSet::new();
+        for block_number in range {
+            accounts.extend(
+                self.account_block_changeset(block_number)?.into_iter().map(|entry| entry.address),
+            );
+            accounts.extend(
+                self.storage_changeset(block_number)?
+                    .into_iter()
+                    .map(|(BlockNumberAddress((_, address)), _)| address),
+            );
+        }
+        Ok(accounts)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -23,14 +23,16 @@ use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, NodePrimitivesProvider, StateCommitmentProvider,
-    TryIntoHistoricalStateProvider,
+    AddressAppearancesReader, BlockBodyIndicesProvider, LogIndexReader, ModifiedAccountsReader,
+    NodePrimitivesProvider, StateCommitmentProvider, StorageOverlay, StorageRange,
+    StorageRangeProvider, TraceIndexReader, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
 use reth_trie_db::StateCommitment;
 use revm_database::BundleState;
 use std::{
+    collections::BTreeSet,
     ops::{RangeBounds, RangeInclusive},
     path::Path,
     sync::Arc,
@@ -560,6 +562,88 @@ impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressAppearancesReader for ProviderFactory<N> {
+    fn address_appearances_indexed_to(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.address_appearances_indexed_to()
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.address_appearances_before(address, block, limit)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.address_appearances_after(address, block, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexReader for ProviderFactory<N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.log_topic_blocks(topic, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> TraceIndexReader for ProviderFactory<N> {
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.trace_index_range()
+    }
+
+    fn trace_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.trace_address_blocks(address, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> StorageRangeProvider for ProviderFactory<N> {
+    fn storage_range(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        overlay: StorageOverlay,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        self.provider()?.storage_range(block_number, address, overlay, start, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> ModifiedAccountsReader for ProviderFactory<N> {
+    fn modified_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.provider()?.modified_accounts_in_range(range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for ProviderFactory<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.provider()?.get_stage_checkpoint(id)
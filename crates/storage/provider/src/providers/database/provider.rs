@@ -7,7 +7,8 @@ use crate::{
     },
     to_range,
     traits::{
-        AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
+        AccountExtReader, AddressAppearancesReader, AddressAppearancesWriter, BlockSource,
+        ChangeSetReader, LogIndexReader, LogIndexWriter, ReceiptProvider, StageCheckpointWriter,
     },
     AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
     BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
@@ -20,7 +21,7 @@ use crate::{
 };
 use alloy_consensus::{
     transaction::{SignerRecoverable, TransactionMeta},
-    BlockHeader, Header, TxReceipt,
+    BlockHeader, Header, Transaction as _, TxReceipt,
 };
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{
@@ -55,8 +56,10 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, BlockBodyReader, LogIndexEntries, ModifiedAccountsReader,
+    NodePrimitivesProvider, StateProvider, StorageChangeSetReader, StorageOverlay, StorageRange,
+    StorageRangeEntry, StorageRangeProvider, TraceIndexReader, TraceIndexWriter,
+    TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
@@ -902,6 +905,158 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> AddressAppearancesReader for DatabaseProvider<TX, N> {
+    fn address_appearances_indexed_to(&self) -> ProviderResult<Option<BlockNumber>> {
+        // The stage checkpoint only exists if the stage was enabled at some point.
+        Ok(self
+            .get_stage_checkpoint(StageId::IndexAddressAppearances)?
+            .map(|checkpoint| checkpoint.block_number))
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::AddressAppearances>()?;
+        let mut blocks = Vec::new();
+
+        // The first shard with the highest block number greater or equal to the requested one
+        // contains the closest lower appearances, if any. Otherwise, they're in the last shard of
+        // the address, which is right before the found one.
+        let mut shard = match cursor.seek(ShardedKey::new(address, block))? {
+            Some((key, _)) if key.key != address => cursor.prev()?,
+            None => cursor.last()?,
+            shard => shard,
+        };
+        while let Some((key, list)) = shard {
+            if key.key != address || blocks.len() >= limit {
+                break
+            }
+            let remaining = limit - blocks.len();
+            blocks.extend(list.iter().rev().skip_while(|number| *number >= block).take(remaining));
+            shard = cursor.prev()?;
+        }
+
+        Ok(blocks)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::AddressAppearances>()?;
+        let mut blocks = Vec::new();
+
+        let mut shard = cursor.seek(ShardedKey::new(address, block.saturating_add(1)))?;
+        while let Some((key, list)) = shard {
+            if key.key != address || blocks.len() >= limit {
+                break
+            }
+            let remaining = limit - blocks.len();
+            blocks.extend(list.iter().skip_while(|number| *number <= block).take(remaining));
+            shard = cursor.next()?;
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> LogIndexReader for DatabaseProvider<TX, N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        // The stage checkpoint only exists if the stage was enabled at some point.
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(None)
+        };
+        let first_block = self
+            .get_prune_checkpoint(PruneSegment::LogIndex)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+
+        Ok(Some(first_block..=checkpoint.block_number))
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks::<tables::LogAddressIndex, _>(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks::<tables::LogTopicIndex, _>(topic, range)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> TraceIndexReader for DatabaseProvider<TX, N> {
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        // The stage checkpoint only exists if the stage was enabled at some point.
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexTraces)? else {
+            return Ok(None)
+        };
+        let first_block = self
+            .get_prune_checkpoint(PruneSegment::TraceIndex)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+
+        Ok(Some(first_block..=checkpoint.block_number))
+    }
+
+    fn trace_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks::<tables::TraceAddressIndex, _>(address, range)
+    }
+}
+
+impl<TX: DbTx, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns the block numbers in the given range that are stored for the key in a sharded index
+    /// table, in ascending order.
+    fn sharded_index_blocks<T, K>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut blocks = Vec::new();
+
+        // The first shard with the highest block number greater or equal to the start of the
+        // range is the first one that can contain blocks of the range.
+        let (first_block, last_block) = range.into_inner();
+        let mut shard = cursor.seek(ShardedKey::new(key, first_block))?;
+        while let Some((sharded_key, list)) = shard {
+            if sharded_key.key != key {
+                break
+            }
+            blocks.extend(
+                list.iter()
+                    .skip_while(|number| *number < first_block)
+                    .take_while(|number| *number <= last_block),
+            );
+            if sharded_key.highest_block_number >= last_block {
+                break
+            }
+            shard = cursor.next()?;
+        }
+
+        Ok(blocks)
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N> {
     fn storage_changeset(
         &self,
@@ -1684,9 +1839,10 @@ impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N
         block_number: BlockNumber,
         drop_stage_checkpoint: bool,
     ) -> ProviderResult<()> {
-        // iterate over all existing stages in the table and update its progress.
+        // iterate over all existing stages in the table and update its progress. Optional stages
+        // are skipped, their checkpoints are advanced and unwound together with their indices.
         let mut cursor = self.tx.cursor_write::<tables::StageCheckpoints>()?;
-        for stage_id in StageId::ALL {
+        for stage_id in StageId::ALL.into_iter().filter(|stage_id| !stage_id.is_optional()) {
             let (_, checkpoint) = cursor.seek_exact(stage_id.to_string())?.unwrap_or_default();
             cursor.upsert(
                 stage_id.to_string(),
@@ -1764,6 +1920,241 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns the values at `block_number` of the storage slots of `address` that were changed
+    /// after it, keyed by the hashed slot.
+    ///
+    /// Only the `limit` smallest hashed slots from `start` on are returned. If more slots were
+    /// changed, the smallest hashed slot that was left out is returned as well, all slots
+    /// from it on are unknown.
+    fn storage_changes_after(
+        &self,
+        block_number: BlockNumber,
+        last_block_number: BlockNumber,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<(StorageChanges, Option<B256>)> {
+        // The next change of each slot is looked up in the storage history index, only the
+        // blocks that aren't indexed yet are read from the changesets directly.
+        let indexed_to = self
+            .get_stage_checkpoint(StageId::IndexStorageHistory)?
+            .unwrap_or_default()
+            .block_number
+            .min(last_block_number);
+
+        // The slots with the block of their next change, or their value if it was read from the
+        // changesets already.
+        let mut changes = BTreeMap::<B256, (B256, Result<U256, BlockNumber>)>::new();
+        let mut bound = None;
+
+        let mut changesets = self.tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let mut history = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut shard = history.seek(StorageShardedKey::new(address, B256::ZERO, 0))?;
+        while let Some((key, list)) = shard.filter(|(key, _)| key.address == address) {
+            let storage_key = key.sharded_key.key;
+
+            // Shards are ordered by their highest block number, skip to the first one that can
+            // contain a change after the requested block.
+            if key.sharded_key.highest_block_number <= block_number {
+                shard =
+                    history.seek(StorageShardedKey::new(address, storage_key, block_number + 1))?;
+                continue
+            }
+
+            if let Some(number) =
+                list.0.select(list.0.rank(block_number)).filter(|number| *number <= indexed_to)
+            {
+                let hashed_key = keccak256(storage_key);
+                if hashed_key >= start {
+                    insert_bounded(
+                        &mut changes,
+                        &mut bound,
+                        limit,
+                        hashed_key,
+                        (storage_key, Err(number)),
+                    );
+                }
+            }
+
+            // Continue with the shards of the next slot.
+            shard = history.seek(StorageShardedKey::last(address, storage_key))?;
+            if shard.as_ref().is_some_and(|(key, _)| key.sharded_key.key == storage_key) {
+                shard = history.next()?;
+            }
+        }
+
+        for number in block_number.max(indexed_to) + 1..=last_block_number {
+            for entry in changesets.walk_dup(Some(BlockNumberAddress((number, address))), None)? {
+                let (_, entry) = entry?;
+                let hashed_key = keccak256(entry.key);
+                if hashed_key >= start {
+                    insert_bounded(
+                        &mut changes,
+                        &mut bound,
+                        limit,
+                        hashed_key,
+                        (entry.key, Ok(entry.value)),
+                    );
+                }
+            }
+        }
+
+        let changes = changes
+            .into_iter()
+            .map(|(hashed_key, (storage_key, value))| {
+                let value = match value {
+                    Ok(value) => value,
+                    Err(number) => {
+                        changesets
+                            .seek_by_key_subkey(BlockNumberAddress((number, address)), storage_key)?
+                            .filter(|entry| entry.key == storage_key)
+                            .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                                block_number: number,
+                                address,
+                                storage_key: Box::new(storage_key),
+                            })?
+                            .value
+                    }
+                };
+                Ok((hashed_key, (storage_key, value)))
+            })
+            .collect::<ProviderResult<_>>()?;
+        Ok((changes, bound))
+    }
+}
+
+/// Storage slots of an account with their plain slot and value, keyed by the hashed slot.
+type StorageChanges = BTreeMap<B256, (B256, U256)>;
+
+/// Inserts a slot into `changes` unless it is present already, keeping only the `limit` smallest
+/// hashed slots. `bound` is lowered to the smallest hashed slot that was left out.
+fn insert_bounded<V>(
+    changes: &mut BTreeMap<B256, V>,
+    bound: &mut Option<B256>,
+    limit: usize,
+    hashed_key: B256,
+    value: V,
+) {
+    if bound.is_some_and(|bound| hashed_key >= bound) || changes.contains_key(&hashed_key) {
+        return
+    }
+    changes.insert(hashed_key, value);
+    if changes.len() > limit {
+        *bound = changes.pop_last().map(|(hashed_key, _)| hashed_key);
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> StorageRangeProvider for DatabaseProvider<TX, N> {
+    fn storage_range(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        overlay: StorageOverlay,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        // The stored storage is the latest one, so the slots changed after the requested block
+        // are reverted to the values from the oldest changeset following it.
+        let last_block_number = self.last_block_number()?;
+        let read_history = !overlay.wiped && block_number < last_block_number;
+        if read_history &&
+            self.get_prune_checkpoint(PruneSegment::StorageHistory)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .is_some_and(|pruned| block_number < pruned)
+        {
+            return Err(ProviderError::StateAtBlockPruned(block_number))
+        }
+
+        let overlay_slots = overlay
+            .slots
+            .into_iter()
+            .map(|(key, value)| (keccak256(key), (key, value)))
+            .collect::<BTreeMap<_, _>>();
+        let mut cursor = self.tx.cursor_dup_read::<tables::HashedStorages>()?;
+
+        // Only the changed slots that can make it into the range are collected. Cleared slots
+        // don't count towards the limit, so the range is continued from the first slot that was
+        // left out until it is full.
+        let mut range = StorageRange::default();
+        let mut from = start;
+        loop {
+            // Slots whose value differs from the stored one, keyed by the hashed slot.
+            let (mut changed, bound) = if read_history {
+                self.storage_changes_after(
+                    block_number,
+                    last_block_number,
+                    address,
+                    from,
+                    limit - range.entries.len() + 1,
+                )?
+            } else {
+                (BTreeMap::new(), None)
+            };
+            changed.extend(overlay_slots.range(from..));
+            if let Some(bound) = bound {
+                changed.split_off(&bound);
+            }
+
+            let mut stored = if overlay.wiped {
+                None
+            } else {
+                cursor.seek_by_key_subkey(keccak256(address), from)?
+            };
+            let mut changed = changed.into_iter().peekable();
+            loop {
+                // Stored slots from the bound on are left for the next round.
+                let next_stored =
+                    stored.filter(|entry| bound.is_none_or(|bound| entry.key < bound));
+
+                // The changed value takes precedence over the stored one.
+                let changed_first = match (&next_stored, changed.peek()) {
+                    (None, None) => break,
+                    (Some(entry), Some((hashed_key, _))) => entry.key >= *hashed_key,
+                    (stored, _) => stored.is_none(),
+                };
+
+                let entry = if changed_first {
+                    let Some((hashed_key, (key, value))) = changed.next() else { break };
+                    if stored.is_some_and(|entry| entry.key == hashed_key) {
+                        stored = cursor.next_dup_val()?;
+                    }
+                    StorageRangeEntry { hashed_key, key: Some(key), value }
+                } else {
+                    let Some(entry) = next_stored else { break };
+                    stored = cursor.next_dup_val()?;
+                    StorageRangeEntry { hashed_key: entry.key, key: None, value: entry.value }
+                };
+
+                if entry.value.is_zero() {
+                    continue
+                }
+                if range.entries.len() == limit {
+                    range.next_key = Some(entry.hashed_key);
+                    return Ok(range)
+                }
+                range.entries.push(entry);
+            }
+
+            match bound {
+                Some(bound) => from = bound,
+                None => return Ok(range),
+            }
+        }
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> ModifiedAccountsReader for DatabaseProvider<TX, N> {
+    fn modified_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let mut accounts = self.changed_accounts_with_range(range.clone())?;
+        accounts.extend(self.changed_storages_with_range(range)?.into_keys());
+        Ok(accounts)
+    }
+}
+
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
     for DatabaseProvider<TX, N>
 {
@@ -2735,6 +3126,247 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
     }
 }
 
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> AddressAppearancesWriter
+    for DatabaseProvider<TX, N>
+{
+    fn address_appearances_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<BlockNumber>>> {
+        let mut appearances = BTreeMap::<Address, Vec<BlockNumber>>::new();
+
+        for block in self.recovered_block_range(range.clone())? {
+            let block_number = block.number();
+            for (transaction, sender) in block.body().transactions_iter().zip(block.senders_iter())
+            {
+                appearances.entry(*sender).or_default().push(block_number);
+                if let Some(to) = transaction.to() {
+                    appearances.entry(to).or_default().push(block_number);
+                }
+            }
+        }
+
+        for (address, blocks) in self.changed_accounts_and_blocks_with_range(range.clone())? {
+            appearances.entry(address).or_default().extend(blocks);
+        }
+        for ((address, _), blocks) in self.changed_storages_and_blocks_with_range(range)? {
+            appearances.entry(address).or_default().extend(blocks);
+        }
+
+        for blocks in appearances.values_mut() {
+            blocks.sort_unstable();
+            blocks.dedup();
+        }
+
+        Ok(appearances)
+    }
+
+    fn insert_address_appearances_index(
+        &self,
+        appearances: impl IntoIterator<Item = (Address, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::AddressAppearances>(appearances, ShardedKey::new)
+    }
+
+    fn unwind_address_appearances_index_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let first_block = *range.start();
+        let appearances = self.address_appearances_with_range(range)?;
+        self.unwind_sharded_index::<tables::AddressAppearances, _>(
+            appearances.keys().copied(),
+            first_block,
+        )?;
+
+        Ok(appearances.len())
+    }
+
+    fn update_address_appearances_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexAddressAppearances)? else {
+            return Ok(())
+        };
+        // The stage hasn't caught up yet, it will index the range itself.
+        if checkpoint.block_number + 1 != *range.start() {
+            return Ok(())
+        }
+
+        let last_block_number = *range.end();
+        let appearances = self.address_appearances_with_range(range)?;
+        self.insert_address_appearances_index(appearances)?;
+
+        self.save_stage_checkpoint(
+            StageId::IndexAddressAppearances,
+            StageCheckpoint::new(last_block_number),
+        )
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> LogIndexWriter
+    for DatabaseProvider<TX, N>
+{
+    fn log_index_entries_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<LogIndexEntries> {
+        let mut entries = LogIndexEntries::default();
+        for (block_number, receipts) in range.clone().zip(self.receipts_by_block_range(range)?) {
+            entries.add_block_receipts(block_number, &receipts);
+        }
+        Ok(entries)
+    }
+
+    fn insert_log_index(&self, entries: LogIndexEntries) -> ProviderResult<()> {
+        let LogIndexEntries { addresses, topics } = entries;
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)
+    }
+
+    fn unwind_log_index_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let first_block = *range.start();
+        let LogIndexEntries { addresses, topics } = self.log_index_entries_with_range(range)?;
+        self.unwind_sharded_index::<tables::LogAddressIndex, _>(
+            addresses.keys().copied(),
+            first_block,
+        )?;
+        self.unwind_sharded_index::<tables::LogTopicIndex, _>(topics.keys().copied(), first_block)?;
+
+        Ok(addresses.len() + topics.len())
+    }
+
+    fn update_log_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        entries: LogIndexEntries,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(())
+        };
+        // The stage hasn't caught up yet, it will index the range itself.
+        if checkpoint.block_number + 1 != *range.start() {
+            return Ok(())
+        }
+
+        self.insert_log_index(entries)?;
+        self.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(*range.end()))
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> TraceIndexWriter
+    for DatabaseProvider<TX, N>
+{
+    fn insert_trace_index(
+        &self,
+        addresses: BTreeMap<BlockNumber, BTreeSet<Address>>,
+    ) -> ProviderResult<()> {
+        let mut index = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut cursor = self.tx.cursor_dup_write::<tables::BlockTraceAddresses>()?;
+        for (block_number, block_addresses) in addresses {
+            for address in block_addresses {
+                cursor.append_dup(block_number, address)?;
+                index.entry(address).or_default().push(block_number);
+            }
+        }
+
+        self.append_history_index::<_, tables::TraceAddressIndex>(index, ShardedKey::new)
+    }
+
+    fn unwind_trace_index_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let first_block = *range.start();
+        let addresses = self
+            .take::<tables::BlockTraceAddresses>(range)?
+            .into_iter()
+            .map(|(_, address)| address)
+            .collect::<BTreeSet<_>>();
+        self.unwind_sharded_index::<tables::TraceAddressIndex, _>(
+            addresses.iter().copied(),
+            first_block,
+        )?;
+
+        Ok(addresses.len())
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Removes all block numbers greater or equal to `first_block` from the shards of the given
+    /// keys in a sharded index table.
+    fn unwind_sharded_index<T, K>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        first_block: BlockNumber,
+    ) -> ProviderResult<()>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for key in keys {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key),
+                first_block,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unwinds the log index above the given block, if it's maintained.
+    fn unwind_log_index_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number <= block {
+            return Ok(())
+        }
+
+        self.unwind_log_index_range(block + 1..=checkpoint.block_number)?;
+        self.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(block))
+    }
+
+    /// Unwinds the trace index above the given block, if it's maintained.
+    fn unwind_trace_index_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexTraces)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number <= block {
+            return Ok(())
+        }
+
+        self.unwind_trace_index_range(block + 1..=checkpoint.block_number)?;
+        self.save_stage_checkpoint(StageId::IndexTraces, StageCheckpoint::new(block))
+    }
+
+    /// Unwinds the address appearance index above the given block, if it's maintained.
+    fn unwind_address_appearances_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexAddressAppearances)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number <= block {
+            return Ok(())
+        }
+
+        self.unwind_address_appearances_index_range(block + 1..=checkpoint.block_number)?;
+        self.save_stage_checkpoint(StageId::IndexAddressAppearances, StageCheckpoint::new(block))
+    }
+}
+
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecutionWriter
     for DatabaseProvider<TX, N>
 {
@@ -2745,6 +3377,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
     ) -> ProviderResult<Chain<Self::Primitives>> {
         let range = block + 1..=self.last_block_number()?;
 
+        self.unwind_address_appearances_above(block)?;
+        self.unwind_log_index_above(block)?;
+        self.unwind_trace_index_above(block)?;
         self.unwind_trie_state_range(range.clone())?;
 
         // get execution res
@@ -2769,6 +3404,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
     ) -> ProviderResult<()> {
         let range = block + 1..=self.last_block_number()?;
 
+        self.unwind_address_appearances_above(block)?;
+        self.unwind_log_index_above(block)?;
+        self.unwind_trace_index_above(block)?;
         self.unwind_trie_state_range(range)?;
 
         // remove execution res
@@ -3081,6 +3719,16 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
         durations_recorder.record_relative(metrics::Action::InsertHashes);
 
         self.update_history_indices(first_number..=last_block_number)?;
+        self.update_address_appearances_index(first_number..=last_block_number)?;
+        if self.log_index_range()?.is_some() {
+            let mut log_index_entries = LogIndexEntries::default();
+            for (block_number, receipts) in
+                (execution_outcome.first_block..).zip(&execution_outcome.receipts)
+            {
+                log_index_entries.add_block_receipts(block_number, receipts);
+            }
+            self.update_log_index(first_number..=last_block_number, log_index_entries)?;
+        }
         durations_recorder.record_relative(metrics::Action::InsertHistoryIndices);
 
         // Update pipeline progress
@@ -3445,4 +4093,151 @@ mod tests {
 
         assert_eq!(range_result, individual_results);
     }
+
+    #[test]
+    fn test_storage_range() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let slot = |n: u8| B256::with_last_byte(n);
+
+        // Slot 2 was changed and slot 4 was cleared in block 2.
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::CanonicalHeaders>(2, B256::ZERO).unwrap();
+        for (n, value) in [(1, 1), (2, 2), (3, 3)] {
+            tx.put::<tables::HashedStorages>(
+                keccak256(address),
+                StorageEntry { key: keccak256(slot(n)), value: U256::from(value) },
+            )
+            .unwrap();
+        }
+        for (n, value) in [(2, 20), (4, 4)] {
+            tx.put::<tables::StorageChangeSets>(
+                BlockNumberAddress((2, address)),
+                StorageEntry { key: slot(n), value: U256::from(value) },
+            )
+            .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        // Slot 3 is cleared and slot 5 is set on top of the state at block 1.
+        let overlay = StorageOverlay {
+            wiped: false,
+            slots: [(slot(3), U256::ZERO), (slot(5), U256::from(5))].into_iter().collect(),
+        };
+        let mut expected =
+            [(1, None, 1), (2, Some(slot(2)), 20), (4, Some(slot(4)), 4), (5, Some(slot(5)), 5)]
+                .into_iter()
+                .map(|(n, key, value)| StorageRangeEntry {
+                    hashed_key: keccak256(slot(n)),
+                    key,
+                    value: U256::from(value),
+                })
+                .collect::<Vec<_>>();
+        expected.sort_by_key(|entry| entry.hashed_key);
+
+        let provider = factory.provider().unwrap();
+        let range = provider.storage_range(1, address, overlay.clone(), B256::ZERO, 10).unwrap();
+        assert_eq!(range, StorageRange { entries: expected.clone(), next_key: None });
+
+        // Paginate by hashed key.
+        let first = provider.storage_range(1, address, overlay.clone(), B256::ZERO, 2).unwrap();
+        assert_eq!(
+            first,
+            StorageRange {
+                entries: expected[..2].to_vec(),
+                next_key: Some(expected[2].hashed_key)
+            }
+        );
+        let second =
+            provider.storage_range(1, address, overlay, first.next_key.unwrap(), 2).unwrap();
+        assert_eq!(second, StorageRange { entries: expected[2..].to_vec(), next_key: None });
+
+        // A wiped storage only contains the overlay slots.
+        let wiped =
+            StorageOverlay { wiped: true, slots: [(slot(5), U256::from(5))].into_iter().collect() };
+        let range = provider.storage_range(1, address, wiped, B256::ZERO, 10).unwrap();
+        assert_eq!(
+            range.entries,
+            expected.iter().filter(|entry| entry.key == Some(slot(5))).copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_storage_range_from_history_index() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let slot = |n: u8| B256::with_last_byte(n);
+
+        // Slot 1 changed in blocks 1 and 3, slot 2 in blocks 2 and 3 and slot 4 in block 4, which
+        // isn't indexed yet.
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::CanonicalHeaders>(4, B256::ZERO).unwrap();
+        for (n, value) in [(1, 100), (2, 200), (3, 3)] {
+            tx.put::<tables::HashedStorages>(
+                keccak256(address),
+                StorageEntry { key: keccak256(slot(n)), value: U256::from(value) },
+            )
+            .unwrap();
+        }
+        for (number, n, value) in [(1, 1, 0), (2, 2, 0), (3, 1, 10), (3, 2, 20), (4, 4, 4)] {
+            tx.put::<tables::StorageChangeSets>(
+                BlockNumberAddress((number, address)),
+                StorageEntry { key: slot(n), value: U256::from(value) },
+            )
+            .unwrap();
+        }
+        for (n, highest_block_number, blocks) in
+            [(1, 1, vec![1]), (1, u64::MAX, vec![3]), (2, u64::MAX, vec![2, 3])]
+        {
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::new(address, slot(n), highest_block_number),
+                BlockNumberList::new_pre_sorted(blocks),
+            )
+            .unwrap();
+        }
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexStorageHistory, StageCheckpoint::new(3))
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let mut expected = [(1, 10), (2, 20), (3, 3), (4, 4)]
+            .into_iter()
+            .map(|(n, value)| StorageRangeEntry {
+                hashed_key: keccak256(slot(n)),
+                key: (n != 3).then(|| slot(n)),
+                value: U256::from(value),
+            })
+            .collect::<Vec<_>>();
+        expected.sort_by_key(|entry| entry.hashed_key);
+
+        let provider = factory.provider().unwrap();
+        let range =
+            provider.storage_range(2, address, StorageOverlay::default(), B256::ZERO, 10).unwrap();
+        assert_eq!(range, StorageRange { entries: expected.clone(), next_key: None });
+
+        // Paginating one slot at a time yields the same entries.
+        let paginate = |block_number| {
+            let mut entries = Vec::new();
+            let mut start = Some(B256::ZERO);
+            while let Some(key) = start {
+                let range = provider
+                    .storage_range(block_number, address, StorageOverlay::default(), key, 1)
+                    .unwrap();
+                entries.extend(range.entries);
+                start = range.next_key;
+            }
+            entries
+        };
+        assert_eq!(paginate(2), expected);
+
+        // Slots 1 and 2 were cleared at block 0 and don't count towards the limit.
+        let cleared = [keccak256(slot(1)), keccak256(slot(2))];
+        let expected_at_genesis = expected
+            .into_iter()
+            .filter(|entry| !cleared.contains(&entry.hashed_key))
+            .collect::<Vec<_>>();
+        assert_eq!(paginate(0), expected_at_genesis);
+    }
 }
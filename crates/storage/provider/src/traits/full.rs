@@ -1,8 +1,10 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
+    AccountReader, AddressAppearancesReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
+    DatabaseProviderFactory, LogIndexReader, ModifiedAccountsReader, StageCheckpointReader,
+    StateProviderFactory, StaticFileProviderFactory, StorageChangeSetReader, StorageRangeProvider,
+    TraceIndexReader,
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
@@ -26,6 +28,12 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + StageCheckpointReader
+    + AddressAppearancesReader
+    + ModifiedAccountsReader
+    + StorageChangeSetReader
+    + StorageRangeProvider
+    + LogIndexReader
+    + TraceIndexReader
     + Clone
     + Debug
     + Unpin
@@ -49,6 +57,12 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + StageCheckpointReader
+        + AddressAppearancesReader
+        + ModifiedAccountsReader
+        + StorageChangeSetReader
+        + StorageRangeProvider
+        + LogIndexReader
+        + TraceIndexReader
         + Clone
         + Debug
         + Unpin
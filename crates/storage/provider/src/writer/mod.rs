@@ -9,7 +9,10 @@ use reth_db_api::transaction::{DbTx, DbTxMut};
 use reth_errors::{ProviderError, ProviderResult};
 use reth_primitives_traits::{NodePrimitives, SignedTransaction};
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{DBProvider, StageCheckpointWriter, TransactionsProviderExt};
+use reth_storage_api::{
+    AddressAppearancesWriter, DBProvider, LogIndexEntries, LogIndexReader, LogIndexWriter,
+    StageCheckpointWriter, TransactionsProviderExt,
+};
 use reth_storage_errors::writer::UnifiedStorageWriterError;
 use revm_database::OriginalValuesKnown;
 use std::sync::Arc;
@@ -126,6 +129,9 @@ where
         + TrieWriter
         + StateWriter
         + HistoryWriter
+        + AddressAppearancesWriter
+        + LogIndexReader
+        + LogIndexWriter
         + StageCheckpointWriter
         + BlockExecutionWriter
         + AsRef<ProviderDB>
@@ -159,6 +165,10 @@ where
         //  * hashed state
         //  * trie updates (cannot naively extend, need helper)
         //  * indices (already done basically)
+        // The log index is built from the in-memory receipts, since the receipts are not yet
+        // readable from the static files.
+        let mut log_index = self.database().log_index_range()?.map(|_| LogIndexEntries::default());
+
         // Insert the blocks
         for ExecutedBlockWithTrieUpdates {
             block: ExecutedBlock { recovered_block, execution_output, hashed_state },
@@ -166,6 +176,12 @@ where
         } in blocks
         {
             let block_hash = recovered_block.hash();
+            if let Some(log_index) = &mut log_index {
+                log_index.add_block_receipts(
+                    recovered_block.number(),
+                    execution_output.receipts.iter().flatten(),
+                );
+            }
             self.database()
                 .insert_block(Arc::unwrap_or_clone(recovered_block), StorageLocation::Both)?;
 
@@ -187,6 +203,10 @@ where
 
         // update history indices
         self.database().update_history_indices(first_number..=last_block_number)?;
+        self.database().update_address_appearances_index(first_number..=last_block_number)?;
+        if let Some(log_index) = log_index {
+            self.database().update_log_index(first_number..=last_block_number, log_index)?;
+        }
 
         // Update pipeline progress
         self.database().update_pipeline_stages(last_block_number, false)?;
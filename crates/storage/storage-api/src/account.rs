@@ -46,6 +46,56 @@ pub trait AccountExtReader {
     ) -> ProviderResult<BTreeMap<Address, Vec<BlockNumber>>>;
 }
 
+/// Reader for the index of blocks each address appeared in.
+///
+/// An address appears in a block if it sent or received one of its transactions, or if its
+/// account or storage was changed by it. The index is only populated when the optional
+/// `IndexAddressAppearances` stage is enabled.
+#[auto_impl(&, Arc, Box)]
+pub trait AddressAppearancesReader: Send + Sync {
+    /// Returns the highest block covered by the index, or `None` if the index is not enabled.
+    fn address_appearances_indexed_to(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns up to `limit` indexed blocks lower than `block` in which `address` appeared, in
+    /// descending order.
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns up to `limit` indexed blocks higher than `block` in which `address` appeared, in
+    /// ascending order.
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
+/// Reader for the index of blocks in which an address appeared in a call trace.
+///
+/// An address appears in the traces of a block if it was the sender or the recipient of a call,
+/// including internal calls, contract creations and selfdestructs, or if it received a block
+/// reward. The index is only populated when the optional `IndexTraces` stage is enabled.
+#[auto_impl(&, Arc, Box)]
+pub trait TraceIndexReader: Send + Sync {
+    /// Returns the range of blocks covered by the index, or `None` if the index is not enabled.
+    ///
+    /// Blocks below the range were pruned from the index.
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the indexed blocks in the given range in which `address` appeared in a trace, in
+    /// ascending order.
+    fn trace_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
 /// `AccountChange` reader
 #[auto_impl(&, Arc, Box)]
 pub trait ChangeSetReader {
@@ -55,3 +105,14 @@ pub trait ChangeSetReader {
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
 }
+
+/// Reader for the accounts modified by a range of blocks.
+#[auto_impl(&, Arc, Box)]
+pub trait ModifiedAccountsReader: Send + Sync {
+    /// Returns the accounts whose nonce, balance, code or storage was changed by any of the blocks
+    /// in the given range.
+    fn modified_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>>;
+}
@@ -3,8 +3,9 @@
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 
 use crate::{
-    BlockReaderIdExt, HeaderProvider, StageCheckpointReader, StateProviderFactory,
-    TransactionsProvider,
+    AddressAppearancesReader, BlockReaderIdExt, HeaderProvider, LogIndexReader,
+    ModifiedAccountsReader, StageCheckpointReader, StateProviderFactory, StorageRangeProvider,
+    TraceIndexReader, TransactionsProvider,
 };
 
 /// Helper trait to unify all provider traits required to support `eth` RPC server behaviour, for
@@ -16,6 +17,11 @@ pub trait FullRpcProvider:
     + HeaderProvider
     + TransactionsProvider
     + StageCheckpointReader
+    + AddressAppearancesReader
+    + ModifiedAccountsReader
+    + StorageRangeProvider
+    + LogIndexReader
+    + TraceIndexReader
     + Clone
     + Unpin
     + 'static
@@ -29,6 +35,11 @@ impl<T> FullRpcProvider for T where
         + HeaderProvider
         + TransactionsProvider
         + StageCheckpointReader
+        + AddressAppearancesReader
+        + ModifiedAccountsReader
+        + StorageRangeProvider
+        + LogIndexReader
+        + TraceIndexReader
         + Clone
         + Unpin
         + 'static
@@ -1,4 +1,9 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, BlockNumber, Log, B256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db_api::models::BlockNumberAddress;
@@ -56,3 +61,136 @@ pub trait HistoryWriter: Send + Sync {
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
+
+/// Address appearance index writer.
+///
+/// See [`AddressAppearancesReader`](crate::AddressAppearancesReader) for the definition of an
+/// appearance.
+#[auto_impl(&, Arc, Box)]
+pub trait AddressAppearancesWriter: Send + Sync {
+    /// Returns all addresses that appeared in the given range of blocks alongside the sorted
+    /// blocks they appeared in.
+    ///
+    /// Senders and recipients are read from the block bodies and touched accounts from the account
+    /// and storage changesets, so the range must not have been unwound yet.
+    fn address_appearances_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<BlockNumber>>>;
+
+    /// Insert address appearance index to database. Used inside `IndexAddressAppearances` stage
+    fn insert_address_appearances_index(
+        &self,
+        appearances: impl IntoIterator<Item = (Address, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear address appearance indices in a given block range.
+    ///
+    /// Returns number of addresses unwound.
+    fn unwind_address_appearances_index_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Update the address appearance index with the given range of blocks.
+    ///
+    /// The index is optional, so this does nothing unless `IndexAddressAppearances` stage
+    /// checkpoint exists and is right below the start of the range.
+    fn update_address_appearances_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()>;
+}
+
+/// Addresses and topics of the logs emitted in a range of blocks, alongside the sorted blocks they
+/// were emitted in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogIndexEntries {
+    /// Blocks in which each address emitted a log.
+    pub addresses: BTreeMap<Address, Vec<BlockNumber>>,
+    /// Blocks in which a log with each topic was emitted, regardless of the topic position.
+    pub topics: BTreeMap<B256, Vec<BlockNumber>>,
+}
+
+impl LogIndexEntries {
+    /// Adds the logs of the given block receipts.
+    ///
+    /// Blocks must be added in ascending order.
+    pub fn add_block_receipts<'a, R>(
+        &mut self,
+        block_number: BlockNumber,
+        receipts: impl IntoIterator<Item = &'a R>,
+    ) where
+        R: TxReceipt<Log = Log> + 'a,
+    {
+        for log in receipts.into_iter().flat_map(|receipt| receipt.logs()) {
+            push_block(self.addresses.entry(log.address).or_default(), block_number);
+            for topic in log.topics() {
+                push_block(self.topics.entry(*topic).or_default(), block_number);
+            }
+        }
+    }
+
+    /// Returns true if no logs were added.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.topics.is_empty()
+    }
+}
+
+fn push_block(blocks: &mut Vec<BlockNumber>, block_number: BlockNumber) {
+    if blocks.last() != Some(&block_number) {
+        blocks.push(block_number);
+    }
+}
+
+/// Log index writer.
+///
+/// See [`LogIndexReader`](crate::LogIndexReader) for the contents of the index.
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexWriter: Send + Sync {
+    /// Returns the addresses and topics of all logs emitted in the given range of blocks.
+    ///
+    /// The logs are read from the receipts, so the range must not have been unwound or pruned
+    /// yet.
+    fn log_index_entries_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<LogIndexEntries>;
+
+    /// Insert log index to database. Used inside `IndexLogs` stage
+    fn insert_log_index(&self, entries: LogIndexEntries) -> ProviderResult<()>;
+
+    /// Unwind and clear log indices in a given block range.
+    ///
+    /// Returns number of addresses and topics unwound.
+    fn unwind_log_index_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+
+    /// Update the log index with the logs of the given range of blocks.
+    ///
+    /// The index is optional, so this does nothing unless `IndexLogs` stage checkpoint exists and
+    /// is right below the start of the range.
+    fn update_log_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        entries: LogIndexEntries,
+    ) -> ProviderResult<()>;
+}
+
+/// Trace index writer.
+///
+/// See [`TraceIndexReader`](crate::TraceIndexReader) for the contents of the index.
+#[auto_impl(&, Arc, Box)]
+pub trait TraceIndexWriter: Send + Sync {
+    /// Insert the addresses that appeared in the traces of each block to database. Used inside
+    /// `IndexTraces` stage
+    fn insert_trace_index(
+        &self,
+        addresses: BTreeMap<BlockNumber, BTreeSet<Address>>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear trace indices in a given block range.
+    ///
+    /// Returns number of addresses unwound.
+    fn unwind_trace_index_range(&self, range: RangeInclusive<BlockNumber>)
+        -> ProviderResult<usize>;
+}
@@ -1,14 +1,15 @@
 //! Various noop implementations for traits.
 
 use crate::{
-    AccountReader, BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, ChangeSetReader, HashedPostStateProvider,
-    HeaderProvider, NodePrimitivesProvider, PruneCheckpointReader, ReceiptProvider,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProofProvider, StateProvider,
-    StateProviderBox, StateProviderFactory, StateRootProvider, StorageRootProvider,
-    TransactionVariant, TransactionsProvider,
+    AccountReader, AddressAppearancesReader, BlockBodyIndicesProvider, BlockHashReader,
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, ChangeSetReader,
+    HashedPostStateProvider, HeaderProvider, LogIndexReader, ModifiedAccountsReader,
+    NodePrimitivesProvider, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    StageCheckpointReader, StateProofProvider, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, StorageOverlay, StorageRange, StorageRangeProvider,
+    StorageRootProvider, TraceIndexReader, TransactionVariant, TransactionsProvider,
 };
-use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeSet, string::String, sync::Arc, vec::Vec};
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{
@@ -366,6 +367,64 @@ impl<C: Send + Sync, N: NodePrimitives> ChangeSetReader for NoopProvider<C, N> {
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> ModifiedAccountsReader for NoopProvider<C, N> {
+    fn modified_accounts_in_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::new())
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> LogIndexReader for NoopProvider<C, N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> TraceIndexReader for NoopProvider<C, N> {
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn trace_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> StorageRangeProvider for NoopProvider<C, N> {
+    fn storage_range(
+        &self,
+        _block_number: BlockNumber,
+        _address: Address,
+        _overlay: StorageOverlay,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        Ok(StorageRange::default())
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> StateRootProvider for NoopProvider<C, N> {
     fn state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(B256::default())
@@ -527,6 +586,30 @@ impl<C: Send + Sync, N: NodePrimitives> StageCheckpointReader for NoopProvider<C
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> AddressAppearancesReader for NoopProvider<C, N> {
+    fn address_appearances_indexed_to(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn address_appearances_before(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn address_appearances_after(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> PruneCheckpointReader for NoopProvider<C, N> {
     fn get_prune_checkpoint(
         &self,
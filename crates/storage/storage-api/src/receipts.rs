@@ -1,7 +1,7 @@
 use crate::BlockIdReader;
 use alloc::vec::Vec;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
-use alloy_primitives::{BlockNumber, TxHash, TxNumber};
+use alloy_primitives::{Address, BlockNumber, TxHash, TxNumber, B256};
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_primitives_traits::Receipt;
 use reth_storage_errors::provider::ProviderResult;
@@ -54,6 +54,34 @@ pub trait ReceiptProvider: Send + Sync {
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>>;
 }
 
+/// Reader for the index of blocks in which logs were emitted.
+///
+/// The index maps log addresses and topics to the blocks containing a matching log. It is only
+/// populated when the optional `IndexLogs` stage is enabled.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait LogIndexReader: Send + Sync {
+    /// Returns the range of blocks covered by the index, or `None` if the index is not enabled.
+    ///
+    /// Blocks below the range were pruned from the index.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the indexed blocks in the given range that contain a log emitted by `address`, in
+    /// ascending order.
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the indexed blocks in the given range that contain a log with `topic` at any
+    /// position, in ascending order.
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
 ///
 /// The `Receipt` trait should be implemented on types that can retrieve receipts from either
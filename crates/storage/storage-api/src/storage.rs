@@ -2,7 +2,7 @@ use alloc::{
     collections::{BTreeMap, BTreeSet},
     vec::Vec,
 };
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{map::B256Map, Address, BlockNumber, B256, U256};
 use core::ops::RangeInclusive;
 use reth_primitives_traits::StorageEntry;
 use reth_storage_errors::provider::ProviderResult;
@@ -43,6 +43,61 @@ pub trait StorageChangeSetReader: Send + Sync {
     ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>>;
 }
 
+/// Provider for paginating over the storage of an account, ordered by hashed slot.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait StorageRangeProvider: Send + Sync {
+    /// Returns up to `limit` non-empty storage slots of `address` with a hashed slot greater or
+    /// equal to `start`, as of the end of `block_number`, with `overlay` applied on top.
+    fn storage_range(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        overlay: StorageOverlay,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange>;
+}
+
+/// Storage changes of a single account, applied on top of the stored state.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageOverlay {
+    /// Whether the stored storage of the account was wiped.
+    pub wiped: bool,
+    /// Changed slots and their new values, keyed by the plain slot.
+    pub slots: B256Map<U256>,
+}
+
+impl StorageOverlay {
+    /// Applies the changes of `other` on top of this overlay.
+    pub fn extend(&mut self, other: Self) {
+        if other.wiped {
+            self.wiped = true;
+            self.slots.clear();
+        }
+        self.slots.extend(other.slots);
+    }
+}
+
+/// A page of storage slots, as returned by [`StorageRangeProvider::storage_range`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageRange {
+    /// Non-empty storage slots, ordered by hashed slot.
+    pub entries: Vec<StorageRangeEntry>,
+    /// Hashed slot of the first entry of the next page, `None` if this is the last page.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot in a [`StorageRange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageRangeEntry {
+    /// Hashed slot.
+    pub hashed_key: B256,
+    /// Plain slot, if it's known.
+    pub key: Option<B256>,
+    /// Value of the slot.
+    pub value: U256,
+}
+
 /// An enum that represents the storage location for a piece of data.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum StorageLocation {
@@ -0,0 +1,328 @@
+//! Storage for transaction bundles and private transactions.
+//!
+//! Bundles are submitted via `eth_sendBundle` and private transactions via
+//! `eth_sendPrivateTransaction`. Neither are ever propagated to peers: they are only kept until the
+//! payload builder includes them atomically at the top of a block, they are cancelled or they
+//! expire.
+
+use alloy_primitives::{keccak256, map::HashMap, TxHash, B256};
+use parking_lot::RwLock;
+use reth_primitives_traits::{Recovered, SignedTransaction};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
+/// The default maximum number of bundles kept in the [`BundlePool`].
+pub const DEFAULT_MAX_BUNDLES: usize = 1024;
+
+/// Returns the hash of a bundle consisting of the given transaction hashes.
+///
+/// This is the keccak256 hash of the concatenated transaction hashes.
+pub fn bundle_hash<'a>(tx_hashes: impl IntoIterator<Item = &'a TxHash>) -> B256 {
+    let mut buf = Vec::new();
+    for hash in tx_hashes {
+        buf.extend_from_slice(hash.as_slice());
+    }
+    keccak256(buf)
+}
+
+/// A bundle of transactions that must be included atomically and in order.
+///
+/// A private transaction is represented as a bundle with a single transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolBundle<T> {
+    /// Hash of the bundle, see [`bundle_hash`].
+    pub hash: B256,
+    /// The ordered transactions of the bundle.
+    pub transactions: Vec<Recovered<T>>,
+    /// The first block number the bundle is valid for.
+    pub min_block_number: u64,
+    /// The last block number the bundle is valid for.
+    pub max_block_number: u64,
+    /// The minimum timestamp the bundle is valid for.
+    pub min_timestamp: Option<u64>,
+    /// The maximum timestamp the bundle is valid for.
+    pub max_timestamp: Option<u64>,
+    /// Hashes of transactions that are allowed to revert.
+    pub reverting_tx_hashes: HashSet<TxHash>,
+    /// Optional identifier that can be used to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+}
+
+impl<T: SignedTransaction> PoolBundle<T> {
+    /// Creates a new bundle that targets the given block range.
+    ///
+    /// The hash of the bundle is derived from the transactions.
+    pub fn new(
+        transactions: Vec<Recovered<T>>,
+        min_block_number: u64,
+        max_block_number: u64,
+    ) -> Self {
+        let hash = bundle_hash(transactions.iter().map(|tx| tx.tx_hash()));
+        Self {
+            hash,
+            transactions,
+            min_block_number,
+            max_block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Default::default(),
+            replacement_uuid: None,
+        }
+    }
+
+    /// Returns true if the bundle can be included in a block with the given number and timestamp.
+    pub fn is_eligible(&self, block_number: u64, timestamp: u64) -> bool {
+        (self.min_block_number..=self.max_block_number).contains(&block_number) &&
+            self.min_timestamp.is_none_or(|min| timestamp >= min) &&
+            self.max_timestamp.is_none_or(|max| timestamp <= max)
+    }
+
+    /// Returns true if the bundle expired and can never be included after the given block.
+    pub const fn is_expired(&self, block_number: u64) -> bool {
+        self.max_block_number <= block_number
+    }
+
+    /// Returns true if the transaction with the given hash is allowed to revert.
+    pub fn can_revert(&self, tx_hash: &TxHash) -> bool {
+        self.reverting_tx_hashes.contains(tx_hash)
+    }
+
+    /// Returns the total gas limit of all transactions in the bundle.
+    pub fn gas_limit(&self) -> u64 {
+        self.transactions.iter().map(|tx| tx.gas_limit()).sum()
+    }
+}
+
+/// Errors that can occur when adding a bundle to the [`BundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// The bundle contains no transactions.
+    #[error("bundle contains no transactions")]
+    EmptyBundle,
+    /// A bundle with the same hash is already known.
+    #[error("bundle {0} already known")]
+    AlreadyKnown(B256),
+    /// The pool reached its capacity.
+    #[error("bundle pool is full")]
+    PoolFull,
+}
+
+/// A shareable store of [`PoolBundle`]s.
+///
+/// Bundles are returned in the order they were submitted.
+#[derive(Debug)]
+pub struct BundlePool<T> {
+    inner: Arc<RwLock<BundlePoolInner<T>>>,
+}
+
+impl<T> Clone for BundlePool<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<T> Default for BundlePool<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUNDLES)
+    }
+}
+
+impl<T> BundlePool<T> {
+    /// Creates a new empty pool that holds at most `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(BundlePoolInner {
+                max_bundles,
+                next_id: 0,
+                by_hash: Default::default(),
+                by_id: Default::default(),
+                by_uuid: Default::default(),
+            })),
+        }
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().by_hash.len()
+    }
+
+    /// Returns true if the pool holds no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().by_hash.is_empty()
+    }
+
+    /// Returns the bundle with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<Arc<PoolBundle<T>>> {
+        let inner = self.inner.read();
+        inner.by_hash.get(hash).and_then(|id| inner.by_id.get(id)).cloned()
+    }
+
+    /// Adds a bundle to the pool and returns its hash.
+    ///
+    /// If the bundle has a replacement uuid, any bundle previously submitted with the same uuid is
+    /// replaced.
+    pub fn add_bundle(&self, bundle: PoolBundle<T>) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+
+        let mut inner = self.inner.write();
+        if inner.by_hash.contains_key(&bundle.hash) {
+            return Err(BundlePoolError::AlreadyKnown(bundle.hash))
+        }
+
+        let replaced = bundle.replacement_uuid.as_ref().and_then(|uuid| inner.by_uuid.get(uuid));
+        if let Some(replaced) = replaced.copied() {
+            inner.remove(&replaced);
+        } else if inner.by_hash.len() >= inner.max_bundles {
+            return Err(BundlePoolError::PoolFull)
+        }
+
+        let hash = bundle.hash;
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.by_hash.insert(hash, id);
+        if let Some(uuid) = bundle.replacement_uuid.clone() {
+            inner.by_uuid.insert(uuid, hash);
+        }
+        inner.by_id.insert(id, Arc::new(bundle));
+        Ok(hash)
+    }
+
+    /// Removes the bundle identified by the given replacement uuid or bundle hash.
+    ///
+    /// Returns the removed bundle, if any.
+    pub fn cancel_bundle(&self, id: &str) -> Option<Arc<PoolBundle<T>>> {
+        let mut inner = self.inner.write();
+        let hash = inner.by_uuid.get(id).copied().or_else(|| id.parse::<B256>().ok())?;
+        inner.remove(&hash)
+    }
+
+    /// Removes the private transaction with the given hash.
+    ///
+    /// Returns true if the transaction was removed.
+    pub fn remove_private_transaction(&self, tx_hash: TxHash) -> bool {
+        self.inner.write().remove(&bundle_hash([&tx_hash])).is_some()
+    }
+
+    /// Returns all bundles that can be included in a block with the given number and timestamp,
+    /// in the order they were submitted.
+    pub fn bundles_for_block(&self, block_number: u64, timestamp: u64) -> Vec<Arc<PoolBundle<T>>>
+    where
+        T: SignedTransaction,
+    {
+        self.inner
+            .read()
+            .by_id
+            .values()
+            .filter(|bundle| bundle.is_eligible(block_number, timestamp))
+            .cloned()
+            .collect()
+    }
+
+    /// Updates the pool after a new canonical block.
+    ///
+    /// This removes all bundles that expired and all bundles that contain a transaction that was
+    /// mined.
+    pub fn on_canonical_state_change(&self, block_number: u64, mined_transactions: &[TxHash])
+    where
+        T: SignedTransaction,
+    {
+        let mined = mined_transactions.iter().collect::<HashSet<_>>();
+        let mut inner = self.inner.write();
+        let stale = inner
+            .by_id
+            .values()
+            .filter(|bundle| {
+                bundle.is_expired(block_number) ||
+                    bundle.transactions.iter().any(|tx| mined.contains(tx.tx_hash()))
+            })
+            .map(|bundle| bundle.hash)
+            .collect::<Vec<_>>();
+        for hash in stale {
+            inner.remove(&hash);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BundlePoolInner<T> {
+    /// Maximum number of bundles.
+    max_bundles: usize,
+    /// Insertion id assigned to the next bundle.
+    next_id: u64,
+    /// Bundle hash to insertion id.
+    by_hash: HashMap<B256, u64>,
+    /// All bundles, ordered by insertion.
+    by_id: BTreeMap<u64, Arc<PoolBundle<T>>>,
+    /// Replacement uuid to bundle hash.
+    by_uuid: HashMap<String, B256>,
+}
+
+impl<T> BundlePoolInner<T> {
+    fn remove(&mut self, hash: &B256) -> Option<Arc<PoolBundle<T>>> {
+        let id = self.by_hash.remove(hash)?;
+        let bundle = self.by_id.remove(&id)?;
+        if let Some(uuid) = &bundle.replacement_uuid {
+            self.by_uuid.remove(uuid);
+        }
+        Some(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Signed, TxLegacy};
+    use alloy_primitives::{Address, Signature};
+    use reth_ethereum_primitives::TransactionSigned;
+
+    fn tx(nonce: u64) -> Recovered<TransactionSigned> {
+        let tx = TxLegacy { nonce, gas_limit: 21_000, ..Default::default() };
+        let signed = Signed::new_unhashed(tx, Signature::test_signature());
+        Recovered::new_unchecked(signed.into(), Address::ZERO)
+    }
+
+    #[test]
+    fn add_replace_and_cancel() {
+        let pool = BundlePool::default();
+
+        let mut first = PoolBundle::new(vec![tx(0)], 1, 1);
+        first.replacement_uuid = Some("uuid".to_string());
+        let first_hash = pool.add_bundle(first.clone()).unwrap();
+        assert_eq!(pool.add_bundle(first), Err(BundlePoolError::AlreadyKnown(first_hash)));
+
+        let mut second = PoolBundle::new(vec![tx(1)], 1, 1);
+        second.replacement_uuid = Some("uuid".to_string());
+        let second_hash = pool.add_bundle(second).unwrap();
+        assert_eq!(pool.len(), 1);
+        assert!(pool.get(&first_hash).is_none());
+
+        assert_eq!(pool.cancel_bundle("uuid").unwrap().hash, second_hash);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn eligibility_and_pruning() {
+        let pool = BundlePool::default();
+        let private = tx(0);
+        let private_hash = *private.tx_hash();
+        pool.add_bundle(PoolBundle::new(vec![private], 1, 25)).unwrap();
+        let mut timed = PoolBundle::new(vec![tx(1), tx(2)], 2, 2);
+        timed.min_timestamp = Some(10);
+        pool.add_bundle(timed).unwrap();
+
+        assert_eq!(pool.bundles_for_block(1, 0).len(), 1);
+        assert_eq!(pool.bundles_for_block(2, 5).len(), 1);
+        assert_eq!(pool.bundles_for_block(2, 10).len(), 2);
+
+        pool.on_canonical_state_change(2, &[]);
+        assert_eq!(pool.len(), 1);
+
+        pool.on_canonical_state_change(3, &[private_hash]);
+        assert!(pool.is_empty());
+        assert!(!pool.remove_private_transaction(private_hash));
+    }
+}
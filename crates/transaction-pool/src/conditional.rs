@@ -0,0 +1,126 @@
+//! Additional support for pooled transactions with [`TransactionConditional`]
+
+use alloy_consensus::conditional::BlockConditionalAttributes;
+use alloy_primitives::{Address, StorageKey, B256, U256};
+use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+use reth_storage_api::{errors::provider::ProviderResult, StateProvider};
+
+/// Helper trait that allows attaching a [`TransactionConditional`].
+pub trait MaybeConditionalTransaction {
+    /// Attach a [`TransactionConditional`].
+    fn set_conditional(&mut self, conditional: TransactionConditional);
+
+    /// Get attached [`TransactionConditional`] if any.
+    fn conditional(&self) -> Option<&TransactionConditional>;
+
+    /// Check if the conditional has exceeded the block attributes.
+    fn has_exceeded_block_attributes(&self, block_attr: &BlockConditionalAttributes) -> bool {
+        self.conditional().map(|tc| tc.has_exceeded_block_attributes(block_attr)).unwrap_or(false)
+    }
+
+    /// Helper that sets the conditional and returns the instance again
+    fn with_conditional(mut self, conditional: TransactionConditional) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_conditional(conditional);
+        self
+    }
+}
+
+/// A `known accounts` entry of a [`TransactionConditional`] that doesn't match the state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum KnownAccountMismatch {
+    /// The value of a storage slot differs from the expected value.
+    #[error("storage value mismatch of {address} at slot {slot}")]
+    StorageValue {
+        /// The account of the slot.
+        address: Address,
+        /// The mismatching slot.
+        slot: B256,
+    },
+    /// The storage root of an account differs from the expected root.
+    #[error("storage root mismatch of {address}")]
+    StorageRoot {
+        /// The mismatching account.
+        address: Address,
+    },
+}
+
+/// Checks the `known accounts` of the conditional against the given state.
+///
+/// Returns the first entry that doesn't match, if any.
+pub fn find_known_account_mismatch<S>(
+    state: &S,
+    conditional: &TransactionConditional,
+) -> ProviderResult<Option<KnownAccountMismatch>>
+where
+    S: StateProvider + ?Sized,
+{
+    for (address, storage) in &conditional.known_accounts {
+        match storage {
+            AccountStorage::Slots(slots) => {
+                for (slot, expected_value) in slots {
+                    let current =
+                        state.storage(*address, StorageKey::from(*slot))?.unwrap_or_default();
+                    if current != U256::from_be_bytes(**expected_value) {
+                        return Ok(Some(KnownAccountMismatch::StorageValue {
+                            address: *address,
+                            slot: (*slot).into(),
+                        }))
+                    }
+                }
+            }
+            AccountStorage::RootHash(expected_root) => {
+                let actual_root = state.storage_root(*address, Default::default())?;
+                if *expected_root != actual_root {
+                    return Ok(Some(KnownAccountMismatch::StorageRoot { address: *address }))
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::constants::EMPTY_ROOT_HASH;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn known_accounts_against_state() {
+        let bridge = Address::random();
+        let slot = U256::from(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            bridge,
+            ExtendedAccount::new(0, U256::ZERO).extend_storage([(slot.into(), U256::from(7))]),
+        );
+
+        let mut conditional = TransactionConditional::default();
+        conditional.known_accounts.insert(
+            bridge,
+            AccountStorage::Slots(std::iter::once((slot, B256::from(U256::from(7)))).collect()),
+        );
+        assert_eq!(find_known_account_mismatch(&provider, &conditional).unwrap(), None);
+
+        conditional.known_accounts.insert(
+            bridge,
+            AccountStorage::Slots(std::iter::once((slot, B256::from(U256::from(8)))).collect()),
+        );
+        assert_eq!(
+            find_known_account_mismatch(&provider, &conditional).unwrap(),
+            Some(KnownAccountMismatch::StorageValue { address: bridge, slot: slot.into() })
+        );
+
+        conditional.known_accounts.insert(bridge, AccountStorage::RootHash(EMPTY_ROOT_HASH));
+        assert_eq!(find_known_account_mismatch(&provider, &conditional).unwrap(), None);
+
+        conditional.known_accounts.insert(bridge, AccountStorage::RootHash(B256::ZERO));
+        assert_eq!(
+            find_known_account_mismatch(&provider, &conditional).unwrap(),
+            Some(KnownAccountMismatch::StorageRoot { address: bridge })
+        );
+    }
+}
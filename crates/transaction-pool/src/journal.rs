@@ -0,0 +1,637 @@
+//! Write-ahead journal of all pool transactions.
+//!
+//! Unlike the local transactions backup of [`maintain`](crate::maintain), which only saves
+//! [`TransactionOrigin::Local`] transactions on shutdown, the journal records every transaction
+//! that enters or leaves the pool as it happens. On startup the journal is replayed through the
+//! validator in the original submission order and with the original [`TransactionOrigin`].
+//!
+//! The journal is a file of RLP records which is compacted periodically by rewriting it with the
+//! current contents of the pool. Blob sidecars are stored next to the journal, one file per
+//! transaction, because the blob store is cleared on startup.
+
+use crate::{
+    traits::{EthPoolTransaction, TransactionPool},
+    FullTransactionEvent, PoolTransaction, TransactionOrigin, ValidPoolTransaction,
+};
+use alloy_consensus::Typed2718;
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    eip7594::BlobTransactionSidecarVariant,
+};
+use alloy_primitives::{Bytes, TxHash};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
+use futures_util::StreamExt;
+use reth_fs_util::FsPathError;
+use reth_primitives_traits::transaction::signed::SignedTransaction;
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use tokio::time::{self, Duration, Instant};
+use tracing::{debug, error, info, trace, warn};
+
+/// Default interval after which the journal is compacted.
+pub const DEFAULT_JOURNAL_COMPACTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Settings for the transaction journal task.
+#[derive(Debug, Clone)]
+pub struct TransactionJournalConfig {
+    /// Path to the journal file.
+    pub path: PathBuf,
+    /// How often the journal is rewritten with the current contents of the pool.
+    pub compaction_interval: Duration,
+}
+
+impl TransactionJournalConfig {
+    /// Creates a new config with the given journal path and the default compaction interval.
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path, compaction_interval: DEFAULT_JOURNAL_COMPACTION_INTERVAL }
+    }
+
+    /// Sets the compaction interval.
+    pub const fn with_compaction_interval(mut self, compaction_interval: Duration) -> Self {
+        self.compaction_interval = compaction_interval;
+        self
+    }
+
+    /// Returns the directory the blob sidecars of journaled transactions are stored in.
+    pub fn blobs_dir(&self) -> PathBuf {
+        self.path.with_extension("blobs")
+    }
+}
+
+/// Errors possible when reading or writing the transaction journal.
+#[derive(thiserror::Error, Debug)]
+pub enum TransactionJournalError {
+    /// Error during RLP decoding of a journal record
+    #[error("failed to read transaction journal. Encountered RLP decode error: {0}")]
+    Decode(#[from] alloy_rlp::Error),
+    /// Error accessing the journal files
+    #[error("failed to access transaction journal. Encountered file error: {0}")]
+    FsPath(#[from] FsPathError),
+}
+
+/// A single entry of the journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JournalRecord {
+    /// A transaction entered the pool.
+    Add {
+        /// Where the transaction originates from.
+        origin: TransactionOrigin,
+        /// The EIP-2718 encoded consensus transaction.
+        transaction: Bytes,
+    },
+    /// A transaction left the pool.
+    Remove(TxHash),
+}
+
+impl JournalRecord {
+    const ADD: u8 = 0;
+    const REMOVE: u8 = 1;
+
+    fn payload_length(&self) -> usize {
+        match self {
+            Self::Add { origin, transaction } => {
+                Self::ADD.length() + encode_origin(*origin).length() + transaction.length()
+            }
+            Self::Remove(hash) => Self::REMOVE.length() + hash.length(),
+        }
+    }
+}
+
+impl Encodable for JournalRecord {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        match self {
+            Self::Add { origin, transaction } => {
+                Self::ADD.encode(out);
+                encode_origin(*origin).encode(out);
+                transaction.encode(out);
+            }
+            Self::Remove(hash) => {
+                Self::REMOVE.encode(out);
+                hash.encode(out);
+            }
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for JournalRecord {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let (mut payload, rest) = buf.split_at(header.payload_length);
+        let record = match u8::decode(&mut payload)? {
+            Self::ADD => Self::Add {
+                origin: decode_origin(u8::decode(&mut payload)?)?,
+                transaction: Bytes::decode(&mut payload)?,
+            },
+            Self::REMOVE => Self::Remove(TxHash::decode(&mut payload)?),
+            _ => return Err(alloy_rlp::Error::Custom("unknown journal record")),
+        };
+        if !payload.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength)
+        }
+        *buf = rest;
+        Ok(record)
+    }
+}
+
+const fn encode_origin(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+const fn decode_origin(origin: u8) -> alloy_rlp::Result<TransactionOrigin> {
+    match origin {
+        0 => Ok(TransactionOrigin::Local),
+        1 => Ok(TransactionOrigin::External),
+        2 => Ok(TransactionOrigin::Private),
+        _ => Err(alloy_rlp::Error::Custom("unknown transaction origin")),
+    }
+}
+
+/// Decodes all records of the journal.
+///
+/// A record that can't be decoded ends the journal, this is expected if the node crashed while
+/// the last record was written.
+fn decode_records(mut data: &[u8], path: &Path) -> Vec<JournalRecord> {
+    let mut records = Vec::new();
+    while !data.is_empty() {
+        match JournalRecord::decode(&mut data) {
+            Ok(record) => records.push(record),
+            Err(err) => {
+                warn!(target: "txpool::journal", %err, journal=?path, remaining=data.len(), "Ignoring corrupted tail of transaction journal");
+                break
+            }
+        }
+    }
+    records
+}
+
+/// Returns the transactions that are still in the pool according to the journal, in the order
+/// they were submitted.
+fn live_transactions(records: Vec<JournalRecord>) -> Vec<(TransactionOrigin, Bytes)> {
+    let mut transactions = Vec::with_capacity(records.len());
+    let mut indices = HashMap::new();
+    for record in records {
+        match record {
+            JournalRecord::Add { origin, transaction } => {
+                let hash = alloy_primitives::keccak256(&transaction);
+                if let Entry::Vacant(entry) = indices.entry(hash) {
+                    entry.insert(transactions.len());
+                    transactions.push(Some((origin, transaction)));
+                }
+            }
+            JournalRecord::Remove(hash) => {
+                if let Some(index) = indices.remove(&hash) {
+                    transactions[index] = None;
+                }
+            }
+        }
+    }
+    transactions.into_iter().flatten().collect()
+}
+
+/// The open journal file.
+#[derive(Debug)]
+struct TransactionJournal {
+    config: TransactionJournalConfig,
+    writer: BufWriter<File>,
+    /// Hashes of the transactions that are currently recorded as added.
+    journaled: HashSet<TxHash>,
+}
+
+impl TransactionJournal {
+    /// Opens the journal for appending.
+    fn open(config: TransactionJournalConfig) -> Result<Self, TransactionJournalError> {
+        if let Some(parent) = config.path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        reth_fs_util::create_dir_all(config.blobs_dir())?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)
+            .map_err(|err| FsPathError::open(err, &config.path))?;
+        Ok(Self { config, writer: BufWriter::new(file), journaled: HashSet::new() })
+    }
+
+    fn sidecar_path(&self, hash: &TxHash) -> PathBuf {
+        self.config.blobs_dir().join(format!("{hash:x}"))
+    }
+
+    fn append(&mut self, record: &JournalRecord) -> Result<(), TransactionJournalError> {
+        let mut buf = Vec::with_capacity(record.length());
+        record.encode(&mut buf);
+        self.writer.write_all(&buf).map_err(|err| FsPathError::write(err, &self.config.path))?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), TransactionJournalError> {
+        self.writer.flush().map_err(|err| FsPathError::write(err, &self.config.path))?;
+        Ok(())
+    }
+
+    /// Records a transaction that entered the pool, with its blob sidecar if it has one.
+    fn add<T: PoolTransaction>(
+        &mut self,
+        transaction: &ValidPoolTransaction<T>,
+        sidecar: Option<&BlobTransactionSidecarVariant>,
+    ) -> Result<(), TransactionJournalError> {
+        let hash = *transaction.hash();
+        if !self.journaled.insert(hash) {
+            return Ok(())
+        }
+        if let Some(sidecar) = sidecar {
+            self.write_sidecar(&hash, sidecar)?;
+        }
+        self.append(&journal_add_record(transaction))
+    }
+
+    /// Records a transaction that left the pool.
+    fn remove(&mut self, hash: TxHash) -> Result<(), TransactionJournalError> {
+        if !self.journaled.remove(&hash) {
+            return Ok(())
+        }
+        let sidecar_path = self.sidecar_path(&hash);
+        if sidecar_path.exists() {
+            reth_fs_util::remove_file(sidecar_path)?;
+        }
+        self.append(&JournalRecord::Remove(hash))
+    }
+
+    fn write_sidecar(
+        &self,
+        hash: &TxHash,
+        sidecar: &BlobTransactionSidecarVariant,
+    ) -> Result<(), TransactionJournalError> {
+        let mut buf = Vec::with_capacity(sidecar.rlp_encoded_fields_length());
+        sidecar.rlp_encode_fields(&mut buf);
+        reth_fs_util::write(self.sidecar_path(hash), buf)?;
+        Ok(())
+    }
+
+    fn read_sidecar(
+        &self,
+        hash: &TxHash,
+    ) -> Result<Option<BlobTransactionSidecarVariant>, TransactionJournalError> {
+        let sidecar_path = self.sidecar_path(hash);
+        if !sidecar_path.exists() {
+            return Ok(None)
+        }
+        let data = reth_fs_util::read(sidecar_path)?;
+        Ok(Some(BlobTransactionSidecarVariant::rlp_decode_fields(&mut data.as_slice())?))
+    }
+
+    /// Rewrites the journal with the current contents of the pool and removes sidecars of
+    /// transactions that are no longer in the pool.
+    fn compact<P>(&mut self, pool: &P) -> Result<(), TransactionJournalError>
+    where
+        P: TransactionPool,
+    {
+        self.flush()?;
+
+        let mut transactions =
+            [TransactionOrigin::Local, TransactionOrigin::External, TransactionOrigin::Private]
+                .into_iter()
+                .flat_map(|origin| pool.get_transactions_by_origin(origin))
+                .collect::<Vec<_>>();
+        transactions.sort_by_key(|tx| tx.timestamp);
+
+        let mut journaled = HashSet::with_capacity(transactions.len());
+        let mut buf = Vec::new();
+        for transaction in &transactions {
+            let hash = *transaction.hash();
+            if transaction.transaction.is_eip4844() && !self.sidecar_path(&hash).exists() {
+                match pool.get_blob(hash) {
+                    Ok(Some(sidecar)) => self.write_sidecar(&hash, &sidecar)?,
+                    Ok(None) => {
+                        debug!(target: "txpool::journal", %hash, "Missing blob sidecar, skipping transaction");
+                        continue
+                    }
+                    Err(err) => {
+                        warn!(target: "txpool::journal", %err, %hash, "Failed to get blob sidecar, skipping transaction");
+                        continue
+                    }
+                }
+            }
+            journal_add_record(transaction).encode(&mut buf);
+            journaled.insert(hash);
+        }
+
+        reth_fs_util::atomic_write_file(&self.config.path, |file| file.write_all(&buf))?;
+        let file = OpenOptions::new()
+            .append(true)
+            .open(&self.config.path)
+            .map_err(|err| FsPathError::open(err, &self.config.path))?;
+        self.writer = BufWriter::new(file);
+
+        let blobs_dir = self.config.blobs_dir();
+        for entry in reth_fs_util::read_dir(&blobs_dir)? {
+            let entry = entry.map_err(|err| FsPathError::read_dir(err, &blobs_dir))?;
+            let is_journaled = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<TxHash>().ok())
+                .is_some_and(|hash| journaled.contains(&hash));
+            if !is_journaled {
+                reth_fs_util::remove_file(entry.path())?;
+            }
+        }
+
+        debug!(target: "txpool::journal", num_txs=journaled.len(), journal=?self.config.path, "Compacted transaction journal");
+        self.journaled = journaled;
+        Ok(())
+    }
+}
+
+fn journal_add_record<T: PoolTransaction>(transaction: &ValidPoolTransaction<T>) -> JournalRecord {
+    JournalRecord::Add {
+        origin: transaction.origin,
+        transaction: transaction
+            .transaction
+            .clone_into_consensus()
+            .into_inner()
+            .encoded_2718()
+            .into(),
+    }
+}
+
+/// Reads the journal and reinserts the transactions that are still live into the pool.
+///
+/// Transactions are validated again and inserted in the order they were originally submitted.
+/// Consecutive transactions of the same origin are inserted as one batch.
+async fn replay_journal<P>(
+    pool: &P,
+    journal: &TransactionJournal,
+) -> Result<usize, TransactionJournalError>
+where
+    P: TransactionPool<Transaction: EthPoolTransaction>,
+{
+    let path = &journal.config.path;
+    let data = reth_fs_util::read(path)?;
+    if data.is_empty() {
+        return Ok(0)
+    }
+
+    let mut batches: Vec<(TransactionOrigin, Vec<P::Transaction>)> = Vec::new();
+    for (origin, encoded) in live_transactions(decode_records(&data, path)) {
+        let consensus = match <P::Transaction as PoolTransaction>::Consensus::decode_2718(
+            &mut encoded.as_ref(),
+        ) {
+            Ok(tx) => tx,
+            Err(err) => {
+                warn!(target: "txpool::journal", %err, "Failed to decode journaled transaction");
+                continue
+            }
+        };
+        let hash = *consensus.tx_hash();
+        let is_eip4844 = consensus.is_eip4844();
+        let Ok(recovered) = consensus.try_into_recovered() else {
+            warn!(target: "txpool::journal", %hash, "Failed to recover signer of journaled transaction");
+            continue
+        };
+
+        let transaction = if is_eip4844 {
+            let Some(sidecar) = journal.read_sidecar(&hash)? else {
+                debug!(target: "txpool::journal", %hash, "Missing blob sidecar of journaled transaction");
+                continue
+            };
+            <P::Transaction as EthPoolTransaction>::try_from_eip4844(recovered, sidecar)
+        } else {
+            <P::Transaction as PoolTransaction>::try_from_consensus(recovered).ok()
+        };
+        let Some(transaction) = transaction else {
+            trace!(target: "txpool::journal", %hash, "Journaled transaction can't be converted into a pool transaction");
+            continue
+        };
+
+        match batches.last_mut() {
+            Some((batch_origin, batch)) if *batch_origin == origin => batch.push(transaction),
+            _ => batches.push((origin, vec![transaction])),
+        }
+    }
+
+    let mut num_txs = 0;
+    for (origin, batch) in batches {
+        let outcome = pool.add_transactions(origin, batch).await;
+        num_txs += outcome.iter().filter(|res| res.is_ok()).count();
+    }
+    Ok(num_txs)
+}
+
+/// Task which journals all transactions of the pool.
+///
+/// Replays the journal into the pool on boot up, then records every transaction that enters or
+/// leaves the pool. The journal is compacted after replay, every
+/// [`TransactionJournalConfig::compaction_interval`] and on shutdown.
+pub async fn journal_transactions_task<P>(
+    mut shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: TransactionJournalConfig,
+) where
+    P: TransactionPool<Transaction: EthPoolTransaction>,
+{
+    let compaction_interval = config.compaction_interval;
+    let mut journal = match TransactionJournal::open(config) {
+        Ok(journal) => journal,
+        Err(err) => {
+            error!(target: "txpool::journal", %err, "Failed to open transaction journal");
+            return
+        }
+    };
+
+    // subscribe before replaying so no transaction is missed
+    let mut events = pool.all_transactions_event_listener();
+
+    match replay_journal(&pool, &journal).await {
+        Ok(num_txs) => {
+            info!(target: "txpool::journal", journal=?journal.config.path, num_txs, "Reinserted journaled transactions")
+        }
+        Err(err) => error!(target: "txpool::journal", %err, "Failed to replay transaction journal"),
+    }
+
+    if let Err(err) = journal.compact(&pool) {
+        error!(target: "txpool::journal", %err, "Failed to compact transaction journal");
+    }
+
+    let mut compaction =
+        time::interval_at(Instant::now() + compaction_interval, compaction_interval);
+
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else { break };
+                if let Err(err) = on_transaction_event(&mut journal, &pool, event) {
+                    warn!(target: "txpool::journal", %err, "Failed to journal transaction event");
+                }
+            }
+            _ = compaction.tick() => {
+                if let Err(err) = journal.compact(&pool) {
+                    warn!(target: "txpool::journal", %err, "Failed to compact transaction journal");
+                }
+            }
+            guard = &mut shutdown => {
+                if let Err(err) = journal.compact(&pool) {
+                    error!(target: "txpool::journal", %err, "Failed to compact transaction journal");
+                }
+                drop(guard);
+                return
+            }
+        }
+    }
+}
+
+fn on_transaction_event<P>(
+    journal: &mut TransactionJournal,
+    pool: &P,
+    event: FullTransactionEvent<P::Transaction>,
+) -> Result<(), TransactionJournalError>
+where
+    P: TransactionPool,
+{
+    match event {
+        FullTransactionEvent::Pending(hash) | FullTransactionEvent::Queued(hash) => {
+            if journal.journaled.contains(&hash) {
+                return Ok(())
+            }
+            let Some(transaction) = pool.get(&hash) else { return Ok(()) };
+            let sidecar = if transaction.transaction.is_eip4844() {
+                match pool.get_blob(hash) {
+                    Ok(Some(sidecar)) => Some(sidecar),
+                    _ => {
+                        debug!(target: "txpool::journal", %hash, "Missing blob sidecar, not journaling transaction");
+                        return Ok(())
+                    }
+                }
+            } else {
+                None
+            };
+            journal.add(&transaction, sidecar.as_deref())?;
+        }
+        FullTransactionEvent::Mined { tx_hash, .. } => journal.remove(tx_hash)?,
+        FullTransactionEvent::Replaced { transaction, .. } => {
+            journal.remove(*transaction.hash())?
+        }
+        FullTransactionEvent::Discarded(hash) | FullTransactionEvent::Invalid(hash) => {
+            journal.remove(hash)?
+        }
+        FullTransactionEvent::Propagated(_) => return Ok(()),
+    }
+    journal.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        CoinbaseTipOrdering, EthPooledTransaction, Pool,
+    };
+    use alloy_primitives::{hex, U256};
+    use reth_ethereum_primitives::PooledTransactionVariant;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_tasks::TaskManager;
+
+    const TX_BYTES: [u8; 117] = hex!(
+        "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+    );
+
+    #[test]
+    fn journal_records_roundtrip() {
+        let added = JournalRecord::Add {
+            origin: TransactionOrigin::External,
+            transaction: Bytes::from_static(&TX_BYTES),
+        };
+        let removed = JournalRecord::Remove(alloy_primitives::keccak256(TX_BYTES));
+
+        let mut buf = Vec::new();
+        added.encode(&mut buf);
+        removed.encode(&mut buf);
+        assert_eq!(buf.len(), added.length() + removed.length());
+
+        let records = decode_records(&buf, Path::new("journal"));
+        assert_eq!(records, vec![added.clone(), removed]);
+        assert!(live_transactions(records).is_empty());
+
+        // a torn record at the end is ignored
+        let mut buf = Vec::new();
+        added.encode(&mut buf);
+        added.encode(&mut buf);
+        buf.truncate(buf.len() - 3);
+        let records = decode_records(&buf, Path::new("journal"));
+        assert_eq!(records, vec![added]);
+        assert_eq!(
+            live_transactions(records),
+            vec![(TransactionOrigin::External, Bytes::from_static(&TX_BYTES))]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_journal_replay() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let journal_path = temp_dir.path().join("txpool-journal.rlp");
+
+        let tx = PooledTransactionVariant::decode_2718(&mut &TX_BYTES[..]).unwrap();
+        let transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap());
+        let provider = MockEthProvider::default();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+
+        let new_pool = || {
+            let blob_store = InMemoryBlobStore::default();
+            let validator =
+                EthTransactionValidatorBuilder::new(provider.clone()).build(blob_store.clone());
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default())
+        };
+
+        // journal an external transaction
+        let txpool = new_pool();
+        let manager = TaskManager::new(tokio::runtime::Handle::current());
+        let config = TransactionJournalConfig::new(journal_path.clone());
+        manager.executor().spawn_critical_with_graceful_shutdown_signal("journal", |shutdown| {
+            journal_transactions_task(shutdown, txpool.clone(), config)
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        manager.graceful_shutdown();
+
+        let data = reth_fs_util::read(&journal_path).unwrap();
+        let records = decode_records(&data, &journal_path);
+        assert_eq!(records.len(), 1);
+
+        // replay into a fresh pool
+        let txpool = new_pool();
+        let manager = TaskManager::new(tokio::runtime::Handle::current());
+        let config = TransactionJournalConfig::new(journal_path.clone());
+        manager.executor().spawn_critical_with_graceful_shutdown_signal("journal", |shutdown| {
+            journal_transactions_task(shutdown, txpool.clone(), config)
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let mut txs = txpool.get_external_transactions();
+        let replayed = txs.pop().expect("there should be 1 transaction");
+        assert_eq!(replayed.hash(), transaction.hash());
+        assert!(txs.is_empty());
+
+        manager.graceful_shutdown();
+        temp_dir.close().unwrap();
+    }
+}
@@ -154,6 +154,7 @@
 
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
+    bundle::{BundlePool, PoolBundle},
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
         DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
@@ -161,7 +162,7 @@ pub use crate::{
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{CoinbaseTipOrdering, Priority, PriorityLane, TransactionOrdering},
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
         NewTransactionEvent, TransactionEvent, TransactionEvents, TransactionListenerKind,
@@ -188,7 +189,9 @@ use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
+pub mod conditional;
 pub mod error;
+pub mod journal;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
@@ -196,6 +199,7 @@ pub mod pool;
 pub mod validate;
 
 pub mod blobstore;
+pub mod bundle;
 mod config;
 pub mod identifier;
 mod ordering;
@@ -623,6 +627,10 @@ where
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
         self.pool.blob_store().get_by_versioned_hashes_v2(versioned_hashes)
     }
+
+    fn bundles(&self) -> BundlePool<PoolConsensusTx<Self>> {
+        self.pool.bundles().clone()
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
@@ -2,12 +2,13 @@
 
 use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    conditional::{find_known_account_mismatch, MaybeConditionalTransaction},
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
     BlockInfo, PoolTransaction, PoolUpdateKind,
 };
-use alloy_consensus::{BlockHeader, Typed2718};
+use alloy_consensus::{conditional::BlockConditionalAttributes, BlockHeader, Typed2718};
 use alloy_eips::BlockNumberOrTag;
 use alloy_primitives::{Address, BlockHash, BlockNumber};
 use alloy_rlp::Encodable;
@@ -708,6 +709,99 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// Returns a spawnable future for maintaining the state of the conditional transactions in the
+/// transaction pool.
+pub fn maintain_transaction_pool_conditional_future<N, Client, P, St>(
+    client: Client,
+    pool: P,
+    events: St,
+) -> BoxFuture<'static, ()>
+where
+    N: NodePrimitives,
+    Client: StateProviderFactory + 'static,
+    P: TransactionPool<Transaction: MaybeConditionalTransaction> + 'static,
+    St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
+{
+    async move {
+        maintain_transaction_pool_conditional(client, pool, events).await;
+    }
+    .boxed()
+}
+
+/// Maintains the conditional transactions in the transaction pool by handling new blocks and
+/// reorgs.
+///
+/// Removes all transactions whose conditional can no longer be met on top of the new canonical tip,
+/// either because the block number or timestamp bounds are exceeded or because the state of the
+/// known accounts changed.
+pub async fn maintain_transaction_pool_conditional<N, Client, P, St>(
+    client: Client,
+    pool: P,
+    mut events: St,
+) where
+    N: NodePrimitives,
+    Client: StateProviderFactory,
+    P: TransactionPool<Transaction: MaybeConditionalTransaction>,
+    St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
+{
+    let metrics = MaintainPoolMetrics::default();
+    while let Some(event) = events.next().await {
+        let tip = event.tip();
+        let block_attr =
+            BlockConditionalAttributes { number: tip.number(), timestamp: tip.timestamp() };
+
+        let conditional_txs = pool
+            .pooled_transactions()
+            .into_iter()
+            .filter(|tx| tx.transaction.conditional().is_some())
+            .collect::<Vec<_>>();
+        if conditional_txs.is_empty() {
+            continue
+        }
+
+        // the state is only needed if a conditional has known accounts
+        let mut state = None;
+        let mut to_remove = Vec::new();
+        for tx in conditional_txs {
+            let Some(conditional) = tx.transaction.conditional() else { continue };
+            if conditional.has_exceeded_block_attributes(&block_attr) {
+                to_remove.push(*tx.hash());
+                continue
+            }
+            if conditional.known_accounts.is_empty() {
+                continue
+            }
+
+            if state.is_none() {
+                match client.state_by_block_hash(tip.hash()) {
+                    Ok(provider) => state = Some(provider),
+                    Err(err) => {
+                        warn!(target: "txpool", %err, "Failed to get state for conditional transactions");
+                        break
+                    }
+                }
+            }
+            let Some(state) = state.as_deref() else { break };
+            match find_known_account_mismatch(state, conditional) {
+                Ok(None) => {}
+                Ok(Some(mismatch)) => {
+                    trace!(target: "txpool", tx=%tx.hash(), ?mismatch, "Conditional no longer met by known accounts");
+                    to_remove.push(*tx.hash());
+                }
+                Err(err) => {
+                    warn!(target: "txpool", %err, tx=%tx.hash(), "Failed to check known accounts of conditional transaction");
+                }
+            }
+        }
+
+        if !to_remove.is_empty() {
+            let removed = pool.remove_transactions(to_remove);
+            debug!(target: "txpool", num_removed=removed.len(), "Removed conditional transactions");
+            metrics.inc_removed_tx_conditional(removed.len());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
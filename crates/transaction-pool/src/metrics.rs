@@ -91,6 +91,9 @@ pub struct MaintainPoolMetrics {
     pub(crate) reinserted_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
+    /// Counter for the number of conditional transactions removed from the pool because their
+    /// conditions can no longer be met.
+    pub(crate) removed_tx_conditional: Counter,
 }
 
 impl MaintainPoolMetrics {
@@ -109,6 +112,11 @@ impl MaintainPoolMetrics {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn inc_removed_tx_conditional(&self, count: usize) {
+        self.removed_tx_conditional.increment(count as u64);
+    }
+
     #[inline]
     pub(crate) fn inc_drift(&self) {
         self.drift_count.increment(1);
@@ -1,5 +1,5 @@
 use crate::traits::PoolTransaction;
-use alloy_primitives::U256;
+use alloy_primitives::{map::HashSet, Address, U256};
 use std::{cmp::Ordering, fmt::Debug, marker::PhantomData};
 
 /// Priority of the transaction that can be missing.
@@ -98,6 +98,38 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
     }
 }
 
+/// A set of contract addresses and senders whose transactions are prioritized over all other
+/// transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PriorityLane {
+    /// Transactions calling one of these addresses are part of the lane.
+    pub addresses: HashSet<Address>,
+    /// Transactions sent by one of these addresses are part of the lane.
+    pub senders: HashSet<Address>,
+}
+
+impl PriorityLane {
+    /// Creates a new lane for the given contract addresses and senders.
+    pub fn new(
+        addresses: impl IntoIterator<Item = Address>,
+        senders: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        Self { addresses: addresses.into_iter().collect(), senders: senders.into_iter().collect() }
+    }
+
+    /// Returns `true` if neither addresses nor senders are configured.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.senders.is_empty()
+    }
+
+    /// Returns `true` if the transaction calls one of the lane's addresses or is sent by one of the
+    /// lane's senders.
+    pub fn contains<T: PoolTransaction>(&self, transaction: &T) -> bool {
+        transaction.to().is_some_and(|to| self.addresses.contains(&to)) ||
+            self.senders.contains(transaction.sender_ref())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
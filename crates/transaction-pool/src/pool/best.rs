@@ -2,11 +2,11 @@ use crate::{
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
     identifier::{SenderId, TransactionId},
     pool::pending::PendingTransaction,
-    PoolTransaction, TransactionOrdering, ValidPoolTransaction,
+    PoolTransaction, PriorityLane, TransactionOrdering, ValidPoolTransaction,
 };
 use alloy_consensus::Transaction;
 use alloy_eips::Typed2718;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use core::fmt;
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 use std::{
@@ -369,6 +369,130 @@ where
     }
 }
 
+/// Wrapper over [`crate::traits::BestTransactions`] that yields the transactions of a
+/// [`PriorityLane`] first, capping the total gas limit of the lane's transactions.
+///
+/// The lane's transactions are pulled from a separate iterator over the same pool, so that neither
+/// iterator has to be drained up front. Once the lane is exhausted, the inner iterator yields all
+/// remaining transactions and skips the ones that were already yielded by the lane.
+#[derive(Debug)]
+pub struct BestTransactionsWithPriorityLane<I: Iterator> {
+    /// Inner iterator
+    inner: I,
+    /// Iterator over the lane's transactions, `None` if there is no lane or it is exhausted
+    lane_txs: Option<I>,
+    /// The lane whose transactions are prioritized
+    lane: Arc<PriorityLane>,
+    /// Maximum total gas limit of the lane's transactions
+    reserved_gas: u64,
+    /// Total gas limit of the prioritized transactions so far
+    lane_gas: u64,
+    /// Hashes of the transactions yielded by the lane
+    yielded: HashSet<TxHash>,
+}
+
+impl<I: Iterator> BestTransactionsWithPriorityLane<I> {
+    /// Constructs a new [`BestTransactionsWithPriorityLane`] without a lane.
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            lane_txs: None,
+            lane: Default::default(),
+            reserved_gas: 0,
+            lane_gas: 0,
+            yielded: Default::default(),
+        }
+    }
+
+    /// Yields the lane's transactions of `lane_txs` first, until their total gas limit reaches
+    /// `reserved_gas`.
+    ///
+    /// `lane_txs` must be another iterator over the best transactions of the same pool.
+    pub fn with_lane(mut self, lane: Arc<PriorityLane>, reserved_gas: u64, lane_txs: I) -> Self {
+        self.lane = lane;
+        self.reserved_gas = reserved_gas;
+        self.lane_txs = Some(lane_txs);
+        self
+    }
+
+    /// Returns `true` if all transactions of the lane were yielded.
+    pub const fn is_lane_exhausted(&self) -> bool {
+        self.lane_txs.is_none()
+    }
+}
+
+impl<I, T> Iterator for BestTransactionsWithPriorityLane<I>
+where
+    I: crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T>>>,
+    T: PoolTransaction,
+{
+    type Item = <I as Iterator>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(lane_txs) = &mut self.lane_txs {
+            while self.lane_gas < self.reserved_gas {
+                let Some(item) = lane_txs.next() else { break };
+                let gas_limit = item.transaction.gas_limit();
+                if !self.lane.contains(&item.transaction) {
+                    // later transactions of the sender depend on this one, so they can't be part
+                    // of the lane either
+                    lane_txs.mark_invalid(
+                        &item,
+                        InvalidPoolTransactionError::Consensus(
+                            InvalidTransactionError::TxTypeNotSupported,
+                        ),
+                    );
+                } else if self.lane_gas + gas_limit > self.reserved_gas {
+                    lane_txs.mark_invalid(
+                        &item,
+                        InvalidPoolTransactionError::ExceedsGasLimit(gas_limit, self.reserved_gas),
+                    );
+                } else {
+                    self.lane_gas += gas_limit;
+                    self.yielded.insert(*item.hash());
+                    return Some(item)
+                }
+            }
+            self.lane_txs = None;
+        }
+
+        loop {
+            let item = self.inner.next()?;
+            if !self.yielded.contains(item.hash()) {
+                return Some(item)
+            }
+        }
+    }
+}
+
+impl<I, T> crate::traits::BestTransactions for BestTransactionsWithPriorityLane<I>
+where
+    I: crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T>>>,
+    T: PoolTransaction,
+{
+    fn mark_invalid(&mut self, tx: &Self::Item, kind: InvalidPoolTransactionError) {
+        // while the lane isn't exhausted, yielded transactions come from the lane
+        match &mut self.lane_txs {
+            Some(lane_txs) => lane_txs.mark_invalid(tx, kind),
+            None => self.inner.mark_invalid(tx, kind),
+        }
+    }
+
+    fn no_updates(&mut self) {
+        if let Some(lane_txs) = &mut self.lane_txs {
+            lane_txs.no_updates()
+        }
+        self.inner.no_updates()
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        if let Some(lane_txs) = &mut self.lane_txs {
+            lane_txs.set_skip_blobs(skip_blobs)
+        }
+        self.inner.set_skip_blobs(skip_blobs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -809,6 +933,54 @@ mod tests {
         assert_eq!(top_of_block_tx2.sender(), prioritized_tx2.sender());
     }
 
+    #[test]
+    fn test_best_transactions_priority_lane() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        // Add 5 plain transactions from different senders with increasing gas price
+        for gas_price in 0..5 {
+            let tx = MockTransaction::eip1559().with_gas_price((gas_price + 1) * 10);
+            let valid_tx = f.validated(tx);
+            pool.add_transaction(Arc::new(valid_tx), 0);
+        }
+
+        // Add a cheap transaction calling a lane contract and a cheap transaction of a lane
+        // sender
+        let contract_tx = MockTransaction::eip1559().with_gas_price(5).with_gas_limit(100);
+        pool.add_transaction(Arc::new(f.validated(contract_tx.clone())), 0);
+        let sender_tx = MockTransaction::eip1559().with_gas_price(4).with_gas_limit(100);
+        pool.add_transaction(Arc::new(f.validated(sender_tx.clone())), 0);
+
+        // Add another lane transaction that exceeds the reserved gas
+        let exceeding_tx = MockTransaction::eip1559().with_gas_price(3).with_gas_limit(1);
+        pool.add_transaction(Arc::new(f.validated(exceeding_tx.clone())), 0);
+
+        let lane = PriorityLane::new(
+            [contract_tx.to().unwrap()],
+            [sender_tx.sender(), exceeding_tx.sender()],
+        );
+        let mut iter = BestTransactionsWithPriorityLane::new(pool.best()).with_lane(
+            Arc::new(lane),
+            200,
+            pool.best(),
+        );
+
+        // Verify that the lane's transactions are returned first and the rest are returned in the
+        // reverse order of gas price
+        assert_eq!(iter.next().unwrap().sender(), contract_tx.sender());
+        assert_eq!(iter.next().unwrap().sender(), sender_tx.sender());
+        assert!(!iter.is_lane_exhausted());
+        for gas_price in (0..5).rev() {
+            assert_eq!(iter.next().unwrap().max_fee_per_gas(), (gas_price + 1) * 10);
+        }
+        assert!(iter.is_lane_exhausted());
+
+        // The reserved gas is used up, so the last lane transaction is not prioritized
+        assert_eq!(iter.next().unwrap().sender(), exceeding_tx.sender());
+        assert!(iter.next().is_none());
+    }
+
     #[test]
     fn test_best_with_fees_iter_no_blob_fee_required() {
         // Tests transactions without blob fees where base fees are checked.
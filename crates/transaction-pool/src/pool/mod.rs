@@ -67,6 +67,7 @@
 
 use crate::{
     blobstore::BlobStore,
+    bundle::BundlePool,
     error::{PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     metrics::BlobStoreMetrics,
@@ -101,7 +102,9 @@ use std::{collections::HashSet, fmt, sync::Arc, time::Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 mod events;
-pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
+pub use best::{
+    BestTransactionFilter, BestTransactionsWithPrioritizedSenders, BestTransactionsWithPriorityLane,
+};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
 pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
@@ -151,6 +154,8 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Bundles and private transactions that are never propagated.
+    bundles: BundlePool<<T::Transaction as PoolTransaction>::Consensus>,
 }
 
 // === impl PoolInner ===
@@ -174,6 +179,7 @@ where
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            bundles: Default::default(),
         }
     }
 
@@ -182,6 +188,11 @@ where
         &self.blob_store
     }
 
+    /// Returns the store for bundles and private transactions.
+    pub const fn bundles(&self) -> &BundlePool<<T::Transaction as PoolTransaction>::Consensus> {
+        &self.bundles
+    }
+
     /// Returns stats about the size of the pool.
     pub fn size(&self) -> PoolSize {
         self.get_pool_data().size()
@@ -396,6 +407,10 @@ where
 
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
+        // bundles that expired or were (partially) mined can never be included again
+        self.bundles
+            .on_canonical_state_change(block_info.last_seen_block_number, &mined_transactions);
+
         // update the pool
         let outcome = self.pool.write().on_canonical_state_change(
             block_info,
@@ -1,6 +1,7 @@
 //! Mock types.
 
 use crate::{
+    conditional::MaybeConditionalTransaction,
     identifier::{SenderIdentifiers, TransactionId},
     pool::txpool::TxPool,
     traits::TransactionOrigin,
@@ -23,6 +24,7 @@ use alloy_eips::{
     eip7702::SignedAuthorization,
 };
 use alloy_primitives::{Address, Bytes, ChainId, Signature, TxHash, TxKind, B256, U256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use paste::paste;
 use rand::{distr::Uniform, prelude::Distribution};
 use reth_ethereum_primitives::{PooledTransactionVariant, Transaction, TransactionSigned};
@@ -916,6 +918,15 @@ impl EthPoolTransaction for MockTransaction {
     }
 }
 
+/// Mock transactions don't track conditionals, the conditional is discarded.
+impl MaybeConditionalTransaction for MockTransaction {
+    fn set_conditional(&mut self, _conditional: TransactionConditional) {}
+
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        None
+    }
+}
+
 impl TryFrom<Recovered<TransactionSigned>> for MockTransaction {
     type Error = TryFromRecoveredTransactionError;
 
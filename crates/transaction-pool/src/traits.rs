@@ -1,5 +1,7 @@
 use crate::{
     blobstore::BlobStoreError,
+    bundle::BundlePool,
+    conditional::MaybeConditionalTransaction,
     error::{InvalidPoolTransactionError, PoolResult},
     pool::{
         state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
@@ -19,6 +21,7 @@ use alloy_eips::{
     eip7702::SignedAuthorization,
 };
 use alloy_primitives::{Address, Bytes, TxHash, TxKind, B256, U256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_ethereum_primitives::{PooledTransactionVariant, TransactionSigned};
@@ -506,6 +509,16 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError>;
+
+    /// Returns the store for bundles and private transactions.
+    ///
+    /// Transactions in this store are never propagated to peers and are only included by the
+    /// payload builder.
+    ///
+    /// By default, this returns an empty store that rejects all bundles.
+    fn bundles(&self) -> BundlePool<PoolConsensusTx<Self>> {
+        BundlePool::new(0)
+    }
 }
 
 /// Extension for [`TransactionPool`] trait that allows to set the current block info.
@@ -1098,6 +1111,9 @@ pub struct EthPooledTransaction<T = TransactionSigned> {
 
     /// The blob side car for this transaction
     pub blob_sidecar: EthBlobTransactionSidecar,
+
+    /// Optional conditional attached to this transaction.
+    conditional: Option<Box<TransactionConditional>>,
 }
 
 impl<T: SignedTransaction> EthPooledTransaction<T> {
@@ -1126,7 +1142,7 @@ impl<T: SignedTransaction> EthPooledTransaction<T> {
             blob_sidecar = EthBlobTransactionSidecar::Missing;
         }
 
-        Self { transaction, cost, encoded_length, blob_sidecar }
+        Self { transaction, cost, encoded_length, blob_sidecar, conditional: None }
     }
 
     /// Return the reference to the underlying transaction.
@@ -1286,6 +1302,16 @@ impl<T: alloy_consensus::Transaction> alloy_consensus::Transaction for EthPooled
     }
 }
 
+impl<T> MaybeConditionalTransaction for EthPooledTransaction<T> {
+    fn set_conditional(&mut self, conditional: TransactionConditional) {
+        self.conditional = Some(Box::new(conditional))
+    }
+
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+}
+
 impl EthPoolTransaction for EthPooledTransaction {
     fn take_blob(&mut self) -> EthBlobTransactionSidecar {
         if self.is_eip4844() {